@@ -1,3 +1,5 @@
+use kernel::entities::product::TaxRates;
+use kernel::entities::ticket::StoreOffset;
 use nitinol::process::manager::ProcessManager;
 use nitinol::projection::EventProjector;
 
@@ -9,8 +11,17 @@ pub trait DependOnEventProjector: 'static + Sync + Send {
     fn event_projector(&self) -> &EventProjector;
 }
 
+pub trait DependOnTaxRates: 'static + Sync + Send {
+    fn tax_rates(&self) -> &TaxRates;
+}
+
+pub trait DependOnStoreOffset: 'static + Sync + Send {
+    fn store_offset(&self) -> &StoreOffset;
+}
+
 pub(crate) mod utils {
     use error_stack::{Report, ResultExt};
+    use kernel::errors::DeletedError;
     use nitinol::process::{Process, Ref};
     use nitinol::process::manager::ProcessManager;
     use nitinol::projection::EventProjector;
@@ -39,4 +50,22 @@ pub(crate) mod utils {
         
         Ok(refs)
     }
-}
\ No newline at end of file
+    
+    /// Projects the latest state of an entity without spawning its process.
+    /// 
+    /// `None` if the entity has been deleted, which its projection rejects with [`DeletedError`].
+    pub async fn project<T>(
+        id: impl ToEntityId,
+        projector: &EventProjector
+    ) -> Result<Option<T>, Report<ApplicationError>>
+        where T: Process + ResolveMapping
+    {
+        match projector.projection_to_latest::<T>(id.to_entity_id(), None).await
+            .change_context_lazy(|| ApplicationError::NotFound)
+        {
+            Ok((entity, _)) => Ok(Some(entity)),
+            Err(e) if e.contains::<DeletedError>() => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+}
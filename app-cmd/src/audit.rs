@@ -0,0 +1,80 @@
+use std::net::IpAddr;
+
+use async_trait::async_trait;
+use error_stack::Report;
+use kernel::entities::device::DeviceId;
+use kernel::entities::staff::StaffName;
+use serde::Serialize;
+
+use crate::errors::ApplicationError;
+
+/// Who dispatched a command, recorded next to the event it produced.
+#[derive(Debug, Clone, Default)]
+pub struct Actor {
+    pub staff: Option<StaffName>,
+    pub device: Option<DeviceId>,
+    pub request_id: Option<String>,
+    pub client_ip: Option<IpAddr>,
+}
+
+impl Actor {
+    /// Commands the application dispatches by itself, e.g. the stock taken by an order.
+    pub fn system() -> Self {
+        Self::default()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct AuditRecord {
+    pub actor: Actor,
+    /// `product`, `category` or `categories`.
+    pub aggregate: &'static str,
+    pub aggregate_id: String,
+    /// Name of the event variant, such as `ChangedProductPrice`.
+    pub event: String,
+}
+
+impl AuditRecord {
+    pub fn new<E: Serialize>(actor: &Actor, aggregate: &'static str, aggregate_id: impl ToString, event: &E) -> Self {
+        Self {
+            actor: actor.clone(),
+            aggregate,
+            aggregate_id: aggregate_id.to_string(),
+            event: variant(event),
+        }
+    }
+}
+
+/// Events are externally tagged, so the variant is either the string itself or the only key.
+fn variant<E: Serialize>(event: &E) -> String {
+    match serde_json::to_value(event) {
+        Ok(serde_json::Value::String(name)) => name,
+        Ok(serde_json::Value::Object(map)) => map.into_iter()
+            .next()
+            .map(|(name, _)| name)
+            .unwrap_or_default(),
+        _ => String::new(),
+    }
+}
+
+pub trait DependOnAuditLog: 'static + Sync + Send {
+    type AuditLog: AuditLog;
+    fn audit_log(&self) -> &Self::AuditLog;
+}
+
+/// Records who dispatched an event once it is in the journal, along with its sequence there.
+#[async_trait]
+pub trait AuditLog: 'static + Sync + Send {
+    async fn record(&self, record: AuditRecord, sequence: i64) -> Result<(), Report<ApplicationError>>;
+}
+
+/// Records an event right after it has been applied at `sequence` of the journal.
+/// 
+/// The event cannot be taken back at that point, so a failure only leaves the change unaudited 
+/// and is logged rather than failing the command.
+pub(crate) async fn audit(log: &impl AuditLog, record: AuditRecord, sequence: i64) {
+    let (aggregate, id, event) = (record.aggregate, record.aggregate_id.clone(), record.event.clone());
+    if let Err(e) = log.record(record, sequence).await {
+        tracing::error!("Failed to audit `{event}` of {aggregate}={id}: {:?}", e);
+    }
+}
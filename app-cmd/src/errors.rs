@@ -17,4 +17,10 @@ pub enum ApplicationError {
     
     #[error("Invalid command")]
     InvalidCommand,
+    
+    #[error("Credentials are invalid")]
+    Unauthenticated,
+    
+    #[error("Failed to record the audit log")]
+    Audit,
 }
\ No newline at end of file
@@ -1,4 +1,5 @@
 pub mod adapter;
+pub mod audit;
 pub mod services;
 pub mod errors;
 pub mod workflow;
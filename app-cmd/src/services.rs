@@ -1,3 +1,8 @@
 pub mod product;
 pub mod category;
-pub mod categories;
\ No newline at end of file
+pub mod categories;
+pub mod device;
+pub mod order;
+pub mod promotion;
+pub mod staff;
+pub mod ticket;
\ No newline at end of file
@@ -4,13 +4,15 @@ use kernel::entities::categories::Categories;
 use kernel::io::commands::CategoriesCommand;
 
 use crate::adapter::{DependOnEventProjector, DependOnProcessManager};
+use crate::audit::{self, Actor, AuditRecord, DependOnAuditLog};
 use crate::errors::ApplicationError;
 
 impl<T> CategoriesCommandService for T 
 where 
     T
     : DependOnProcessManager
-    + DependOnEventProjector {}
+    + DependOnEventProjector
+    + DependOnAuditLog {}
 
 
 pub trait DependOnCategoriesCommandService: 'static + Sync + Send {
@@ -24,8 +26,9 @@ pub trait CategoriesCommandService: 'static + Sync + Send
 where
     Self: DependOnProcessManager
         + DependOnEventProjector
+        + DependOnAuditLog
 {
-    async fn execute(&self, cmd: CategoriesCommand) -> Result<(), Report<ApplicationError>> {
+    async fn execute(&self, actor: &Actor, cmd: CategoriesCommand) -> Result<(), Report<ApplicationError>> {
         let manager = self.process_manager();
         
         let refs = match manager.find::<Categories>(Categories::ID).await
@@ -44,9 +47,13 @@ where
             .change_context_lazy(|| ApplicationError::Process)?
             .change_context_lazy(|| ApplicationError::Kernel)?;
         
-        refs.apply(event).await
+        let record = AuditRecord::new(actor, "categories", Categories::ID, &event);
+        
+        let sequence = refs.apply(event).await
             .change_context_lazy(|| ApplicationError::Process)?;
         
+        audit::audit(self.audit_log(), record, sequence).await;
+        
         Ok(())
     }
 }
\ No newline at end of file
@@ -5,6 +5,7 @@ use kernel::io::commands::{CategoriesCommand, CategoryCommand};
 use kernel::io::events::CategoryEvent;
 
 use crate::adapter::{self, DependOnEventProjector, DependOnProcessManager};
+use crate::audit::{self, Actor, AuditRecord, DependOnAuditLog};
 use crate::errors::ApplicationError;
 use crate::services::categories::{CategoriesCommandService, DependOnCategoriesCommandService};
 
@@ -13,6 +14,7 @@ where T
       : DependOnProcessManager 
       + DependOnEventProjector
       + DependOnCategoriesCommandService 
+      + DependOnAuditLog
 {}
 
 pub trait DependOnCategoryCommandService: 'static + Sync + Send {
@@ -26,40 +28,45 @@ where
     Self: DependOnProcessManager
         + DependOnEventProjector
         + DependOnCategoriesCommandService
+        + DependOnAuditLog
 {
-    async fn execute<I>(&self, id: I, cmd: CategoryCommand) -> Result<(), Report<ApplicationError>>
+    async fn execute<I>(&self, actor: &Actor, id: I, cmd: CategoryCommand) -> Result<(), Report<ApplicationError>>
         where
             I: Into<Option<CategoryId>> + Sync + Send,
     {
         let manager = self.process_manager();
         
-        let refs = if let CategoryCommand::Create { .. } = &cmd {
+        let (id, refs) = if let CategoryCommand::Create { .. } = &cmd {
             let id = CategoryId::default();
             
             let category = Category::try_from((id, cmd.clone()))
                 .change_context_lazy(|| ApplicationError::Formation)?;
             
-            manager.spawn(id, category, 0).await
-                .change_context_lazy(|| ApplicationError::Process)?
+            (id, manager.spawn(id, category, 0).await
+                .change_context_lazy(|| ApplicationError::Process)?)
         } else {
             let id = id.into()
                 .ok_or(ApplicationError::RequiredId)?;
-            adapter::utils::find_or_replay(id, manager, self.event_projector()).await?
+            (id, adapter::utils::find_or_replay(id, manager, self.event_projector()).await?)
         };
         
         let event = refs.publish(cmd).await
             .change_context_lazy(|| ApplicationError::Process)?
             .change_context_lazy(|| ApplicationError::Kernel)?;
 
-        refs.apply(event.clone()).await
+        let record = AuditRecord::new(actor, "category", id, &event);
+        
+        let sequence = refs.apply(event.clone()).await
             .change_context_lazy(|| ApplicationError::Process)?;
         
+        audit::audit(self.audit_log(), record, sequence).await;
+        
         if let CategoryEvent::Created { .. } | CategoryEvent::Deleted { .. } = event {
             let cmd = CategoriesCommand::try_from(event)
                 .change_context_lazy(|| ApplicationError::Formation)?;
             
             self.categories_command_service()
-                .execute(cmd)
+                .execute(actor, cmd)
                 .await
                 .change_context_lazy(|| ApplicationError::Process)?;
         }
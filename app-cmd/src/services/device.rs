@@ -0,0 +1,63 @@
+use async_trait::async_trait;
+use error_stack::{Report, ResultExt};
+use kernel::entities::device::{Device, DeviceId};
+use kernel::io::commands::DeviceCommand;
+
+use crate::adapter::{self, DependOnEventProjector, DependOnProcessManager};
+use crate::errors::ApplicationError;
+
+
+impl<T> DeviceCommandService for T 
+where 
+    T
+    : DependOnProcessManager
+    + DependOnEventProjector
+{}
+
+
+pub trait DependOnDeviceCommandService: 'static + Sync + Send {
+    type DeviceCommandService: DeviceCommandService;
+    fn device_command_service(&self) -> &Self::DeviceCommandService;
+}
+
+#[async_trait]
+pub trait DeviceCommandService: 'static + Sync + Send
+where
+    Self: DependOnProcessManager
+        + DependOnEventProjector
+{
+    /// Returns the [`DeviceId`] of the device the command was applied to,
+    /// so that the caller can hand it to a newly registered device along with its key.
+    async fn execute<I>(&self, id: I, cmd: DeviceCommand) -> Result<DeviceId, Report<ApplicationError>>
+        where 
+            I: Into<Option<DeviceId>> + Sync + Send,
+    {
+        let manager = self.process_manager();
+        
+        let (id, refs) = if let DeviceCommand::Register { .. } = &cmd {
+            let id = DeviceId::default();
+            
+            let device = Device::try_from((id, cmd.clone()))
+                .change_context_lazy(|| ApplicationError::Formation)?;
+            
+            let refs = manager.spawn(id, device, 0).await
+                .change_context_lazy(|| ApplicationError::Process)?;
+            
+            (id, refs)
+        } else {
+            let id = id.into()
+                .ok_or(ApplicationError::RequiredId)?;
+            
+            (id, adapter::utils::find_or_replay(id, manager, self.event_projector()).await?)
+        };
+        
+        let event = refs.publish(cmd).await
+            .change_context_lazy(|| ApplicationError::Process)?
+            .change_context_lazy(|| ApplicationError::Kernel)?;
+        
+        refs.apply(event).await
+            .change_context_lazy(|| ApplicationError::Process)?;
+        
+        Ok(id)
+    }
+}
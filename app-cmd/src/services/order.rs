@@ -0,0 +1,205 @@
+use std::collections::{BTreeMap, HashMap};
+
+use async_trait::async_trait;
+use error_stack::{Report, ResultExt};
+use kernel::entities::order::{Order, OrderId, OrderLine, OrderStatus};
+use kernel::entities::product::{Product, ProductId, ProductStock};
+use kernel::io::commands::{OrderCommand, ProductCommand, PromotionCommand, TicketCounterCommand};
+use kernel::io::events::OrderEvent;
+use nitinol::projection::EventProjector;
+
+use crate::adapter::{self, DependOnEventProjector, DependOnProcessManager, DependOnStoreOffset};
+use crate::audit::Actor;
+use crate::errors::ApplicationError;
+use crate::services::product::{DependOnProductCommandService, ProductCommandService};
+use crate::services::promotion::{DependOnPromotionCommandService, PromotionCommandService};
+use crate::services::ticket::{DependOnTicketCounterCommandService, TicketCounterCommandService};
+
+
+impl<T> OrderCommandService for T 
+where 
+    T
+    : DependOnProcessManager
+    + DependOnEventProjector
+    + DependOnStoreOffset
+    + DependOnProductCommandService
+    + DependOnPromotionCommandService
+    + DependOnTicketCounterCommandService
+{}
+
+
+pub trait DependOnOrderCommandService: 'static + Sync + Send {
+    type OrderCommandService: OrderCommandService;
+    fn order_command_service(&self) -> &Self::OrderCommandService;
+}
+
+#[async_trait]
+pub trait OrderCommandService: 'static + Sync + Send
+where
+    Self: DependOnProcessManager
+        + DependOnEventProjector
+        + DependOnStoreOffset
+        + DependOnProductCommandService
+        + DependOnPromotionCommandService
+        + DependOnTicketCounterCommandService
+{
+    /// Returns the [`OrderId`] of the order the command was applied to,
+    /// so that the caller can keep track of a newly placed order.
+    /// 
+    /// A ticket number of the current business day in the [`StoreOffset`](kernel::entities::ticket::StoreOffset)
+    /// is issued once the order is confirmed,
+    /// and the ordered quantity is taken out of the stock of every product that tracks it,
+    /// including the products chosen for the slots of a bundle.
+    /// Orders that cannot be served from the remaining stock are rejected with [`ApplicationError::Kernel`].
+    /// 
+    /// Cancelling an order gives back the redemption of its coupon, if any,
+    /// and returns the stock taken for it once it was confirmed.
+    async fn execute<I>(&self, id: I, cmd: OrderCommand) -> Result<OrderId, Report<ApplicationError>>
+        where 
+            I: Into<Option<OrderId>> + Sync + Send,
+    {
+        let manager = self.process_manager();
+        
+        let (id, refs) = if let OrderCommand::Place = &cmd {
+            let id = OrderId::default();
+            
+            let order = Order::try_from((id, cmd.clone()))
+                .change_context_lazy(|| ApplicationError::Formation)?;
+            
+            let refs = manager.spawn(id, order, 0).await
+                .change_context_lazy(|| ApplicationError::Process)?;
+            
+            (id, refs)
+        } else {
+            let id = id.into()
+                .ok_or(ApplicationError::RequiredId)?;
+
+            (id, adapter::utils::find_or_replay(id, manager, self.event_projector()).await?)
+        };
+        
+        let event = refs.publish(cmd).await
+            .change_context_lazy(|| ApplicationError::Process)?
+            .change_context_lazy(|| ApplicationError::Kernel)?;
+        
+        // Stock is taken and the ticket is issued before the confirmation is applied, 
+        // so that nothing can fail once the order is confirmed. 
+        // Should applying fail, the ticket number is skipped rather than reused.
+        if let OrderEvent::Confirmed { lines, .. } = &event {
+            reserve_stock(self.event_projector(), self.product_command_service(), lines).await?;
+            
+            let cmd = TicketCounterCommand::Issue { order: id, day: self.store_offset().today() };
+            
+            self.ticket_counter_command_service()
+                .execute(cmd)
+                .await
+                .change_context_lazy(|| ApplicationError::Process)?;
+        }
+        
+        if let OrderEvent::Cancelled { .. } = &event {
+            let (order, _) = self.event_projector().projection_to_latest::<Order>(id, None).await
+                .change_context_lazy(|| ApplicationError::Formation)?;
+            
+            if let Some(coupon) = order.coupon() {
+                PromotionCommandService::execute(self.promotion_command_service(), coupon.code().clone(), PromotionCommand::Release { order: id }).await?;
+            }
+            
+            // Stock is only taken once the order is confirmed.
+            if order.status() != &OrderStatus::Placed {
+                return_stock(self.event_projector(), self.product_command_service(), order.lines()).await?;
+            }
+        }
+        
+        refs.apply(event).await
+            .change_context_lazy(|| ApplicationError::Process)?;
+        
+        Ok(id)
+    }
+}
+
+/// Takes the ordered quantity out of the stock of every product that tracks it.
+/// 
+/// Every product is checked to exist before any stock is taken, so that an order 
+/// containing a deleted product is rejected as a whole. Whether enough is left is decided 
+/// by the product itself when the stock is taken, and should any of them reject it, 
+/// the stock already taken for the order is put back.
+async fn reserve_stock(projector: &EventProjector, products: &impl ProductCommandService, lines: &BTreeMap<i64, OrderLine>) -> Result<(), Report<ApplicationError>> {
+    let mut reserved = Vec::new();
+    
+    for (product, quantity) in demand(lines) {
+        let Some(projected) = adapter::utils::project::<Product>(product, projector).await
+            .attach_printable_lazy(|| format!("Product={product} could not be found"))? 
+        else {
+            return Err(Report::new(ApplicationError::InvalidCommand)
+                .attach_printable(format!("Product={product} has been deleted")));
+        };
+        
+        if projected.stock().is_none() {
+            continue;
+        }
+        
+        let amount = ProductStock::new(quantity)
+            .change_context_lazy(|| ApplicationError::Kernel)?;
+        
+        reserved.push((product, amount));
+    }
+    
+    let mut taken = Vec::new();
+    
+    for (product, amount) in reserved {
+        if let Err(e) = ProductCommandService::execute(products, &Actor::system(), product, ProductCommand::DecrementStock { amount }).await {
+            put_back(products, taken).await;
+            return Err(e.attach_printable(format!("Product={product} does not have enough stock")));
+        }
+        taken.push((product, amount));
+    }
+    
+    Ok(())
+}
+
+/// Restocks what has been taken for an order that could not be confirmed.
+/// 
+/// The order is rejected either way, so a product that fails to be restocked is only logged.
+async fn put_back(products: &impl ProductCommandService, taken: Vec<(ProductId, ProductStock)>) {
+    for (product, amount) in taken {
+        if let Err(e) = ProductCommandService::execute(products, &Actor::system(), product, ProductCommand::Restock { amount }).await {
+            tracing::error!("Failed to put back the stock of Product={product}: {:?}", e);
+        }
+    }
+}
+
+/// Restocks the ordered quantity of every product that still tracks its stock.
+/// 
+/// Products deleted since the order was confirmed have no stock to return to.
+async fn return_stock(projector: &EventProjector, products: &impl ProductCommandService, lines: &BTreeMap<i64, OrderLine>) -> Result<(), Report<ApplicationError>> {
+    for (product, quantity) in demand(lines) {
+        let Some(projected) = adapter::utils::project::<Product>(product, projector).await
+            .attach_printable_lazy(|| format!("Product={product} could not be found"))?
+        else {
+            continue;
+        };
+        
+        if projected.stock().is_none() {
+            continue;
+        }
+        
+        let amount = ProductStock::new(quantity)
+            .change_context_lazy(|| ApplicationError::Kernel)?;
+        
+        ProductCommandService::execute(products, &Actor::system(), product, ProductCommand::Restock { amount }).await?;
+    }
+    
+    Ok(())
+}
+
+/// Quantity of each product ordered, counting the products chosen for the slots of a bundle.
+fn demand(lines: &BTreeMap<i64, OrderLine>) -> HashMap<ProductId, i64> {
+    lines.values()
+        .fold(HashMap::new(), |mut acc, line| {
+            let products = std::iter::once(line.product())
+                .chain(line.components().iter().map(|component| component.product()));
+            for product in products {
+                *acc.entry(*product).or_default() += line.quantity().as_ref();
+            }
+            acc
+        })
+}
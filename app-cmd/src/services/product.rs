@@ -4,6 +4,7 @@ use kernel::entities::product::{Product, ProductId};
 use kernel::io::commands::ProductCommand;
 
 use crate::adapter::{self, DependOnEventProjector, DependOnProcessManager};
+use crate::audit::{self, Actor, AuditRecord, DependOnAuditLog};
 use crate::errors::ApplicationError;
 
 
@@ -12,6 +13,7 @@ where
     T
     : DependOnProcessManager
     + DependOnEventProjector
+    + DependOnAuditLog
 {}
 
 
@@ -25,35 +27,41 @@ pub trait ProductCommandService: 'static + Sync + Send
 where
     Self: DependOnProcessManager
         + DependOnEventProjector
+        + DependOnAuditLog
 {
-    async fn execute<I>(&self, id: I, cmd: ProductCommand) -> Result<(), Report<ApplicationError>>
+    /// Returns the id of the product, which is newly generated for `ProductCommand::Register`.
+    async fn execute<I>(&self, actor: &Actor, id: I, cmd: ProductCommand) -> Result<ProductId, Report<ApplicationError>>
         where 
             I: Into<Option<ProductId>> + Sync + Send,
     {
         let manager = self.process_manager();
         
-        let refs = if let ProductCommand::Register { .. } = &cmd {
+        let (id, refs) = if let ProductCommand::Register { .. } = &cmd {
             let id = ProductId::default();
             
             let product = Product::try_from((id, cmd.clone()))
                 .change_context_lazy(|| ApplicationError::Formation)?;
             
-            manager.spawn(id, product, 0).await
-                .change_context_lazy(|| ApplicationError::Process)?
+            (id, manager.spawn(id, product, 0).await
+                .change_context_lazy(|| ApplicationError::Process)?)
         } else {
             let id = id.into()
                 .ok_or(ApplicationError::RequiredId)?;
 
-            adapter::utils::find_or_replay(id, manager, self.event_projector()).await?
+            (id, adapter::utils::find_or_replay(id, manager, self.event_projector()).await?)
         };
         
         let event = refs.publish(cmd).await
             .change_context_lazy(|| ApplicationError::Process)?
             .change_context_lazy(|| ApplicationError::Kernel)?;
         
-        refs.apply(event).await
+        let record = AuditRecord::new(actor, "product", id, &event);
+        
+        let sequence = refs.apply(event).await
             .change_context_lazy(|| ApplicationError::Process)?;
         
-        Ok(())
+        audit::audit(self.audit_log(), record, sequence).await;
+        
+        Ok(id)
     }
 }
\ No newline at end of file
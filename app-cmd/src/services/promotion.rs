@@ -0,0 +1,64 @@
+use async_trait::async_trait;
+use error_stack::{Report, ResultExt};
+use kernel::entities::promotion::{CouponCode, Promotion};
+use kernel::io::commands::PromotionCommand;
+
+use crate::adapter::{self, DependOnEventProjector, DependOnProcessManager};
+use crate::errors::ApplicationError;
+
+
+impl<T> PromotionCommandService for T 
+where 
+    T
+    : DependOnProcessManager
+    + DependOnEventProjector
+{}
+
+
+pub trait DependOnPromotionCommandService: 'static + Sync + Send {
+    type PromotionCommandService: PromotionCommandService;
+    fn promotion_command_service(&self) -> &Self::PromotionCommandService;
+}
+
+#[async_trait]
+pub trait PromotionCommandService: 'static + Sync + Send
+where
+    Self: DependOnProcessManager
+        + DependOnEventProjector
+{
+    /// Promotions are identified by their [`CouponCode`], 
+    /// so creating a promotion with a code that is already taken is rejected with [`ApplicationError::InvalidCommand`].
+    async fn execute(&self, code: CouponCode, cmd: PromotionCommand) -> Result<(), Report<ApplicationError>> {
+        let manager = self.process_manager();
+        let projector = self.event_projector();
+        
+        let refs = if let PromotionCommand::Create { .. } = &cmd {
+            let taken = manager.find::<Promotion>(code.clone()).await
+                .change_context_lazy(|| ApplicationError::Process)?
+                .is_some()
+                || projector.projection_to_latest::<Promotion>(code.clone(), None).await.is_ok();
+            
+            if taken {
+                return Err(Report::new(ApplicationError::InvalidCommand)
+                    .attach_printable(format!("Coupon={code} is already taken")));
+            }
+            
+            let promotion = Promotion::try_from((code.clone(), cmd.clone()))
+                .change_context_lazy(|| ApplicationError::Formation)?;
+            
+            manager.spawn(code, promotion, 0).await
+                .change_context_lazy(|| ApplicationError::Process)?
+        } else {
+            adapter::utils::find_or_replay(code, manager, projector).await?
+        };
+        
+        let event = refs.publish(cmd).await
+            .change_context_lazy(|| ApplicationError::Process)?
+            .change_context_lazy(|| ApplicationError::Kernel)?;
+        
+        refs.apply(event).await
+            .change_context_lazy(|| ApplicationError::Process)?;
+        
+        Ok(())
+    }
+}
@@ -0,0 +1,64 @@
+use async_trait::async_trait;
+use error_stack::{Report, ResultExt};
+use kernel::entities::staff::{Staff, StaffName};
+use kernel::io::commands::StaffCommand;
+
+use crate::adapter::{self, DependOnEventProjector, DependOnProcessManager};
+use crate::errors::ApplicationError;
+
+
+impl<T> StaffCommandService for T 
+where 
+    T
+    : DependOnProcessManager
+    + DependOnEventProjector
+{}
+
+
+pub trait DependOnStaffCommandService: 'static + Sync + Send {
+    type StaffCommandService: StaffCommandService;
+    fn staff_command_service(&self) -> &Self::StaffCommandService;
+}
+
+#[async_trait]
+pub trait StaffCommandService: 'static + Sync + Send
+where
+    Self: DependOnProcessManager
+        + DependOnEventProjector
+{
+    /// Staff are identified by their [`StaffName`], 
+    /// so registering a name that is already taken is rejected with [`ApplicationError::InvalidCommand`].
+    async fn execute(&self, name: StaffName, cmd: StaffCommand) -> Result<(), Report<ApplicationError>> {
+        let manager = self.process_manager();
+        let projector = self.event_projector();
+        
+        let refs = if let StaffCommand::Register { .. } = &cmd {
+            let taken = manager.find::<Staff>(name.clone()).await
+                .change_context_lazy(|| ApplicationError::Process)?
+                .is_some()
+                || projector.projection_to_latest::<Staff>(name.clone(), None).await.is_ok();
+            
+            if taken {
+                return Err(Report::new(ApplicationError::InvalidCommand)
+                    .attach_printable(format!("Staff={name} is already taken")));
+            }
+            
+            let staff = Staff::try_from((name.clone(), cmd.clone()))
+                .change_context_lazy(|| ApplicationError::Formation)?;
+            
+            manager.spawn(name, staff, 0).await
+                .change_context_lazy(|| ApplicationError::Process)?
+        } else {
+            adapter::utils::find_or_replay(name, manager, projector).await?
+        };
+        
+        let event = refs.publish(cmd).await
+            .change_context_lazy(|| ApplicationError::Process)?
+            .change_context_lazy(|| ApplicationError::Kernel)?;
+        
+        refs.apply(event).await
+            .change_context_lazy(|| ApplicationError::Process)?;
+        
+        Ok(())
+    }
+}
@@ -0,0 +1,52 @@
+use async_trait::async_trait;
+use error_stack::{Report, ResultExt};
+use kernel::entities::ticket::TicketCounter;
+use kernel::io::commands::TicketCounterCommand;
+
+use crate::adapter::{DependOnEventProjector, DependOnProcessManager};
+use crate::errors::ApplicationError;
+
+impl<T> TicketCounterCommandService for T 
+where 
+    T
+    : DependOnProcessManager
+    + DependOnEventProjector {}
+
+
+pub trait DependOnTicketCounterCommandService: 'static + Sync + Send {
+    type TicketCounterCommandService: TicketCounterCommandService;
+    fn ticket_counter_command_service(&self) -> &Self::TicketCounterCommandService;
+}
+
+
+#[async_trait]
+pub trait TicketCounterCommandService: 'static + Sync + Send 
+where
+    Self: DependOnProcessManager
+        + DependOnEventProjector
+{
+    async fn execute(&self, cmd: TicketCounterCommand) -> Result<(), Report<ApplicationError>> {
+        let manager = self.process_manager();
+        
+        let refs = match manager.find::<TicketCounter>(TicketCounter::ID).await
+            .change_context_lazy(|| ApplicationError::Process)? {
+            Some(refs) => refs,
+            None => {
+                let projector = self.event_projector();
+                let replay = projector.projection_to_latest::<TicketCounter>(TicketCounter::ID, (TicketCounter::default(), 0)).await
+                    .change_context_lazy(|| ApplicationError::Formation)?;
+                manager.spawn(TicketCounter::ID, replay.0, replay.1).await
+                    .change_context_lazy(|| ApplicationError::Process)?
+            }
+        };
+        
+        let event = refs.publish(cmd).await
+            .change_context_lazy(|| ApplicationError::Process)?
+            .change_context_lazy(|| ApplicationError::Kernel)?;
+        
+        refs.apply(event).await
+            .change_context_lazy(|| ApplicationError::Process)?;
+        
+        Ok(())
+    }
+}
@@ -1 +1,4 @@
-pub mod product;
\ No newline at end of file
+pub mod device;
+pub mod order;
+pub mod product;
+pub mod staff;
\ No newline at end of file
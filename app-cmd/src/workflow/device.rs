@@ -0,0 +1,40 @@
+use async_trait::async_trait;
+use error_stack::{Report, ResultExt};
+use kernel::entities::device::{ApiKey, Device, DeviceId, StoreCode};
+
+use crate::adapter::DependOnEventProjector;
+use crate::errors::ApplicationError;
+
+impl<T> AuthenticateDeviceWorkflow for T 
+where 
+    T: DependOnEventProjector
+{}
+
+pub trait DependOnAuthenticateDeviceWorkflow: 'static + Sync + Send {
+    type AuthenticateDeviceWorkflow: AuthenticateDeviceWorkflow;
+    fn authenticate_device_workflow(&self) -> &Self::AuthenticateDeviceWorkflow;
+}
+
+/// Verifies the [`ApiKey`] a [`Device`] presents on behalf of the given store, returning the device on success.
+///
+/// The device is replayed from the journal every time, so that a revocation takes effect on the very next request.
+/// Unknown and revoked devices, devices of another store and wrong keys are all rejected with 
+/// [`ApplicationError::Unauthenticated`].
+#[async_trait]
+pub trait AuthenticateDeviceWorkflow: 'static + Send + Sync 
+where
+    Self: DependOnEventProjector
+{
+    async fn execute(&self, id: DeviceId, key: ApiKey, store: &StoreCode) -> Result<Device, Report<ApplicationError>> {
+        let (device, _) = self.event_projector().projection_to_latest::<Device>(id, None).await
+            .change_context_lazy(|| ApplicationError::Unauthenticated)
+            .attach_printable_lazy(|| format!("Device={id} could not be found"))?;
+        
+        if !device.authenticate(&key, store) {
+            return Err(Report::new(ApplicationError::Unauthenticated)
+                .attach_printable(format!("Device={id} failed to authenticate for Store={store}")));
+        }
+        
+        Ok(device)
+    }
+}
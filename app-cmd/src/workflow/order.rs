@@ -0,0 +1,187 @@
+use crate::adapter::{self, DependOnEventProjector, DependOnProcessManager, DependOnTaxRates};
+use crate::errors::ApplicationError;
+use std::collections::{BTreeSet, HashMap};
+
+use async_trait::async_trait;
+use error_stack::{Report, ResultExt};
+use kernel::entities::category::Category;
+use kernel::entities::order::{AppliedCoupon, LineTax, Order, OrderId, OrderQuantity};
+use kernel::entities::product::{BundleChoice, OptionId, Product, ProductId};
+use kernel::entities::promotion::{CouponCode, Promotion, PromotionScope};
+use kernel::io::commands::{OrderCommand, PromotionCommand};
+use nitinol::projection::EventProjector;
+use time::OffsetDateTime;
+
+impl<T> AddProductToOrderWorkflow for T 
+where 
+    T
+    : DependOnProcessManager
+    + DependOnEventProjector
+    + DependOnTaxRates
+{}
+
+pub trait DependOnAddProductToOrderWorkflow: 'static + Sync + Send {
+    type AddProductToOrderWorkflow: AddProductToOrderWorkflow;
+    fn add_product_to_order_workflow(&self) -> &Self::AddProductToOrderWorkflow;
+}
+
+/// Adds a [`Product`] to an [`Order`], snapshotting the current price of the product
+/// so that the order is not affected by later price changes.
+///
+/// The selected options are validated against the option groups of the product
+/// and snapshotted along with their price deltas, as is the current rate of its tax category.
+///
+/// Products that are marked as sold out, or do not have enough stock left,
+/// are rejected with [`ApplicationError::InvalidCommand`], as are invalid option selections.
+/// For a bundle, the same applies to every product chosen for its slots.
+#[async_trait]
+pub trait AddProductToOrderWorkflow: 'static + Send + Sync 
+where
+    Self: DependOnProcessManager
+        + DependOnEventProjector
+        + DependOnTaxRates
+{
+    async fn execute(
+        &self,
+        order_id: OrderId,
+        product_id: ProductId,
+        quantity: OrderQuantity,
+        options: Vec<OptionId>,
+        components: Vec<BundleChoice>,
+    ) -> Result<(), Report<ApplicationError>> {
+        let manager = self.process_manager();
+        let projector = self.event_projector();
+        
+        let product = orderable(projector, product_id, *quantity.as_ref()).await?;
+        
+        let options = product.select_options(&options)
+            .change_context_lazy(|| ApplicationError::InvalidCommand)?;
+        
+        let components = product.choose_components(&components)
+            .change_context_lazy(|| ApplicationError::InvalidCommand)?;
+        
+        // A product chosen for several slots needs enough stock for all of them.
+        let demand = components.iter()
+            .fold(HashMap::<ProductId, i64>::new(), |mut acc, component| {
+                *acc.entry(*component.product()).or_default() += quantity.as_ref();
+                acc
+            });
+        
+        for (component, demand) in demand {
+            orderable(projector, component, demand).await?;
+        }
+        
+        let order = adapter::utils::find_or_replay::<Order>(order_id, manager, projector).await?;
+        
+        let cmd = OrderCommand::AddLine {
+            product: product_id,
+            quantity,
+            price: product.price().clone(),
+            options,
+            components,
+            tax: LineTax::of(product.tax(), self.tax_rates()),
+        };
+        
+        order.employ(cmd).await
+            .change_context_lazy(|| ApplicationError::Process)?
+            .change_context_lazy(|| ApplicationError::Kernel)?;
+        
+        Ok(())
+    }
+}
+
+impl<T> RedeemCouponWorkflow for T 
+where 
+    T
+    : DependOnProcessManager
+    + DependOnEventProjector
+{}
+
+pub trait DependOnRedeemCouponWorkflow: 'static + Sync + Send {
+    type RedeemCouponWorkflow: RedeemCouponWorkflow;
+    fn redeem_coupon_workflow(&self) -> &Self::RedeemCouponWorkflow;
+}
+
+/// Redeems the [`Promotion`] of a coupon code on an [`Order`], 
+/// recording the redemption on the promotion and applying its discount to the order.
+///
+/// A promotion limited to categories is resolved into the products of those categories at this point.
+///
+/// Unknown codes are rejected with [`ApplicationError::NotFound`], while promotions that
+/// are exhausted, ended or out of their validity, and orders that can no longer take a coupon, 
+/// are rejected with [`ApplicationError::InvalidCommand`].
+#[async_trait]
+pub trait RedeemCouponWorkflow: 'static + Send + Sync 
+where
+    Self: DependOnProcessManager
+        + DependOnEventProjector
+{
+    async fn execute(&self, order_id: OrderId, code: CouponCode) -> Result<(), Report<ApplicationError>> {
+        let manager = self.process_manager();
+        let projector = self.event_projector();
+        
+        let (promotion, _) = projector.projection_to_latest::<Promotion>(code.clone(), None).await
+            .change_context_lazy(|| ApplicationError::NotFound)
+            .attach_printable_lazy(|| format!("Coupon={code} could not be found"))?;
+        
+        let products = match promotion.scope() {
+            PromotionScope::Order => None,
+            PromotionScope::Products(ids) => Some(ids.clone()),
+            PromotionScope::Categories(ids) => {
+                let mut products = BTreeSet::new();
+                // Categories deleted since the promotion was created no longer cover any products.
+                for id in ids {
+                    let Some(category) = adapter::utils::project::<Category>(*id, projector).await
+                        .attach_printable_lazy(|| format!("Category={id} could not be found"))?
+                    else {
+                        continue;
+                    };
+                    products.extend(category.products().values().copied());
+                }
+                Some(products)
+            }
+        };
+        
+        let coupon = AppliedCoupon::new(code.clone(), promotion.discount().clone(), products);
+        
+        // The order is validated before the redemption is recorded, 
+        // but only changed once the promotion has accepted it.
+        let order = adapter::utils::find_or_replay::<Order>(order_id, manager, projector).await?;
+        
+        let event = order.publish(OrderCommand::ApplyCoupon { coupon }).await
+            .change_context_lazy(|| ApplicationError::Process)?
+            .change_context_lazy(|| ApplicationError::InvalidCommand)?;
+        
+        let promotion = adapter::utils::find_or_replay::<Promotion>(code, manager, projector).await?;
+        
+        promotion.employ(PromotionCommand::Redeem { order: order_id, at: OffsetDateTime::now_utc() }).await
+            .change_context_lazy(|| ApplicationError::Process)?
+            .change_context_lazy(|| ApplicationError::InvalidCommand)?;
+        
+        order.apply(event).await
+            .change_context_lazy(|| ApplicationError::Process)?;
+        
+        Ok(())
+    }
+}
+
+async fn orderable(projector: &EventProjector, product_id: ProductId, demand: i64) -> Result<Product, Report<ApplicationError>> {
+    let Some(product) = adapter::utils::project::<Product>(product_id, projector).await
+        .attach_printable_lazy(|| format!("Product={product_id} could not be found"))?
+    else {
+        return Err(Report::new(ApplicationError::NotFound)
+            .attach_printable(format!("Product={product_id} has been deleted")));
+    };
+    
+    if !product.available() {
+        return Err(Report::new(ApplicationError::InvalidCommand)
+            .attach_printable(format!("Product={product_id} is sold out")));
+    }
+    
+    if product.stock().is_some_and(|stock| stock.as_ref() < &demand) {
+        return Err(Report::new(ApplicationError::InvalidCommand)
+            .attach_printable(format!("Product={product_id} does not have enough stock")));
+    }
+    
+    Ok(product)
+}
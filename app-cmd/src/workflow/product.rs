@@ -1,16 +1,22 @@
-use crate::adapter::{self, DependOnEventProjector, DependOnProcessManager};
+use crate::adapter::{self, DependOnEventProjector};
+use crate::audit::Actor;
 use crate::errors::ApplicationError;
+use crate::services::category::{CategoryCommandService, DependOnCategoryCommandService};
+use crate::services::product::{DependOnProductCommandService, ProductCommandService};
+use std::collections::BTreeSet;
 use async_trait::async_trait;
 use error_stack::{Report, ResultExt};
 use kernel::entities::category::{Category, CategoryId};
-use kernel::entities::product::{Product, ProductId};
+use kernel::entities::product::{BundleComponent, Product, ProductId, SlotId, SlotName};
 use kernel::io::commands::{CategoryCommand, ProductCommand};
+use nitinol::projection::EventProjector;
 
 impl<T> RegisterProductWithCategoryWorkflow for T 
 where 
     T
-    : DependOnProcessManager
-    + DependOnEventProjector
+    : DependOnEventProjector
+    + DependOnProductCommandService
+    + DependOnCategoryCommandService
 {}
 
 pub trait DependOnRegisterProductWithCategoryWorkflow: 'static + Sync + Send {
@@ -18,39 +24,166 @@ pub trait DependOnRegisterProductWithCategoryWorkflow: 'static + Sync + Send {
     fn register_product_with_category_workflow(&self) -> &Self::RegisterProductWithCategoryWorkflow;
 }
 
+/// Registers a product and adds it to a category, dispatching both commands 
+/// through their services so that each of them is audited as `actor`.
+/// 
+/// An unknown or deleted category is rejected with [`ApplicationError::NotFound`] before the product is registered.
 #[async_trait]
 pub trait RegisterProductWithCategoryWorkflow: 'static + Send + Sync 
 where
-    Self: DependOnProcessManager
-        + DependOnEventProjector
+    Self: DependOnEventProjector
+        + DependOnProductCommandService
+        + DependOnCategoryCommandService
 {
-    async fn execute(&self, category_id: CategoryId, reg: ProductCommand) -> Result<(), Report<ApplicationError>> {
-        let manager = self.process_manager();
-        
+    async fn execute(&self, actor: &Actor, category_id: CategoryId, reg: ProductCommand) -> Result<ProductId, Report<ApplicationError>> {
         let ProductCommand::Register { .. } = &reg else {
             return Err(Report::new(ApplicationError::InvalidCommand)
                 .attach_printable("Workflow only accepts `ProductCommand::Register { .. }`."));
         };
         
-        let category = adapter::utils::find_or_replay::<Category>(category_id, manager, self.event_projector()).await?;
+        if adapter::utils::project::<Category>(category_id, self.event_projector()).await?.is_none() {
+            return Err(Report::new(ApplicationError::NotFound)
+                .attach_printable(format!("Category={category_id} has been deleted")));
+        }
+        
+        let product_id = ProductCommandService::execute(self.product_command_service(), actor, None, reg).await?;
         
-        let product_id = ProductId::default();
-        let product = Product::try_from((product_id, reg.clone()))
-            .change_context_lazy(|| ApplicationError::Formation)?;
+        let cmd = CategoryCommand::AddProduct { id: product_id };
+        CategoryCommandService::execute(self.category_command_service(), actor, category_id, cmd).await?;
         
-        let product = manager.spawn(product_id, product, 0).await
-            .change_context_lazy(|| ApplicationError::Process)?;
+        Ok(product_id)
+    }
+}
+
+impl<T> ComposeBundleWorkflow for T 
+where 
+    T
+    : DependOnEventProjector
+    + DependOnProductCommandService
+{}
+
+pub trait DependOnComposeBundleWorkflow: 'static + Sync + Send {
+    type ComposeBundleWorkflow: ComposeBundleWorkflow;
+    fn compose_bundle_workflow(&self) -> &Self::ComposeBundleWorkflow;
+}
+
+/// Composes a bundle (set menu) out of registered products.
+/// 
+/// Every choice is looked up before it is handed to the bundle,
+/// so unknown products are rejected with [`ApplicationError::NotFound`].
+/// 
+/// The chosen products keep track of the bundles offering them,
+/// so that they are neither deleted nor turned into bundles themselves while they are offered.
+#[async_trait]
+pub trait ComposeBundleWorkflow: 'static + Send + Sync 
+where
+    Self: DependOnEventProjector
+        + DependOnProductCommandService
+{
+    async fn add_slot(&self, actor: &Actor, bundle: ProductId, name: SlotName, choices: Vec<ProductId>) -> Result<(), Report<ApplicationError>> {
+        let choices = components(self.event_projector(), choices).await?;
         
-        product.employ(reg).await
-            .change_context_lazy(|| ApplicationError::Process)?
-            .change_context_lazy(|| ApplicationError::Kernel)?;
+        recompose(self.product_command_service(), self.event_projector(), actor, bundle, ProductCommand::AddBundleSlot { name, choices }).await
+    }
+    
+    async fn change_choices(&self, actor: &Actor, bundle: ProductId, slot: SlotId, choices: Vec<ProductId>) -> Result<(), Report<ApplicationError>> {
+        let choices = components(self.event_projector(), choices).await?;
         
-        let cmd = CategoryCommand::AddProduct { id: product_id };
+        recompose(self.product_command_service(), self.event_projector(), actor, bundle, ProductCommand::ChangeBundleChoices { slot, choices }).await
+    }
+    
+    async fn remove_slot(&self, actor: &Actor, bundle: ProductId, slot: SlotId) -> Result<(), Report<ApplicationError>> {
+        recompose(self.product_command_service(), self.event_projector(), actor, bundle, ProductCommand::RemoveBundleSlot { slot }).await
+    }
+}
+
+impl<T> DeleteProductWorkflow for T 
+where 
+    T
+    : DependOnEventProjector
+    + DependOnProductCommandService
+{}
+
+pub trait DependOnDeleteProductWorkflow: 'static + Sync + Send {
+    type DeleteProductWorkflow: DeleteProductWorkflow;
+    fn delete_product_workflow(&self) -> &Self::DeleteProductWorkflow;
+}
+
+/// Deletes a product, releasing the products that a bundle offered so that they can be deleted in turn.
+/// 
+/// A product that is still offered by a bundle is rejected with [`ApplicationError::Kernel`],
+/// and has to be removed from the slots of that bundle first.
+#[async_trait]
+pub trait DeleteProductWorkflow: 'static + Send + Sync 
+where
+    Self: DependOnEventProjector
+        + DependOnProductCommandService
+{
+    async fn execute(&self, actor: &Actor, id: ProductId) -> Result<(), Report<ApplicationError>> {
+        let service = self.product_command_service();
+        
+        let offered = offered(self.event_projector(), id).await?;
         
-        category.employ(cmd).await
-            .change_context_lazy(|| ApplicationError::Process)?
-            .change_context_lazy(|| ApplicationError::Kernel)?;
+        ProductCommandService::execute(service, actor, id, ProductCommand::Delete).await?;
+        
+        for component in offered {
+            ProductCommandService::execute(service, actor, component, ProductCommand::LeaveBundle { bundle: id }).await?;
+        }
         
         Ok(())
     }
-}
\ No newline at end of file
+}
+
+async fn components(projector: &EventProjector, choices: Vec<ProductId>) -> Result<Vec<BundleComponent>, Report<ApplicationError>> {
+    let mut components = Vec::with_capacity(choices.len());
+    for choice in choices {
+        let Some(product) = adapter::utils::project::<Product>(choice, projector).await
+            .attach_printable_lazy(|| format!("Product={choice} could not be found"))?
+        else {
+            return Err(Report::new(ApplicationError::NotFound)
+                .attach_printable(format!("Product={choice} has been deleted")));
+        };
+        
+        components.push(BundleComponent::from(&product));
+    }
+    Ok(components)
+}
+
+/// Applies `cmd` to the bundle, then lets the products that became or stopped being 
+/// one of its choices know about it.
+async fn recompose(
+    service: &impl ProductCommandService,
+    projector: &EventProjector,
+    actor: &Actor,
+    bundle: ProductId,
+    cmd: ProductCommand,
+) -> Result<(), Report<ApplicationError>> {
+    let before = offered(projector, bundle).await?;
+    
+    ProductCommandService::execute(service, actor, bundle, cmd).await?;
+    
+    let after = offered(projector, bundle).await?;
+    
+    for joined in after.difference(&before) {
+        ProductCommandService::execute(service, actor, *joined, ProductCommand::JoinBundle { bundle }).await?;
+    }
+    
+    for left in before.difference(&after) {
+        ProductCommandService::execute(service, actor, *left, ProductCommand::LeaveBundle { bundle }).await?;
+    }
+    
+    Ok(())
+}
+
+/// Products offered in any slot of `bundle`.
+async fn offered(projector: &EventProjector, bundle: ProductId) -> Result<BTreeSet<ProductId>, Report<ApplicationError>> {
+    let Some(projected) = adapter::utils::project::<Product>(bundle, projector).await
+        .attach_printable_lazy(|| format!("Product={bundle} could not be found"))?
+    else {
+        return Ok(BTreeSet::new());
+    };
+    
+    Ok(projected.bundle().iter()
+        .flat_map(|slot| slot.choices().iter().copied())
+        .collect())
+}
@@ -0,0 +1,72 @@
+use async_trait::async_trait;
+use error_stack::{Report, ResultExt};
+use kernel::entities::staff::{Staff, StaffName};
+
+use crate::adapter::DependOnEventProjector;
+use crate::errors::ApplicationError;
+
+impl<T> AuthenticateStaffWorkflow for T 
+where 
+    T: DependOnEventProjector
+{}
+
+pub trait DependOnAuthenticateStaffWorkflow: 'static + Sync + Send {
+    type AuthenticateStaffWorkflow: AuthenticateStaffWorkflow;
+    fn authenticate_staff_workflow(&self) -> &Self::AuthenticateStaffWorkflow;
+}
+
+/// Verifies the password of a [`Staff`] signing in, returning the staff on success.
+///
+/// Unknown names, removed staff and wrong passwords are all rejected with 
+/// [`ApplicationError::Unauthenticated`], so that callers cannot tell which names exist.
+#[async_trait]
+pub trait AuthenticateStaffWorkflow: 'static + Send + Sync 
+where
+    Self: DependOnEventProjector
+{
+    async fn execute(&self, name: StaffName, password: String) -> Result<Staff, Report<ApplicationError>> {
+        let (staff, _) = self.event_projector().projection_to_latest::<Staff>(name.clone(), None).await
+            .change_context_lazy(|| ApplicationError::Unauthenticated)
+            .attach_printable_lazy(|| format!("Staff={name} could not be found"))?;
+        
+        if !staff.authenticate(password) {
+            return Err(Report::new(ApplicationError::Unauthenticated)
+                .attach_printable(format!("Staff={name} failed to authenticate")));
+        }
+        
+        Ok(staff)
+    }
+}
+
+impl<T> VerifyStaffSessionWorkflow for T 
+where 
+    T: DependOnEventProjector
+{}
+
+pub trait DependOnVerifyStaffSessionWorkflow: 'static + Sync + Send {
+    type VerifyStaffSessionWorkflow: VerifyStaffSessionWorkflow;
+    fn verify_staff_session_workflow(&self) -> &Self::VerifyStaffSessionWorkflow;
+}
+
+/// Looks up the [`Staff`] behind a signed-in session, returning the staff as they are now.
+///
+/// A session outlives the sign-in, so the staff is replayed from the journal on every request,
+/// and a removal takes effect on the very next request rather than when the session expires.
+#[async_trait]
+pub trait VerifyStaffSessionWorkflow: 'static + Send + Sync 
+where
+    Self: DependOnEventProjector
+{
+    async fn execute(&self, name: StaffName) -> Result<Staff, Report<ApplicationError>> {
+        let (staff, _) = self.event_projector().projection_to_latest::<Staff>(name.clone(), None).await
+            .change_context_lazy(|| ApplicationError::Unauthenticated)
+            .attach_printable_lazy(|| format!("Staff={name} could not be found"))?;
+        
+        if staff.removed() {
+            return Err(Report::new(ApplicationError::Unauthenticated)
+                .attach_printable(format!("Staff={name} has been removed")));
+        }
+        
+        Ok(staff)
+    }
+}
@@ -1,13 +1,17 @@
 use std::collections::BTreeMap;
 use app_cmd::services::category::{CategoryCommandService, DependOnCategoryCommandService};
 use kernel::entities::category::{CategoryId, CategoryName};
-use kernel::io::commands::CategoryCommand;
+use kernel::io::commands::{CategoryCommand, ProductCommand};
 use kernel::io::events::CategoryEvent;
 use nitinol::Event;
 use tracing_subscriber::layer::SubscriberExt;
 use tracing_subscriber::util::SubscriberInitExt;
 use app_cmd::services::categories::DependOnCategoriesCommandService;
-use kernel::entities::product::ProductId;
+use app_cmd::services::product::DependOnProductCommandService;
+use app_cmd::workflow::product::{DependOnRegisterProductWithCategoryWorkflow, RegisterProductWithCategoryWorkflow};
+use kernel::entities::money::{Currency, Money};
+use kernel::entities::product::{ProductDesc, ProductId, ProductName, ProductPrice};
+use kernel::entities::staff::StaffName;
 
 include!("./test_framework.rs");
 
@@ -25,6 +29,20 @@ impl DependOnCategoriesCommandService for TestFramework {
     }
 }
 
+impl DependOnProductCommandService for TestFramework {
+    type ProductCommandService = Self;
+    fn product_command_service(&self) -> &Self::ProductCommandService {
+        self
+    }
+}
+
+impl DependOnRegisterProductWithCategoryWorkflow for TestFramework {
+    type RegisterProductWithCategoryWorkflow = Self;
+    fn register_product_with_category_workflow(&self) -> &Self::RegisterProductWithCategoryWorkflow {
+        self
+    }
+}
+
 fn setup_logging() {
     std::env::set_var("RUST_LOG", "trace");
     tracing_subscriber::registry().with(tracing_subscriber::fmt::layer()).init();
@@ -51,7 +69,7 @@ async fn create_category(framework: &TestFramework) -> Result<(), Report<Unrecov
             .change_context_lazy(|| UnrecoverableError)?,
     };
     
-    service.execute(None, cmd).await
+    service.execute(&Actor::system(), None, cmd).await
         .change_context_lazy(|| UnrecoverableError)?;
     
     Ok(())
@@ -77,7 +95,7 @@ async fn rename_category(id: CategoryId, framework: &TestFramework) -> Result<()
             .change_context_lazy(|| UnrecoverableError)?,
     };
     
-    service.execute(id, cmd).await
+    service.execute(&Actor::system(), id, cmd).await
         .change_context_lazy(|| UnrecoverableError)?;
     
     Ok(())
@@ -106,7 +124,7 @@ async fn delete_category(id: CategoryId, framework: &TestFramework) -> Result<()
     
     let cmd = CategoryCommand::Delete;
     
-    service.execute(id, cmd).await
+    service.execute(&Actor::system(), id, cmd).await
         .change_context_lazy(|| UnrecoverableError)?;
     
     Ok(())
@@ -141,7 +159,7 @@ async fn add_product_to_category(
         id: product_id,
     };
     
-    service.execute(category_id, cmd).await
+    service.execute(&Actor::system(), category_id, cmd).await
         .change_context_lazy(|| UnrecoverableError)?;
     
     Ok(())
@@ -165,6 +183,54 @@ async fn test_add_product_to_category() -> Result<(), Report<UnrecoverableError>
     Ok(())
 }
 
+#[tokio::test]
+async fn test_register_product_with_category() -> Result<(), Report<UnrecoverableError>> {
+    let framework = TestFramework::new()?;
+    
+    create_category(&framework).await?;
+    let CategoryEvent::Created { id, .. } = extract_first_event(&framework).await? else {
+        return Err(Report::new(UnrecoverableError)
+            .attach_printable("Event is not a Created event"));
+    };
+    
+    let actor = Actor {
+        staff: Some(StaffName::new("manager").change_context_lazy(|| UnrecoverableError)?),
+        ..Actor::system()
+    };
+    
+    let reg = ProductCommand::Register {
+        name: ProductName::new("test"),
+        desc: ProductDesc::new("test desc"),
+        price: ProductPrice::new(Money::new(100, Currency::default())).change_context_lazy(|| UnrecoverableError)?,
+        image: vec![],
+    };
+    
+    let product = RegisterProductWithCategoryWorkflow::execute(framework.register_product_with_category_workflow(), &actor, id, reg.clone()).await
+        .change_context_lazy(|| UnrecoverableError)?;
+    
+    let audited = framework.audit().into_iter()
+        .filter(|record| record.actor.staff.is_some())
+        .map(|record| (record.aggregate, record.aggregate_id, record.event))
+        .collect::<Vec<_>>();
+    
+    let expected = vec![
+        ("product", product.to_string(), "Registered".to_string()),
+        ("category", id.to_string(), "AddedProduct".to_string()),
+    ];
+    
+    if audited != expected {
+        return Err(Report::new(UnrecoverableError)
+            .attach_printable(format!("Both commands must be audited as the staff, but were {audited:?}")));
+    }
+    
+    if RegisterProductWithCategoryWorkflow::execute(framework.register_product_with_category_workflow(), &actor, CategoryId::default(), reg).await.is_ok() {
+        return Err(Report::new(UnrecoverableError)
+            .attach_printable("Product must not be registered into an unknown category"));
+    }
+    
+    Ok(())
+}
+
 async fn remove_product_from_category(
     category_id: CategoryId,
     product_id: ProductId,
@@ -176,7 +242,7 @@ async fn remove_product_from_category(
         id: product_id,
     };
     
-    service.execute(category_id, cmd).await
+    service.execute(&Actor::system(), category_id, cmd).await
         .change_context_lazy(|| UnrecoverableError)?;
     
     Ok(())
@@ -212,7 +278,7 @@ async fn change_ordering_product_in_category(
     
     let cmd = CategoryCommand::ChangeProductOrdering { new };
     
-    service.execute(category_id, cmd).await
+    service.execute(&Actor::system(), category_id, cmd).await
         .change_context_lazy(|| UnrecoverableError)?;
     
     Ok(())
@@ -0,0 +1,141 @@
+use tracing_subscriber::EnvFilter;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use app_cmd::services::device::{DependOnDeviceCommandService, DeviceCommandService};
+use app_cmd::workflow::device::{AuthenticateDeviceWorkflow, DependOnAuthenticateDeviceWorkflow};
+use kernel::entities::device::{ApiKey, DeviceId, DeviceName, StoreCode};
+use kernel::entities::staff::Role;
+use kernel::io::commands::DeviceCommand;
+
+include!("./test_framework.rs");
+
+//noinspection RsTraitImplOrphanRules
+impl DependOnDeviceCommandService for TestFramework {
+    type DeviceCommandService = Self;
+    fn device_command_service(&self) -> &Self::DeviceCommandService {
+        self
+    }
+}
+
+//noinspection RsTraitImplOrphanRules
+impl DependOnAuthenticateDeviceWorkflow for TestFramework {
+    type AuthenticateDeviceWorkflow = Self;
+    fn authenticate_device_workflow(&self) -> &Self::AuthenticateDeviceWorkflow {
+        self
+    }
+}
+
+fn setup_logging() {
+    let _ = tracing_subscriber::registry()
+        .with(EnvFilter::new("trace"))
+        .with(tracing_subscriber::fmt::layer())
+        .try_init();
+}
+
+async fn register_device(role: Role, key: &ApiKey, framework: &TestFramework) -> Result<DeviceId, Report<UnrecoverableError>> {
+    let cmd = DeviceCommand::Register {
+        name: DeviceName::new("kiosk"),
+        role,
+        store: store("main")?,
+        key: key.hash(),
+    };
+
+    DeviceCommandService::execute(framework.device_command_service(), None, cmd).await
+        .change_context_lazy(|| UnrecoverableError)
+}
+
+fn store(code: &str) -> Result<StoreCode, Report<UnrecoverableError>> {
+    StoreCode::new(code).change_context_lazy(|| UnrecoverableError)
+}
+
+async fn authenticates(id: DeviceId, key: &ApiKey, store: &StoreCode, framework: &TestFramework) -> bool {
+    AuthenticateDeviceWorkflow::execute(framework.authenticate_device_workflow(), id, key.clone(), store).await.is_ok()
+}
+
+#[test]
+fn test_generate_api_key() -> Result<(), Report<UnrecoverableError>> {
+    let key = ApiKey::generate();
+    let hash = key.hash();
+
+    if key.as_ref().len() != 64 || key.as_ref() == ApiKey::generate().as_ref() {
+        return Err(Report::new(UnrecoverableError).attach_printable("Key must be 256 random bits"));
+    }
+
+    if hash.as_ref() == key.as_ref() || !hash.verify(&key) || hash.verify(&ApiKey::generate()) {
+        return Err(Report::new(UnrecoverableError).attach_printable("Key must only be stored as a hash matching itself"));
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_authenticate_device() -> Result<(), Report<UnrecoverableError>> {
+    setup_logging();
+    let framework = TestFramework::new()?;
+
+    let key = ApiKey::generate();
+    let id = register_device(Role::Kiosk, &key, &framework).await?;
+
+    if !authenticates(id, &key, &store("main")?, &framework).await {
+        return Err(Report::new(UnrecoverableError).attach_printable("Device must authenticate with its key"));
+    }
+
+    if authenticates(id, &ApiKey::generate(), &store("main")?, &framework).await {
+        return Err(Report::new(UnrecoverableError).attach_printable("Wrong key must be rejected"));
+    }
+
+    if authenticates(id, &key, &store("annex")?, &framework).await {
+        return Err(Report::new(UnrecoverableError).attach_printable("Device must be rejected by another store"));
+    }
+
+    let rotated = ApiKey::generate();
+    DeviceCommandService::execute(framework.device_command_service(), id, DeviceCommand::RotateKey { key: rotated.hash() }).await
+        .change_context_lazy(|| UnrecoverableError)?;
+
+    if authenticates(id, &key, &store("main")?, &framework).await || !authenticates(id, &rotated, &store("main")?, &framework).await {
+        return Err(Report::new(UnrecoverableError).attach_printable("Only the rotated key must be accepted"));
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_revoke_device() -> Result<(), Report<UnrecoverableError>> {
+    setup_logging();
+    let framework = TestFramework::new()?;
+
+    let stolen = ApiKey::generate();
+    let stolen_id = register_device(Role::Kiosk, &stolen, &framework).await?;
+    let other = ApiKey::generate();
+    let other_id = register_device(Role::Kiosk, &other, &framework).await?;
+
+    DeviceCommandService::execute(framework.device_command_service(), stolen_id, DeviceCommand::Revoke).await
+        .change_context_lazy(|| UnrecoverableError)?;
+
+    if authenticates(stolen_id, &stolen, &store("main")?, &framework).await {
+        return Err(Report::new(UnrecoverableError).attach_printable("Revoked device must be rejected"));
+    }
+
+    if !authenticates(other_id, &other, &store("main")?, &framework).await {
+        return Err(Report::new(UnrecoverableError).attach_printable("Other devices must not be affected by a revocation"));
+    }
+
+    let cmd = DeviceCommand::RotateKey { key: ApiKey::generate().hash() };
+    if DeviceCommandService::execute(framework.device_command_service(), stolen_id, cmd).await.is_ok() {
+        return Err(Report::new(UnrecoverableError).attach_printable("Revoked device must not be given a new key"));
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_register_admin_device() -> Result<(), Report<UnrecoverableError>> {
+    setup_logging();
+    let framework = TestFramework::new()?;
+
+    if register_device(Role::Admin, &ApiKey::generate(), &framework).await.is_ok() {
+        return Err(Report::new(UnrecoverableError).attach_printable("Device must not be registered as admin"));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,905 @@
+use nitinol::Event;
+use tracing_subscriber::EnvFilter;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use app_cmd::services::order::{DependOnOrderCommandService, OrderCommandService};
+use app_cmd::services::product::{DependOnProductCommandService, ProductCommandService};
+use app_cmd::services::promotion::DependOnPromotionCommandService;
+use app_cmd::services::ticket::DependOnTicketCounterCommandService;
+use app_cmd::workflow::order::{AddProductToOrderWorkflow, DependOnAddProductToOrderWorkflow};
+use app_cmd::workflow::product::{ComposeBundleWorkflow, DeleteProductWorkflow, DependOnComposeBundleWorkflow, DependOnDeleteProductWorkflow};
+use kernel::entities::order::{Order, OrderId, OrderQuantity, OrderStatus, Tender};
+use kernel::entities::money::{Currency, Money};
+use kernel::entities::ticket::StoreOffset;
+use kernel::entities::product::{BundleChoice, OptionId, OptionName, Product, ProductDesc, ProductId, ProductName, ProductPrice, ProductStock, ProductTax, SlotName, TaxCategory};
+use kernel::io::commands::{OrderCommand, ProductCommand};
+use kernel::io::events::{ProductEvent, TicketCounterEvent};
+
+include!("./test_framework.rs");
+
+//noinspection RsTraitImplOrphanRules
+impl DependOnOrderCommandService for TestFramework {
+    type OrderCommandService = Self;
+    fn order_command_service(&self) -> &Self::OrderCommandService {
+        self
+    }
+}
+
+//noinspection RsTraitImplOrphanRules
+impl DependOnProductCommandService for TestFramework {
+    type ProductCommandService = Self;
+    fn product_command_service(&self) -> &Self::ProductCommandService {
+        self
+    }
+}
+
+//noinspection RsTraitImplOrphanRules
+impl DependOnPromotionCommandService for TestFramework {
+    type PromotionCommandService = Self;
+    fn promotion_command_service(&self) -> &Self::PromotionCommandService {
+        self
+    }
+}
+
+//noinspection RsTraitImplOrphanRules
+impl DependOnTicketCounterCommandService for TestFramework {
+    type TicketCounterCommandService = Self;
+    fn ticket_counter_command_service(&self) -> &Self::TicketCounterCommandService {
+        self
+    }
+}
+
+//noinspection RsTraitImplOrphanRules
+impl DependOnAddProductToOrderWorkflow for TestFramework {
+    type AddProductToOrderWorkflow = Self;
+    fn add_product_to_order_workflow(&self) -> &Self::AddProductToOrderWorkflow {
+        self
+    }
+}
+
+//noinspection RsTraitImplOrphanRules
+impl DependOnComposeBundleWorkflow for TestFramework {
+    type ComposeBundleWorkflow = Self;
+    fn compose_bundle_workflow(&self) -> &Self::ComposeBundleWorkflow {
+        self
+    }
+}
+
+//noinspection RsTraitImplOrphanRules
+impl DependOnDeleteProductWorkflow for TestFramework {
+    type DeleteProductWorkflow = Self;
+    fn delete_product_workflow(&self) -> &Self::DeleteProductWorkflow {
+        self
+    }
+}
+
+fn setup_logging() {
+    let _ = tracing_subscriber::registry()
+        .with(EnvFilter::new("trace"))
+        .with(tracing_subscriber::fmt::layer())
+        .try_init();
+}
+
+async fn register_product(framework: &TestFramework) -> Result<ProductId, Report<UnrecoverableError>> {
+    let cmd = ProductCommand::Register {
+        name: ProductName::new("test"),
+        desc: ProductDesc::new("test desc"),
+        price: ProductPrice::new(Money::new(100, Currency::default())).change_context_lazy(|| UnrecoverableError)?,
+        image: vec![],
+    };
+
+    ProductCommandService::execute(framework.product_command_service(), &Actor::system(), None, cmd).await
+        .change_context_lazy(|| UnrecoverableError)?;
+
+    let event = framework.journal()
+        .read_all_by_event::<ProductEvent>()
+        .await
+        .change_context_lazy(|| UnrecoverableError)?
+        .last()
+        .ok_or(Report::new(UnrecoverableError).attach_printable("No event found"))
+        .map(|payload| ProductEvent::from_bytes(&payload.bytes))?
+        .change_context_lazy(|| UnrecoverableError)?;
+
+    let ProductEvent::Registered { id, .. } = event else {
+        return Err(Report::new(UnrecoverableError).attach_printable("Unexpected event"));
+    };
+
+    Ok(id)
+}
+
+async fn place_order(framework: &TestFramework) -> Result<OrderId, Report<UnrecoverableError>> {
+    let id = OrderCommandService::execute(framework.order_command_service(), None, OrderCommand::Place).await
+        .change_context_lazy(|| UnrecoverableError)?;
+
+    Ok(id)
+}
+
+async fn add_product_to_order(
+    order: OrderId,
+    product: ProductId,
+    framework: &TestFramework
+) -> Result<(), Report<UnrecoverableError>> {
+    add_product_with_options_to_order(order, product, Vec::new(), framework).await
+}
+
+async fn add_bundle_to_order(
+    order: OrderId,
+    bundle: ProductId,
+    components: Vec<BundleChoice>,
+    framework: &TestFramework
+) -> Result<(), Report<UnrecoverableError>> {
+    let quantity = OrderQuantity::new(2)
+        .change_context_lazy(|| UnrecoverableError)?;
+
+    AddProductToOrderWorkflow::execute(framework.add_product_to_order_workflow(), order, bundle, quantity, Vec::new(), components).await
+        .change_context_lazy(|| UnrecoverableError)?;
+
+    Ok(())
+}
+
+async fn add_product_with_options_to_order(
+    order: OrderId,
+    product: ProductId,
+    options: Vec<OptionId>,
+    framework: &TestFramework
+) -> Result<(), Report<UnrecoverableError>> {
+    let quantity = OrderQuantity::new(2)
+        .change_context_lazy(|| UnrecoverableError)?;
+
+    AddProductToOrderWorkflow::execute(framework.add_product_to_order_workflow(), order, product, quantity, options, Vec::new()).await
+        .change_context_lazy(|| UnrecoverableError)?;
+
+    Ok(())
+}
+
+async fn product_events(framework: &TestFramework) -> Result<Vec<ProductEvent>, Report<UnrecoverableError>> {
+    framework.journal()
+        .read_all_by_event::<ProductEvent>()
+        .await
+        .change_context_lazy(|| UnrecoverableError)?
+        .iter()
+        .map(|payload| ProductEvent::from_bytes(&payload.bytes))
+        .collect::<Result<Vec<_>, _>>()
+        .change_context_lazy(|| UnrecoverableError)
+}
+
+async fn stock_of(product: ProductId, framework: &TestFramework) -> Result<Option<i64>, Report<UnrecoverableError>> {
+    let (product, _) = framework.event_projector()
+        .projection_to_latest::<Product>(product, None).await
+        .change_context_lazy(|| UnrecoverableError)?;
+
+    Ok(product.stock().map(|stock| *stock.as_ref()))
+}
+
+/// Tenders enough cash for any order placed in these tests.
+async fn pay_in_cash(order: OrderId, framework: &TestFramework) -> Result<(), Report<UnrecoverableError>> {
+    execute(order, OrderCommand::Pay { tender: Tender::Cash, tendered: Money::new(1000, Currency::default()) }, framework).await
+}
+
+async fn execute(order: OrderId, cmd: OrderCommand, framework: &TestFramework) -> Result<(), Report<UnrecoverableError>> {
+    OrderCommandService::execute(framework.order_command_service(), order, cmd).await
+        .change_context_lazy(|| UnrecoverableError)?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_place_order() -> Result<(), Report<UnrecoverableError>> {
+    setup_logging();
+    let framework = TestFramework::new()?;
+
+    place_order(&framework).await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_add_product_to_order() -> Result<(), Report<UnrecoverableError>> {
+    setup_logging();
+    let framework = TestFramework::new()?;
+
+    let product = register_product(&framework).await?;
+    let order = place_order(&framework).await?;
+
+    add_product_to_order(order, product, &framework).await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_add_unknown_product_to_order() -> Result<(), Report<UnrecoverableError>> {
+    setup_logging();
+    let framework = TestFramework::new()?;
+
+    let order = place_order(&framework).await?;
+
+    if add_product_to_order(order, ProductId::default(), &framework).await.is_ok() {
+        return Err(Report::new(UnrecoverableError)
+            .attach_printable("Unknown product must not be added to order"));
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_add_sold_out_product_to_order() -> Result<(), Report<UnrecoverableError>> {
+    setup_logging();
+    let framework = TestFramework::new()?;
+
+    let product = register_product(&framework).await?;
+    let order = place_order(&framework).await?;
+
+    ProductCommandService::execute(framework.product_command_service(), &Actor::system(), product, ProductCommand::MarkSoldOut).await
+        .change_context_lazy(|| UnrecoverableError)?;
+
+    if add_product_to_order(order, product, &framework).await.is_ok() {
+        return Err(Report::new(UnrecoverableError)
+            .attach_printable("Sold out product must not be added to order"));
+    }
+
+    ProductCommandService::execute(framework.product_command_service(), &Actor::system(), product, ProductCommand::MarkAvailable).await
+        .change_context_lazy(|| UnrecoverableError)?;
+
+    add_product_to_order(order, product, &framework).await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_add_product_with_options_to_order() -> Result<(), Report<UnrecoverableError>> {
+    setup_logging();
+    let framework = TestFramework::new()?;
+
+    let product = register_product(&framework).await?;
+
+    let add_group = ProductCommand::AddOptionGroup { name: OptionName::new("topping"), min: 1, max: 1 };
+    ProductCommandService::execute(framework.product_command_service(), &Actor::system(), product, add_group).await
+        .change_context_lazy(|| UnrecoverableError)?;
+
+    let Some(group) = product_events(&framework).await?.into_iter().find_map(|event| match event {
+        ProductEvent::AddedOptionGroup { group, .. } => Some(*group.id()),
+        _ => None,
+    }) else {
+        return Err(Report::new(UnrecoverableError).attach_printable("No option group found"));
+    };
+
+    let add_option = ProductCommand::AddOption { group, name: OptionName::new("egg"), price_delta: Money::new(50, Currency::default()) };
+    ProductCommandService::execute(framework.product_command_service(), &Actor::system(), product, add_option).await
+        .change_context_lazy(|| UnrecoverableError)?;
+
+    let Some(option) = product_events(&framework).await?.into_iter().find_map(|event| match event {
+        ProductEvent::AddedOption { option, .. } => Some(*option.id()),
+        _ => None,
+    }) else {
+        return Err(Report::new(UnrecoverableError).attach_printable("No option found"));
+    };
+
+    let order = place_order(&framework).await?;
+
+    if add_product_to_order(order, product, &framework).await.is_ok() {
+        return Err(Report::new(UnrecoverableError)
+            .attach_printable("Required option group must be selected"));
+    }
+
+    if add_product_with_options_to_order(order, product, vec![OptionId::default()], &framework).await.is_ok() {
+        return Err(Report::new(UnrecoverableError)
+            .attach_printable("Unknown option must be rejected"));
+    }
+
+    add_product_with_options_to_order(order, product, vec![option], &framework).await?;
+
+    // 2 x (100 + 50)
+    execute(order, OrderCommand::Pay { tender: Tender::EMoney, tendered: Money::new(300, Currency::default()) }, &framework).await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_order_bundle_decrements_component_stock() -> Result<(), Report<UnrecoverableError>> {
+    setup_logging();
+    let framework = TestFramework::new()?;
+
+    let curry = register_product(&framework).await?;
+    let drink = register_product(&framework).await?;
+    let bundle = register_product(&framework).await?;
+
+    let stock = ProductStock::new(2)
+        .change_context_lazy(|| UnrecoverableError)?;
+
+    ProductCommandService::execute(framework.product_command_service(), &Actor::system(), curry, ProductCommand::SetStock { new: stock }).await
+        .change_context_lazy(|| UnrecoverableError)?;
+
+    let workflow = framework.compose_bundle_workflow();
+
+    if ComposeBundleWorkflow::add_slot(workflow, &Actor::system(), bundle, SlotName::new("main"), vec![ProductId::default()]).await.is_ok() {
+        return Err(Report::new(UnrecoverableError)
+            .attach_printable("Unknown product must not be a bundle component"));
+    }
+
+    ComposeBundleWorkflow::add_slot(workflow, &Actor::system(), bundle, SlotName::new("main"), vec![curry]).await
+        .change_context_lazy(|| UnrecoverableError)?;
+    ComposeBundleWorkflow::add_slot(workflow, &Actor::system(), bundle, SlotName::new("drink"), vec![drink]).await
+        .change_context_lazy(|| UnrecoverableError)?;
+
+    let slots = product_events(&framework).await?.into_iter()
+        .filter_map(|event| match event {
+            ProductEvent::AddedBundleSlot { slot, .. } => Some(slot),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    let [main, drinks] = slots.as_slice() else {
+        return Err(Report::new(UnrecoverableError).attach_printable("Bundle must have two slots"));
+    };
+
+    let order = place_order(&framework).await?;
+
+    if add_bundle_to_order(order, bundle, vec![BundleChoice::new(*main.id(), curry)], &framework).await.is_ok() {
+        return Err(Report::new(UnrecoverableError)
+            .attach_printable("Every slot of a bundle must be filled"));
+    }
+
+    if add_bundle_to_order(order, bundle, vec![BundleChoice::new(*main.id(), drink), BundleChoice::new(*drinks.id(), drink)], &framework).await.is_ok() {
+        return Err(Report::new(UnrecoverableError)
+            .attach_printable("Slot must be filled with one of its choices"));
+    }
+
+    add_bundle_to_order(order, bundle, vec![BundleChoice::new(*main.id(), curry), BundleChoice::new(*drinks.id(), drink)], &framework).await?;
+    pay_in_cash(order, &framework).await?;
+    execute(order, OrderCommand::Confirm, &framework).await?;
+
+    let decremented = product_events(&framework).await?.into_iter()
+        .any(|event| matches!(event, ProductEvent::DecrementedStock { id, new, .. } if id == curry && new.is_empty()));
+
+    if !decremented {
+        return Err(Report::new(UnrecoverableError)
+            .attach_printable("Confirmed bundle must decrement the stock of its components"));
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_order_bundle_with_product_in_several_slots() -> Result<(), Report<UnrecoverableError>> {
+    setup_logging();
+    let framework = TestFramework::new()?;
+
+    let curry = register_product(&framework).await?;
+    let bundle = register_product(&framework).await?;
+
+    let stock = ProductStock::new(3)
+        .change_context_lazy(|| UnrecoverableError)?;
+
+    ProductCommandService::execute(framework.product_command_service(), &Actor::system(), curry, ProductCommand::SetStock { new: stock }).await
+        .change_context_lazy(|| UnrecoverableError)?;
+
+    let workflow = framework.compose_bundle_workflow();
+
+    ComposeBundleWorkflow::add_slot(workflow, &Actor::system(), bundle, SlotName::new("main"), vec![curry]).await
+        .change_context_lazy(|| UnrecoverableError)?;
+    ComposeBundleWorkflow::add_slot(workflow, &Actor::system(), bundle, SlotName::new("extra"), vec![curry]).await
+        .change_context_lazy(|| UnrecoverableError)?;
+
+    let slots = product_events(&framework).await?.into_iter()
+        .filter_map(|event| match event {
+            ProductEvent::AddedBundleSlot { slot, .. } => Some(*slot.id()),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    let choices = slots.iter()
+        .map(|slot| BundleChoice::new(*slot, curry))
+        .collect::<Vec<_>>();
+
+    let order = place_order(&framework).await?;
+
+    // 2 bundles need 4 of it, one for each slot.
+    if add_bundle_to_order(order, bundle, choices, &framework).await.is_ok() {
+        return Err(Report::new(UnrecoverableError)
+            .attach_printable("Stock must cover the product in every slot it fills"));
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_bundle_component_cannot_be_deleted_or_nested() -> Result<(), Report<UnrecoverableError>> {
+    setup_logging();
+    let framework = TestFramework::new()?;
+
+    let curry = register_product(&framework).await?;
+    let drink = register_product(&framework).await?;
+    let bundle = register_product(&framework).await?;
+
+    let workflow = framework.compose_bundle_workflow();
+
+    ComposeBundleWorkflow::add_slot(workflow, &Actor::system(), bundle, SlotName::new("main"), vec![curry]).await
+        .change_context_lazy(|| UnrecoverableError)?;
+
+    if ComposeBundleWorkflow::add_slot(workflow, &Actor::system(), curry, SlotName::new("drink"), vec![drink]).await.is_ok() {
+        return Err(Report::new(UnrecoverableError)
+            .attach_printable("Choice of a bundle must not become a bundle itself"));
+    }
+
+    let delete = framework.delete_product_workflow();
+
+    if DeleteProductWorkflow::execute(delete, &Actor::system(), curry).await.is_ok() {
+        return Err(Report::new(UnrecoverableError)
+            .attach_printable("Choice of a bundle must not be deleted"));
+    }
+
+    DeleteProductWorkflow::execute(delete, &Actor::system(), bundle).await
+        .change_context_lazy(|| UnrecoverableError)?;
+
+    DeleteProductWorkflow::execute(delete, &Actor::system(), curry).await
+        .change_context_lazy(|| UnrecoverableError)?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_remove_line_from_order() -> Result<(), Report<UnrecoverableError>> {
+    setup_logging();
+    let framework = TestFramework::new()?;
+
+    let product = register_product(&framework).await?;
+    let order = place_order(&framework).await?;
+
+    add_product_to_order(order, product, &framework).await?;
+    execute(order, OrderCommand::RemoveLine { line: 0 }, &framework).await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_confirm_order() -> Result<(), Report<UnrecoverableError>> {
+    setup_logging();
+    let framework = TestFramework::new()?;
+
+    let product = register_product(&framework).await?;
+    let order = place_order(&framework).await?;
+
+    add_product_to_order(order, product, &framework).await?;
+    pay_in_cash(order, &framework).await?;
+    execute(order, OrderCommand::Confirm, &framework).await?;
+
+    let issued = framework.journal()
+        .read_all_by_event::<TicketCounterEvent>()
+        .await
+        .change_context_lazy(|| UnrecoverableError)?;
+
+    if issued.len() != 1 {
+        return Err(Report::new(UnrecoverableError)
+            .attach_printable("Confirmed order must be issued a ticket number"));
+    }
+
+    if add_product_to_order(order, product, &framework).await.is_ok() {
+        return Err(Report::new(UnrecoverableError)
+            .attach_printable("Confirmed order must not accept new lines"));
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_confirm_order_in_store_offset() -> Result<(), Report<UnrecoverableError>> {
+    setup_logging();
+    
+    // These offsets are 26 hours apart, so they never share a business day.
+    for offset in ["+14:00", "-12:00"] {
+        let offset = StoreOffset::parse(offset)
+            .change_context_lazy(|| UnrecoverableError)?;
+        let framework = TestFramework::new()?.with_store_offset(offset);
+        
+        let product = register_product(&framework).await?;
+        let order = place_order(&framework).await?;
+        
+        add_product_to_order(order, product, &framework).await?;
+        pay_in_cash(order, &framework).await?;
+        execute(order, OrderCommand::Confirm, &framework).await?;
+        
+        let days = framework.journal()
+            .read_all_by_event::<TicketCounterEvent>()
+            .await
+            .change_context_lazy(|| UnrecoverableError)?
+            .iter()
+            .map(|payload| TicketCounterEvent::from_bytes(&payload.bytes))
+            .collect::<Result<Vec<_>, _>>()
+            .change_context_lazy(|| UnrecoverableError)?
+            .into_iter()
+            .map(|TicketCounterEvent::Issued { day, .. }| day)
+            .collect::<Vec<_>>();
+        
+        if days != vec![offset.today()] {
+            return Err(Report::new(UnrecoverableError)
+                .attach_printable(format!("Ticket must be issued on the business day in {offset}, but was issued on {days:?}")));
+        }
+    }
+    
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_confirm_order_decrements_stock() -> Result<(), Report<UnrecoverableError>> {
+    setup_logging();
+    let framework = TestFramework::new()?;
+
+    let product = register_product(&framework).await?;
+    let stock = ProductStock::new(2)
+        .change_context_lazy(|| UnrecoverableError)?;
+
+    ProductCommandService::execute(framework.product_command_service(), &Actor::system(), product, ProductCommand::SetStock { new: stock }).await
+        .change_context_lazy(|| UnrecoverableError)?;
+
+    let order = place_order(&framework).await?;
+    add_product_to_order(order, product, &framework).await?;
+    pay_in_cash(order, &framework).await?;
+    execute(order, OrderCommand::Confirm, &framework).await?;
+
+    let decremented = framework.journal()
+        .read_all_by_event::<ProductEvent>()
+        .await
+        .change_context_lazy(|| UnrecoverableError)?
+        .iter()
+        .map(|payload| ProductEvent::from_bytes(&payload.bytes))
+        .collect::<Result<Vec<_>, _>>()
+        .change_context_lazy(|| UnrecoverableError)?
+        .into_iter()
+        .any(|event| matches!(event, ProductEvent::DecrementedStock { new, .. } if new.is_empty()));
+
+    if !decremented {
+        return Err(Report::new(UnrecoverableError)
+            .attach_printable("Confirmed order must decrement the product stock"));
+    }
+
+    let other = place_order(&framework).await?;
+
+    if add_product_to_order(other, product, &framework).await.is_ok() {
+        return Err(Report::new(UnrecoverableError)
+            .attach_printable("Product without stock must not be added to order"));
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_confirm_order_without_enough_stock() -> Result<(), Report<UnrecoverableError>> {
+    setup_logging();
+    let framework = TestFramework::new()?;
+
+    let product = register_product(&framework).await?;
+    let stock = ProductStock::new(3)
+        .change_context_lazy(|| UnrecoverableError)?;
+
+    ProductCommandService::execute(framework.product_command_service(), &Actor::system(), product, ProductCommand::SetStock { new: stock }).await
+        .change_context_lazy(|| UnrecoverableError)?;
+
+    // Both orders are taken while there is still enough stock for either of them.
+    let first = place_order(&framework).await?;
+    let second = place_order(&framework).await?;
+    for order in [first, second] {
+        add_product_to_order(order, product, &framework).await?;
+        pay_in_cash(order, &framework).await?;
+    }
+
+    execute(first, OrderCommand::Confirm, &framework).await?;
+
+    if execute(second, OrderCommand::Confirm, &framework).await.is_ok() {
+        return Err(Report::new(UnrecoverableError)
+            .attach_printable("Order must not be confirmed without enough stock"));
+    }
+
+    let (second, _) = framework.event_projector()
+        .projection_to_latest::<Order>(second, None).await
+        .change_context_lazy(|| UnrecoverableError)?;
+
+    if second.status() != &OrderStatus::Placed {
+        return Err(Report::new(UnrecoverableError)
+            .attach_printable("Rejected order must be left as it was"));
+    }
+
+    let issued = framework.journal()
+        .read_all_by_event::<TicketCounterEvent>()
+        .await
+        .change_context_lazy(|| UnrecoverableError)?;
+
+    if issued.len() != 1 {
+        return Err(Report::new(UnrecoverableError)
+            .attach_printable("Rejected order must not be issued a ticket number"));
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_confirm_order_puts_back_taken_stock() -> Result<(), Report<UnrecoverableError>> {
+    setup_logging();
+    let framework = TestFramework::new()?;
+
+    let curry = register_product(&framework).await?;
+    let drink = register_product(&framework).await?;
+    let stock = |amount: i64| ProductStock::new(amount).change_context_lazy(|| UnrecoverableError);
+
+    for product in [curry, drink] {
+        ProductCommandService::execute(framework.product_command_service(), &Actor::system(), product, ProductCommand::SetStock { new: stock(3)? }).await
+            .change_context_lazy(|| UnrecoverableError)?;
+    }
+
+    let order = place_order(&framework).await?;
+    add_product_to_order(order, curry, &framework).await?;
+    add_product_to_order(order, drink, &framework).await?;
+    pay_in_cash(order, &framework).await?;
+
+    // Sold elsewhere after the order was taken.
+    ProductCommandService::execute(framework.product_command_service(), &Actor::system(), drink, ProductCommand::SetStock { new: stock(1)? }).await
+        .change_context_lazy(|| UnrecoverableError)?;
+
+    if execute(order, OrderCommand::Confirm, &framework).await.is_ok() {
+        return Err(Report::new(UnrecoverableError)
+            .attach_printable("Order must not be confirmed without enough stock"));
+    }
+
+    for (product, left) in [(curry, 3), (drink, 1)] {
+        if stock_of(product, &framework).await? != Some(left) {
+            return Err(Report::new(UnrecoverableError)
+                .attach_printable("Rejected order must not take any stock"));
+        }
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cancel_order_returns_stock() -> Result<(), Report<UnrecoverableError>> {
+    setup_logging();
+    let framework = TestFramework::new()?;
+
+    let product = register_product(&framework).await?;
+    let stock = ProductStock::new(3)
+        .change_context_lazy(|| UnrecoverableError)?;
+
+    ProductCommandService::execute(framework.product_command_service(), &Actor::system(), product, ProductCommand::SetStock { new: stock }).await
+        .change_context_lazy(|| UnrecoverableError)?;
+
+    let unconfirmed = place_order(&framework).await?;
+    add_product_to_order(unconfirmed, product, &framework).await?;
+    execute(unconfirmed, OrderCommand::Cancel, &framework).await?;
+
+    if stock_of(product, &framework).await? != Some(3) {
+        return Err(Report::new(UnrecoverableError)
+            .attach_printable("Unconfirmed order has no stock to return"));
+    }
+
+    let order = place_order(&framework).await?;
+    add_product_to_order(order, product, &framework).await?;
+    pay_in_cash(order, &framework).await?;
+    execute(order, OrderCommand::Confirm, &framework).await?;
+
+    if stock_of(product, &framework).await? != Some(1) {
+        return Err(Report::new(UnrecoverableError)
+            .attach_printable("Confirmed order must take its stock"));
+    }
+
+    execute(order, OrderCommand::Cancel, &framework).await?;
+
+    if stock_of(product, &framework).await? != Some(3) {
+        return Err(Report::new(UnrecoverableError)
+            .attach_printable("Cancelled order must return the stock it took"));
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_confirm_order_with_deleted_product() -> Result<(), Report<UnrecoverableError>> {
+    setup_logging();
+    let framework = TestFramework::new()?;
+
+    let product = register_product(&framework).await?;
+    let order = place_order(&framework).await?;
+
+    add_product_to_order(order, product, &framework).await?;
+    pay_in_cash(order, &framework).await?;
+
+    ProductCommandService::execute(framework.product_command_service(), &Actor::system(), product, ProductCommand::Delete).await
+        .change_context_lazy(|| UnrecoverableError)?;
+
+    if execute(order, OrderCommand::Confirm, &framework).await.is_ok() {
+        return Err(Report::new(UnrecoverableError)
+            .attach_printable("Order of a deleted product must not be confirmed"));
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_pay_order() -> Result<(), Report<UnrecoverableError>> {
+    setup_logging();
+    let framework = TestFramework::new()?;
+
+    let product = register_product(&framework).await?;
+    let order = place_order(&framework).await?;
+
+    add_product_to_order(order, product, &framework).await?;
+
+    if execute(order, OrderCommand::Confirm, &framework).await.is_ok() {
+        return Err(Report::new(UnrecoverableError)
+            .attach_printable("Unpaid order must not be confirmed"));
+    }
+
+    if execute(order, OrderCommand::Pay { tender: Tender::Cash, tendered: Money::new(199, Currency::default()) }, &framework).await.is_ok() {
+        return Err(Report::new(UnrecoverableError)
+            .attach_printable("Tendered amount less than the total must be rejected"));
+    }
+
+    if execute(order, OrderCommand::Pay { tender: Tender::EMoney, tendered: Money::new(1000, Currency::default()) }, &framework).await.is_ok() {
+        return Err(Report::new(UnrecoverableError)
+            .attach_printable("Cashless payments must not give change"));
+    }
+
+    execute(order, OrderCommand::Pay { tender: Tender::EMoney, tendered: Money::new(200, Currency::default()) }, &framework).await?;
+
+    if execute(order, OrderCommand::RemoveLine { line: 0 }, &framework).await.is_ok() {
+        return Err(Report::new(UnrecoverableError)
+            .attach_printable("Paid order must not be changed"));
+    }
+
+    execute(order, OrderCommand::Confirm, &framework).await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_pay_tax_exclusive_order() -> Result<(), Report<UnrecoverableError>> {
+    setup_logging();
+    let framework = TestFramework::new()?;
+
+    let standard = register_product(&framework).await?;
+    let reduced = register_product(&framework).await?;
+
+    let tax = ProductTax::new(TaxCategory::Reduced, false);
+    ProductCommandService::execute(framework.product_command_service(), &Actor::system(), reduced, ProductCommand::ChangeTax { new: tax }).await
+        .change_context_lazy(|| UnrecoverableError)?;
+
+    let order = place_order(&framework).await?;
+
+    add_product_to_order(order, standard, &framework).await?;
+    add_product_to_order(order, reduced, &framework).await?;
+
+    // 2 x 100 tax included + 2 x (100 + 8% tax)
+    if execute(order, OrderCommand::Pay { tender: Tender::EMoney, tendered: Money::new(400, Currency::default()) }, &framework).await.is_ok() {
+        return Err(Report::new(UnrecoverableError)
+            .attach_printable("Tax must be added to tax-exclusive prices"));
+    }
+
+    execute(order, OrderCommand::Pay { tender: Tender::EMoney, tendered: Money::new(416, Currency::default()) }, &framework).await?;
+
+    let (order, _) = framework.event_projector()
+        .projection_to_latest::<Order>(order, None).await
+        .change_context_lazy(|| UnrecoverableError)?;
+
+    let breakdown = order.tax_breakdown()
+        .iter()
+        .map(|summary| (i64::from(*summary.rate()), summary.gross(), summary.tax()))
+        .collect::<Vec<_>>();
+
+    if breakdown != vec![(8, 216, 16), (10, 200, 18)] {
+        return Err(Report::new(UnrecoverableError)
+            .attach_printable(format!("Unexpected tax breakdown: {breakdown:?}")));
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_add_product_in_another_currency_to_order() -> Result<(), Report<UnrecoverableError>> {
+    setup_logging();
+    let framework = TestFramework::new()?;
+
+    let yen = register_product(&framework).await?;
+    let dollar = register_product(&framework).await?;
+
+    let usd = Currency::new("USD").change_context_lazy(|| UnrecoverableError)?;
+    let new = ProductPrice::new(Money::new(450, usd)).change_context_lazy(|| UnrecoverableError)?;
+    ProductCommandService::execute(framework.product_command_service(), &Actor::system(), dollar, ProductCommand::ChangeProductPrice { new }).await
+        .change_context_lazy(|| UnrecoverableError)?;
+
+    let order = place_order(&framework).await?;
+
+    add_product_to_order(order, yen, &framework).await?;
+
+    if add_product_to_order(order, dollar, &framework).await.is_ok() {
+        return Err(Report::new(UnrecoverableError)
+            .attach_printable("Products in different currencies must not be mixed in an order"));
+    }
+
+    let tendered = Money::new(100, Currency::new("USD").change_context_lazy(|| UnrecoverableError)?);
+    if execute(order, OrderCommand::Pay { tender: Tender::EMoney, tendered }, &framework).await.is_ok() {
+        return Err(Report::new(UnrecoverableError)
+            .attach_printable("Order must not be paid in another currency"));
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_confirm_empty_order() -> Result<(), Report<UnrecoverableError>> {
+    setup_logging();
+    let framework = TestFramework::new()?;
+
+    let order = place_order(&framework).await?;
+
+    if execute(order, OrderCommand::Confirm, &framework).await.is_ok() {
+        return Err(Report::new(UnrecoverableError)
+            .attach_printable("Empty order must not be confirmed"));
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_order_lifecycle() -> Result<(), Report<UnrecoverableError>> {
+    setup_logging();
+    let framework = TestFramework::new()?;
+
+    let product = register_product(&framework).await?;
+    let order = place_order(&framework).await?;
+
+    add_product_to_order(order, product, &framework).await?;
+    pay_in_cash(order, &framework).await?;
+    execute(order, OrderCommand::Confirm, &framework).await?;
+    execute(order, OrderCommand::ChangeStatus { new: OrderStatus::Preparing }, &framework).await?;
+    execute(order, OrderCommand::ChangeStatus { new: OrderStatus::Ready }, &framework).await?;
+    execute(order, OrderCommand::ChangeStatus { new: OrderStatus::Completed }, &framework).await?;
+
+    if execute(order, OrderCommand::Cancel, &framework).await.is_ok() {
+        return Err(Report::new(UnrecoverableError)
+            .attach_printable("Completed order must not be cancelled"));
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_illegal_status_transition() -> Result<(), Report<UnrecoverableError>> {
+    setup_logging();
+    let framework = TestFramework::new()?;
+
+    let product = register_product(&framework).await?;
+    let order = place_order(&framework).await?;
+
+    if execute(order, OrderCommand::ChangeStatus { new: OrderStatus::Preparing }, &framework).await.is_ok() {
+        return Err(Report::new(UnrecoverableError)
+            .attach_printable("Unconfirmed order must not be prepared"));
+    }
+
+    add_product_to_order(order, product, &framework).await?;
+    pay_in_cash(order, &framework).await?;
+    execute(order, OrderCommand::Confirm, &framework).await?;
+
+    if execute(order, OrderCommand::ChangeStatus { new: OrderStatus::Ready }, &framework).await.is_ok() {
+        return Err(Report::new(UnrecoverableError)
+            .attach_printable("Accepted order must not skip preparing"));
+    }
+
+    execute(order, OrderCommand::Cancel, &framework).await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cancel_order() -> Result<(), Report<UnrecoverableError>> {
+    setup_logging();
+    let framework = TestFramework::new()?;
+
+    let order = place_order(&framework).await?;
+
+    execute(order, OrderCommand::Cancel, &framework).await?;
+
+    if execute(order, OrderCommand::Cancel, &framework).await.is_ok() {
+        return Err(Report::new(UnrecoverableError)
+            .attach_printable("Cancelled order must not be cancelled twice"));
+    }
+
+    Ok(())
+}
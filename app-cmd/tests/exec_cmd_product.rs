@@ -3,7 +3,12 @@ use tracing_subscriber::EnvFilter;
 use tracing_subscriber::layer::SubscriberExt;
 use tracing_subscriber::util::SubscriberInitExt;
 use app_cmd::services::product::{DependOnProductCommandService, ProductCommandService};
-use kernel::entities::product::{ProductDesc, ProductId, ProductName, ProductPrice};
+use time::macros::time;
+use time::Weekday;
+use kernel::entities::money::{Currency, Money};
+use kernel::entities::product::{OptionName, Product, ProductDesc, ProductId, ProductName, ProductPrice, ProductStock, SlotId, SlotName};
+use kernel::entities::schedule::{Schedule, TimeWindow};
+use kernel::entities::staff::StaffName;
 use kernel::io::commands::ProductCommand;
 use kernel::io::events::ProductEvent;
 
@@ -37,17 +42,30 @@ async fn extract_first_event(framework: &TestFramework) -> Result<ProductEvent,
     Ok(create_event)
 }
 
+async fn extract_last_event(framework: &TestFramework) -> Result<ProductEvent, Report<UnrecoverableError>> {
+    let last_event = framework.journal()
+        .read_all_by_event::<ProductEvent>()
+        .await
+        .change_context_lazy(|| UnrecoverableError)?
+        .last()
+        .ok_or(Report::new(UnrecoverableError).attach_printable("No event found"))
+        .map(|payload| ProductEvent::from_bytes(&payload.bytes))?
+        .change_context_lazy(|| UnrecoverableError)?;
+    
+    Ok(last_event)
+}
+
 async fn register_product(framework: &TestFramework) -> Result<(), Report<UnrecoverableError>> {
     let service = framework.product_command_service();
     
     let cmd = ProductCommand::Register {
         name: ProductName::new("test"),
         desc: ProductDesc::new("test desc"),
-        price: ProductPrice::new(100).change_context_lazy(|| UnrecoverableError)?,
+        price: ProductPrice::new(Money::new(100, Currency::default())).change_context_lazy(|| UnrecoverableError)?,
         image: vec![],
     };
     
-    service.execute(None, cmd).await
+    service.execute(&Actor::system(), None, cmd).await
         .change_context_lazy(|| UnrecoverableError)?;
     
     Ok(())
@@ -71,7 +89,7 @@ async fn rename_product(id: ProductId, framework: &TestFramework) -> Result<(),
         new: ProductName::new("test 2"),
     };
     
-    service.execute(id, cmd).await
+    service.execute(&Actor::system(), id, cmd).await
         .change_context_lazy(|| UnrecoverableError)?;
     
     Ok(())
@@ -101,7 +119,7 @@ async fn edit_product_desc(id: ProductId, framework: &TestFramework) -> Result<(
         new: ProductDesc::new("test desc 2"),
     };
     
-    service.execute(id, cmd).await
+    service.execute(&Actor::system(), id, cmd).await
         .change_context_lazy(|| UnrecoverableError)?;
     
     Ok(())
@@ -128,10 +146,10 @@ async fn change_product_price(id: ProductId, framework: &TestFramework) -> Resul
     let service = framework.product_command_service();
     
     let cmd = ProductCommand::ChangeProductPrice {
-        new: ProductPrice::new(200).change_context_lazy(|| UnrecoverableError)?,
+        new: ProductPrice::new(Money::new(200, Currency::default())).change_context_lazy(|| UnrecoverableError)?,
     };
     
-    service.execute(id, cmd).await
+    service.execute(&Actor::system(), id, cmd).await
         .change_context_lazy(|| UnrecoverableError)?;
     
     Ok(())
@@ -154,12 +172,63 @@ async fn test_change_product_price() -> Result<(), Report<UnrecoverableError>> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_change_product_price_currency() -> Result<(), Report<UnrecoverableError>> {
+    setup_logging();
+    let framework = TestFramework::new()?;
+    
+    register_product(&framework).await?;
+    
+    let event = extract_first_event(&framework).await?;
+    let ProductEvent::Registered { id, .. } = event else {
+        return Err(Report::new(UnrecoverableError).attach_printable("Unexpected event"));
+    };
+    
+    let usd = Currency::new("usd").change_context_lazy(|| UnrecoverableError)?;
+    let new = ProductPrice::new(Money::new(1250, usd.clone())).change_context_lazy(|| UnrecoverableError)?;
+    
+    framework.product_command_service()
+        .execute(&Actor::system(), id, ProductCommand::ChangeProductPrice { new }).await
+        .change_context_lazy(|| UnrecoverableError)?;
+    
+    let ProductEvent::ChangedProductPrice { new, .. } = extract_last_event(&framework).await? else {
+        return Err(Report::new(UnrecoverableError).attach_printable("Unexpected event"));
+    };
+    
+    if new.as_ref().to_string() != "12.50 USD" || new.currency() != &usd {
+        return Err(Report::new(UnrecoverableError)
+            .attach_printable(format!("Unexpected price: {}", new.as_ref())));
+    }
+    
+    Ok(())
+}
+
+#[test]
+fn test_decode_legacy_product_price() -> Result<(), Report<UnrecoverableError>> {
+    // Journaled before prices had a currency.
+    let bytes = br#"{"ChangedProductPrice":{"id":"0192a2c4-8b2e-7c3a-9f1e-3b6c2d1e4f5a","new":300}}"#;
+    
+    let event = ProductEvent::from_bytes(bytes)
+        .change_context_lazy(|| UnrecoverableError)?;
+    
+    let ProductEvent::ChangedProductPrice { new, .. } = event else {
+        return Err(Report::new(UnrecoverableError).attach_printable("Unexpected event"));
+    };
+    
+    if new.amount() != 300 || new.currency() != &Currency::default() {
+        return Err(Report::new(UnrecoverableError)
+            .attach_printable(format!("Legacy price decoded as {}", new.as_ref())));
+    }
+    
+    Ok(())
+}
+
 async fn delete_product(id: ProductId, framework: &TestFramework) -> Result<(), Report<UnrecoverableError>> {
     let service = framework.product_command_service();
     
     let cmd = ProductCommand::Delete;
     
-    service.execute(id, cmd).await
+    service.execute(&Actor::system(), id, cmd).await
         .change_context_lazy(|| UnrecoverableError)?;
     
     Ok(())
@@ -180,4 +249,301 @@ async fn test_delete_product() -> Result<(), Report<UnrecoverableError>> {
     delete_product(id, &framework).await?;
     
     Ok(())
-}
\ No newline at end of file
+}
+
+async fn mark_sold_out(id: ProductId, framework: &TestFramework) -> Result<(), Report<UnrecoverableError>> {
+    let service = framework.product_command_service();
+    
+    service.execute(&Actor::system(), id, ProductCommand::MarkSoldOut).await
+        .change_context_lazy(|| UnrecoverableError)?;
+    
+    Ok(())
+}
+
+async fn mark_available(id: ProductId, framework: &TestFramework) -> Result<(), Report<UnrecoverableError>> {
+    let service = framework.product_command_service();
+    
+    service.execute(&Actor::system(), id, ProductCommand::MarkAvailable).await
+        .change_context_lazy(|| UnrecoverableError)?;
+    
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_mark_sold_out() -> Result<(), Report<UnrecoverableError>> {
+    setup_logging();
+    let framework = TestFramework::new()?;
+    
+    register_product(&framework).await?;
+    
+    let event = extract_first_event(&framework).await?;
+    let ProductEvent::Registered { id, .. } = event else {
+        return Err(Report::new(UnrecoverableError).attach_printable("Unexpected event"));
+    };
+    
+    if mark_available(id, &framework).await.is_ok() {
+        return Err(Report::new(UnrecoverableError).attach_printable("Product is available on registration"));
+    }
+    
+    mark_sold_out(id, &framework).await?;
+    
+    if mark_sold_out(id, &framework).await.is_ok() {
+        return Err(Report::new(UnrecoverableError).attach_printable("Product must not be sold out twice"));
+    }
+    
+    mark_available(id, &framework).await?;
+    
+    Ok(())
+}
+
+async fn execute(id: ProductId, cmd: ProductCommand, framework: &TestFramework) -> Result<(), Report<UnrecoverableError>> {
+    framework.product_command_service()
+        .execute(&Actor::system(), id, cmd)
+        .await
+        .change_context_lazy(|| UnrecoverableError)?;
+    
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_product_stock() -> Result<(), Report<UnrecoverableError>> {
+    setup_logging();
+    let framework = TestFramework::new()?;
+    
+    register_product(&framework).await?;
+    
+    let event = extract_first_event(&framework).await?;
+    let ProductEvent::Registered { id, .. } = event else {
+        return Err(Report::new(UnrecoverableError).attach_printable("Unexpected event"));
+    };
+    
+    let stock = |amount: i64| ProductStock::new(amount).change_context_lazy(|| UnrecoverableError);
+    
+    if execute(id, ProductCommand::DecrementStock { amount: stock(1)? }, &framework).await.is_ok() {
+        return Err(Report::new(UnrecoverableError).attach_printable("Untracked stock must not be decremented"));
+    }
+    
+    execute(id, ProductCommand::SetStock { new: stock(2)? }, &framework).await?;
+    
+    if execute(id, ProductCommand::DecrementStock { amount: stock(3)? }, &framework).await.is_ok() {
+        return Err(Report::new(UnrecoverableError).attach_printable("Stock must not be taken beyond what is left"));
+    }
+    
+    execute(id, ProductCommand::DecrementStock { amount: stock(2)? }, &framework).await?;
+    
+    if available(id, &framework).await? {
+        return Err(Report::new(UnrecoverableError).attach_printable("Product without stock must be sold out"));
+    }
+    
+    execute(id, ProductCommand::Restock { amount: stock(5)? }, &framework).await?;
+    
+    if !available(id, &framework).await? {
+        return Err(Report::new(UnrecoverableError).attach_printable("Restocked product must be available again"));
+    }
+    
+    execute(id, ProductCommand::MarkSoldOut, &framework).await?;
+    execute(id, ProductCommand::Restock { amount: stock(5)? }, &framework).await?;
+    
+    if available(id, &framework).await? {
+        return Err(Report::new(UnrecoverableError).attach_printable("Product marked as sold out must stay so when restocked"));
+    }
+    
+    execute(id, ProductCommand::MarkAvailable, &framework).await?;
+    
+    if !available(id, &framework).await? {
+        return Err(Report::new(UnrecoverableError).attach_printable("Product in stock must be available once the mark is lifted"));
+    }
+    
+    Ok(())
+}
+
+async fn available(id: ProductId, framework: &TestFramework) -> Result<bool, Report<UnrecoverableError>> {
+    let (product, _) = framework.event_projector()
+        .projection_to_latest::<Product>(id, None).await
+        .change_context_lazy(|| UnrecoverableError)?;
+    
+    Ok(product.available())
+}
+
+#[tokio::test]
+async fn test_product_options() -> Result<(), Report<UnrecoverableError>> {
+    setup_logging();
+    let framework = TestFramework::new()?;
+    
+    register_product(&framework).await?;
+    
+    let event = extract_first_event(&framework).await?;
+    let ProductEvent::Registered { id, .. } = event else {
+        return Err(Report::new(UnrecoverableError).attach_printable("Unexpected event"));
+    };
+    
+    if execute(id, ProductCommand::AddOptionGroup { name: OptionName::new("size"), min: 2, max: 1 }, &framework).await.is_ok() {
+        return Err(Report::new(UnrecoverableError).attach_printable("Option group with min greater than max must be rejected"));
+    }
+    
+    execute(id, ProductCommand::AddOptionGroup { name: OptionName::new("size"), min: 1, max: 1 }, &framework).await?;
+    
+    let ProductEvent::AddedOptionGroup { group, .. } = extract_last_event(&framework).await? else {
+        return Err(Report::new(UnrecoverableError).attach_printable("Unexpected event"));
+    };
+    let group = *group.id();
+    
+    execute(id, ProductCommand::AddOption { group, name: OptionName::new("large"), price_delta: Money::new(100, Currency::default()) }, &framework).await?;
+    
+    let ProductEvent::AddedOption { option, .. } = extract_last_event(&framework).await? else {
+        return Err(Report::new(UnrecoverableError).attach_printable("Unexpected event"));
+    };
+    let option = *option.id();
+    
+    let usd = Money::new(100, Currency::new("USD").change_context_lazy(|| UnrecoverableError)?);
+    if execute(id, ProductCommand::EditOption { group, option, name: OptionName::new("large"), price_delta: usd }, &framework).await.is_ok() {
+        return Err(Report::new(UnrecoverableError).attach_printable("Price delta must be in the currency of the product"));
+    }
+    
+    execute(id, ProductCommand::EditOption { group, option, name: OptionName::new("large"), price_delta: Money::new(120, Currency::default()) }, &framework).await?;
+    execute(id, ProductCommand::EditOptionGroup { group, name: OptionName::new("size"), min: 0, max: 1 }, &framework).await?;
+    execute(id, ProductCommand::RemoveOption { group, option }, &framework).await?;
+    
+    if execute(id, ProductCommand::RemoveOption { group, option }, &framework).await.is_ok() {
+        return Err(Report::new(UnrecoverableError).attach_printable("Removed option must not be removed twice"));
+    }
+    
+    execute(id, ProductCommand::RemoveOptionGroup { group }, &framework).await?;
+    
+    if execute(id, ProductCommand::AddOption { group, name: OptionName::new("small"), price_delta: Money::new(0, Currency::default()) }, &framework).await.is_ok() {
+        return Err(Report::new(UnrecoverableError).attach_printable("Option must not be added to a removed group"));
+    }
+    
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_product_bundle() -> Result<(), Report<UnrecoverableError>> {
+    setup_logging();
+    let framework = TestFramework::new()?;
+    
+    register_product(&framework).await?;
+    
+    let event = extract_first_event(&framework).await?;
+    let ProductEvent::Registered { id, .. } = event else {
+        return Err(Report::new(UnrecoverableError).attach_printable("Unexpected event"));
+    };
+    
+    if execute(id, ProductCommand::RemoveBundleSlot { slot: SlotId::default() }, &framework).await.is_ok() {
+        return Err(Report::new(UnrecoverableError).attach_printable("Unknown slot must not be removed"));
+    }
+    
+    if execute(id, ProductCommand::AddBundleSlot { name: SlotName::new("main"), choices: Vec::new() }, &framework).await.is_ok() {
+        return Err(Report::new(UnrecoverableError).attach_printable("Slot without choices must be rejected"));
+    }
+    
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_product_schedule() -> Result<(), Report<UnrecoverableError>> {
+    setup_logging();
+    let framework = TestFramework::new()?;
+    
+    register_product(&framework).await?;
+    
+    let event = extract_first_event(&framework).await?;
+    let ProductEvent::Registered { id, .. } = event else {
+        return Err(Report::new(UnrecoverableError).attach_printable("Unexpected event"));
+    };
+    
+    if execute(id, ProductCommand::ClearSchedule, &framework).await.is_ok() {
+        return Err(Report::new(UnrecoverableError).attach_printable("Product without schedule must not be cleared"));
+    }
+    
+    let breakfast = TimeWindow::new([Weekday::Monday, Weekday::Tuesday], time!(7:00), time!(10:30))
+        .change_context_lazy(|| UnrecoverableError)?;
+    let new = Schedule::new(vec![breakfast]).change_context_lazy(|| UnrecoverableError)?;
+    
+    execute(id, ProductCommand::ChangeSchedule { new: new.clone() }, &framework).await?;
+    
+    let ProductEvent::ChangedSchedule { new: changed, .. } = extract_last_event(&framework).await? else {
+        return Err(Report::new(UnrecoverableError).attach_printable("Unexpected event"));
+    };
+    
+    if changed != new {
+        return Err(Report::new(UnrecoverableError).attach_printable("Schedule was not recorded as requested"));
+    }
+    
+    execute(id, ProductCommand::ClearSchedule, &framework).await?;
+    
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_audit_actor() -> Result<(), Report<UnrecoverableError>> {
+    setup_logging();
+    let framework = TestFramework::new()?;
+    
+    register_product(&framework).await?;
+    
+    let ProductEvent::Registered { id, .. } = extract_first_event(&framework).await? else {
+        return Err(Report::new(UnrecoverableError).attach_printable("Unexpected event"));
+    };
+    
+    let actor = Actor {
+        staff: Some(StaffName::new("cashier.1").change_context_lazy(|| UnrecoverableError)?),
+        request_id: Some("req-1".to_string()),
+        client_ip: Some("192.168.0.10".parse().change_context_lazy(|| UnrecoverableError)?),
+        ..Actor::system()
+    };
+    
+    framework.product_command_service()
+        .execute(&actor, id, ProductCommand::MarkSoldOut).await
+        .change_context_lazy(|| UnrecoverableError)?;
+    
+    let audit = framework.audit();
+    let [registered, sold_out] = audit.as_slice() else {
+        return Err(Report::new(UnrecoverableError).attach_printable(format!("Unexpected audit log: {audit:?}")));
+    };
+    
+    if registered.event != "Registered" || registered.actor.staff.is_some() {
+        return Err(Report::new(UnrecoverableError).attach_printable(format!("Unexpected record: {registered:?}")));
+    }
+    
+    if sold_out.aggregate != "product"
+        || sold_out.aggregate_id != id.to_string()
+        || sold_out.event != "MarkedSoldOut"
+        || sold_out.actor.staff.as_ref().map(ToString::to_string).as_deref() != Some("cashier.1")
+        || sold_out.actor.request_id.as_deref() != Some("req-1")
+    {
+        return Err(Report::new(UnrecoverableError).attach_printable(format!("Unexpected record: {sold_out:?}")));
+    }
+    
+    let sequences = framework.audited_sequences();
+    if !sequences.windows(2).all(|pair| pair[0] < pair[1]) {
+        return Err(Report::new(UnrecoverableError).attach_printable(format!("Each record must carry the sequence of its own event: {sequences:?}")));
+    }
+    
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_failed_audit_keeps_command() -> Result<(), Report<UnrecoverableError>> {
+    setup_logging();
+    let framework = TestFramework::new()?.with_failing_audit();
+    
+    register_product(&framework).await?;
+    
+    let ProductEvent::Registered { id, .. } = extract_first_event(&framework).await? else {
+        return Err(Report::new(UnrecoverableError).attach_printable("Unexpected event"));
+    };
+    
+    if framework.product_command_service()
+        .execute(&Actor::system(), id, ProductCommand::MarkSoldOut).await
+        .is_err() 
+    {
+        return Err(Report::new(UnrecoverableError).attach_printable("Failed audit must not fail an applied command"));
+    }
+    
+    if available(id, &framework).await? {
+        return Err(Report::new(UnrecoverableError).attach_printable("Command must be applied even though it went unaudited"));
+    }
+    
+    Ok(())
+}
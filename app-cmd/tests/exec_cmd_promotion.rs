@@ -0,0 +1,290 @@
+use nitinol::Event;
+use time::{Duration, OffsetDateTime};
+use tracing_subscriber::EnvFilter;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use app_cmd::services::order::{DependOnOrderCommandService, OrderCommandService};
+use app_cmd::services::product::{DependOnProductCommandService, ProductCommandService};
+use app_cmd::services::promotion::{DependOnPromotionCommandService, PromotionCommandService};
+use app_cmd::services::ticket::DependOnTicketCounterCommandService;
+use app_cmd::workflow::order::{AddProductToOrderWorkflow, DependOnAddProductToOrderWorkflow, DependOnRedeemCouponWorkflow, RedeemCouponWorkflow};
+use kernel::entities::order::{OrderId, OrderQuantity, Tender};
+use kernel::entities::money::{Currency, Money};
+use kernel::entities::product::{ProductDesc, ProductId, ProductName, ProductPrice};
+use kernel::entities::promotion::{CouponCode, Discount, PromotionName, PromotionScope, UsageCap, Validity};
+use kernel::io::commands::{OrderCommand, ProductCommand, PromotionCommand};
+use kernel::io::events::{ProductEvent, PromotionEvent};
+
+include!("./test_framework.rs");
+
+//noinspection RsTraitImplOrphanRules
+impl DependOnOrderCommandService for TestFramework {
+    type OrderCommandService = Self;
+    fn order_command_service(&self) -> &Self::OrderCommandService {
+        self
+    }
+}
+
+//noinspection RsTraitImplOrphanRules
+impl DependOnProductCommandService for TestFramework {
+    type ProductCommandService = Self;
+    fn product_command_service(&self) -> &Self::ProductCommandService {
+        self
+    }
+}
+
+//noinspection RsTraitImplOrphanRules
+impl DependOnTicketCounterCommandService for TestFramework {
+    type TicketCounterCommandService = Self;
+    fn ticket_counter_command_service(&self) -> &Self::TicketCounterCommandService {
+        self
+    }
+}
+
+//noinspection RsTraitImplOrphanRules
+impl DependOnPromotionCommandService for TestFramework {
+    type PromotionCommandService = Self;
+    fn promotion_command_service(&self) -> &Self::PromotionCommandService {
+        self
+    }
+}
+
+//noinspection RsTraitImplOrphanRules
+impl DependOnAddProductToOrderWorkflow for TestFramework {
+    type AddProductToOrderWorkflow = Self;
+    fn add_product_to_order_workflow(&self) -> &Self::AddProductToOrderWorkflow {
+        self
+    }
+}
+
+//noinspection RsTraitImplOrphanRules
+impl DependOnRedeemCouponWorkflow for TestFramework {
+    type RedeemCouponWorkflow = Self;
+    fn redeem_coupon_workflow(&self) -> &Self::RedeemCouponWorkflow {
+        self
+    }
+}
+
+fn setup_logging() {
+    let _ = tracing_subscriber::registry()
+        .with(EnvFilter::new("trace"))
+        .with(tracing_subscriber::fmt::layer())
+        .try_init();
+}
+
+async fn register_product(framework: &TestFramework) -> Result<ProductId, Report<UnrecoverableError>> {
+    let cmd = ProductCommand::Register {
+        name: ProductName::new("test"),
+        desc: ProductDesc::new("test desc"),
+        price: ProductPrice::new(Money::new(100, Currency::default())).change_context_lazy(|| UnrecoverableError)?,
+        image: vec![],
+    };
+
+    ProductCommandService::execute(framework.product_command_service(), &Actor::system(), None, cmd).await
+        .change_context_lazy(|| UnrecoverableError)?;
+
+    let event = framework.journal()
+        .read_all_by_event::<ProductEvent>()
+        .await
+        .change_context_lazy(|| UnrecoverableError)?
+        .last()
+        .ok_or(Report::new(UnrecoverableError).attach_printable("No event found"))
+        .map(|payload| ProductEvent::from_bytes(&payload.bytes))?
+        .change_context_lazy(|| UnrecoverableError)?;
+
+    let ProductEvent::Registered { id, .. } = event else {
+        return Err(Report::new(UnrecoverableError).attach_printable("Unexpected event"));
+    };
+
+    Ok(id)
+}
+
+/// Places an order with 2 of the product, i.e. a subtotal of 200.
+async fn place_order(product: ProductId, framework: &TestFramework) -> Result<OrderId, Report<UnrecoverableError>> {
+    let order = OrderCommandService::execute(framework.order_command_service(), None, OrderCommand::Place).await
+        .change_context_lazy(|| UnrecoverableError)?;
+
+    let quantity = OrderQuantity::new(2)
+        .change_context_lazy(|| UnrecoverableError)?;
+
+    AddProductToOrderWorkflow::execute(framework.add_product_to_order_workflow(), order, product, quantity, Vec::new(), Vec::new()).await
+        .change_context_lazy(|| UnrecoverableError)?;
+
+    Ok(order)
+}
+
+async fn create_promotion(
+    code: &CouponCode,
+    scope: PromotionScope,
+    validity: Validity,
+    cap: Option<i64>,
+    framework: &TestFramework
+) -> Result<(), Report<UnrecoverableError>> {
+    let cmd = PromotionCommand::Create {
+        name: PromotionName::new("test"),
+        discount: Discount::percentage(10).change_context_lazy(|| UnrecoverableError)?,
+        scope,
+        validity,
+        cap: cap.map(UsageCap::new).transpose().change_context_lazy(|| UnrecoverableError)?,
+    };
+
+    PromotionCommandService::execute(framework.promotion_command_service(), code.clone(), cmd).await
+        .change_context_lazy(|| UnrecoverableError)?;
+
+    Ok(())
+}
+
+/// Valid from an hour ago until an hour from now.
+fn current() -> Result<Validity, Report<UnrecoverableError>> {
+    let now = OffsetDateTime::now_utc();
+    Validity::new(now - Duration::hours(1), now + Duration::hours(1))
+        .change_context_lazy(|| UnrecoverableError)
+}
+
+async fn redeem(order: OrderId, code: &CouponCode, framework: &TestFramework) -> Result<(), Report<UnrecoverableError>> {
+    RedeemCouponWorkflow::execute(framework.redeem_coupon_workflow(), order, code.clone()).await
+        .change_context_lazy(|| UnrecoverableError)?;
+
+    Ok(())
+}
+
+async fn pay(order: OrderId, amount: i64, framework: &TestFramework) -> Result<(), Report<UnrecoverableError>> {
+    OrderCommandService::execute(framework.order_command_service(), order, OrderCommand::Pay { tender: Tender::EMoney, tendered: Money::new(amount, Currency::default()) }).await
+        .change_context_lazy(|| UnrecoverableError)?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_create_promotion() -> Result<(), Report<UnrecoverableError>> {
+    setup_logging();
+    let framework = TestFramework::new()?;
+
+    let code = CouponCode::new("welcome").change_context_lazy(|| UnrecoverableError)?;
+
+    create_promotion(&code, PromotionScope::Order, current()?, None, &framework).await?;
+
+    let taken = CouponCode::new(" WELCOME ").change_context_lazy(|| UnrecoverableError)?;
+    if create_promotion(&taken, PromotionScope::Order, current()?, None, &framework).await.is_ok() {
+        return Err(Report::new(UnrecoverableError).attach_printable("Coupon code must not be taken twice"));
+    }
+
+    PromotionCommandService::execute(framework.promotion_command_service(), code.clone(), PromotionCommand::End).await
+        .change_context_lazy(|| UnrecoverableError)?;
+
+    if PromotionCommandService::execute(framework.promotion_command_service(), code, PromotionCommand::End).await.is_ok() {
+        return Err(Report::new(UnrecoverableError).attach_printable("Ended promotion must not be ended twice"));
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_redeem_coupon() -> Result<(), Report<UnrecoverableError>> {
+    setup_logging();
+    let framework = TestFramework::new()?;
+
+    let product = register_product(&framework).await?;
+    let other = register_product(&framework).await?;
+
+    let code = CouponCode::new("TENOFF").change_context_lazy(|| UnrecoverableError)?;
+    let scope = PromotionScope::products([product]).change_context_lazy(|| UnrecoverableError)?;
+    create_promotion(&code, scope, current()?, Some(1), &framework).await?;
+
+    let order = place_order(product, &framework).await?;
+    let quantity = OrderQuantity::new(1).change_context_lazy(|| UnrecoverableError)?;
+    AddProductToOrderWorkflow::execute(framework.add_product_to_order_workflow(), order, other, quantity, Vec::new(), Vec::new()).await
+        .change_context_lazy(|| UnrecoverableError)?;
+
+    redeem(order, &code, &framework).await?;
+
+    if redeem(order, &code, &framework).await.is_ok() {
+        return Err(Report::new(UnrecoverableError).attach_printable("Coupon must not be applied to the same order twice"));
+    }
+
+    // 10% off the 200 of the eligible product only, plus the 100 of the other product.
+    if pay(order, 300, &framework).await.is_ok() {
+        return Err(Report::new(UnrecoverableError).attach_printable("Discount must be taken off the total"));
+    }
+
+    pay(order, 280, &framework).await?;
+
+    let redeemed = framework.journal()
+        .read_all_by_event::<PromotionEvent>()
+        .await
+        .change_context_lazy(|| UnrecoverableError)?
+        .iter()
+        .map(|payload| PromotionEvent::from_bytes(&payload.bytes))
+        .collect::<Result<Vec<_>, _>>()
+        .change_context_lazy(|| UnrecoverableError)?
+        .into_iter()
+        .any(|event| matches!(event, PromotionEvent::Redeemed { order: redeemed, .. } if redeemed == order));
+
+    if !redeemed {
+        return Err(Report::new(UnrecoverableError).attach_printable("Redemption must be recorded"));
+    }
+
+    let another = place_order(product, &framework).await?;
+
+    if redeem(another, &code, &framework).await.is_ok() {
+        return Err(Report::new(UnrecoverableError).attach_printable("Exhausted coupon must be rejected"));
+    }
+
+    pay(another, 200, &framework).await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_redeem_expired_coupon() -> Result<(), Report<UnrecoverableError>> {
+    setup_logging();
+    let framework = TestFramework::new()?;
+
+    let product = register_product(&framework).await?;
+
+    let now = OffsetDateTime::now_utc();
+    let expired = Validity::new(now - Duration::days(2), now - Duration::days(1))
+        .change_context_lazy(|| UnrecoverableError)?;
+
+    let code = CouponCode::new("LASTWEEK").change_context_lazy(|| UnrecoverableError)?;
+    create_promotion(&code, PromotionScope::Order, expired, None, &framework).await?;
+
+    let order = place_order(product, &framework).await?;
+
+    if redeem(order, &code, &framework).await.is_ok() {
+        return Err(Report::new(UnrecoverableError).attach_printable("Expired coupon must be rejected"));
+    }
+
+    let unknown = CouponCode::new("UNKNOWN").change_context_lazy(|| UnrecoverableError)?;
+    if redeem(order, &unknown, &framework).await.is_ok() {
+        return Err(Report::new(UnrecoverableError).attach_printable("Unknown coupon must be rejected"));
+    }
+
+    pay(order, 200, &framework).await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cancel_order_releases_coupon() -> Result<(), Report<UnrecoverableError>> {
+    setup_logging();
+    let framework = TestFramework::new()?;
+
+    let product = register_product(&framework).await?;
+
+    let code = CouponCode::new("ONCE").change_context_lazy(|| UnrecoverableError)?;
+    create_promotion(&code, PromotionScope::Order, current()?, Some(1), &framework).await?;
+
+    let order = place_order(product, &framework).await?;
+    redeem(order, &code, &framework).await?;
+
+    OrderCommandService::execute(framework.order_command_service(), order, OrderCommand::Cancel).await
+        .change_context_lazy(|| UnrecoverableError)?;
+
+    let another = place_order(product, &framework).await?;
+    redeem(another, &code, &framework).await?;
+
+    pay(another, 180, &framework).await?;
+
+    Ok(())
+}
@@ -0,0 +1,200 @@
+use tracing_subscriber::EnvFilter;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use app_cmd::errors::ApplicationError;
+use app_cmd::services::staff::{DependOnStaffCommandService, StaffCommandService};
+use app_cmd::workflow::staff::{AuthenticateStaffWorkflow, DependOnAuthenticateStaffWorkflow, DependOnVerifyStaffSessionWorkflow, VerifyStaffSessionWorkflow};
+use kernel::entities::staff::{HashedPassword, Permission, Role, StaffName};
+use kernel::io::commands::StaffCommand;
+
+include!("./test_framework.rs");
+
+//noinspection RsTraitImplOrphanRules
+impl DependOnStaffCommandService for TestFramework {
+    type StaffCommandService = Self;
+    fn staff_command_service(&self) -> &Self::StaffCommandService {
+        self
+    }
+}
+
+//noinspection RsTraitImplOrphanRules
+impl DependOnAuthenticateStaffWorkflow for TestFramework {
+    type AuthenticateStaffWorkflow = Self;
+    fn authenticate_staff_workflow(&self) -> &Self::AuthenticateStaffWorkflow {
+        self
+    }
+}
+
+//noinspection RsTraitImplOrphanRules
+impl DependOnVerifyStaffSessionWorkflow for TestFramework {
+    type VerifyStaffSessionWorkflow = Self;
+    fn verify_staff_session_workflow(&self) -> &Self::VerifyStaffSessionWorkflow {
+        self
+    }
+}
+
+fn setup_logging() {
+    let _ = tracing_subscriber::registry()
+        .with(EnvFilter::new("trace"))
+        .with(tracing_subscriber::fmt::layer())
+        .try_init();
+}
+
+async fn register_staff(name: &StaffName, role: Role, password: &str, framework: &TestFramework) -> Result<(), Report<UnrecoverableError>> {
+    let cmd = StaffCommand::Register {
+        role,
+        password: HashedPassword::new(password).change_context_lazy(|| UnrecoverableError)?,
+    };
+
+    StaffCommandService::execute(framework.staff_command_service(), name.clone(), cmd).await
+        .change_context_lazy(|| UnrecoverableError)?;
+
+    Ok(())
+}
+
+async fn authenticate(name: &StaffName, password: &str, framework: &TestFramework) -> Result<Role, Report<ApplicationError>> {
+    let staff = AuthenticateStaffWorkflow::execute(framework.authenticate_staff_workflow(), name.clone(), password.to_string()).await?;
+    Ok(*staff.role())
+}
+
+#[test]
+fn test_hash_password() -> Result<(), Report<UnrecoverableError>> {
+    let hash = HashedPassword::new("correct horse").change_context_lazy(|| UnrecoverableError)?;
+
+    if hash.as_ref().contains("correct horse") || !hash.as_ref().starts_with("$argon2") {
+        return Err(Report::new(UnrecoverableError).attach_printable("Password must be stored as an argon2 hash"));
+    }
+
+    if !hash.verify("correct horse") || hash.verify("wrong horse") {
+        return Err(Report::new(UnrecoverableError).attach_printable("Password must only match itself"));
+    }
+
+    if HashedPassword::new("short").is_ok() {
+        return Err(Report::new(UnrecoverableError).attach_printable("Short password must be rejected"));
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_register_staff() -> Result<(), Report<UnrecoverableError>> {
+    setup_logging();
+    let framework = TestFramework::new()?;
+
+    let name = StaffName::new("cashier").change_context_lazy(|| UnrecoverableError)?;
+    register_staff(&name, Role::Cashier, "password1", &framework).await?;
+
+    let taken = StaffName::new(" Cashier ").change_context_lazy(|| UnrecoverableError)?;
+    if register_staff(&taken, Role::Cashier, "password2", &framework).await.is_ok() {
+        return Err(Report::new(UnrecoverableError).attach_printable("Staff name must not be taken twice"));
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_authenticate_staff() -> Result<(), Report<UnrecoverableError>> {
+    setup_logging();
+    let framework = TestFramework::new()?;
+
+    let name = StaffName::new("manager").change_context_lazy(|| UnrecoverableError)?;
+    register_staff(&name, Role::Admin, "password1", &framework).await?;
+
+    authenticate(&name, "password1", &framework).await
+        .change_context_lazy(|| UnrecoverableError)?;
+
+    let Err(e) = authenticate(&name, "password2", &framework).await else {
+        return Err(Report::new(UnrecoverableError).attach_printable("Wrong password must be rejected"));
+    };
+    if !matches!(e.current_context(), ApplicationError::Unauthenticated) {
+        return Err(Report::new(UnrecoverableError).attach_printable("Wrong password must be rejected as unauthenticated"));
+    }
+
+    let unknown = StaffName::new("nobody").change_context_lazy(|| UnrecoverableError)?;
+    if authenticate(&unknown, "password1", &framework).await.is_ok() {
+        return Err(Report::new(UnrecoverableError).attach_printable("Unknown staff must be rejected"));
+    }
+
+    let cmd = StaffCommand::ChangePassword {
+        password: HashedPassword::new("password2").change_context_lazy(|| UnrecoverableError)?,
+    };
+    StaffCommandService::execute(framework.staff_command_service(), name.clone(), cmd).await
+        .change_context_lazy(|| UnrecoverableError)?;
+
+    if authenticate(&name, "password1", &framework).await.is_ok() {
+        return Err(Report::new(UnrecoverableError).attach_printable("Old password must be rejected after it has been changed"));
+    }
+    authenticate(&name, "password2", &framework).await
+        .change_context_lazy(|| UnrecoverableError)?;
+
+    StaffCommandService::execute(framework.staff_command_service(), name.clone(), StaffCommand::Remove).await
+        .change_context_lazy(|| UnrecoverableError)?;
+
+    if authenticate(&name, "password2", &framework).await.is_ok() {
+        return Err(Report::new(UnrecoverableError).attach_printable("Removed staff must be rejected"));
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_verify_staff_session() -> Result<(), Report<UnrecoverableError>> {
+    setup_logging();
+    let framework = TestFramework::new()?;
+
+    let name = StaffName::new("waiter").change_context_lazy(|| UnrecoverableError)?;
+    register_staff(&name, Role::Cashier, "password1", &framework).await?;
+
+    VerifyStaffSessionWorkflow::execute(framework.verify_staff_session_workflow(), name.clone()).await
+        .change_context_lazy(|| UnrecoverableError)?;
+
+    StaffCommandService::execute(framework.staff_command_service(), name.clone(), StaffCommand::Remove).await
+        .change_context_lazy(|| UnrecoverableError)?;
+
+    let Err(e) = VerifyStaffSessionWorkflow::execute(framework.verify_staff_session_workflow(), name.clone()).await else {
+        return Err(Report::new(UnrecoverableError).attach_printable("Session of removed staff must be rejected"));
+    };
+    if !matches!(e.current_context(), ApplicationError::Unauthenticated) {
+        return Err(Report::new(UnrecoverableError).attach_printable("Session of removed staff must be rejected as unauthenticated"));
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_role_permissions() -> Result<(), Report<UnrecoverableError>> {
+    if !Role::Admin.permits(Permission::EditCatalog) || Role::Cashier.permits(Permission::EditCatalog) {
+        return Err(Report::new(UnrecoverableError).attach_printable("Only admins may edit the catalog"));
+    }
+
+    if !Role::Cashier.permits(Permission::TakePayment) || Role::Kiosk.permits(Permission::TakePayment) {
+        return Err(Report::new(UnrecoverableError).attach_printable("Kiosks must not take payments"));
+    }
+
+    if !Role::Kitchen.permits(Permission::PrepareOrder) || Role::Kitchen.permits(Permission::PlaceOrder) {
+        return Err(Report::new(UnrecoverableError).attach_printable("Kitchen may only prepare orders"));
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_change_staff_role() -> Result<(), Report<UnrecoverableError>> {
+    setup_logging();
+    let framework = TestFramework::new()?;
+
+    let name = StaffName::new("kitchen").change_context_lazy(|| UnrecoverableError)?;
+    register_staff(&name, Role::Kitchen, "password1", &framework).await?;
+
+    StaffCommandService::execute(framework.staff_command_service(), name.clone(), StaffCommand::ChangeRole { role: Role::Cashier }).await
+        .change_context_lazy(|| UnrecoverableError)?;
+
+    let role = authenticate(&name, "password1", &framework).await
+        .change_context_lazy(|| UnrecoverableError)?;
+
+    if role != Role::Cashier {
+        return Err(Report::new(UnrecoverableError).attach_printable(format!("Role must have been changed, but was {role}")));
+    }
+
+    Ok(())
+}
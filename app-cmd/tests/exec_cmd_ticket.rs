@@ -0,0 +1,111 @@
+use nitinol::Event;
+use time::macros::date;
+use tracing_subscriber::EnvFilter;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use app_cmd::services::ticket::{DependOnTicketCounterCommandService, TicketCounterCommandService};
+use kernel::entities::order::OrderId;
+use kernel::entities::ticket::{BusinessDay, TicketNumber};
+use kernel::io::commands::TicketCounterCommand;
+use kernel::io::events::TicketCounterEvent;
+
+include!("./test_framework.rs");
+
+//noinspection RsTraitImplOrphanRules
+impl DependOnTicketCounterCommandService for TestFramework {
+    type TicketCounterCommandService = Self;
+    fn ticket_counter_command_service(&self) -> &Self::TicketCounterCommandService {
+        self
+    }
+}
+
+fn setup_logging() {
+    let _ = tracing_subscriber::registry()
+        .with(EnvFilter::new("trace"))
+        .with(tracing_subscriber::fmt::layer())
+        .try_init();
+}
+
+async fn issue(day: BusinessDay, framework: &TestFramework) -> Result<(), Report<UnrecoverableError>> {
+    let cmd = TicketCounterCommand::Issue { order: OrderId::default(), day };
+    
+    framework.ticket_counter_command_service()
+        .execute(cmd).await
+        .change_context_lazy(|| UnrecoverableError)?;
+    
+    Ok(())
+}
+
+async fn issued_numbers(framework: &TestFramework) -> Result<Vec<i64>, Report<UnrecoverableError>> {
+    let numbers = framework.journal()
+        .read_all_by_event::<TicketCounterEvent>()
+        .await
+        .change_context_lazy(|| UnrecoverableError)?
+        .iter()
+        .map(|payload| TicketCounterEvent::from_bytes(&payload.bytes))
+        .collect::<Result<Vec<_>, _>>()
+        .change_context_lazy(|| UnrecoverableError)?
+        .into_iter()
+        .map(|TicketCounterEvent::Issued { number, .. }| number.into())
+        .collect();
+    
+    Ok(numbers)
+}
+
+#[tokio::test]
+async fn test_issue_sequential_numbers() -> Result<(), Report<UnrecoverableError>> {
+    setup_logging();
+    let framework = TestFramework::new()?;
+    
+    let day = BusinessDay::new(date!(2024 - 10 - 20));
+    
+    issue(day, &framework).await?;
+    issue(day, &framework).await?;
+    issue(day, &framework).await?;
+    
+    let numbers = issued_numbers(&framework).await?;
+    
+    if numbers != vec![1, 2, 3] {
+        return Err(Report::new(UnrecoverableError)
+            .attach_printable(format!("Unexpected numbers: {numbers:?}")));
+    }
+    
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_reset_numbers_on_next_day() -> Result<(), Report<UnrecoverableError>> {
+    setup_logging();
+    let framework = TestFramework::new()?;
+    
+    issue(BusinessDay::new(date!(2024 - 10 - 20)), &framework).await?;
+    issue(BusinessDay::new(date!(2024 - 10 - 20)), &framework).await?;
+    issue(BusinessDay::new(date!(2024 - 10 - 21)), &framework).await?;
+    
+    let numbers = issued_numbers(&framework).await?;
+    
+    if numbers != vec![1, 2, 1] {
+        return Err(Report::new(UnrecoverableError)
+            .attach_printable(format!("Unexpected numbers: {numbers:?}")));
+    }
+    
+    if issue(BusinessDay::new(date!(2024 - 10 - 20)), &framework).await.is_ok() {
+        return Err(Report::new(UnrecoverableError)
+            .attach_printable("Numbers must not be issued for a past business day"));
+    }
+    
+    Ok(())
+}
+
+#[test]
+fn test_wrap_around_ticket_number() -> Result<(), Report<UnrecoverableError>> {
+    let last = TicketNumber::new(TicketNumber::MAX)
+        .change_context_lazy(|| UnrecoverableError)?;
+    
+    if last.next() != TicketNumber::first() {
+        return Err(Report::new(UnrecoverableError)
+            .attach_printable("Ticket number must wrap around after the maximum"));
+    }
+    
+    Ok(())
+}
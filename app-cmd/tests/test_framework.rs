@@ -15,7 +15,15 @@ use nitinol::protocol::adapter::inmemory::InMemoryEventStore;
 #[allow(unused_imports)]
 use nitinol::protocol::io::ReadProtocol;
 #[allow(unused_imports)]
-use app_cmd::adapter::{DependOnEventProjector, DependOnProcessManager};
+use app_cmd::adapter::{DependOnEventProjector, DependOnProcessManager, DependOnStoreOffset, DependOnTaxRates};
+#[allow(unused_imports)]
+use app_cmd::audit::{Actor, AuditLog, AuditRecord, DependOnAuditLog};
+#[allow(unused_imports)]
+use app_cmd::errors::ApplicationError;
+#[allow(unused_imports)]
+use kernel::entities::product::TaxRates;
+#[allow(unused_imports)]
+use kernel::entities::ticket::StoreOffset;
 
 #[derive(Debug, thiserror::Error)]
 #[error("unrecoverable error")]
@@ -25,7 +33,27 @@ pub struct UnrecoverableError;
 pub struct TestFramework {
     manager: ProcessManager,
     projector: EventProjector,
-    journal: InMemoryEventStore
+    journal: InMemoryEventStore,
+    tax_rates: TaxRates,
+    store_offset: StoreOffset,
+    audit: InMemoryAuditLog,
+}
+
+#[derive(Clone, Default)]
+pub struct InMemoryAuditLog {
+    records: std::sync::Arc<std::sync::Mutex<Vec<(AuditRecord, i64)>>>,
+    failing: bool,
+}
+
+#[async_trait::async_trait]
+impl AuditLog for InMemoryAuditLog {
+    async fn record(&self, record: AuditRecord, sequence: i64) -> Result<(), Report<ApplicationError>> {
+        if self.failing {
+            return Err(Report::new(ApplicationError::Audit));
+        }
+        self.records.lock().unwrap().push((record, sequence));
+        Ok(())
+    }
 }
 
 impl TestFramework {
@@ -39,12 +67,44 @@ impl TestFramework {
         
         let projector = EventProjector::new(inmemory.clone());
         
-        Ok(TestFramework { manager, projector, journal: inmemory })
+        Ok(TestFramework { 
+            manager, 
+            projector, 
+            journal: inmemory, 
+            tax_rates: TaxRates::default(), 
+            store_offset: StoreOffset::default(), 
+            audit: InMemoryAuditLog::default() 
+        })
+    }
+    
+    #[allow(dead_code)]
+    pub fn with_store_offset(self, store_offset: StoreOffset) -> TestFramework {
+        TestFramework { store_offset, ..self }
+    }
+    
+    #[allow(dead_code)]
+    pub fn with_failing_audit(self) -> TestFramework {
+        TestFramework { audit: InMemoryAuditLog { failing: true, ..self.audit }, ..self }
     }
     
     pub fn journal(&self) -> ReadProtocol {
         ReadProtocol::new(self.journal.clone())
     }
+    
+    #[allow(dead_code)]
+    pub fn audit(&self) -> Vec<AuditRecord> {
+        self.audit.records.lock().unwrap().iter()
+            .map(|(record, _)| record.clone())
+            .collect()
+    }
+    
+    /// Journal sequences the audited events were applied at, in the order they were recorded.
+    #[allow(dead_code)]
+    pub fn audited_sequences(&self) -> Vec<i64> {
+        self.audit.records.lock().unwrap().iter()
+            .map(|(_, sequence)| *sequence)
+            .collect()
+    }
 }
 
 impl DependOnProcessManager for TestFramework {
@@ -57,4 +117,23 @@ impl DependOnEventProjector for TestFramework {
     fn event_projector(&self) -> &EventProjector {
         &self.projector
     }
-}
\ No newline at end of file
+}
+
+impl DependOnTaxRates for TestFramework {
+    fn tax_rates(&self) -> &TaxRates {
+        &self.tax_rates
+    }
+}
+
+impl DependOnStoreOffset for TestFramework {
+    fn store_offset(&self) -> &StoreOffset {
+        &self.store_offset
+    }
+}
+
+impl DependOnAuditLog for TestFramework {
+    type AuditLog = InMemoryAuditLog;
+    fn audit_log(&self) -> &Self::AuditLog {
+        &self.audit
+    }
+}
@@ -1,11 +1,33 @@
+mod audit;
 mod categories_all;
 mod category;
+mod device;
+mod history;
 mod product;
 mod products_all;
 mod image;
+mod menu;
+mod money;
+mod order;
+mod payment;
+mod promotion;
+mod receipt;
+mod sales;
+mod ticket;
 
+pub use audit::*;
 pub use category::*;
 pub use categories_all::*;
+pub use device::*;
+pub use history::*;
 pub use product::*;
 pub use image::*;
-pub use products_all::*;
\ No newline at end of file
+pub use menu::*;
+pub use money::*;
+pub use products_all::*;
+pub use order::*;
+pub use payment::*;
+pub use promotion::*;
+pub use receipt::*;
+pub use sales::*;
+pub use ticket::*;
\ No newline at end of file
@@ -0,0 +1,42 @@
+use async_trait::async_trait;
+use error_stack::Report;
+use serde::Serialize;
+use time::PrimitiveDateTime;
+use uuid::Uuid;
+use crate::errors::QueryError;
+
+#[derive(Debug, Serialize, sqlx::FromRow, utoipa::ToSchema)]
+pub struct AuditEntry {
+    pub id: i64,
+    /// `product`, `category` or `categories`.
+    pub aggregate: String,
+    pub aggregate_id: String,
+    /// Sequence of the event in the journal of the aggregate, unknown for entries recorded before it was kept.
+    pub sequence: Option<i64>,
+    /// Name of the event variant, such as `ChangedProductPrice`.
+    pub event: String,
+    /// Staff name of the session that dispatched the command.
+    pub staff: Option<String>,
+    pub device: Option<Uuid>,
+    pub request_id: Option<String>,
+    pub client_ip: Option<String>,
+    /// UTC.
+    pub recorded_at: PrimitiveDateTime,
+}
+
+pub trait DependOnGetAuditLogQueryService: 'static + Sync + Send {
+    type GetAuditLogQueryService: GetAuditLogQueryService;
+    fn get_audit_log_query_service(&self) -> &Self::GetAuditLogQueryService;
+}
+
+/// `aggregate` matches either the kind of aggregate or its id. 
+/// `from` and `to` are inclusive UTC times, unbounded when `None`.
+#[async_trait]
+pub trait GetAuditLogQueryService: 'static + Sync + Send {
+    async fn get_audit_log(
+        &self, 
+        aggregate: Option<String>, 
+        from: Option<PrimitiveDateTime>, 
+        to: Option<PrimitiveDateTime>
+    ) -> Result<Vec<AuditEntry>, Report<QueryError>>;
+}
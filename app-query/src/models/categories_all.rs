@@ -4,6 +4,7 @@ use async_trait::async_trait;
 use error_stack::Report;
 use serde::Serialize;
 use std::collections::BTreeSet;
+use time::PrimitiveDateTime;
 
 #[derive(Serialize, sqlx::FromRow, utoipa::ToSchema)]
 pub struct AllCategories(pub BTreeSet<OrderedCategory>);
@@ -15,5 +16,6 @@ pub trait DependOnGetAllCategoriesQueryService: 'static + Sync + Send {
 
 #[async_trait]
 pub trait GetAllCategoriesQueryService: 'static + Sync + Send {
-    async fn get_all_categories(&self) -> Result<AllCategories, Report<QueryError>>;
+    /// With `at`, categories whose schedule does not include that local time are left out.
+    async fn get_all_categories(&self, at: Option<PrimitiveDateTime>) -> Result<AllCategories, Report<QueryError>>;
 }
@@ -0,0 +1,29 @@
+use async_trait::async_trait;
+use error_stack::Report;
+use serde::Serialize;
+use time::PrimitiveDateTime;
+use uuid::Uuid;
+use crate::errors::QueryError;
+
+#[derive(Serialize, sqlx::FromRow, utoipa::ToSchema)]
+pub struct DeviceRecord {
+    pub id: Uuid,
+    pub name: String,
+    /// `cashier`, `kitchen` or `kiosk`.
+    pub role: String,
+    pub store: String,
+    pub registered_at: PrimitiveDateTime,
+    /// When the key was last replaced, if ever.
+    pub rotated_at: Option<PrimitiveDateTime>,
+    pub revoked: bool,
+}
+
+pub trait DependOnGetDeviceQueryService: 'static + Sync + Send {
+    type GetDeviceQueryService: GetDeviceQueryService;
+    fn get_device_query_service(&self) -> &Self::GetDeviceQueryService;
+}
+
+#[async_trait]
+pub trait GetDeviceQueryService: 'static + Sync + Send {
+    async fn get_all_devices(&self) -> Result<Vec<DeviceRecord>, Report<QueryError>>;
+}
@@ -0,0 +1,107 @@
+use std::collections::BTreeMap;
+use async_trait::async_trait;
+use error_stack::Report;
+use serde::Serialize;
+use time::{OffsetDateTime, Time};
+use uuid::Uuid;
+use crate::errors::QueryError;
+use crate::models::Money;
+
+/// An event of a product as it was persisted in the journal.
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct ProductHistory {
+    /// Position of the event in the sequence of the product.
+    pub sequence: i64,
+    pub recorded_at: OffsetDateTime,
+    pub event: ProductChange,
+}
+
+/// What an event changed about a product, named after the event.
+///
+/// Images are referred to by their id, the bytes are served by `/images/{image_id}`.
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub enum ProductChange {
+    Registered { name: String, desc: String, price: Money, image: Uuid },
+    RenamedProductName { new: String },
+    EditedProductDesc { new: String },
+    ChangedProductPrice { new: Money },
+    ChangedProductImage { image: Uuid },
+    MarkedSoldOut,
+    MarkedAvailable,
+    ChangedStock { new: i64 },
+    Restocked { amount: i64, new: i64 },
+    DecrementedStock { amount: i64, new: i64 },
+    AddedOptionGroup { group: Uuid, name: String, min: i64, max: i64, options: Vec<HistoryOption> },
+    EditedOptionGroup { group: Uuid, name: String, min: i64, max: i64 },
+    RemovedOptionGroup { group: Uuid },
+    AddedOption { group: Uuid, option: HistoryOption },
+    EditedOption { group: Uuid, option: HistoryOption },
+    RemovedOption { group: Uuid, option: Uuid },
+    AddedBundleSlot { slot: Uuid, name: String, choices: Vec<Uuid> },
+    ChangedBundleChoices { slot: Uuid, choices: Vec<Uuid> },
+    RemovedBundleSlot { slot: Uuid },
+    /// The product became a choice of `bundle`.
+    JoinedBundle { bundle: Uuid },
+    /// The product is no longer a choice of `bundle`.
+    LeftBundle { bundle: Uuid },
+    /// `category` is `standard` or `reduced`.
+    ChangedTax { category: String, inclusive: bool },
+    ChangedSchedule { windows: Vec<HistoryTimeWindow> },
+    ClearedSchedule,
+    Deleted,
+}
+
+/// An option as it was added or edited.
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct HistoryOption {
+    pub id: Uuid,
+    pub name: String,
+    pub price_delta: Money,
+}
+
+/// `start..end` in the local time of the store on each of `weekdays`.
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct HistoryTimeWindow {
+    /// Counted from Sunday (0) to Saturday (6).
+    pub weekdays: Vec<u8>,
+    #[schema(value_type = String)]
+    pub start: Time,
+    #[schema(value_type = String)]
+    pub end: Time,
+}
+
+/// An event of a category as it was persisted in the journal.
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct CategoryHistory {
+    /// Position of the event in the sequence of the category.
+    pub sequence: i64,
+    pub recorded_at: OffsetDateTime,
+    pub event: CategoryChange,
+}
+
+/// What an event changed about a category, named after the event.
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub enum CategoryChange {
+    Created { name: String },
+    Renamed { new: String },
+    Deleted,
+    ChangedSchedule { windows: Vec<HistoryTimeWindow> },
+    ClearedSchedule,
+    AddedProduct { product: Uuid, ordering: i64 },
+    /// `new` is the ordering of the products that are left.
+    RemovedProduct { new: BTreeMap<i64, Uuid> },
+    ChangedProductOrdering { new: BTreeMap<i64, Uuid> },
+}
+
+pub trait DependOnGetHistoryQueryService: 'static + Sync + Send {
+    type GetHistoryQueryService: GetHistoryQueryService;
+    fn get_history_query_service(&self) -> &Self::GetHistoryQueryService;
+}
+
+/// Reads the timeline of an aggregate straight from the event journal, oldest first.
+/// The timeline is empty if the aggregate has never existed.
+#[async_trait]
+pub trait GetHistoryQueryService: 'static + Sync + Send {
+    async fn get_product_history(&self, id: &Uuid) -> Result<Vec<ProductHistory>, Report<QueryError>>;
+    async fn get_category_history(&self, id: &Uuid) -> Result<Vec<CategoryHistory>, Report<QueryError>>;
+}
@@ -0,0 +1,57 @@
+use async_trait::async_trait;
+use error_stack::Report;
+use serde::Serialize;
+use time::OffsetDateTime;
+use uuid::Uuid;
+use crate::errors::QueryError;
+use crate::models::Money;
+
+/// The categories and their products as they were listed at `at`, replayed from the event journal.
+/// 
+/// Categories and products whose schedule did not offer them at `at` are left out.
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct Menu {
+    pub at: OffsetDateTime,
+    pub categories: Vec<MenuCategory>,
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct MenuCategory {
+    pub id: Uuid,
+    pub name: String,
+    pub ordering: i64,
+    pub products: Vec<MenuProduct>,
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct MenuProduct {
+    pub id: Uuid,
+    pub name: String,
+    pub ordering: i64,
+    pub price: Money,
+    /// `false` while the product was marked as sold out.
+    pub available: bool,
+}
+
+/// Where the replay of the journal stops.
+#[derive(Debug, Clone, Copy)]
+pub enum MenuCutoff {
+    /// Every event recorded until then (inclusive).
+    At(OffsetDateTime),
+    /// Every event of the product or category `id` up to `sequence` (inclusive), as numbered in its history,
+    /// along with the events of everything else recorded until that event was.
+    /// 
+    /// Unlike a time, this tells apart the events of `id` that were recorded within the same instant.
+    Sequence { id: Uuid, sequence: i64 },
+}
+
+pub trait DependOnGetMenuQueryService: 'static + Sync + Send {
+    type GetMenuQueryService: GetMenuQueryService;
+    fn get_menu_query_service(&self) -> &Self::GetMenuQueryService;
+}
+
+#[async_trait]
+pub trait GetMenuQueryService: 'static + Sync + Send {
+    /// `None` if `cutoff` names a sequence that is not in the history of its product or category.
+    async fn get_menu_as_of(&self, cutoff: MenuCutoff) -> Result<Option<Menu>, Report<QueryError>>;
+}
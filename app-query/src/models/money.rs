@@ -0,0 +1,17 @@
+use serde::Serialize;
+
+/// An amount in the minor units of an ISO 4217 currency, e.g. `{"amount": 1250, "currency": "USD"}` for $12.50.
+/// 
+/// Selected from the `amount` and `currency` columns.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, sqlx::FromRow, utoipa::ToSchema)]
+pub struct Money {
+    pub amount: i64,
+    pub currency: String,
+}
+
+impl Money {
+    /// Another amount in the same currency.
+    pub fn with_amount(&self, amount: i64) -> Money {
+        Money { amount, currency: self.currency.clone() }
+    }
+}
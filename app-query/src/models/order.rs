@@ -0,0 +1,54 @@
+use async_trait::async_trait;
+use error_stack::Report;
+use serde::Serialize;
+use uuid::Uuid;
+use crate::errors::QueryError;
+
+#[derive(Serialize, sqlx::FromRow, utoipa::ToSchema)]
+pub struct OpenOrderItem {
+    #[serde(skip)]
+    pub order_id: Uuid,
+    pub line: i64,
+    pub product: Uuid,
+    /// `None` when the product has been deleted after it was ordered.
+    pub name: Option<String>,
+    pub quantity: i64,
+    /// Names of the selected options.
+    #[sqlx(skip)]
+    pub options: Vec<String>,
+    /// Names of the products chosen for the slots of a bundle.
+    #[sqlx(skip)]
+    pub components: Vec<String>,
+}
+
+#[derive(Serialize, sqlx::FromRow, utoipa::ToSchema)]
+pub struct OpenOrder {
+    pub id: Uuid,
+    pub number: Option<i64>,
+    pub status: String,
+    /// Seconds elapsed since the order was accepted.
+    pub elapsed: i64,
+    #[sqlx(skip)]
+    pub items: Vec<OpenOrderItem>,
+}
+
+/// Orders the kitchen still has to work on, grouped by their status.
+#[derive(Serialize, Default, utoipa::ToSchema)]
+pub struct OpenOrders {
+    pub accepted: Vec<OpenOrder>,
+    pub preparing: Vec<OpenOrder>,
+    pub ready: Vec<OpenOrder>,
+}
+
+pub trait DependOnGetOpenOrdersQueryService: 'static + Sync + Send {
+    type GetOpenOrdersQueryService: GetOpenOrdersQueryService;
+    fn get_open_orders_query_service(&self) -> &Self::GetOpenOrdersQueryService;
+}
+
+#[async_trait]
+pub trait GetOpenOrdersQueryService: 'static + Sync + Send {
+    async fn get_open_orders(&self) -> Result<OpenOrders, Report<QueryError>>;
+    /// An order accepted by the kitchen in any status, e.g. to print its ticket slip. 
+    /// `None` if the order does not exist or has not been accepted yet.
+    async fn get_accepted_order(&self, order: &Uuid) -> Result<Option<OpenOrder>, Report<QueryError>>;
+}
@@ -0,0 +1,59 @@
+use async_trait::async_trait;
+use error_stack::Report;
+use serde::Serialize;
+use time::{Date, PrimitiveDateTime};
+use uuid::Uuid;
+use crate::errors::QueryError;
+
+#[derive(Serialize, sqlx::FromRow, utoipa::ToSchema)]
+pub struct PaymentRecord {
+    pub order_id: Uuid,
+    pub tender: String,
+    pub amount: i64,
+    pub tendered: i64,
+    pub change: i64,
+    /// ISO 4217 currency code of the amounts.
+    pub currency: String,
+    pub paid_at: PrimitiveDateTime,
+}
+
+#[derive(Serialize, sqlx::FromRow, utoipa::ToSchema)]
+pub struct TenderSummary {
+    pub tender: String,
+    pub currency: String,
+    pub count: i64,
+    pub amount: i64,
+    pub tendered: i64,
+    pub change: i64,
+}
+
+/// Totals of the payments made in a single currency.
+#[derive(Serialize, utoipa::ToSchema)]
+pub struct PaymentTotal {
+    pub currency: String,
+    pub total: i64,
+    /// Cash that should have been added to the drawer, i.e. cash tendered minus change given.
+    pub cash_in_drawer: i64,
+}
+
+/// Totals of a business day used to reconcile the cash drawer, one for each currency paid in.
+/// 
+/// Payments of cancelled orders are not included.
+#[derive(Serialize, utoipa::ToSchema)]
+pub struct PaymentSummary {
+    pub business_day: Date,
+    pub totals: Vec<PaymentTotal>,
+    pub tenders: Vec<TenderSummary>,
+}
+
+pub trait DependOnGetPaymentQueryService: 'static + Sync + Send {
+    type GetPaymentQueryService: GetPaymentQueryService;
+    fn get_payment_query_service(&self) -> &Self::GetPaymentQueryService;
+}
+
+/// `day` defaults to the current business day when `None`.
+#[async_trait]
+pub trait GetPaymentQueryService: 'static + Sync + Send {
+    async fn get_payments(&self, day: Option<Date>) -> Result<Vec<PaymentRecord>, Report<QueryError>>;
+    async fn get_payment_summary(&self, day: Option<Date>) -> Result<PaymentSummary, Report<QueryError>>;
+}
@@ -4,12 +4,15 @@ use error_stack::Report;
 use serde::Serialize;
 use uuid::Uuid;
 use crate::errors::QueryError;
+use crate::models::Money;
 
 #[derive(Serialize, sqlx::FromRow, utoipa::ToSchema)]
 pub struct Product {
     pub id: Uuid,
     pub name: String,
-    pub price: i64,
+    #[sqlx(flatten)]
+    pub price: Money,
+    pub available: bool,
 }
 
 impl PartialEq<Self> for Product {
@@ -25,6 +28,7 @@ impl Hash for Product {
         self.id.hash(state);
         self.name.hash(state);
         self.price.hash(state);
+        self.available.hash(state);
     }
 }
 
@@ -34,7 +38,46 @@ pub struct ProductDetails {
     pub id: Uuid,
     pub name: String,
     pub desc: String,
-    pub price: i64,
+    #[sqlx(flatten)]
+    pub price: Money,
+    pub available: bool,
+    /// Tax category of the product, `standard` or `reduced`.
+    pub tax: String,
+    /// Whether `price` already includes the tax.
+    pub tax_inclusive: bool,
+    /// Tax rate in percent currently applied to `tax`.
+    #[sqlx(skip)]
+    pub tax_rate: i64,
+    #[sqlx(skip)]
+    pub price_including_tax: Money,
+    #[sqlx(skip)]
+    pub price_excluding_tax: Money,
+    #[sqlx(skip)]
+    pub options: Vec<ProductOptionGroup>,
+    /// Empty unless the product is a set menu.
+    #[sqlx(skip)]
+    pub bundle: Vec<ProductBundleSlot>,
+}
+
+/// A group of choices shown to the customer, of which at least `min` and at most `max` are selected.
+#[derive(Serialize, sqlx::FromRow, utoipa::ToSchema)]
+pub struct ProductOptionGroup {
+    pub id: Uuid,
+    pub name: String,
+    pub min: i64,
+    pub max: i64,
+    #[sqlx(skip)]
+    pub options: Vec<ProductOption>,
+}
+
+#[derive(Serialize, sqlx::FromRow, utoipa::ToSchema)]
+pub struct ProductOption {
+    #[serde(skip)]
+    pub option_group: Uuid,
+    pub id: Uuid,
+    pub name: String,
+    /// In minor units of the currency of the product.
+    pub price_delta: i64,
 }
 
 
@@ -52,9 +95,30 @@ impl Hash for ProductDetails {
         self.name.hash(state);
         self.desc.hash(state);
         self.price.hash(state);
+        self.available.hash(state);
     }
 }
 
+#[derive(Serialize, sqlx::FromRow, utoipa::ToSchema)]
+pub struct ProductBundleSlot {
+    pub id: Uuid,
+    pub name: String,
+    #[sqlx(skip)]
+    pub choices: Vec<ProductBundleChoice>,
+}
+
+/// A product that can be chosen for a [`ProductBundleSlot`].
+#[derive(Serialize, sqlx::FromRow, utoipa::ToSchema)]
+pub struct ProductBundleChoice {
+    #[serde(skip)]
+    pub slot: Uuid,
+    pub id: Uuid,
+    pub name: String,
+    #[sqlx(flatten)]
+    pub price: Money,
+    pub available: bool,
+}
+
 pub trait DependOnGetProductQueryService: 'static + Sync + Send {
     type GetProductQueryService: GetProductQueryService;
     fn get_product_query_service(&self) -> &Self::GetProductQueryService;
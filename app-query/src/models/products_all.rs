@@ -1,10 +1,11 @@
 use error_stack::Report;
 use serde::Serialize;
 use std::collections::{BTreeSet, HashSet};
+use time::PrimitiveDateTime;
 use uuid::Uuid;
 
 use crate::errors::QueryError;
-use crate::models::Product;
+use crate::models::{Money, Product};
 
 #[derive(Serialize, sqlx::FromRow, utoipa::ToSchema)]
 pub struct AllProduct(pub HashSet<Product>);
@@ -14,7 +15,11 @@ pub struct OrderedProduct {
     pub ordering: i64,
     pub id: Uuid,
     pub name: String,
-    pub price: i64,
+    #[sqlx(flatten)]
+    pub price: Money,
+    pub available: bool,
+    /// `None` if the stock of this product is not tracked.
+    pub stock: Option<i64>,
 }
 
 impl Eq for OrderedProduct {}
@@ -52,5 +57,7 @@ pub trait DependOnGetAllProductQueryService: 'static + Sync + Send {
 #[async_trait::async_trait]
 pub trait GetAllProductQueryService: 'static + Sync + Send {
     async fn get_all_product(&self) -> Result<AllProduct, Report<QueryError>>;
-    async fn get_all_product_by_category(&self, category: &Uuid) -> Result<OrderedProducts, Report<QueryError>>;
+    /// With `at`, products are left out if the schedule of either the product or the category 
+    /// does not include that local time.
+    async fn get_all_product_by_category(&self, category: &Uuid, at: Option<PrimitiveDateTime>) -> Result<OrderedProducts, Report<QueryError>>;
 }
\ No newline at end of file
@@ -0,0 +1,38 @@
+use async_trait::async_trait;
+use error_stack::Report;
+use serde::Serialize;
+use time::OffsetDateTime;
+use uuid::Uuid;
+use crate::errors::QueryError;
+
+#[derive(Serialize, sqlx::FromRow, utoipa::ToSchema)]
+pub struct PromotionRecord {
+    pub code: String,
+    pub name: String,
+    /// `percentage` or `fixed`.
+    pub discount: String,
+    /// The rate of a percentage discount, or the amount of a fixed one.
+    pub value: i64,
+    /// ISO 4217 currency code of a fixed discount, `None` for a percentage.
+    pub currency: Option<String>,
+    /// `order`, `categories` or `products`.
+    pub scope: String,
+    /// Ids of the categories or products the promotion is limited to.
+    #[sqlx(skip)]
+    pub targets: Vec<Uuid>,
+    pub starts_at: OffsetDateTime,
+    pub ends_at: OffsetDateTime,
+    pub cap: Option<i64>,
+    pub redeemed: i64,
+    pub ended: bool,
+}
+
+pub trait DependOnGetPromotionQueryService: 'static + Sync + Send {
+    type GetPromotionQueryService: GetPromotionQueryService;
+    fn get_promotion_query_service(&self) -> &Self::GetPromotionQueryService;
+}
+
+#[async_trait]
+pub trait GetPromotionQueryService: 'static + Sync + Send {
+    async fn get_all_promotions(&self) -> Result<Vec<PromotionRecord>, Report<QueryError>>;
+}
@@ -0,0 +1,80 @@
+use async_trait::async_trait;
+use error_stack::Report;
+use serde::Serialize;
+use time::PrimitiveDateTime;
+use uuid::Uuid;
+use crate::errors::QueryError;
+
+/// Everything printed on the receipt of a paid order. 
+/// 
+/// Amounts are in the minor units of `currency` and include the tax.
+#[derive(Serialize, sqlx::FromRow, utoipa::ToSchema)]
+pub struct Receipt {
+    pub order_id: Uuid,
+    /// `None` until the ticket has been issued on confirmation.
+    pub number: Option<i64>,
+    pub currency: String,
+    /// Code of the redeemed coupon, if any.
+    pub coupon: Option<String>,
+    #[sqlx(skip)]
+    pub subtotal: i64,
+    #[sqlx(skip)]
+    pub discount: i64,
+    pub total: i64,
+    pub tender: String,
+    pub tendered: i64,
+    pub change: i64,
+    pub paid_at: PrimitiveDateTime,
+    #[sqlx(skip)]
+    pub items: Vec<ReceiptItem>,
+    /// In ascending order of the rate.
+    #[sqlx(skip)]
+    pub taxes: Vec<ReceiptTax>,
+}
+
+#[derive(Serialize, sqlx::FromRow, utoipa::ToSchema)]
+pub struct ReceiptItem {
+    #[serde(skip)]
+    pub order_id: Uuid,
+    pub line: i64,
+    /// `None` when the product has been deleted after it was ordered.
+    pub name: Option<String>,
+    pub quantity: i64,
+    /// Charged for a single unit, including the price deltas of the options.
+    pub unit_price: i64,
+    pub amount: i64,
+    /// Tax rate in percent.
+    pub tax_rate: i64,
+    #[sqlx(skip)]
+    pub options: Vec<ReceiptOption>,
+    /// Names of the products chosen for the slots of a bundle.
+    #[sqlx(skip)]
+    pub components: Vec<String>,
+}
+
+#[derive(Serialize, sqlx::FromRow, utoipa::ToSchema)]
+pub struct ReceiptOption {
+    pub name: String,
+    /// As listed, i.e. before any tax is added.
+    pub price_delta: i64,
+}
+
+#[derive(Serialize, sqlx::FromRow, utoipa::ToSchema)]
+pub struct ReceiptTax {
+    /// Tax rate in percent.
+    pub rate: i64,
+    /// Amount charged at the rate, including the tax.
+    pub gross: i64,
+    pub tax: i64,
+}
+
+pub trait DependOnGetReceiptQueryService: 'static + Sync + Send {
+    type GetReceiptQueryService: GetReceiptQueryService;
+    fn get_receipt_query_service(&self) -> &Self::GetReceiptQueryService;
+}
+
+#[async_trait]
+pub trait GetReceiptQueryService: 'static + Sync + Send {
+    /// `None` if the order does not exist or has not been paid yet.
+    async fn get_receipt(&self, order: &Uuid) -> Result<Option<Receipt>, Report<QueryError>>;
+}
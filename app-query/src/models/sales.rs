@@ -0,0 +1,70 @@
+use async_trait::async_trait;
+use error_stack::Report;
+use serde::Serialize;
+use time::Date;
+use uuid::Uuid;
+use crate::errors::QueryError;
+
+#[derive(Serialize, sqlx::FromRow, utoipa::ToSchema)]
+pub struct ProductSales {
+    pub product: Uuid,
+    /// Name of the product at the time it was last sold in the range.
+    pub name: Option<String>,
+    pub currency: String,
+    pub quantity: i64,
+    pub amount: i64,
+}
+
+#[derive(Serialize, sqlx::FromRow, utoipa::ToSchema)]
+pub struct CategorySales {
+    pub category: Uuid,
+    pub name: String,
+    pub currency: String,
+    pub quantity: i64,
+    pub amount: i64,
+}
+
+#[derive(Serialize, sqlx::FromRow, utoipa::ToSchema)]
+pub struct HourlySales {
+    /// Local hour of the day, `0..=23`.
+    pub hour: i64,
+    pub currency: String,
+    pub orders: i64,
+    pub quantity: i64,
+    pub amount: i64,
+}
+
+/// Totals of the sales in a single currency.
+#[derive(Serialize, sqlx::FromRow, utoipa::ToSchema)]
+pub struct SalesTotal {
+    pub currency: String,
+    pub orders: i64,
+    pub quantity: i64,
+    pub amount: i64,
+}
+
+/// Sales between `from` and `to` (both inclusive), built from confirmed orders.
+/// 
+/// A product that belongs to several categories is counted in each of them.
+/// Amounts are what was charged, i.e. after the discount of any coupon,
+/// and are never summed across currencies, so every breakdown is grouped by currency as well.
+#[derive(Serialize, utoipa::ToSchema)]
+pub struct SalesReport {
+    pub from: Date,
+    pub to: Date,
+    pub totals: Vec<SalesTotal>,
+    pub products: Vec<ProductSales>,
+    pub categories: Vec<CategorySales>,
+    pub hours: Vec<HourlySales>,
+}
+
+pub trait DependOnSalesReportQueryService: 'static + Sync + Send {
+    type SalesReportQueryService: SalesReportQueryService;
+    fn sales_report_query_service(&self) -> &Self::SalesReportQueryService;
+}
+
+/// `from` and `to` default to the current business day when `None`.
+#[async_trait]
+pub trait SalesReportQueryService: 'static + Sync + Send {
+    async fn get_sales_report(&self, from: Option<Date>, to: Option<Date>) -> Result<SalesReport, Report<QueryError>>;
+}
@@ -0,0 +1,36 @@
+use async_trait::async_trait;
+use error_stack::Report;
+use serde::Serialize;
+use time::Date;
+use uuid::Uuid;
+use crate::errors::QueryError;
+
+/// Ticket numbers as seen from the current business day.
+/// 
+/// `current` is the latest number issued today, while `last_issued` may
+/// refer to a previous business day when no ticket has been issued yet today.
+#[derive(Serialize, sqlx::FromRow, utoipa::ToSchema)]
+pub struct TicketNumbers {
+    pub business_day: Date,
+    pub current: Option<i64>,
+    pub last_issued: Option<i64>,
+    pub last_issued_day: Option<Date>,
+}
+
+#[derive(Serialize, sqlx::FromRow, utoipa::ToSchema)]
+pub struct IssuedTicket {
+    pub order_id: Uuid,
+    pub number: i64,
+    pub business_day: Date,
+}
+
+pub trait DependOnGetTicketQueryService: 'static + Sync + Send {
+    type GetTicketQueryService: GetTicketQueryService;
+    fn get_ticket_query_service(&self) -> &Self::GetTicketQueryService;
+}
+
+#[async_trait]
+pub trait GetTicketQueryService: 'static + Sync + Send {
+    async fn get_ticket_numbers(&self) -> Result<TicketNumbers, Report<QueryError>>;
+    async fn get_ticket_by_order(&self, order: &Uuid) -> Result<IssuedTicket, Report<QueryError>>;
+}
@@ -1,9 +1,22 @@
+mod audit;
 mod product;
 mod category;
+mod device;
+mod order;
+mod promotion;
+mod sales;
+mod schedule;
+mod ticket;
 pub mod query;
 
+pub use self::audit::*;
 pub use self::product::*;
 pub use self::category::*;
+pub use self::device::*;
+pub use self::order::*;
+pub use self::promotion::*;
+pub use self::sales::*;
+pub use self::ticket::*;
 
 use std::str::FromStr;
 use std::time::Duration;
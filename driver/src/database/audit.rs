@@ -0,0 +1,136 @@
+use app_cmd::audit::{AuditLog, AuditRecord};
+use app_cmd::errors::ApplicationError;
+use async_trait::async_trait;
+use error_stack::{Report, ResultExt};
+use sqlx::{SqliteConnection, SqlitePool};
+
+use crate::errors::FailedRecordAudit;
+
+/// Writes the audit log into the read model database.
+#[derive(Clone)]
+pub struct AuditLogService {
+    pool: SqlitePool,
+}
+
+impl AuditLogService {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl AuditLog for AuditLogService {
+    async fn record(&self, record: AuditRecord, sequence: i64) -> Result<(), Report<ApplicationError>> {
+        let mut con = self.pool.begin().await
+            .change_context_lazy(|| ApplicationError::Audit)?;
+        InternalAuditLogService::record(record, sequence, &mut con).await
+            .change_context_lazy(|| ApplicationError::Audit)?;
+        con.commit().await
+            .change_context_lazy(|| ApplicationError::Audit)?;
+        Ok(())
+    }
+}
+
+
+pub(crate) struct InternalAuditLogService;
+
+impl InternalAuditLogService {
+    pub async fn record(record: AuditRecord, sequence: i64, con: &mut SqliteConnection) -> Result<(), Report<FailedRecordAudit>> {
+        let AuditRecord { actor, aggregate, aggregate_id, event } = record;
+        
+        // language=sqlite
+        sqlx::query(r#"
+            INSERT INTO audit_log(aggregate, aggregate_id, sequence, event, staff, device, request_id, client_ip) 
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+        "#)
+            .bind(aggregate)
+            .bind(aggregate_id)
+            .bind(sequence)
+            .bind(event)
+            .bind(actor.staff.map(|staff| staff.to_string()))
+            .bind(actor.device.map(|device| *device.as_ref()))
+            .bind(actor.request_id)
+            .bind(actor.client_ip.map(|ip| ip.to_string()))
+            .execute(&mut *con)
+            .await
+            .change_context_lazy(|| FailedRecordAudit)?;
+        
+        Ok(())
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use app_cmd::audit::Actor;
+    use error_stack::{Report, ResultExt};
+    use kernel::entities::device::DeviceId;
+    use kernel::entities::product::ProductId;
+    use kernel::io::events::ProductEvent;
+    use sqlx::types::time::Duration;
+    
+    use super::*;
+    use crate::database;
+    use crate::database::query::InternalAuditQueryService;
+    use crate::errors::test::UnrecoverableError;
+    
+    #[tokio::test]
+    async fn test_audit_log() -> Result<(), Report<UnrecoverableError>> {
+        let pool = database::init("sqlite:../query.db").await
+            .change_context_lazy(|| UnrecoverableError)?;
+        let mut con = pool.begin().await
+            .change_context_lazy(|| UnrecoverableError)?;
+        
+        let product = ProductId::default();
+        
+        let device = DeviceId::default();
+        let actor = Actor {
+            device: Some(device),
+            request_id: Some("req-1".to_string()),
+            client_ip: Some("10.0.0.7".parse().change_context_lazy(|| UnrecoverableError)?),
+            ..Actor::system()
+        };
+        
+        // The same event twice, each at the sequence it was applied at.
+        let event = ProductEvent::MarkedSoldOut { id: product };
+        for sequence in [1, 2] {
+            let record = AuditRecord::new(&actor, "product", product, &event);
+            InternalAuditLogService::record(record, sequence, &mut con).await
+                .change_context_lazy(|| UnrecoverableError)?;
+        }
+        
+        let entries = InternalAuditQueryService::get_audit_log(&mut con, Some(product.to_string()), None, None).await
+            .change_context_lazy(|| UnrecoverableError)?;
+        
+        let [older, newer] = entries.as_slice() else {
+            return Err(Report::new(UnrecoverableError).attach_printable(format!("Unexpected entries: {entries:?}")));
+        };
+        
+        if older.aggregate != "product" 
+            || older.event != "MarkedSoldOut" 
+            || older.staff.is_some()
+            || older.device != Some(*device.as_ref())
+            || older.client_ip.as_deref() != Some("10.0.0.7")
+        {
+            return Err(Report::new(UnrecoverableError).attach_printable(format!("Unexpected entry: {older:?}")));
+        }
+        
+        let mut sequences = [older.sequence, newer.sequence];
+        sequences.sort();
+        if sequences != [Some(1), Some(2)] {
+            return Err(Report::new(UnrecoverableError).attach_printable(format!("Entries must carry the sequences of both events: {entries:?}")));
+        }
+        
+        let later = older.recorded_at + Duration::minutes(1);
+        let entries = InternalAuditQueryService::get_audit_log(&mut con, Some(product.to_string()), Some(later), None).await
+            .change_context_lazy(|| UnrecoverableError)?;
+        
+        if !entries.is_empty() {
+            return Err(Report::new(UnrecoverableError).attach_printable("Entries before `from` were returned"));
+        }
+        
+        con.rollback().await
+            .change_context_lazy(|| UnrecoverableError)?;
+        Ok(())
+    }
+}
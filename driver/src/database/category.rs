@@ -5,6 +5,7 @@ use nitinol::eventstream::resolver::{DecodeMapping, SubscriptionMapper};
 use nitinol::eventstream::EventSubscriber;
 use sqlx::{QueryBuilder, SqliteConnection, SqlitePool};
 use sqlx::types::Uuid;
+use crate::database::schedule;
 use crate::errors::FailedBuildReadModel;
 
 #[derive(Clone)]
@@ -43,6 +44,10 @@ impl EventSubscriber<CategoryEvent> for CategoryQueryModelService {
             CategoryEvent::Deleted { .. } => {
                 InternalCategoryQueryModelService::delete_category(event, &mut con).await?
             }
+            CategoryEvent::ChangedSchedule { .. } |
+            CategoryEvent::ClearedSchedule { .. } => {
+                InternalCategoryQueryModelService::update_schedule(event, &mut con).await?
+            }
             CategoryEvent::AddedProduct { .. } => {
                 InternalCategoryQueryModelService::add_product(event, &mut con).await?
             }
@@ -102,6 +107,43 @@ impl InternalCategoryQueryModelService {
         Ok(())
     }
     
+    pub async fn update_schedule(
+        update: CategoryEvent, 
+        con: &mut SqliteConnection
+    ) -> Result<(), Report<FailedBuildReadModel>> {
+        let (id, schedule) = match update {
+            CategoryEvent::ChangedSchedule { id, new } => (id, Some(new)),
+            CategoryEvent::ClearedSchedule { id } => (id, None),
+            _ => return Err(Report::new(FailedBuildReadModel)
+                .attach_printable("Invalid event type")),
+        };
+        
+        // language=sqlite
+        sqlx::query(r#"
+            DELETE FROM category_schedules WHERE category = ?
+        "#)
+            .bind(id.as_ref())
+            .execute(&mut *con)
+            .await
+            .change_context_lazy(|| FailedBuildReadModel)?;
+        
+        for (weekday, start, end) in schedule.iter().flat_map(schedule::rows) {
+            // language=sqlite
+            sqlx::query(r#"
+                INSERT INTO category_schedules(category, weekday, start, end) VALUES (?, ?, ?, ?)
+            "#)
+                .bind(id.as_ref())
+                .bind(weekday)
+                .bind(start)
+                .bind(end)
+                .execute(&mut *con)
+                .await
+                .change_context_lazy(|| FailedBuildReadModel)?;
+        }
+        
+        Ok(())
+    }
+    
     pub async fn register_category(
         create: CategoriesEvent, 
         con: &mut SqliteConnection
@@ -600,6 +642,65 @@ mod tests {
         Ok(())
     }
 
+    async fn listed(id: CategoryId, at: Option<sqlx::types::time::PrimitiveDateTime>, con: &mut SqliteConnection) -> Result<bool, Report<UnrecoverableError>> {
+        let all = crate::database::query::InternalCategoryQueryService::get_all_categories(con, at).await
+            .change_context_lazy(|| UnrecoverableError)?;
+        
+        Ok(all.0.iter().any(|category| category.id == *id.as_ref()))
+    }
+
+    #[tokio::test]
+    async fn test_category_schedule() -> Result<(), Report<UnrecoverableError>> {
+        use sqlx::types::time::{Date, Month, PrimitiveDateTime, Time, Weekday};
+        use kernel::entities::schedule::{Schedule, TimeWindow};
+        
+        let con = crate::database::init("sqlite:../query.db").await
+            .change_context_lazy(|| UnrecoverableError)?;
+
+        let mut transaction = con.begin().await
+            .change_context_lazy(|| UnrecoverableError)?;
+
+        let category_id = CategoryId::default();
+
+        create_category(category_id, 0, &mut transaction).await?;
+        
+        let time = |hour, minute| Time::from_hms(hour, minute, 0)
+            .change_context_lazy(|| UnrecoverableError);
+        let window = TimeWindow::new([Weekday::Monday], time(7, 0)?, time(10, 30)?)
+            .change_context_lazy(|| UnrecoverableError)?;
+        let new = Schedule::new(vec![window])
+            .change_context_lazy(|| UnrecoverableError)?;
+        
+        InternalCategoryQueryModelService::update_schedule(CategoryEvent::ChangedSchedule { id: category_id, new }, &mut transaction).await
+            .change_context_lazy(|| UnrecoverableError)?;
+        
+        let monday = Date::from_calendar_date(2026, Month::October, 19)
+            .change_context_lazy(|| UnrecoverableError)?;
+        if !listed(category_id, None, &mut transaction).await? {
+            return Err(Report::new(UnrecoverableError).attach_printable("Scheduled category must be listed without `at`"));
+        }
+        
+        if !listed(category_id, Some(PrimitiveDateTime::new(monday, time(8, 0)?)), &mut transaction).await? {
+            return Err(Report::new(UnrecoverableError).attach_printable("Category must be listed within its schedule"));
+        }
+        
+        if listed(category_id, Some(PrimitiveDateTime::new(monday, time(10, 30)?)), &mut transaction).await? {
+            return Err(Report::new(UnrecoverableError).attach_printable("Category must not be listed outside its schedule"));
+        }
+        
+        InternalCategoryQueryModelService::update_schedule(CategoryEvent::ClearedSchedule { id: category_id }, &mut transaction).await
+            .change_context_lazy(|| UnrecoverableError)?;
+        
+        if !listed(category_id, Some(PrimitiveDateTime::new(monday, time(10, 30)?)), &mut transaction).await? {
+            return Err(Report::new(UnrecoverableError).attach_printable("Category without schedule must always be listed"));
+        }
+
+        transaction.rollback().await
+            .change_context_lazy(|| UnrecoverableError)?;
+
+        Ok(())
+    }
+
     // noinspection DuplicatedCode
     #[tokio::test]
     async fn test_all() -> Result<(), Report<UnrecoverableError>> {
@@ -0,0 +1,160 @@
+use async_trait::async_trait;
+use error_stack::{Report, ResultExt};
+use kernel::io::events::DeviceEvent;
+use nitinol::eventstream::resolver::{DecodeMapping, SubscriptionMapper};
+use nitinol::eventstream::EventSubscriber;
+use sqlx::{SqliteConnection, SqlitePool};
+
+use crate::errors::FailedBuildReadModel;
+
+#[derive(Clone)]
+pub struct DeviceReadModelService {
+    pool: SqlitePool
+}
+
+impl DeviceReadModelService {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+impl SubscriptionMapper for DeviceReadModelService {
+    fn mapping(mapping: &mut DecodeMapping<Self>) {
+        mapping.register::<DeviceEvent>();
+    }
+}
+
+#[async_trait]
+impl EventSubscriber<DeviceEvent> for DeviceReadModelService {
+    type Error = Report<FailedBuildReadModel>;
+
+    async fn on(&mut self, event: DeviceEvent) -> Result<(), Self::Error> {
+        let mut con = self.pool.begin().await
+            .change_context_lazy(|| FailedBuildReadModel)?;
+        match event {
+            DeviceEvent::Registered { .. } => {
+                InternalDeviceReadModelService::register(event, &mut con).await?
+            }
+            DeviceEvent::RotatedKey { .. } => {
+                InternalDeviceReadModelService::rotate_key(event, &mut con).await?
+            }
+            DeviceEvent::Revoked { .. } => {
+                InternalDeviceReadModelService::revoke(event, &mut con).await?
+            }
+        }
+        con.commit().await
+            .change_context_lazy(|| FailedBuildReadModel)?;
+        Ok(())
+    }
+}
+
+
+pub(crate) struct InternalDeviceReadModelService;
+
+impl InternalDeviceReadModelService {
+    pub async fn register(register: DeviceEvent, con: &mut SqliteConnection) -> Result<(), Report<FailedBuildReadModel>> {
+        let DeviceEvent::Registered { id, name, role, store, .. } = register else {
+            return Err(Report::new(FailedBuildReadModel).attach_printable("Invalid event type"));
+        };
+        
+        // language=sqlite
+        sqlx::query(r#"
+            INSERT INTO devices(id, name, role, store) VALUES (?, ?, ?, ?)
+        "#)
+            .bind(id.as_ref())
+            .bind(name.as_ref())
+            .bind(role.as_ref())
+            .bind(store.as_ref())
+            .execute(&mut *con)
+            .await
+            .change_context_lazy(|| FailedBuildReadModel)?;
+        
+        Ok(())
+    }
+    
+    pub async fn rotate_key(rotate: DeviceEvent, con: &mut SqliteConnection) -> Result<(), Report<FailedBuildReadModel>> {
+        let DeviceEvent::RotatedKey { id, .. } = rotate else {
+            return Err(Report::new(FailedBuildReadModel).attach_printable("Invalid event type"));
+        };
+        
+        // language=sqlite
+        sqlx::query(r#"
+            UPDATE devices SET rotated_at = CURRENT_TIMESTAMP WHERE id = ?
+        "#)
+            .bind(id.as_ref())
+            .execute(&mut *con)
+            .await
+            .change_context_lazy(|| FailedBuildReadModel)?;
+        
+        Ok(())
+    }
+    
+    pub async fn revoke(revoke: DeviceEvent, con: &mut SqliteConnection) -> Result<(), Report<FailedBuildReadModel>> {
+        let DeviceEvent::Revoked { id } = revoke else {
+            return Err(Report::new(FailedBuildReadModel).attach_printable("Invalid event type"));
+        };
+        
+        // language=sqlite
+        sqlx::query(r#"
+            UPDATE devices SET revoked = 1 WHERE id = ?
+        "#)
+            .bind(id.as_ref())
+            .execute(&mut *con)
+            .await
+            .change_context_lazy(|| FailedBuildReadModel)?;
+        
+        Ok(())
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use error_stack::{Report, ResultExt};
+    use kernel::entities::device::{ApiKey, DeviceId, DeviceName, StoreCode};
+    use kernel::entities::staff::Role;
+    
+    use super::*;
+    use crate::database;
+    use crate::database::query::InternalDeviceQueryService;
+    use crate::errors::test::UnrecoverableError;
+    
+    #[tokio::test]
+    async fn test_device() -> Result<(), Report<UnrecoverableError>> {
+        let pool = database::init("sqlite:../query.db").await
+            .change_context_lazy(|| UnrecoverableError)?;
+        let mut con = pool.begin().await
+            .change_context_lazy(|| UnrecoverableError)?;
+        
+        let id = DeviceId::default();
+        let register = DeviceEvent::Registered {
+            id,
+            name: DeviceName::new("Kiosk by the entrance"),
+            role: Role::Kiosk,
+            store: StoreCode::new("main").change_context_lazy(|| UnrecoverableError)?,
+            key: ApiKey::generate().hash(),
+        };
+        
+        InternalDeviceReadModelService::register(register, &mut con).await
+            .change_context_lazy(|| UnrecoverableError)?;
+        InternalDeviceReadModelService::rotate_key(DeviceEvent::RotatedKey { id, key: ApiKey::generate().hash() }, &mut con).await
+            .change_context_lazy(|| UnrecoverableError)?;
+        InternalDeviceReadModelService::revoke(DeviceEvent::Revoked { id }, &mut con).await
+            .change_context_lazy(|| UnrecoverableError)?;
+        
+        let devices = InternalDeviceQueryService::get_all_devices(&mut con).await
+            .change_context_lazy(|| UnrecoverableError)?;
+        
+        let Some(device) = devices.iter().find(|device| device.id == *id.as_ref()) else {
+            return Err(Report::new(UnrecoverableError).attach_printable("Device was not recorded"));
+        };
+        
+        if device.role != "kiosk" || device.store != "main" || device.rotated_at.is_none() || !device.revoked {
+            return Err(Report::new(UnrecoverableError).attach_printable("Device was not updated"));
+        }
+        
+        con.rollback().await
+            .change_context_lazy(|| UnrecoverableError)?;
+        Ok(())
+    }
+}
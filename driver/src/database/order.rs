@@ -0,0 +1,360 @@
+use async_trait::async_trait;
+use error_stack::{Report, ResultExt};
+use kernel::entities::order::OrderStatus;
+use kernel::io::events::OrderEvent;
+use nitinol::eventstream::resolver::{DecodeMapping, SubscriptionMapper};
+use nitinol::eventstream::EventSubscriber;
+use sqlx::{SqliteConnection, SqlitePool};
+
+use crate::errors::FailedBuildReadModel;
+
+#[derive(Clone)]
+pub struct OrderReadModelService {
+    pool: SqlitePool
+}
+
+impl OrderReadModelService {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+impl SubscriptionMapper for OrderReadModelService {
+    fn mapping(mapping: &mut DecodeMapping<Self>) {
+        mapping.register::<OrderEvent>();
+    }
+}
+
+#[async_trait]
+impl EventSubscriber<OrderEvent> for OrderReadModelService {
+    type Error = Report<FailedBuildReadModel>;
+
+    async fn on(&mut self, event: OrderEvent) -> Result<(), Self::Error> {
+        let mut con = self.pool.begin().await
+            .change_context_lazy(|| FailedBuildReadModel)?;
+        match event {
+            OrderEvent::Placed { .. } => {
+                InternalOrderReadModelService::create(event, &mut con).await?
+            }
+            OrderEvent::Paid { .. } => {
+                InternalOrderReadModelService::pay(event, &mut con).await?
+            }
+            OrderEvent::Confirmed { .. } => {
+                InternalOrderReadModelService::accept(event, &mut con).await?
+            }
+            OrderEvent::ChangedStatus { .. } |
+            OrderEvent::Cancelled { .. } => {
+                InternalOrderReadModelService::update_status(event, &mut con).await?
+            }
+            OrderEvent::AddedLine { .. } => {
+                InternalOrderReadModelService::add_line(event, &mut con).await?
+            }
+            OrderEvent::RemovedLine { .. } => {
+                InternalOrderReadModelService::remove_line(event, &mut con).await?
+            }
+            OrderEvent::AppliedCoupon { .. } => {
+                InternalOrderReadModelService::apply_coupon(event, &mut con).await?
+            }
+        }
+        con.commit().await
+            .change_context_lazy(|| FailedBuildReadModel)?;
+        Ok(())
+    }
+}
+
+
+pub(crate) struct InternalOrderReadModelService;
+
+impl InternalOrderReadModelService {
+    pub async fn create(create: OrderEvent, con: &mut SqliteConnection) -> Result<(), Report<FailedBuildReadModel>> {
+        let OrderEvent::Placed { id } = create else {
+            return Err(Report::new(FailedBuildReadModel).attach_printable("Invalid event type"));
+        };
+        
+        // language=sqlite
+        sqlx::query(r#"
+            INSERT INTO order_status(order_id, status) VALUES (?, ?)
+        "#)
+            .bind(id.as_ref())
+            .bind(OrderStatus::Placed.as_ref())
+            .execute(&mut *con)
+            .await
+            .change_context_lazy(|| FailedBuildReadModel)?;
+        
+        Ok(())
+    }
+    
+    pub async fn add_line(add: OrderEvent, con: &mut SqliteConnection) -> Result<(), Report<FailedBuildReadModel>> {
+        let OrderEvent::AddedLine { id, line, item } = add else {
+            return Err(Report::new(FailedBuildReadModel).attach_printable("Invalid event type"));
+        };
+        
+        // language=sqlite
+        sqlx::query(r#"
+            INSERT INTO order_items(order_id, line, product, quantity, price, unit_price, currency, tax_rate, tax_inclusive) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+        "#)
+            .bind(id.as_ref())
+            .bind(line)
+            .bind(item.product().as_ref())
+            .bind(item.quantity().as_ref())
+            .bind(item.price().amount())
+            .bind(item.unit_price())
+            .bind(item.currency().as_ref())
+            .bind(item.tax().rate().as_ref())
+            .bind(item.tax().inclusive())
+            .execute(&mut *con)
+            .await
+            .change_context_lazy(|| FailedBuildReadModel)?;
+        
+        for option in item.options() {
+            // language=sqlite
+            sqlx::query(r#"
+                INSERT INTO order_item_options(order_id, line, option, name, price_delta) VALUES (?, ?, ?, ?, ?)
+            "#)
+                .bind(id.as_ref())
+                .bind(line)
+                .bind(option.option().as_ref())
+                .bind(option.name().as_ref())
+                .bind(option.price_delta().amount())
+                .execute(&mut *con)
+                .await
+                .change_context_lazy(|| FailedBuildReadModel)?;
+        }
+        
+        for component in item.components() {
+            // language=sqlite
+            sqlx::query(r#"
+                INSERT INTO order_item_components(order_id, line, slot, product) VALUES (?, ?, ?, ?)
+            "#)
+                .bind(id.as_ref())
+                .bind(line)
+                .bind(component.slot().as_ref())
+                .bind(component.product().as_ref())
+                .execute(&mut *con)
+                .await
+                .change_context_lazy(|| FailedBuildReadModel)?;
+        }
+        
+        Ok(())
+    }
+    
+    pub async fn remove_line(remove: OrderEvent, con: &mut SqliteConnection) -> Result<(), Report<FailedBuildReadModel>> {
+        let OrderEvent::RemovedLine { id, line } = remove else {
+            return Err(Report::new(FailedBuildReadModel).attach_printable("Invalid event type"));
+        };
+        
+        // language=sqlite
+        sqlx::query(r#"
+            DELETE FROM order_items WHERE order_id = ? AND line = ?
+        "#)
+            .bind(id.as_ref())
+            .bind(line)
+            .execute(&mut *con)
+            .await
+            .change_context_lazy(|| FailedBuildReadModel)?;
+        
+        Ok(())
+    }
+    
+    pub async fn apply_coupon(apply: OrderEvent, con: &mut SqliteConnection) -> Result<(), Report<FailedBuildReadModel>> {
+        let OrderEvent::AppliedCoupon { id, coupon } = apply else {
+            return Err(Report::new(FailedBuildReadModel).attach_printable("Invalid event type"));
+        };
+        
+        // language=sqlite
+        sqlx::query(r#"
+            INSERT INTO order_coupons(order_id, code) VALUES (?, ?)
+        "#)
+            .bind(id.as_ref())
+            .bind(coupon.code().as_ref())
+            .execute(&mut *con)
+            .await
+            .change_context_lazy(|| FailedBuildReadModel)?;
+        
+        Ok(())
+    }
+    
+    pub async fn pay(pay: OrderEvent, con: &mut SqliteConnection) -> Result<(), Report<FailedBuildReadModel>> {
+        let OrderEvent::Paid { id, payment, taxes } = pay else {
+            return Err(Report::new(FailedBuildReadModel).attach_printable("Invalid event type"));
+        };
+        
+        // language=sqlite
+        sqlx::query(r#"
+            INSERT INTO payments(order_id, tender, amount, tendered, change, currency) VALUES (?, ?, ?, ?, ?, ?)
+        "#)
+            .bind(id.as_ref())
+            .bind(payment.tender().as_ref())
+            .bind(payment.amount().amount())
+            .bind(payment.tendered().amount())
+            .bind(payment.change().amount())
+            .bind(payment.currency().as_ref())
+            .execute(&mut *con)
+            .await
+            .change_context_lazy(|| FailedBuildReadModel)?;
+        
+        for summary in taxes {
+            // language=sqlite
+            sqlx::query(r#"
+                INSERT INTO order_taxes(order_id, rate, gross, tax) VALUES (?, ?, ?, ?)
+            "#)
+                .bind(id.as_ref())
+                .bind(summary.rate().as_ref())
+                .bind(summary.gross())
+                .bind(summary.tax())
+                .execute(&mut *con)
+                .await
+                .change_context_lazy(|| FailedBuildReadModel)?;
+        }
+        
+        Ok(())
+    }
+    
+    pub async fn accept(accept: OrderEvent, con: &mut SqliteConnection) -> Result<(), Report<FailedBuildReadModel>> {
+        let OrderEvent::Confirmed { id, .. } = accept else {
+            return Err(Report::new(FailedBuildReadModel).attach_printable("Invalid event type"));
+        };
+        
+        // language=sqlite
+        sqlx::query(r#"
+            UPDATE order_status 
+            SET 
+                status = ?, 
+                accepted_at = CURRENT_TIMESTAMP, 
+                updated_at = CURRENT_TIMESTAMP 
+            WHERE order_id = ?
+        "#)
+            .bind(OrderStatus::Accepted.as_ref())
+            .bind(id.as_ref())
+            .execute(&mut *con)
+            .await
+            .change_context_lazy(|| FailedBuildReadModel)?;
+        
+        Ok(())
+    }
+    
+    pub async fn update_status(update: OrderEvent, con: &mut SqliteConnection) -> Result<(), Report<FailedBuildReadModel>> {
+        let (id, status) = match update {
+            OrderEvent::ChangedStatus { id, new } => (id, new),
+            OrderEvent::Cancelled { id } => (id, OrderStatus::Cancelled),
+            _ => return Err(Report::new(FailedBuildReadModel).attach_printable("Invalid event type")),
+        };
+        
+        // language=sqlite
+        sqlx::query(r#"
+            UPDATE order_status SET status = ?, updated_at = CURRENT_TIMESTAMP WHERE order_id = ?
+        "#)
+            .bind(status.as_ref())
+            .bind(id.as_ref())
+            .execute(&mut *con)
+            .await
+            .change_context_lazy(|| FailedBuildReadModel)?;
+        
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod test {
+    use std::collections::BTreeMap;
+    use error_stack::{Report, ResultExt};
+    use kernel::entities::order::{LineTax, OrderId, OrderLine, OrderQuantity, Payment, Tender};
+    use kernel::entities::money::{Currency, Money};
+    use kernel::entities::product::{ProductId, ProductPrice};
+    
+    use super::*;
+    use crate::database;
+    use crate::errors::test::UnrecoverableError;
+    
+    pub async fn place_order(id: OrderId, con: &mut SqliteConnection) -> Result<(), Report<UnrecoverableError>> {
+        InternalOrderReadModelService::create(OrderEvent::Placed { id }, con).await
+            .change_context_lazy(|| UnrecoverableError)?;
+        
+        Ok(())
+    }
+    
+    pub async fn add_line(id: OrderId, line: i64, product: ProductId, con: &mut SqliteConnection) -> Result<(), Report<UnrecoverableError>> {
+        let quantity = OrderQuantity::new(1)
+            .change_context_lazy(|| UnrecoverableError)?;
+        let price = ProductPrice::new(Money::new(100, Currency::default()))
+            .change_context_lazy(|| UnrecoverableError)?;
+        let add = OrderEvent::AddedLine { id, line, item: OrderLine::new(product, quantity, price, Vec::new(), Vec::new(), LineTax::default()) };
+        
+        InternalOrderReadModelService::add_line(add, con).await
+            .change_context_lazy(|| UnrecoverableError)?;
+        
+        Ok(())
+    }
+    
+    pub async fn pay_order(id: OrderId, tender: Tender, amount: i64, tendered: i64, con: &mut SqliteConnection) -> Result<(), Report<UnrecoverableError>> {
+        let payment = Payment::new(tender, Money::new(amount, Currency::default()), Money::new(tendered, Currency::default()))
+            .change_context_lazy(|| UnrecoverableError)?;
+        
+        InternalOrderReadModelService::pay(OrderEvent::Paid { id, payment, taxes: Vec::new() }, con).await
+            .change_context_lazy(|| UnrecoverableError)?;
+        
+        Ok(())
+    }
+    
+    pub async fn accept_order(id: OrderId, con: &mut SqliteConnection) -> Result<(), Report<UnrecoverableError>> {
+        let accept = OrderEvent::Confirmed { id, lines: BTreeMap::new(), coupon: None };
+        
+        InternalOrderReadModelService::accept(accept, con).await
+            .change_context_lazy(|| UnrecoverableError)?;
+        
+        Ok(())
+    }
+    
+    pub async fn change_status(id: OrderId, new: OrderStatus, con: &mut SqliteConnection) -> Result<(), Report<UnrecoverableError>> {
+        InternalOrderReadModelService::update_status(OrderEvent::ChangedStatus { id, new }, con).await
+            .change_context_lazy(|| UnrecoverableError)?;
+        
+        Ok(())
+    }
+    
+    #[tokio::test]
+    async fn test_order_lifecycle() -> Result<(), Report<UnrecoverableError>> {
+        let pool = database::init("sqlite:../query.db").await
+            .change_context_lazy(|| UnrecoverableError)?;
+        let mut con = pool.begin().await
+            .change_context_lazy(|| UnrecoverableError)?;
+        
+        let order_id = OrderId::default();
+        
+        place_order(order_id, &mut con).await?;
+        add_line(order_id, 0, ProductId::default(), &mut con).await?;
+        add_line(order_id, 1, ProductId::default(), &mut con).await?;
+        
+        InternalOrderReadModelService::remove_line(OrderEvent::RemovedLine { id: order_id, line: 1 }, &mut con).await
+            .change_context_lazy(|| UnrecoverableError)?;
+        
+        pay_order(order_id, Tender::Cash, 100, 500, &mut con).await?;
+        accept_order(order_id, &mut con).await?;
+        change_status(order_id, OrderStatus::Preparing, &mut con).await?;
+        change_status(order_id, OrderStatus::Ready, &mut con).await?;
+        change_status(order_id, OrderStatus::Completed, &mut con).await?;
+        
+        con.rollback().await
+            .change_context_lazy(|| UnrecoverableError)?;
+        Ok(())
+    }
+    
+    #[tokio::test]
+    async fn test_cancel_order() -> Result<(), Report<UnrecoverableError>> {
+        let pool = database::init("sqlite:../query.db").await
+            .change_context_lazy(|| UnrecoverableError)?;
+        let mut con = pool.begin().await
+            .change_context_lazy(|| UnrecoverableError)?;
+        
+        let order_id = OrderId::default();
+        
+        place_order(order_id, &mut con).await?;
+        
+        InternalOrderReadModelService::update_status(OrderEvent::Cancelled { id: order_id }, &mut con).await
+            .change_context_lazy(|| UnrecoverableError)?;
+        
+        con.rollback().await
+            .change_context_lazy(|| UnrecoverableError)?;
+        Ok(())
+    }
+}
@@ -1,10 +1,12 @@
 use async_trait::async_trait;
 use error_stack::{Report, ResultExt};
+use kernel::entities::product::{ProductId, SlotId};
 use kernel::io::events::ProductEvent;
 use nitinol::eventstream::resolver::{DecodeMapping, SubscriptionMapper};
 use nitinol::eventstream::EventSubscriber;
 use sqlx::{SqliteConnection, SqlitePool};
 
+use crate::database::schedule;
 use crate::errors::FailedBuildReadModel;
 
 #[derive(Clone)]
@@ -47,6 +49,50 @@ impl EventSubscriber<ProductEvent> for ProductReadModelService {
             ProductEvent::ChangedProductImage { .. } => {
                 InternalProductReadModelService::update_image(event, &mut con).await?
             }
+            ProductEvent::ChangedTax { .. } => {
+                InternalProductReadModelService::update_tax(event, &mut con).await?
+            }
+            ProductEvent::MarkedSoldOut { .. } |
+            ProductEvent::MarkedAvailable { .. } => {
+                InternalProductReadModelService::update_available(event, &mut con).await?
+            }
+            ProductEvent::ChangedStock { .. } |
+            ProductEvent::Restocked { .. } |
+            ProductEvent::DecrementedStock { .. } => {
+                InternalProductReadModelService::update_stock(event, &mut con).await?
+            }
+            ProductEvent::AddedOptionGroup { .. } => {
+                InternalProductReadModelService::add_option_group(event, &mut con).await?
+            }
+            ProductEvent::EditedOptionGroup { .. } => {
+                InternalProductReadModelService::edit_option_group(event, &mut con).await?
+            }
+            ProductEvent::RemovedOptionGroup { .. } => {
+                InternalProductReadModelService::remove_option_group(event, &mut con).await?
+            }
+            ProductEvent::AddedOption { .. } |
+            ProductEvent::EditedOption { .. } => {
+                InternalProductReadModelService::upsert_option(event, &mut con).await?
+            }
+            ProductEvent::RemovedOption { .. } => {
+                InternalProductReadModelService::remove_option(event, &mut con).await?
+            }
+            ProductEvent::AddedBundleSlot { .. } => {
+                InternalProductReadModelService::add_bundle_slot(event, &mut con).await?
+            }
+            ProductEvent::ChangedBundleChoices { .. } => {
+                InternalProductReadModelService::change_bundle_choices(event, &mut con).await?
+            }
+            ProductEvent::RemovedBundleSlot { .. } => {
+                InternalProductReadModelService::remove_bundle_slot(event, &mut con).await?
+            }
+            // The choices of every slot are already kept with the bundle.
+            ProductEvent::JoinedBundle { .. } |
+            ProductEvent::LeftBundle { .. } => {}
+            ProductEvent::ChangedSchedule { .. } |
+            ProductEvent::ClearedSchedule { .. } => {
+                InternalProductReadModelService::update_schedule(event, &mut con).await?
+            }
             ProductEvent::Deleted { .. } => {
                 InternalProductReadModelService::delete(event, &mut con).await?
             }
@@ -77,13 +123,14 @@ impl InternalProductReadModelService {
         
         // language=sqlite
         sqlx::query(r#"
-            INSERT INTO products(id, name, image, desc, price) VALUES (?, ?, ?, ?, ?)
+            INSERT INTO products(id, name, image, desc, price, currency) VALUES (?, ?, ?, ?, ?, ?)
         "#)
             .bind(id.as_ref())
             .bind(name.as_ref())
             .bind(image.id().as_ref())
             .bind(desc.as_ref())
-            .bind(price.as_ref())
+            .bind(price.amount())
+            .bind(price.currency().as_ref())
             .execute(&mut *con)
             .await
             .change_context_lazy(|| FailedBuildReadModel)?;
@@ -134,9 +181,29 @@ impl InternalProductReadModelService {
         
         // language=sqlite
         sqlx::query(r#"
-            UPDATE products SET price = ? WHERE id = ?
+            UPDATE products SET price = ?, currency = ? WHERE id = ?
         "#)
-            .bind(new.as_ref())
+            .bind(new.amount())
+            .bind(new.currency().as_ref())
+            .bind(id.as_ref())
+            .execute(&mut *con)
+            .await
+            .change_context_lazy(|| FailedBuildReadModel)?;
+        
+        Ok(())
+    }
+    
+    pub async fn update_tax(update: ProductEvent, con: &mut SqliteConnection) -> Result<(), Report<FailedBuildReadModel>> {
+        let ProductEvent::ChangedTax { id, new } = update else {
+            return Err(Report::new(FailedBuildReadModel).attach_printable("Invalid event type"));
+        };
+        
+        // language=sqlite
+        sqlx::query(r#"
+            UPDATE products SET tax_category = ?, tax_inclusive = ? WHERE id = ?
+        "#)
+            .bind(new.category().as_ref())
+            .bind(new.inclusive())
             .bind(id.as_ref())
             .execute(&mut *con)
             .await
@@ -163,6 +230,265 @@ impl InternalProductReadModelService {
         Ok(())
     }
     
+    pub async fn update_available(update: ProductEvent, con: &mut SqliteConnection) -> Result<(), Report<FailedBuildReadModel>> {
+        let (id, marked) = match update {
+            ProductEvent::MarkedSoldOut { id } => (id, true),
+            ProductEvent::MarkedAvailable { id } => (id, false),
+            _ => return Err(Report::new(FailedBuildReadModel).attach_printable("Invalid event type")),
+        };
+        
+        // language=sqlite
+        sqlx::query(r#"
+            UPDATE products 
+            SET 
+                marked_sold_out = ?1, 
+                available = NOT ?1 AND COALESCE(stock > 0, 1) 
+            WHERE id = ?2
+        "#)
+            .bind(marked)
+            .bind(id.as_ref())
+            .execute(&mut *con)
+            .await
+            .change_context_lazy(|| FailedBuildReadModel)?;
+        
+        Ok(())
+    }
+    
+    pub async fn update_stock(update: ProductEvent, con: &mut SqliteConnection) -> Result<(), Report<FailedBuildReadModel>> {
+        let (ProductEvent::ChangedStock { id, new } 
+            | ProductEvent::Restocked { id, new, .. } 
+            | ProductEvent::DecrementedStock { id, new, .. }) = update else {
+            return Err(Report::new(FailedBuildReadModel).attach_printable("Invalid event type"));
+        };
+        
+        // A product marked as sold out by hand stays so when it is restocked.
+        // language=sqlite
+        sqlx::query(r#"
+            UPDATE products SET stock = ?1, available = ?2 AND NOT marked_sold_out WHERE id = ?3
+        "#)
+            .bind(new.as_ref())
+            .bind(!new.is_empty())
+            .bind(id.as_ref())
+            .execute(&mut *con)
+            .await
+            .change_context_lazy(|| FailedBuildReadModel)?;
+        
+        Ok(())
+    }
+    
+    pub async fn add_option_group(add: ProductEvent, con: &mut SqliteConnection) -> Result<(), Report<FailedBuildReadModel>> {
+        let ProductEvent::AddedOptionGroup { id, group } = add else {
+            return Err(Report::new(FailedBuildReadModel).attach_printable("Invalid event type"));
+        };
+        
+        // language=sqlite
+        sqlx::query(r#"
+            INSERT INTO product_option_groups(id, product, name, min, max) VALUES (?, ?, ?, ?, ?)
+        "#)
+            .bind(group.id().as_ref())
+            .bind(id.as_ref())
+            .bind(group.name().as_ref())
+            .bind(group.min())
+            .bind(group.max())
+            .execute(&mut *con)
+            .await
+            .change_context_lazy(|| FailedBuildReadModel)?;
+        
+        for option in group.options() {
+            // language=sqlite
+            sqlx::query(r#"
+                INSERT INTO product_options(id, option_group, name, price_delta) VALUES (?, ?, ?, ?)
+            "#)
+                .bind(option.id().as_ref())
+                .bind(group.id().as_ref())
+                .bind(option.name().as_ref())
+                .bind(option.price_delta().amount())
+                .execute(&mut *con)
+                .await
+                .change_context_lazy(|| FailedBuildReadModel)?;
+        }
+        
+        Ok(())
+    }
+    
+    pub async fn edit_option_group(update: ProductEvent, con: &mut SqliteConnection) -> Result<(), Report<FailedBuildReadModel>> {
+        let ProductEvent::EditedOptionGroup { group, name, min, max, .. } = update else {
+            return Err(Report::new(FailedBuildReadModel).attach_printable("Invalid event type"));
+        };
+        
+        // language=sqlite
+        sqlx::query(r#"
+            UPDATE product_option_groups SET name = ?, min = ?, max = ? WHERE id = ?
+        "#)
+            .bind(name.as_ref())
+            .bind(min)
+            .bind(max)
+            .bind(group.as_ref())
+            .execute(&mut *con)
+            .await
+            .change_context_lazy(|| FailedBuildReadModel)?;
+        
+        Ok(())
+    }
+    
+    pub async fn remove_option_group(remove: ProductEvent, con: &mut SqliteConnection) -> Result<(), Report<FailedBuildReadModel>> {
+        let ProductEvent::RemovedOptionGroup { group, .. } = remove else {
+            return Err(Report::new(FailedBuildReadModel).attach_printable("Invalid event type"));
+        };
+        
+        // language=sqlite
+        sqlx::query(r#"
+            DELETE FROM product_option_groups WHERE id = ?
+        "#)
+            .bind(group.as_ref())
+            .execute(&mut *con)
+            .await
+            .change_context_lazy(|| FailedBuildReadModel)?;
+        
+        Ok(())
+    }
+    
+    pub async fn upsert_option(upsert: ProductEvent, con: &mut SqliteConnection) -> Result<(), Report<FailedBuildReadModel>> {
+        let (ProductEvent::AddedOption { group, option, .. } 
+            | ProductEvent::EditedOption { group, option, .. }) = upsert else {
+            return Err(Report::new(FailedBuildReadModel).attach_printable("Invalid event type"));
+        };
+        
+        // language=sqlite
+        sqlx::query(r#"
+            INSERT INTO product_options(id, option_group, name, price_delta) VALUES (?, ?, ?, ?)
+            ON CONFLICT(id) DO UPDATE SET name = excluded.name, price_delta = excluded.price_delta
+        "#)
+            .bind(option.id().as_ref())
+            .bind(group.as_ref())
+            .bind(option.name().as_ref())
+            .bind(option.price_delta().amount())
+            .execute(&mut *con)
+            .await
+            .change_context_lazy(|| FailedBuildReadModel)?;
+        
+        Ok(())
+    }
+    
+    pub async fn remove_option(remove: ProductEvent, con: &mut SqliteConnection) -> Result<(), Report<FailedBuildReadModel>> {
+        let ProductEvent::RemovedOption { option, .. } = remove else {
+            return Err(Report::new(FailedBuildReadModel).attach_printable("Invalid event type"));
+        };
+        
+        // language=sqlite
+        sqlx::query(r#"
+            DELETE FROM product_options WHERE id = ?
+        "#)
+            .bind(option.as_ref())
+            .execute(&mut *con)
+            .await
+            .change_context_lazy(|| FailedBuildReadModel)?;
+        
+        Ok(())
+    }
+    
+    pub async fn add_bundle_slot(add: ProductEvent, con: &mut SqliteConnection) -> Result<(), Report<FailedBuildReadModel>> {
+        let ProductEvent::AddedBundleSlot { id, slot } = add else {
+            return Err(Report::new(FailedBuildReadModel).attach_printable("Invalid event type"));
+        };
+        
+        // language=sqlite
+        sqlx::query(r#"
+            INSERT INTO product_bundle_slots(id, product, name) VALUES (?, ?, ?)
+        "#)
+            .bind(slot.id().as_ref())
+            .bind(id.as_ref())
+            .bind(slot.name().as_ref())
+            .execute(&mut *con)
+            .await
+            .change_context_lazy(|| FailedBuildReadModel)?;
+        
+        Self::insert_bundle_choices(slot.id(), slot.choices(), con).await
+    }
+    
+    pub async fn change_bundle_choices(update: ProductEvent, con: &mut SqliteConnection) -> Result<(), Report<FailedBuildReadModel>> {
+        let ProductEvent::ChangedBundleChoices { slot, choices, .. } = update else {
+            return Err(Report::new(FailedBuildReadModel).attach_printable("Invalid event type"));
+        };
+        
+        // language=sqlite
+        sqlx::query(r#"
+            DELETE FROM product_bundle_choices WHERE slot = ?
+        "#)
+            .bind(slot.as_ref())
+            .execute(&mut *con)
+            .await
+            .change_context_lazy(|| FailedBuildReadModel)?;
+        
+        Self::insert_bundle_choices(&slot, &choices, con).await
+    }
+    
+    async fn insert_bundle_choices(slot: &SlotId, choices: &[ProductId], con: &mut SqliteConnection) -> Result<(), Report<FailedBuildReadModel>> {
+        for choice in choices {
+            // language=sqlite
+            sqlx::query(r#"
+                INSERT INTO product_bundle_choices(slot, product) VALUES (?, ?)
+            "#)
+                .bind(slot.as_ref())
+                .bind(choice.as_ref())
+                .execute(&mut *con)
+                .await
+                .change_context_lazy(|| FailedBuildReadModel)?;
+        }
+        
+        Ok(())
+    }
+    
+    pub async fn remove_bundle_slot(remove: ProductEvent, con: &mut SqliteConnection) -> Result<(), Report<FailedBuildReadModel>> {
+        let ProductEvent::RemovedBundleSlot { slot, .. } = remove else {
+            return Err(Report::new(FailedBuildReadModel).attach_printable("Invalid event type"));
+        };
+        
+        // language=sqlite
+        sqlx::query(r#"
+            DELETE FROM product_bundle_slots WHERE id = ?
+        "#)
+            .bind(slot.as_ref())
+            .execute(&mut *con)
+            .await
+            .change_context_lazy(|| FailedBuildReadModel)?;
+        
+        Ok(())
+    }
+    
+    pub async fn update_schedule(update: ProductEvent, con: &mut SqliteConnection) -> Result<(), Report<FailedBuildReadModel>> {
+        let (id, schedule) = match update {
+            ProductEvent::ChangedSchedule { id, new } => (id, Some(new)),
+            ProductEvent::ClearedSchedule { id } => (id, None),
+            _ => return Err(Report::new(FailedBuildReadModel).attach_printable("Invalid event type")),
+        };
+        
+        // language=sqlite
+        sqlx::query(r#"
+            DELETE FROM product_schedules WHERE product = ?
+        "#)
+            .bind(id.as_ref())
+            .execute(&mut *con)
+            .await
+            .change_context_lazy(|| FailedBuildReadModel)?;
+        
+        for (weekday, start, end) in schedule.iter().flat_map(schedule::rows) {
+            // language=sqlite
+            sqlx::query(r#"
+                INSERT INTO product_schedules(product, weekday, start, end) VALUES (?, ?, ?, ?)
+            "#)
+                .bind(id.as_ref())
+                .bind(weekday)
+                .bind(start)
+                .bind(end)
+                .execute(&mut *con)
+                .await
+                .change_context_lazy(|| FailedBuildReadModel)?;
+        }
+        
+        Ok(())
+    }
+    
     pub async fn delete(delete: ProductEvent, con: &mut SqliteConnection) -> Result<(), Report<FailedBuildReadModel>> {
         let ProductEvent::Deleted { id } = delete else {
             return Err(Report::new(FailedBuildReadModel).attach_printable("Invalid event type"));
@@ -188,6 +514,7 @@ pub(crate) mod test {
     use tracing_subscriber::layer::SubscriberExt;
     use tracing_subscriber::util::SubscriberInitExt;
     use kernel::entities::image::{Image, ImageId};
+    use kernel::entities::money::{Currency, Money};
     use kernel::entities::product::*;
     
     use super::*;
@@ -201,7 +528,7 @@ pub(crate) mod test {
             id,
             name: ProductName::new("test"),
             desc: ProductDesc::new("test description"),
-            price: ProductPrice::new(100).change_context_lazy(|| UnrecoverableError)?,
+            price: ProductPrice::new(Money::new(100, Currency::default())).change_context_lazy(|| UnrecoverableError)?,
             image: Image::new(ImageId::from(id), image),
         };
         
@@ -292,10 +619,45 @@ pub(crate) mod test {
         Ok(())
     }
     
+    #[tokio::test]
+    async fn test_change_product_tax() -> Result<(), Report<UnrecoverableError>> {
+        let pool = database::init("sqlite:../query.db").await
+            .change_context_lazy(|| UnrecoverableError)?;
+        let mut con = pool.begin().await
+            .change_context_lazy(|| UnrecoverableError)?;
+        
+        let product_id = ProductId::default();
+        
+        register_product(product_id, &mut con).await
+            .change_context_lazy(|| UnrecoverableError)?;
+        
+        let update = ProductEvent::ChangedTax { id: product_id, new: ProductTax::new(TaxCategory::Reduced, false) };
+        
+        InternalProductReadModelService::update_tax(update, &mut con).await
+            .change_context_lazy(|| UnrecoverableError)?;
+        
+        // language=sqlite
+        let (category, inclusive) = sqlx::query_as::<_, (String, bool)>(r#"
+            SELECT tax_category, tax_inclusive FROM products WHERE id = ?
+        "#)
+            .bind(product_id.as_ref())
+            .fetch_one(&mut *con)
+            .await
+            .change_context_lazy(|| UnrecoverableError)?;
+        
+        if category != "reduced" || inclusive {
+            return Err(Report::new(UnrecoverableError).attach_printable("Tax was not updated"));
+        }
+        
+        con.rollback().await
+            .change_context_lazy(|| UnrecoverableError)?;
+        Ok(())
+    }
+    
     pub async fn change_product_price(id: ProductId, con: &mut SqliteConnection) -> Result<(), Report<UnrecoverableError>> {
         let update = ProductEvent::ChangedProductPrice {
             id,
-            new: ProductPrice::new(200).change_context_lazy(|| UnrecoverableError)?,
+            new: ProductPrice::new(Money::new(200, Currency::default())).change_context_lazy(|| UnrecoverableError)?,
         };
         
         InternalProductReadModelService::update_price(update, con).await
@@ -358,6 +720,242 @@ pub(crate) mod test {
         Ok(())
     }
     
+    pub async fn mark_sold_out(id: ProductId, con: &mut SqliteConnection) -> Result<(), Report<UnrecoverableError>> {
+        InternalProductReadModelService::update_available(ProductEvent::MarkedSoldOut { id }, con).await
+            .change_context_lazy(|| UnrecoverableError)?;
+        
+        Ok(())
+    }
+    
+    #[tokio::test]
+    async fn test_mark_sold_out() -> Result<(), Report<UnrecoverableError>> {
+        let pool = database::init("sqlite:../query.db").await
+            .change_context_lazy(|| UnrecoverableError)?;
+        let mut con = pool.begin().await
+            .change_context_lazy(|| UnrecoverableError)?;
+        
+        let product_id = ProductId::default();
+        
+        register_product(product_id, &mut con).await
+            .change_context_lazy(|| UnrecoverableError)?;
+        
+        mark_sold_out(product_id, &mut con).await
+            .change_context_lazy(|| UnrecoverableError)?;
+        
+        InternalProductReadModelService::update_available(ProductEvent::MarkedAvailable { id: product_id }, &mut con).await
+            .change_context_lazy(|| UnrecoverableError)?;
+        
+        con.rollback().await
+            .change_context_lazy(|| UnrecoverableError)?;
+        Ok(())
+    }
+    
+    pub async fn decrement_stock(id: ProductId, amount: i64, new: i64, con: &mut SqliteConnection) -> Result<(), Report<UnrecoverableError>> {
+        let update = ProductEvent::DecrementedStock {
+            id,
+            amount: ProductStock::new(amount).change_context_lazy(|| UnrecoverableError)?,
+            new: ProductStock::new(new).change_context_lazy(|| UnrecoverableError)?,
+        };
+        
+        InternalProductReadModelService::update_stock(update, con).await
+            .change_context_lazy(|| UnrecoverableError)?;
+        
+        Ok(())
+    }
+    
+    #[tokio::test]
+    async fn test_update_stock() -> Result<(), Report<UnrecoverableError>> {
+        let pool = database::init("sqlite:../query.db").await
+            .change_context_lazy(|| UnrecoverableError)?;
+        let mut con = pool.begin().await
+            .change_context_lazy(|| UnrecoverableError)?;
+        
+        let product_id = ProductId::default();
+        
+        register_product(product_id, &mut con).await
+            .change_context_lazy(|| UnrecoverableError)?;
+        
+        let set = ProductEvent::ChangedStock {
+            id: product_id,
+            new: ProductStock::new(3).change_context_lazy(|| UnrecoverableError)?,
+        };
+        
+        InternalProductReadModelService::update_stock(set, &mut con).await
+            .change_context_lazy(|| UnrecoverableError)?;
+        
+        decrement_stock(product_id, 3, 0, &mut con).await
+            .change_context_lazy(|| UnrecoverableError)?;
+        
+        // language=sqlite
+        let available = sqlx::query_scalar::<_, bool>(r#"
+            SELECT available FROM products WHERE id = ?
+        "#)
+            .bind(product_id.as_ref())
+            .fetch_one(&mut *con)
+            .await
+            .change_context_lazy(|| UnrecoverableError)?;
+        
+        if available {
+            return Err(Report::new(UnrecoverableError).attach_printable("Product without stock must be sold out"));
+        }
+        
+        mark_sold_out(product_id, &mut con).await?;
+        
+        let restock = ProductEvent::Restocked {
+            id: product_id,
+            amount: ProductStock::new(5).change_context_lazy(|| UnrecoverableError)?,
+            new: ProductStock::new(5).change_context_lazy(|| UnrecoverableError)?,
+        };
+        
+        InternalProductReadModelService::update_stock(restock, &mut con).await
+            .change_context_lazy(|| UnrecoverableError)?;
+        
+        // language=sqlite
+        let available = sqlx::query_scalar::<_, bool>(r#"
+            SELECT available FROM products WHERE id = ?
+        "#)
+            .bind(product_id.as_ref())
+            .fetch_one(&mut *con)
+            .await
+            .change_context_lazy(|| UnrecoverableError)?;
+        
+        if available {
+            return Err(Report::new(UnrecoverableError).attach_printable("Product marked as sold out must stay so when restocked"));
+        }
+        
+        InternalProductReadModelService::update_available(ProductEvent::MarkedAvailable { id: product_id }, &mut con).await
+            .change_context_lazy(|| UnrecoverableError)?;
+        
+        // language=sqlite
+        let available = sqlx::query_scalar::<_, bool>(r#"
+            SELECT available FROM products WHERE id = ?
+        "#)
+            .bind(product_id.as_ref())
+            .fetch_one(&mut *con)
+            .await
+            .change_context_lazy(|| UnrecoverableError)?;
+        
+        if !available {
+            return Err(Report::new(UnrecoverableError).attach_printable("Product in stock must be available once it is no longer marked as sold out"));
+        }
+        
+        con.rollback().await
+            .change_context_lazy(|| UnrecoverableError)?;
+        Ok(())
+    }
+    
+    pub async fn add_option_group(id: ProductId, con: &mut SqliteConnection) -> Result<OptionGroupId, Report<UnrecoverableError>> {
+        let group = ProductOptionGroup::new(OptionGroupId::default(), OptionName::new("toppings"), 0, 2)
+            .change_context_lazy(|| UnrecoverableError)?;
+        let group_id = *group.id();
+        
+        InternalProductReadModelService::add_option_group(ProductEvent::AddedOptionGroup { id, group }, con).await
+            .change_context_lazy(|| UnrecoverableError)?;
+        
+        Ok(group_id)
+    }
+    
+    #[tokio::test]
+    async fn test_product_options() -> Result<(), Report<UnrecoverableError>> {
+        let pool = database::init("sqlite:../query.db").await
+            .change_context_lazy(|| UnrecoverableError)?;
+        let mut con = pool.begin().await
+            .change_context_lazy(|| UnrecoverableError)?;
+        
+        let product_id = ProductId::default();
+        
+        register_product(product_id, &mut con).await
+            .change_context_lazy(|| UnrecoverableError)?;
+        
+        let group = add_option_group(product_id, &mut con).await?;
+        let option = ProductOption::new(OptionId::default(), OptionName::new("egg"), Money::new(50, Currency::default()));
+        let option_id = *option.id();
+        
+        InternalProductReadModelService::upsert_option(ProductEvent::AddedOption { id: product_id, group, option }, &mut con).await
+            .change_context_lazy(|| UnrecoverableError)?;
+        
+        let option = ProductOption::new(option_id, OptionName::new("soft-boiled egg"), Money::new(80, Currency::default()));
+        InternalProductReadModelService::upsert_option(ProductEvent::EditedOption { id: product_id, group, option }, &mut con).await
+            .change_context_lazy(|| UnrecoverableError)?;
+        
+        // language=sqlite
+        let price_delta = sqlx::query_scalar::<_, i64>(r#"
+            SELECT price_delta FROM product_options WHERE id = ?
+        "#)
+            .bind(option_id.as_ref())
+            .fetch_one(&mut *con)
+            .await
+            .change_context_lazy(|| UnrecoverableError)?;
+        
+        if price_delta != 80 {
+            return Err(Report::new(UnrecoverableError).attach_printable("Edited option must replace the price delta"));
+        }
+        
+        let edit = ProductEvent::EditedOptionGroup { id: product_id, group, name: OptionName::new("topping"), min: 1, max: 1 };
+        InternalProductReadModelService::edit_option_group(edit, &mut con).await
+            .change_context_lazy(|| UnrecoverableError)?;
+        
+        InternalProductReadModelService::remove_option(ProductEvent::RemovedOption { id: product_id, group, option: option_id }, &mut con).await
+            .change_context_lazy(|| UnrecoverableError)?;
+        
+        InternalProductReadModelService::remove_option_group(ProductEvent::RemovedOptionGroup { id: product_id, group }, &mut con).await
+            .change_context_lazy(|| UnrecoverableError)?;
+        
+        con.rollback().await
+            .change_context_lazy(|| UnrecoverableError)?;
+        Ok(())
+    }
+    
+    #[tokio::test]
+    async fn test_product_bundle() -> Result<(), Report<UnrecoverableError>> {
+        let pool = database::init("sqlite:../query.db").await
+            .change_context_lazy(|| UnrecoverableError)?;
+        let mut con = pool.begin().await
+            .change_context_lazy(|| UnrecoverableError)?;
+        
+        let bundle = ProductId::default();
+        let curry = ProductId::default();
+        let coffee = ProductId::default();
+        let tea = ProductId::default();
+        
+        for id in [bundle, curry, coffee, tea] {
+            register_product(id, &mut con).await?;
+        }
+        
+        let main = BundleSlot::new(SlotId::default(), SlotName::new("main"), vec![curry]);
+        let drink = BundleSlot::new(SlotId::default(), SlotName::new("drink"), vec![coffee]);
+        let drink_id = *drink.id();
+        
+        for slot in [main, drink] {
+            InternalProductReadModelService::add_bundle_slot(ProductEvent::AddedBundleSlot { id: bundle, slot }, &mut con).await
+                .change_context_lazy(|| UnrecoverableError)?;
+        }
+        
+        let change = ProductEvent::ChangedBundleChoices { id: bundle, slot: drink_id, choices: vec![coffee, tea] };
+        InternalProductReadModelService::change_bundle_choices(change, &mut con).await
+            .change_context_lazy(|| UnrecoverableError)?;
+        
+        // language=sqlite
+        let choices = sqlx::query_scalar::<_, i64>(r#"
+            SELECT COUNT(*) FROM product_bundle_choices WHERE slot = ?
+        "#)
+            .bind(drink_id.as_ref())
+            .fetch_one(&mut *con)
+            .await
+            .change_context_lazy(|| UnrecoverableError)?;
+        
+        if choices != 2 {
+            return Err(Report::new(UnrecoverableError).attach_printable("Changed choices must replace the previous ones"));
+        }
+        
+        InternalProductReadModelService::remove_bundle_slot(ProductEvent::RemovedBundleSlot { id: bundle, slot: drink_id }, &mut con).await
+            .change_context_lazy(|| UnrecoverableError)?;
+        
+        con.rollback().await
+            .change_context_lazy(|| UnrecoverableError)?;
+        Ok(())
+    }
+    
     pub async fn delete_product(id: ProductId, con: &mut SqliteConnection) -> Result<(), Report<UnrecoverableError>> {
         let delete = ProductEvent::Deleted { id };
         
@@ -0,0 +1,233 @@
+use async_trait::async_trait;
+use error_stack::{Report, ResultExt};
+use kernel::entities::promotion::{Discount, PromotionScope};
+use kernel::io::events::PromotionEvent;
+use nitinol::eventstream::resolver::{DecodeMapping, SubscriptionMapper};
+use nitinol::eventstream::EventSubscriber;
+use sqlx::types::Uuid;
+use sqlx::{SqliteConnection, SqlitePool};
+
+use crate::errors::FailedBuildReadModel;
+
+#[derive(Clone)]
+pub struct PromotionReadModelService {
+    pool: SqlitePool
+}
+
+impl PromotionReadModelService {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+impl SubscriptionMapper for PromotionReadModelService {
+    fn mapping(mapping: &mut DecodeMapping<Self>) {
+        mapping.register::<PromotionEvent>();
+    }
+}
+
+#[async_trait]
+impl EventSubscriber<PromotionEvent> for PromotionReadModelService {
+    type Error = Report<FailedBuildReadModel>;
+
+    async fn on(&mut self, event: PromotionEvent) -> Result<(), Self::Error> {
+        let mut con = self.pool.begin().await
+            .change_context_lazy(|| FailedBuildReadModel)?;
+        match event {
+            PromotionEvent::Created { .. } => {
+                InternalPromotionReadModelService::create(event, &mut con).await?
+            }
+            PromotionEvent::Redeemed { .. } => {
+                InternalPromotionReadModelService::redeem(event, &mut con).await?
+            }
+            PromotionEvent::Released { .. } => {
+                InternalPromotionReadModelService::release(event, &mut con).await?
+            }
+            PromotionEvent::Ended { .. } => {
+                InternalPromotionReadModelService::end(event, &mut con).await?
+            }
+        }
+        con.commit().await
+            .change_context_lazy(|| FailedBuildReadModel)?;
+        Ok(())
+    }
+}
+
+
+pub(crate) struct InternalPromotionReadModelService;
+
+impl InternalPromotionReadModelService {
+    pub async fn create(create: PromotionEvent, con: &mut SqliteConnection) -> Result<(), Report<FailedBuildReadModel>> {
+        let PromotionEvent::Created { code, name, discount, scope, validity, cap } = create else {
+            return Err(Report::new(FailedBuildReadModel).attach_printable("Invalid event type"));
+        };
+        
+        let (kind, value, currency) = match discount {
+            Discount::Percentage { rate } => ("percentage", rate, None),
+            Discount::Fixed { amount } => ("fixed", amount.amount(), Some(amount.currency().to_string())),
+        };
+        
+        let (scope, targets) = match scope {
+            PromotionScope::Order => ("order", Vec::new()),
+            PromotionScope::Categories(ids) => ("categories", ids.into_iter().map(Uuid::from).collect()),
+            PromotionScope::Products(ids) => ("products", ids.into_iter().map(Uuid::from).collect::<Vec<_>>()),
+        };
+        
+        // language=sqlite
+        sqlx::query(r#"
+            INSERT INTO promotions(code, name, discount, value, currency, scope, starts_at, ends_at, cap) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+        "#)
+            .bind(code.as_ref())
+            .bind(name.as_ref())
+            .bind(kind)
+            .bind(value)
+            .bind(currency)
+            .bind(scope)
+            .bind(validity.starts_at())
+            .bind(validity.ends_at())
+            .bind(cap.map(i64::from))
+            .execute(&mut *con)
+            .await
+            .change_context_lazy(|| FailedBuildReadModel)?;
+        
+        for target in targets {
+            // language=sqlite
+            sqlx::query(r#"
+                INSERT INTO promotion_targets(code, target) VALUES (?, ?)
+            "#)
+                .bind(code.as_ref())
+                .bind(target)
+                .execute(&mut *con)
+                .await
+                .change_context_lazy(|| FailedBuildReadModel)?;
+        }
+        
+        Ok(())
+    }
+    
+    pub async fn redeem(redeem: PromotionEvent, con: &mut SqliteConnection) -> Result<(), Report<FailedBuildReadModel>> {
+        let PromotionEvent::Redeemed { code, order } = redeem else {
+            return Err(Report::new(FailedBuildReadModel).attach_printable("Invalid event type"));
+        };
+        
+        // language=sqlite
+        sqlx::query(r#"
+            INSERT INTO promotion_redemptions(code, order_id) VALUES (?, ?)
+        "#)
+            .bind(code.as_ref())
+            .bind(order.as_ref())
+            .execute(&mut *con)
+            .await
+            .change_context_lazy(|| FailedBuildReadModel)?;
+        
+        Ok(())
+    }
+    
+    pub async fn release(release: PromotionEvent, con: &mut SqliteConnection) -> Result<(), Report<FailedBuildReadModel>> {
+        let PromotionEvent::Released { code, order } = release else {
+            return Err(Report::new(FailedBuildReadModel).attach_printable("Invalid event type"));
+        };
+        
+        // language=sqlite
+        sqlx::query(r#"
+            DELETE FROM promotion_redemptions WHERE code = ? AND order_id = ?
+        "#)
+            .bind(code.as_ref())
+            .bind(order.as_ref())
+            .execute(&mut *con)
+            .await
+            .change_context_lazy(|| FailedBuildReadModel)?;
+        
+        Ok(())
+    }
+    
+    pub async fn end(end: PromotionEvent, con: &mut SqliteConnection) -> Result<(), Report<FailedBuildReadModel>> {
+        let PromotionEvent::Ended { code } = end else {
+            return Err(Report::new(FailedBuildReadModel).attach_printable("Invalid event type"));
+        };
+        
+        // language=sqlite
+        sqlx::query(r#"
+            UPDATE promotions SET ended = 1 WHERE code = ?
+        "#)
+            .bind(code.as_ref())
+            .execute(&mut *con)
+            .await
+            .change_context_lazy(|| FailedBuildReadModel)?;
+        
+        Ok(())
+    }
+}
+
+
+#[cfg(test)]
+pub(crate) mod test {
+    use error_stack::{Report, ResultExt};
+    use kernel::entities::money::{Currency, Money};
+    use kernel::entities::order::OrderId;
+    use kernel::entities::product::ProductId;
+    use kernel::entities::promotion::{CouponCode, PromotionName, UsageCap, Validity};
+    use sqlx::types::time::{Duration, OffsetDateTime};
+    
+    use super::*;
+    use crate::database;
+    use crate::database::query::InternalPromotionQueryService;
+    use crate::errors::test::UnrecoverableError;
+    
+    pub async fn create_promotion(code: &CouponCode, product: ProductId, con: &mut SqliteConnection) -> Result<(), Report<UnrecoverableError>> {
+        let now = OffsetDateTime::now_utc();
+        let create = PromotionEvent::Created {
+            code: code.clone(),
+            name: PromotionName::new("test"),
+            discount: Discount::fixed(Money::new(50, Currency::default())).change_context_lazy(|| UnrecoverableError)?,
+            scope: PromotionScope::products([product]).change_context_lazy(|| UnrecoverableError)?,
+            validity: Validity::new(now, now + Duration::days(1)).change_context_lazy(|| UnrecoverableError)?,
+            cap: Some(UsageCap::new(10).change_context_lazy(|| UnrecoverableError)?),
+        };
+        
+        InternalPromotionReadModelService::create(create, con).await
+            .change_context_lazy(|| UnrecoverableError)?;
+        
+        Ok(())
+    }
+    
+    #[tokio::test]
+    async fn test_promotion() -> Result<(), Report<UnrecoverableError>> {
+        let pool = database::init("sqlite:../query.db").await
+            .change_context_lazy(|| UnrecoverableError)?;
+        let mut con = pool.begin().await
+            .change_context_lazy(|| UnrecoverableError)?;
+        
+        let code = CouponCode::new(&OrderId::default().as_ref().simple().to_string()[..12])
+            .change_context_lazy(|| UnrecoverableError)?;
+        let product = ProductId::default();
+        
+        create_promotion(&code, product, &mut con).await?;
+        
+        let cancelled = OrderId::default();
+        
+        InternalPromotionReadModelService::redeem(PromotionEvent::Redeemed { code: code.clone(), order: OrderId::default() }, &mut con).await
+            .change_context_lazy(|| UnrecoverableError)?;
+        InternalPromotionReadModelService::redeem(PromotionEvent::Redeemed { code: code.clone(), order: cancelled }, &mut con).await
+            .change_context_lazy(|| UnrecoverableError)?;
+        InternalPromotionReadModelService::release(PromotionEvent::Released { code: code.clone(), order: cancelled }, &mut con).await
+            .change_context_lazy(|| UnrecoverableError)?;
+        InternalPromotionReadModelService::end(PromotionEvent::Ended { code: code.clone() }, &mut con).await
+            .change_context_lazy(|| UnrecoverableError)?;
+        
+        let promotions = InternalPromotionQueryService::get_all_promotions(&mut con).await
+            .change_context_lazy(|| UnrecoverableError)?;
+        
+        let Some(promotion) = promotions.iter().find(|promotion| promotion.code == code.as_ref()) else {
+            return Err(Report::new(UnrecoverableError).attach_printable("Promotion was not recorded"));
+        };
+        
+        if promotion.redeemed != 1 || !promotion.ended || promotion.targets != vec![*product.as_ref()] {
+            return Err(Report::new(UnrecoverableError).attach_printable("Promotion was not updated"));
+        }
+        
+        con.rollback().await
+            .change_context_lazy(|| UnrecoverableError)?;
+        Ok(())
+    }
+}
@@ -1,5 +1,21 @@
+mod audit;
 mod category;
+mod device;
+mod history;
+mod order;
+mod payment;
 mod product;
+mod promotion;
+mod sales;
+mod ticket;
 
+pub use audit::*;
 pub use category::*;
-pub use product::*;
\ No newline at end of file
+pub use device::*;
+pub use history::*;
+pub use order::*;
+pub use payment::*;
+pub use product::*;
+pub use promotion::*;
+pub use sales::*;
+pub use ticket::*;
\ No newline at end of file
@@ -0,0 +1,63 @@
+use app_query::errors::QueryError;
+use app_query::models::{AuditEntry, GetAuditLogQueryService};
+use async_trait::async_trait;
+use error_stack::{Report, ResultExt};
+use sqlx::types::time::PrimitiveDateTime;
+use sqlx::SqliteConnection;
+
+use crate::errors::FailedQuery;
+
+#[derive(Clone)]
+pub struct AuditQueryService {
+    pool: sqlx::SqlitePool,
+}
+
+impl AuditQueryService {
+    pub fn new(pool: sqlx::SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl GetAuditLogQueryService for AuditQueryService {
+    async fn get_audit_log(
+        &self,
+        aggregate: Option<String>,
+        from: Option<PrimitiveDateTime>,
+        to: Option<PrimitiveDateTime>
+    ) -> Result<Vec<AuditEntry>, Report<QueryError>> {
+        let mut con = self.pool.acquire().await
+            .change_context_lazy(|| QueryError::Driver)?;
+        let entries = InternalAuditQueryService::get_audit_log(&mut con, aggregate, from, to).await
+            .change_context_lazy(|| QueryError::Driver)?;
+        Ok(entries)
+    }
+}
+
+pub(crate) struct InternalAuditQueryService;
+
+impl InternalAuditQueryService {
+    pub async fn get_audit_log(
+        con: &mut SqliteConnection,
+        aggregate: Option<String>,
+        from: Option<PrimitiveDateTime>,
+        to: Option<PrimitiveDateTime>
+    ) -> Result<Vec<AuditEntry>, Report<FailedQuery>> {
+        // `datetime` drops the fraction sqlx appends, which would otherwise break the text comparison.
+        // language=sqlite
+        sqlx::query_as::<_, AuditEntry>(r#"
+            SELECT id, aggregate, aggregate_id, sequence, event, staff, device, request_id, client_ip, recorded_at
+            FROM audit_log
+            WHERE (?1 IS NULL OR aggregate = ?1 OR aggregate_id = ?1)
+              AND recorded_at >= COALESCE(datetime(?2), recorded_at)
+              AND recorded_at <= COALESCE(datetime(?3), recorded_at)
+            ORDER BY recorded_at, id
+        "#)
+            .bind(aggregate)
+            .bind(from)
+            .bind(to)
+            .fetch_all(&mut *con)
+            .await
+            .change_context_lazy(|| FailedQuery)
+    }
+}
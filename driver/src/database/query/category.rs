@@ -1,9 +1,11 @@
+use crate::database::schedule;
 use crate::errors::FailedQuery;
 use app_query::errors::QueryError;
 use app_query::models::{AllCategories, GetAllCategoriesQueryService, OrderedCategory};
 use async_trait::async_trait;
 use error_stack::{Report, ResultExt};
 use sqlx::SqliteConnection;
+use sqlx::types::time::PrimitiveDateTime;
 use std::collections::BTreeSet;
 
 #[derive(Clone)]
@@ -19,10 +21,10 @@ impl CategoryQueryService {
 
 #[async_trait]
 impl GetAllCategoriesQueryService for CategoryQueryService {
-    async fn get_all_categories(&self) -> Result<AllCategories, Report<QueryError>> {
+    async fn get_all_categories(&self, at: Option<PrimitiveDateTime>) -> Result<AllCategories, Report<QueryError>> {
         let mut con = self.pool.acquire().await
             .change_context_lazy(|| QueryError::Driver)?;
-        let all = InternalCategoryQueryService::get_all_categories(&mut con).await
+        let all = InternalCategoryQueryService::get_all_categories(&mut con, at).await
             .change_context_lazy(|| QueryError::Driver)?;
         Ok(all)
     }
@@ -31,7 +33,9 @@ impl GetAllCategoriesQueryService for CategoryQueryService {
 pub(crate) struct InternalCategoryQueryService;
 
 impl InternalCategoryQueryService {
-    pub async fn get_all_categories(con: &mut SqliteConnection) -> Result<AllCategories, Report<FailedQuery>> {
+    pub async fn get_all_categories(con: &mut SqliteConnection, at: Option<PrimitiveDateTime>) -> Result<AllCategories, Report<FailedQuery>> {
+        let (weekday, seconds) = schedule::split(at);
+        
         // language=sqlite
         let all = sqlx::query_as::<_, OrderedCategory>(r#"
             SELECT 
@@ -42,7 +46,16 @@ impl InternalCategoryQueryService {
                 categories c
             JOIN 
                 categories_ordering co ON c.id = co.category
+            WHERE
+                ?1 IS NULL
+                OR NOT EXISTS (SELECT 1 FROM category_schedules cs WHERE cs.category = c.id)
+                OR EXISTS (
+                    SELECT 1 FROM category_schedules cs 
+                    WHERE cs.category = c.id AND cs.weekday = ?1 AND cs.start <= ?2 AND ?2 < cs.end
+                )
         "#)
+            .bind(weekday)
+            .bind(seconds)
             .fetch_all(&mut *con)
             .await
             .change_context_lazy(|| FailedQuery)?;
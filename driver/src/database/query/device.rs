@@ -0,0 +1,45 @@
+use app_query::errors::QueryError;
+use app_query::models::{DeviceRecord, GetDeviceQueryService};
+use async_trait::async_trait;
+use error_stack::{Report, ResultExt};
+use sqlx::SqliteConnection;
+
+use crate::errors::FailedQuery;
+
+#[derive(Clone)]
+pub struct DeviceQueryService {
+    pool: sqlx::SqlitePool,
+}
+
+impl DeviceQueryService {
+    pub fn new(pool: sqlx::SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl GetDeviceQueryService for DeviceQueryService {
+    async fn get_all_devices(&self) -> Result<Vec<DeviceRecord>, Report<QueryError>> {
+        let mut con = self.pool.acquire().await
+            .change_context_lazy(|| QueryError::Driver)?;
+        let devices = InternalDeviceQueryService::get_all_devices(&mut con).await
+            .change_context_lazy(|| QueryError::Driver)?;
+        Ok(devices)
+    }
+}
+
+pub(crate) struct InternalDeviceQueryService;
+
+impl InternalDeviceQueryService {
+    pub async fn get_all_devices(con: &mut SqliteConnection) -> Result<Vec<DeviceRecord>, Report<FailedQuery>> {
+        // language=sqlite
+        sqlx::query_as::<_, DeviceRecord>(r#"
+            SELECT id, name, role, store, registered_at, rotated_at, revoked
+            FROM devices
+            ORDER BY revoked, registered_at, name
+        "#)
+            .fetch_all(&mut *con)
+            .await
+            .change_context_lazy(|| FailedQuery)
+    }
+}
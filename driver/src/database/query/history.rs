@@ -0,0 +1,638 @@
+use std::collections::{BTreeMap, HashMap};
+
+use app_query::errors::QueryError;
+use app_query::models::{CategoryChange, CategoryHistory, GetHistoryQueryService, GetMenuQueryService, HistoryOption, HistoryTimeWindow, Menu, MenuCategory, MenuCutoff, MenuProduct, Money, ProductChange, ProductHistory};
+use async_trait::async_trait;
+use error_stack::{Context, Report, ResultExt};
+use kernel::entities::categories::Categories;
+use kernel::entities::category::{Category, CategoryId};
+use kernel::entities::money::Currency;
+use kernel::entities::product::{Product, ProductId, ProductOption};
+use kernel::entities::schedule::{Schedule, TimeWindow};
+use kernel::entities::ticket::StoreOffset;
+use kernel::io::events::{CategoriesEvent, CategoryEvent, ProductEvent};
+use nitinol::projection::Projection;
+use nitinol::protocol::io::ReadProtocol;
+use nitinol::{Event, ToEntityId};
+use sqlx::types::time::{OffsetDateTime, PrimitiveDateTime};
+use sqlx::types::Uuid;
+
+use crate::errors::FailedQuery;
+
+/// Reads from the event journal rather than the read models, 
+/// so that the catalog can be followed event by event, or replayed up to a past moment.
+pub struct HistoryQueryService {
+    journal: ReadProtocol,
+    offset: StoreOffset,
+}
+
+impl HistoryQueryService {
+    pub fn new(journal: ReadProtocol, offset: StoreOffset) -> Self {
+        Self { journal, offset }
+    }
+}
+
+#[async_trait]
+impl GetHistoryQueryService for HistoryQueryService {
+    async fn get_product_history(&self, id: &Uuid) -> Result<Vec<ProductHistory>, Report<QueryError>> {
+        let history = InternalHistoryQueryService::read::<ProductEvent>(&self.journal, ProductId::new(*id)).await
+            .change_context_lazy(|| QueryError::Driver)?
+            .into_iter()
+            .map(|Recorded { sequence, recorded_at, event }| ProductHistory { 
+                sequence, 
+                recorded_at, 
+                event: product_change(event) 
+            })
+            .collect();
+        Ok(history)
+    }
+    
+    async fn get_category_history(&self, id: &Uuid) -> Result<Vec<CategoryHistory>, Report<QueryError>> {
+        let history = InternalHistoryQueryService::read::<CategoryEvent>(&self.journal, CategoryId::new(*id)).await
+            .change_context_lazy(|| QueryError::Driver)?
+            .into_iter()
+            .map(|Recorded { sequence, recorded_at, event }| CategoryHistory { sequence, recorded_at, event: category_change(event) })
+            .collect();
+        Ok(history)
+    }
+}
+
+#[async_trait]
+impl GetMenuQueryService for HistoryQueryService {
+    async fn get_menu_as_of(&self, cutoff: MenuCutoff) -> Result<Option<Menu>, Report<QueryError>> {
+        let menu = InternalHistoryQueryService::menu(&self.journal, cutoff, &self.offset).await
+            .change_context_lazy(|| QueryError::Driver)?;
+        Ok(menu)
+    }
+}
+
+fn product_change(event: ProductEvent) -> ProductChange {
+    match event {
+        ProductEvent::Registered { name, desc, price, image, .. } => ProductChange::Registered {
+            name: name.as_ref().to_string(),
+            desc: desc.as_ref().to_string(),
+            price: money(price.amount(), price.currency()),
+            image: *image.id().as_ref(),
+        },
+        ProductEvent::RenamedProductName { new, .. } => ProductChange::RenamedProductName { new: new.as_ref().to_string() },
+        ProductEvent::EditedProductDesc { new, .. } => ProductChange::EditedProductDesc { new: new.as_ref().to_string() },
+        ProductEvent::ChangedProductPrice { new, .. } => ProductChange::ChangedProductPrice { new: money(new.amount(), new.currency()) },
+        ProductEvent::ChangedProductImage { image, .. } => ProductChange::ChangedProductImage { image: *image.id().as_ref() },
+        ProductEvent::MarkedSoldOut { .. } => ProductChange::MarkedSoldOut,
+        ProductEvent::MarkedAvailable { .. } => ProductChange::MarkedAvailable,
+        ProductEvent::ChangedStock { new, .. } => ProductChange::ChangedStock { new: *new.as_ref() },
+        ProductEvent::Restocked { amount, new, .. } => ProductChange::Restocked { amount: *amount.as_ref(), new: *new.as_ref() },
+        ProductEvent::DecrementedStock { amount, new, .. } => ProductChange::DecrementedStock { amount: *amount.as_ref(), new: *new.as_ref() },
+        ProductEvent::AddedOptionGroup { group, .. } => ProductChange::AddedOptionGroup {
+            group: *group.id().as_ref(),
+            name: group.name().as_ref().to_string(),
+            min: group.min(),
+            max: group.max(),
+            options: group.options().iter().map(option).collect(),
+        },
+        ProductEvent::EditedOptionGroup { group, name, min, max, .. } => ProductChange::EditedOptionGroup {
+            group: *group.as_ref(),
+            name: name.as_ref().to_string(),
+            min,
+            max,
+        },
+        ProductEvent::RemovedOptionGroup { group, .. } => ProductChange::RemovedOptionGroup { group: *group.as_ref() },
+        ProductEvent::AddedOption { group, option: added, .. } => ProductChange::AddedOption { group: *group.as_ref(), option: option(&added) },
+        ProductEvent::EditedOption { group, option: edited, .. } => ProductChange::EditedOption { group: *group.as_ref(), option: option(&edited) },
+        ProductEvent::RemovedOption { group, option, .. } => ProductChange::RemovedOption { group: *group.as_ref(), option: *option.as_ref() },
+        ProductEvent::AddedBundleSlot { slot, .. } => ProductChange::AddedBundleSlot {
+            slot: *slot.id().as_ref(),
+            name: slot.name().as_ref().to_string(),
+            choices: slot.choices().iter().map(|choice| *choice.as_ref()).collect(),
+        },
+        ProductEvent::ChangedBundleChoices { slot, choices, .. } => ProductChange::ChangedBundleChoices {
+            slot: *slot.as_ref(),
+            choices: choices.iter().map(|choice| *choice.as_ref()).collect(),
+        },
+        ProductEvent::RemovedBundleSlot { slot, .. } => ProductChange::RemovedBundleSlot { slot: *slot.as_ref() },
+        ProductEvent::JoinedBundle { bundle, .. } => ProductChange::JoinedBundle { bundle: *bundle.as_ref() },
+        ProductEvent::LeftBundle { bundle, .. } => ProductChange::LeftBundle { bundle: *bundle.as_ref() },
+        ProductEvent::ChangedTax { new, .. } => ProductChange::ChangedTax {
+            category: new.category().as_ref().to_string(),
+            inclusive: new.inclusive(),
+        },
+        ProductEvent::ChangedSchedule { new, .. } => ProductChange::ChangedSchedule { windows: windows(&new) },
+        ProductEvent::ClearedSchedule { .. } => ProductChange::ClearedSchedule,
+        ProductEvent::Deleted { .. } => ProductChange::Deleted,
+    }
+}
+
+fn category_change(event: CategoryEvent) -> CategoryChange {
+    let products = |ordering: BTreeMap<i64, ProductId>| ordering.into_iter()
+        .map(|(ordering, id)| (ordering, *id.as_ref()))
+        .collect();
+    
+    match event {
+        CategoryEvent::Created { name, .. } => CategoryChange::Created { name: name.as_ref().to_string() },
+        CategoryEvent::Renamed { new, .. } => CategoryChange::Renamed { new: new.as_ref().to_string() },
+        CategoryEvent::Deleted { .. } => CategoryChange::Deleted,
+        CategoryEvent::ChangedSchedule { new, .. } => CategoryChange::ChangedSchedule { windows: windows(&new) },
+        CategoryEvent::ClearedSchedule { .. } => CategoryChange::ClearedSchedule,
+        CategoryEvent::AddedProduct { id, ordering, .. } => CategoryChange::AddedProduct { product: *id.as_ref(), ordering },
+        CategoryEvent::RemovedProduct { new, .. } => CategoryChange::RemovedProduct { new: products(new) },
+        CategoryEvent::ChangedProductOrdering { new, .. } => CategoryChange::ChangedProductOrdering { new: products(new) },
+    }
+}
+
+fn money(amount: i64, currency: &Currency) -> Money {
+    Money { amount, currency: currency.to_string() }
+}
+
+fn option(option: &ProductOption) -> HistoryOption {
+    HistoryOption {
+        id: *option.id().as_ref(),
+        name: option.name().as_ref().to_string(),
+        price_delta: money(option.price_delta().amount(), option.price_delta().currency()),
+    }
+}
+
+fn windows(schedule: &Schedule) -> Vec<HistoryTimeWindow> {
+    let windows: &[TimeWindow] = schedule.as_ref();
+    windows.iter()
+        .map(|window| HistoryTimeWindow {
+            weekdays: window.weekdays().collect(),
+            start: *window.start(),
+            end: *window.end(),
+        })
+        .collect()
+}
+
+/// Where the replay of a single entity stops, both inclusive.
+#[derive(Clone, Copy)]
+pub(crate) enum Until {
+    At(OffsetDateTime),
+    Sequence(i64),
+}
+
+pub(crate) struct Recorded<E> {
+    pub sequence: i64,
+    pub recorded_at: OffsetDateTime,
+    pub event: E,
+}
+
+pub(crate) struct InternalHistoryQueryService;
+
+impl InternalHistoryQueryService {
+    /// Every event persisted for the entity, in the order of their sequence.
+    pub async fn read<E: Event>(journal: &ReadProtocol, id: impl ToEntityId) -> Result<Vec<Recorded<E>>, Report<FailedQuery>> {
+        let payloads = journal.read_to_latest(id.to_entity_id(), 0).await
+            .change_context_lazy(|| FailedQuery)?;
+        
+        let mut recorded = payloads.into_iter()
+            .map(|payload| {
+                E::from_bytes(&payload.bytes)
+                    .change_context_lazy(|| FailedQuery)
+                    .attach_printable_lazy(|| format!("Failed to decode the event #{} of `{}`", payload.sequence_id, payload.id))
+                    .map(|event| Recorded { sequence: payload.sequence_id, recorded_at: payload.created_at, event })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        
+        recorded.sort_by_key(|recorded| recorded.sequence);
+        
+        Ok(recorded)
+    }
+    
+    /// When the event numbered `sequence` in the history of the product or category `id` was recorded.
+    pub async fn recorded_at(journal: &ReadProtocol, id: Uuid, sequence: i64) -> Result<Option<OffsetDateTime>, Report<FailedQuery>> {
+        let products = journal.read_to_latest(ProductId::new(id).to_entity_id(), 0).await
+            .change_context_lazy(|| FailedQuery)?;
+        let categories = journal.read_to_latest(CategoryId::new(id).to_entity_id(), 0).await
+            .change_context_lazy(|| FailedQuery)?;
+        
+        Ok(products.into_iter()
+            .chain(categories)
+            .find(|payload| payload.sequence_id == sequence)
+            .map(|payload| payload.created_at))
+    }
+    
+    /// Replays an entity the way `EventProjector::projection_to_latest` does, 
+    /// but only from the events `until` allows.
+    /// 
+    /// `None` if the entity did not exist yet, or had already been `deleted` by then.
+    pub async fn replay<T, E>(
+        journal: &ReadProtocol,
+        id: impl ToEntityId,
+        until: Until,
+        mut entity: Option<T>,
+        deleted: impl Fn(&E) -> bool + Send,
+    ) -> Result<Option<T>, Report<FailedQuery>>
+        where
+            T: Projection<E>,
+            T::Rejection: Context,
+            E: Event,
+    {
+        for Recorded { sequence, recorded_at, event } in Self::read::<E>(journal, id).await? {
+            let later = match until {
+                Until::At(at) => recorded_at > at,
+                Until::Sequence(until) => sequence > until,
+            };
+            
+            if later {
+                break;
+            }
+            
+            if deleted(&event) {
+                return Ok(None);
+            }
+            
+            entity = Some(match entity {
+                None => T::first(event).await
+                    .change_context_lazy(|| FailedQuery)?,
+                Some(mut entity) => {
+                    entity.apply(event).await
+                        .change_context_lazy(|| FailedQuery)?;
+                    entity
+                }
+            });
+        }
+        
+        Ok(entity)
+    }
+    
+    pub async fn menu(journal: &ReadProtocol, cutoff: MenuCutoff, offset: &StoreOffset) -> Result<Option<Menu>, Report<FailedQuery>> {
+        let (at, pinned) = match cutoff {
+            MenuCutoff::At(at) => (at, None),
+            MenuCutoff::Sequence { id, sequence } => {
+                let Some(at) = Self::recorded_at(journal, id, sequence).await? else {
+                    return Ok(None);
+                };
+                (at, Some((id, sequence)))
+            }
+        };
+        
+        // The pinned entity stops at its sequence, as other events may have been recorded within the same instant.
+        let until = |id: &Uuid| match pinned {
+            Some((pinned, sequence)) if pinned == *id => Until::Sequence(sequence),
+            _ => Until::At(at),
+        };
+        
+        // Schedules are kept in the local time of the store.
+        let local = at.to_offset((*offset).into());
+        let local = PrimitiveDateTime::new(local.date(), local.time());
+        let offered = |schedule: Option<&Schedule>| schedule.map_or(true, |schedule| schedule.is_available_at(&local));
+        
+        let categories = Self::replay::<Categories, CategoriesEvent>(journal, Categories::ID, Until::At(at), Some(Categories::default()), |_| false).await?
+            .unwrap_or_default();
+        
+        // A product may be listed in several categories, but only has to be replayed once.
+        let mut replayed: HashMap<ProductId, Option<Product>> = HashMap::new();
+        let mut menu = Vec::new();
+        
+        for (ordering, id) in categories.as_ref() {
+            let deleted = |event: &CategoryEvent| matches!(event, CategoryEvent::Deleted { .. });
+            let Some(category) = Self::replay::<Category, CategoryEvent>(journal, *id, until(id.as_ref()), None, deleted).await? else {
+                continue;
+            };
+            
+            if !offered(category.schedule()) {
+                continue;
+            }
+            
+            let mut products = Vec::new();
+            
+            for (ordering, id) in category.products() {
+                if !replayed.contains_key(id) {
+                    let deleted = |event: &ProductEvent| matches!(event, ProductEvent::Deleted { .. });
+                    let product = Self::replay::<Product, ProductEvent>(journal, *id, until(id.as_ref()), None, deleted).await?;
+                    replayed.insert(*id, product);
+                }
+                
+                let Some(Some(product)) = replayed.get(id) else {
+                    continue;
+                };
+                
+                if !offered(product.schedule()) {
+                    continue;
+                }
+                
+                products.push(MenuProduct {
+                    id: *id.as_ref(),
+                    name: product.name().as_ref().to_string(),
+                    ordering: *ordering,
+                    price: money(product.price().amount(), product.price().currency()),
+                    available: product.available(),
+                });
+            }
+            
+            menu.push(MenuCategory {
+                id: *id.as_ref(),
+                name: category.name().as_ref().to_string(),
+                ordering: *ordering,
+                products,
+            });
+        }
+        
+        Ok(Some(Menu { at, categories: menu }))
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use app_cmd::adapter::{DependOnEventProjector, DependOnProcessManager};
+    use app_cmd::audit::{Actor, AuditLog, AuditRecord, DependOnAuditLog};
+    use app_cmd::errors::ApplicationError;
+    use app_cmd::services::categories::DependOnCategoriesCommandService;
+    use app_cmd::services::category::CategoryCommandService;
+    use app_cmd::services::product::ProductCommandService;
+    use error_stack::{Report, ResultExt};
+    use kernel::entities::category::CategoryName;
+    use kernel::entities::money::{Currency, Money};
+    use kernel::entities::product::{ProductDesc, ProductName, ProductPrice};
+    use kernel::io::commands::{CategoryCommand, ProductCommand};
+    use nitinol::eventstream::EventStream;
+    use nitinol::process::eventstream::EventStreamExtension;
+    use nitinol::process::manager::ProcessManager;
+    use nitinol::process::persistence::PersistenceExtension;
+    use nitinol::projection::EventProjector;
+    use nitinol::protocol::adapter::inmemory::InMemoryEventStore;
+    use sqlx::types::time::Time;
+    
+    use super::*;
+    use crate::errors::test::UnrecoverableError;
+    
+    /// Dispatches commands into an in-memory journal, without auditing them.
+    struct Journal {
+        manager: ProcessManager,
+        projector: EventProjector,
+        store: InMemoryEventStore,
+    }
+    
+    impl Journal {
+        fn new() -> Result<Self, Report<UnrecoverableError>> {
+            let store = InMemoryEventStore::default();
+            let manager = ProcessManager::with_extension(|ext| {
+                ext.install(PersistenceExtension::new(store.clone()))?
+                    .install(EventStreamExtension::new(EventStream::default()))
+            }).change_context_lazy(|| UnrecoverableError)?;
+            
+            Ok(Self { manager, projector: EventProjector::new(store.clone()), store })
+        }
+        
+        fn service(&self) -> HistoryQueryService {
+            HistoryQueryService::new(ReadProtocol::new(self.store.clone()), StoreOffset::default())
+        }
+        
+        async fn first<E: Event>(&self) -> Result<E, Report<UnrecoverableError>> {
+            ReadProtocol::new(self.store.clone())
+                .read_all_by_event::<E>()
+                .await
+                .change_context_lazy(|| UnrecoverableError)?
+                .first()
+                .ok_or(Report::new(UnrecoverableError).attach_printable("No event found"))
+                .map(|payload| E::from_bytes(&payload.bytes))?
+                .change_context_lazy(|| UnrecoverableError)
+        }
+        
+        async fn register(&self, amount: i64) -> Result<ProductId, Report<UnrecoverableError>> {
+            let register = ProductCommand::Register {
+                name: ProductName::new("curry"),
+                desc: ProductDesc::new("with rice"),
+                price: price(amount)?,
+                image: vec![0xFF; 16],
+            };
+            ProductCommandService::execute(self, &Actor::system(), None, register).await
+                .change_context_lazy(|| UnrecoverableError)?;
+            
+            let ProductEvent::Registered { id, .. } = self.first::<ProductEvent>().await? else {
+                return Err(Report::new(UnrecoverableError).attach_printable("Product was not registered"));
+            };
+            
+            Ok(id)
+        }
+        
+        async fn change_price(&self, id: ProductId, amount: i64) -> Result<(), Report<UnrecoverableError>> {
+            ProductCommandService::execute(self, &Actor::system(), id, ProductCommand::ChangeProductPrice { new: price(amount)? }).await
+                .change_context_lazy(|| UnrecoverableError)?;
+            Ok(())
+        }
+    }
+    
+    impl DependOnProcessManager for Journal {
+        fn process_manager(&self) -> &ProcessManager {
+            &self.manager
+        }
+    }
+    
+    impl DependOnEventProjector for Journal {
+        fn event_projector(&self) -> &EventProjector {
+            &self.projector
+        }
+    }
+    
+    impl DependOnCategoriesCommandService for Journal {
+        type CategoriesCommandService = Self;
+        fn categories_command_service(&self) -> &Self::CategoriesCommandService {
+            self
+        }
+    }
+    
+    impl DependOnAuditLog for Journal {
+        type AuditLog = Self;
+        fn audit_log(&self) -> &Self::AuditLog {
+            self
+        }
+    }
+    
+    #[async_trait]
+    impl AuditLog for Journal {
+        async fn record(&self, _: AuditRecord, _: i64) -> Result<(), Report<ApplicationError>> {
+            Ok(())
+        }
+    }
+    
+    fn price(amount: i64) -> Result<ProductPrice, Report<UnrecoverableError>> {
+        ProductPrice::new(Money::new(amount, Currency::default()))
+            .change_context_lazy(|| UnrecoverableError)
+    }
+    
+    #[tokio::test]
+    async fn test_product_history() -> Result<(), Report<UnrecoverableError>> {
+        let journal = Journal::new()?;
+        let service = journal.service();
+        
+        let id = journal.register(500).await?;
+        journal.change_price(id, 450).await?;
+        
+        let history = service.get_product_history(id.as_ref()).await
+            .change_context_lazy(|| UnrecoverableError)?;
+        
+        let [registered, changed] = history.as_slice() else {
+            return Err(Report::new(UnrecoverableError).attach_printable(format!("Unexpected history: {history:?}")));
+        };
+        
+        let ProductChange::Registered { name, image, .. } = &registered.event else {
+            return Err(Report::new(UnrecoverableError).attach_printable(format!("Unexpected event: {registered:?}")));
+        };
+        
+        if name != "curry" || image != id.as_ref() {
+            return Err(Report::new(UnrecoverableError).attach_printable(format!("Image must be referred to by its id: {registered:?}")));
+        }
+        
+        if !matches!(&changed.event, ProductChange::ChangedProductPrice { new } if new.amount == 450) || changed.sequence <= registered.sequence {
+            return Err(Report::new(UnrecoverableError).attach_printable(format!("Unexpected event: {changed:?}")));
+        }
+        
+        let history = service.get_product_history(ProductId::default().as_ref()).await
+            .change_context_lazy(|| UnrecoverableError)?;
+        
+        if !history.is_empty() {
+            return Err(Report::new(UnrecoverableError).attach_printable("Unknown product must have no history"));
+        }
+        
+        Ok(())
+    }
+    
+    #[test]
+    fn test_bundle_membership_changes() -> Result<(), Report<UnrecoverableError>> {
+        let (id, bundle) = (ProductId::default(), ProductId::default());
+        
+        let joined = product_change(ProductEvent::JoinedBundle { id, bundle });
+        if !matches!(&joined, ProductChange::JoinedBundle { bundle: joined } if joined == bundle.as_ref()) {
+            return Err(Report::new(UnrecoverableError).attach_printable(format!("Unexpected change: {joined:?}")));
+        }
+        
+        let left = product_change(ProductEvent::LeftBundle { id, bundle });
+        if !matches!(&left, ProductChange::LeftBundle { bundle: left } if left == bundle.as_ref()) {
+            return Err(Report::new(UnrecoverableError).attach_printable(format!("Unexpected change: {left:?}")));
+        }
+        
+        Ok(())
+    }
+    
+    /// Creates a category listing `product`.
+    async fn list(journal: &Journal, product: ProductId) -> Result<CategoryId, Report<UnrecoverableError>> {
+        let create = CategoryCommand::Create { 
+            name: CategoryName::new("meals").change_context_lazy(|| UnrecoverableError)? 
+        };
+        CategoryCommandService::execute(journal, &Actor::system(), None, create).await
+            .change_context_lazy(|| UnrecoverableError)?;
+        
+        let CategoryEvent::Created { id: category, .. } = journal.first::<CategoryEvent>().await? else {
+            return Err(Report::new(UnrecoverableError).attach_printable("Category was not created"));
+        };
+        
+        CategoryCommandService::execute(journal, &Actor::system(), category, CategoryCommand::AddProduct { id: product }).await
+            .change_context_lazy(|| UnrecoverableError)?;
+        
+        Ok(category)
+    }
+    
+    async fn menu_as_of(service: &HistoryQueryService, cutoff: MenuCutoff) -> Result<Menu, Report<UnrecoverableError>> {
+        service.get_menu_as_of(cutoff).await
+            .change_context_lazy(|| UnrecoverableError)?
+            .ok_or_else(|| Report::new(UnrecoverableError).attach_printable(format!("No menu for {cutoff:?}")))
+    }
+    
+    fn price_of(menu: &Menu, product: ProductId) -> Option<i64> {
+        menu.categories.iter()
+            .flat_map(|category| category.products.iter())
+            .find(|listed| listed.id == *product.as_ref())
+            .map(|listed| listed.price.amount)
+    }
+    
+    #[tokio::test]
+    async fn test_menu_as_of() -> Result<(), Report<UnrecoverableError>> {
+        let journal = Journal::new()?;
+        let service = journal.service();
+        
+        let before = OffsetDateTime::now_utc();
+        
+        let product = journal.register(500).await?;
+        list(&journal, product).await?;
+        
+        let displayed = OffsetDateTime::now_utc();
+        
+        journal.change_price(product, 450).await?;
+        
+        let menu = menu_as_of(&service, MenuCutoff::At(displayed)).await?;
+        if price_of(&menu, product) != Some(500) {
+            return Err(Report::new(UnrecoverableError).attach_printable(format!("Price must be the one displayed at the time: {menu:?}")));
+        }
+        
+        let menu = menu_as_of(&service, MenuCutoff::At(OffsetDateTime::now_utc())).await?;
+        if price_of(&menu, product) != Some(450) {
+            return Err(Report::new(UnrecoverableError).attach_printable(format!("Price must be the latest one: {menu:?}")));
+        }
+        
+        let menu = menu_as_of(&service, MenuCutoff::At(before)).await?;
+        if !menu.categories.is_empty() {
+            return Err(Report::new(UnrecoverableError).attach_printable(format!("Menu must be empty before anything was created: {menu:?}")));
+        }
+        
+        Ok(())
+    }
+    
+    #[tokio::test]
+    async fn test_menu_as_of_sequence() -> Result<(), Report<UnrecoverableError>> {
+        let journal = Journal::new()?;
+        let service = journal.service();
+        
+        let product = journal.register(500).await?;
+        list(&journal, product).await?;
+        
+        // Both changes may well be recorded within the same instant.
+        journal.change_price(product, 450).await?;
+        journal.change_price(product, 400).await?;
+        
+        let history = service.get_product_history(product.as_ref()).await
+            .change_context_lazy(|| UnrecoverableError)?;
+        let Some(sequence) = history.iter()
+            .find(|recorded| matches!(&recorded.event, ProductChange::ChangedProductPrice { new } if new.amount == 450))
+            .map(|recorded| recorded.sequence)
+        else {
+            return Err(Report::new(UnrecoverableError).attach_printable(format!("Unexpected history: {history:?}")));
+        };
+        
+        let menu = menu_as_of(&service, MenuCutoff::Sequence { id: *product.as_ref(), sequence }).await?;
+        if price_of(&menu, product) != Some(450) {
+            return Err(Report::new(UnrecoverableError).attach_printable(format!("Price must be the one after the given event: {menu:?}")));
+        }
+        
+        let unknown = MenuCutoff::Sequence { id: *product.as_ref(), sequence: sequence + 100 };
+        let menu = service.get_menu_as_of(unknown).await
+            .change_context_lazy(|| UnrecoverableError)?;
+        if menu.is_some() {
+            return Err(Report::new(UnrecoverableError).attach_printable(format!("Unknown sequence must have no menu: {menu:?}")));
+        }
+        
+        Ok(())
+    }
+    
+    #[tokio::test]
+    async fn test_menu_applies_schedules() -> Result<(), Report<UnrecoverableError>> {
+        let journal = Journal::new()?;
+        let service = journal.service();
+        
+        let product = journal.register(500).await?;
+        let category = list(&journal, product).await?;
+        
+        let unscheduled = OffsetDateTime::now_utc();
+        
+        // Offered all day, but only tomorrow.
+        let tomorrow = StoreOffset::default().now().weekday().next();
+        let window = TimeWindow::new([tomorrow], Time::MIDNIGHT, Time::from_hms(23, 59, 0).change_context_lazy(|| UnrecoverableError)?)
+            .change_context_lazy(|| UnrecoverableError)?;
+        let schedule = Schedule::new(vec![window])
+            .change_context_lazy(|| UnrecoverableError)?;
+        ProductCommandService::execute(&journal, &Actor::system(), product, ProductCommand::ChangeSchedule { new: schedule }).await
+            .change_context_lazy(|| UnrecoverableError)?;
+        
+        let menu = menu_as_of(&service, MenuCutoff::At(OffsetDateTime::now_utc())).await?;
+        if price_of(&menu, product).is_some() || !menu.categories.iter().any(|listed| listed.id == *category.as_ref()) {
+            return Err(Report::new(UnrecoverableError).attach_printable(format!("Only the product that was not offered must be left out: {menu:?}")));
+        }
+        
+        let menu = menu_as_of(&service, MenuCutoff::At(unscheduled)).await?;
+        if price_of(&menu, product).is_none() {
+            return Err(Report::new(UnrecoverableError).attach_printable(format!("Product must be listed before it was scheduled: {menu:?}")));
+        }
+        
+        Ok(())
+    }
+}
@@ -0,0 +1,563 @@
+use app_query::errors::QueryError;
+use app_query::models::{GetOpenOrdersQueryService, GetReceiptQueryService, OpenOrder, OpenOrderItem, OpenOrders, Receipt, ReceiptItem, ReceiptOption, ReceiptTax};
+use async_trait::async_trait;
+use error_stack::{Report, ResultExt};
+use kernel::entities::order::OrderStatus;
+use sqlx::types::Uuid;
+use sqlx::SqliteConnection;
+use std::collections::HashMap;
+
+use crate::errors::FailedQuery;
+
+#[derive(Clone)]
+pub struct OrderQueryService {
+    pool: sqlx::SqlitePool,
+}
+
+impl OrderQueryService {
+    pub fn new(pool: sqlx::SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl GetOpenOrdersQueryService for OrderQueryService {
+    async fn get_open_orders(&self) -> Result<OpenOrders, Report<QueryError>> {
+        let mut con = self.pool.acquire().await
+            .change_context_lazy(|| QueryError::Driver)?;
+        let open = InternalOrderQueryService::get_open_orders(&mut con).await
+            .change_context_lazy(|| QueryError::Driver)?;
+        Ok(open)
+    }
+    
+    async fn get_accepted_order(&self, order: &Uuid) -> Result<Option<OpenOrder>, Report<QueryError>> {
+        let mut con = self.pool.acquire().await
+            .change_context_lazy(|| QueryError::Driver)?;
+        let accepted = InternalOrderQueryService::get_accepted_order(&mut con, order).await
+            .change_context_lazy(|| QueryError::Driver)?;
+        Ok(accepted)
+    }
+}
+
+#[async_trait]
+impl GetReceiptQueryService for OrderQueryService {
+    async fn get_receipt(&self, order: &Uuid) -> Result<Option<Receipt>, Report<QueryError>> {
+        let mut con = self.pool.acquire().await
+            .change_context_lazy(|| QueryError::Driver)?;
+        let receipt = InternalOrderQueryService::get_receipt(&mut con, order).await
+            .change_context_lazy(|| QueryError::Driver)?;
+        Ok(receipt)
+    }
+}
+
+pub(crate) struct InternalOrderQueryService;
+
+impl InternalOrderQueryService {
+    pub async fn get_open_orders(con: &mut SqliteConnection) -> Result<OpenOrders, Report<FailedQuery>> {
+        // language=sqlite
+        let orders = sqlx::query_as::<_, OpenOrder>(r#"
+            SELECT
+                os.order_id AS id,
+                t.number,
+                os.status,
+                CAST(strftime('%s', 'now') - strftime('%s', os.accepted_at) AS INTEGER) AS elapsed
+            FROM
+                order_status os
+            LEFT JOIN
+                tickets t ON os.order_id = t.order_id
+            WHERE
+                os.status IN (?, ?, ?)
+            ORDER BY
+                os.accepted_at
+        "#)
+            .bind(OrderStatus::Accepted.as_ref())
+            .bind(OrderStatus::Preparing.as_ref())
+            .bind(OrderStatus::Ready.as_ref())
+            .fetch_all(&mut *con)
+            .await
+            .change_context_lazy(|| FailedQuery)?;
+        
+        // language=sqlite
+        let items = sqlx::query_as::<_, OpenOrderItem>(r#"
+            SELECT
+                oi.order_id,
+                oi.line,
+                oi.product,
+                p.name,
+                oi.quantity
+            FROM
+                order_items oi
+            JOIN
+                order_status os ON oi.order_id = os.order_id
+            LEFT JOIN
+                products p ON oi.product = p.id
+            WHERE
+                os.status IN (?, ?, ?)
+            ORDER BY
+                oi.order_id, oi.line
+        "#)
+            .bind(OrderStatus::Accepted.as_ref())
+            .bind(OrderStatus::Preparing.as_ref())
+            .bind(OrderStatus::Ready.as_ref())
+            .fetch_all(&mut *con)
+            .await
+            .change_context_lazy(|| FailedQuery)?;
+        
+        // language=sqlite
+        let options = sqlx::query_as::<_, (Uuid, i64, String)>(r#"
+            SELECT
+                oio.order_id,
+                oio.line,
+                oio.name
+            FROM
+                order_item_options oio
+            JOIN
+                order_status os ON oio.order_id = os.order_id
+            WHERE
+                os.status IN (?, ?, ?)
+            ORDER BY
+                oio.rowid
+        "#)
+            .bind(OrderStatus::Accepted.as_ref())
+            .bind(OrderStatus::Preparing.as_ref())
+            .bind(OrderStatus::Ready.as_ref())
+            .fetch_all(&mut *con)
+            .await
+            .change_context_lazy(|| FailedQuery)?;
+        
+        let mut options = options.into_iter()
+            .fold(HashMap::<(Uuid, i64), Vec<String>>::new(), |mut acc, (order_id, line, name)| {
+                acc.entry((order_id, line)).or_default().push(name);
+                acc
+            });
+        
+        // language=sqlite
+        let components = sqlx::query_as::<_, (Uuid, i64, Option<String>)>(r#"
+            SELECT
+                oic.order_id,
+                oic.line,
+                p.name
+            FROM
+                order_item_components oic
+            JOIN
+                order_status os ON oic.order_id = os.order_id
+            LEFT JOIN
+                products p ON oic.product = p.id
+            WHERE
+                os.status IN (?, ?, ?)
+            ORDER BY
+                oic.rowid
+        "#)
+            .bind(OrderStatus::Accepted.as_ref())
+            .bind(OrderStatus::Preparing.as_ref())
+            .bind(OrderStatus::Ready.as_ref())
+            .fetch_all(&mut *con)
+            .await
+            .change_context_lazy(|| FailedQuery)?;
+        
+        let mut components = components.into_iter()
+            .fold(HashMap::<(Uuid, i64), Vec<String>>::new(), |mut acc, (order_id, line, name)| {
+                acc.entry((order_id, line)).or_default().push(name.unwrap_or_default());
+                acc
+            });
+        
+        let mut items = items.into_iter()
+            .map(|mut item| {
+                item.options = options.remove(&(item.order_id, item.line)).unwrap_or_default();
+                item.components = components.remove(&(item.order_id, item.line)).unwrap_or_default();
+                item
+            })
+            .fold(HashMap::<Uuid, Vec<OpenOrderItem>>::new(), |mut acc, item| {
+                acc.entry(item.order_id).or_default().push(item);
+                acc
+            });
+        
+        let mut open = OpenOrders::default();
+        
+        for mut order in orders {
+            order.items = items.remove(&order.id).unwrap_or_default();
+            match order.status.as_str() {
+                s if s == OrderStatus::Accepted.as_ref() => open.accepted.push(order),
+                s if s == OrderStatus::Preparing.as_ref() => open.preparing.push(order),
+                _ => open.ready.push(order),
+            }
+        }
+        
+        Ok(open)
+    }
+    
+    pub async fn get_accepted_order(con: &mut SqliteConnection, order: &Uuid) -> Result<Option<OpenOrder>, Report<FailedQuery>> {
+        // language=sqlite
+        let accepted = sqlx::query_as::<_, OpenOrder>(r#"
+            SELECT
+                os.order_id AS id,
+                t.number,
+                os.status,
+                CAST(strftime('%s', 'now') - strftime('%s', os.accepted_at) AS INTEGER) AS elapsed
+            FROM
+                order_status os
+            LEFT JOIN
+                tickets t ON os.order_id = t.order_id
+            WHERE
+                os.order_id = ?
+                AND os.accepted_at IS NOT NULL
+        "#)
+            .bind(order)
+            .fetch_optional(&mut *con)
+            .await
+            .change_context_lazy(|| FailedQuery)?;
+        
+        let Some(mut accepted) = accepted else {
+            return Ok(None);
+        };
+        
+        // language=sqlite
+        let items = sqlx::query_as::<_, OpenOrderItem>(r#"
+            SELECT
+                oi.order_id,
+                oi.line,
+                oi.product,
+                p.name,
+                oi.quantity
+            FROM
+                order_items oi
+            LEFT JOIN
+                products p ON oi.product = p.id
+            WHERE
+                oi.order_id = ?
+            ORDER BY
+                oi.line
+        "#)
+            .bind(order)
+            .fetch_all(&mut *con)
+            .await
+            .change_context_lazy(|| FailedQuery)?;
+        
+        // language=sqlite
+        let options = sqlx::query_as::<_, (i64, String)>(r#"
+            SELECT
+                line,
+                name
+            FROM
+                order_item_options
+            WHERE
+                order_id = ?
+            ORDER BY
+                rowid
+        "#)
+            .bind(order)
+            .fetch_all(&mut *con)
+            .await
+            .change_context_lazy(|| FailedQuery)?;
+        
+        let mut options = options.into_iter()
+            .fold(HashMap::<i64, Vec<String>>::new(), |mut acc, (line, name)| {
+                acc.entry(line).or_default().push(name);
+                acc
+            });
+        
+        // language=sqlite
+        let components = sqlx::query_as::<_, (i64, Option<String>)>(r#"
+            SELECT
+                oic.line,
+                p.name
+            FROM
+                order_item_components oic
+            LEFT JOIN
+                products p ON oic.product = p.id
+            WHERE
+                oic.order_id = ?
+            ORDER BY
+                oic.rowid
+        "#)
+            .bind(order)
+            .fetch_all(&mut *con)
+            .await
+            .change_context_lazy(|| FailedQuery)?;
+        
+        let mut components = components.into_iter()
+            .fold(HashMap::<i64, Vec<String>>::new(), |mut acc, (line, name)| {
+                acc.entry(line).or_default().push(name.unwrap_or_default());
+                acc
+            });
+        
+        accepted.items = items.into_iter()
+            .map(|mut item| {
+                item.options = options.remove(&item.line).unwrap_or_default();
+                item.components = components.remove(&item.line).unwrap_or_default();
+                item
+            })
+            .collect();
+        
+        Ok(Some(accepted))
+    }
+    
+    pub async fn get_receipt(con: &mut SqliteConnection, order: &Uuid) -> Result<Option<Receipt>, Report<FailedQuery>> {
+        // language=sqlite
+        let receipt = sqlx::query_as::<_, Receipt>(r#"
+            SELECT
+                os.order_id,
+                t.number,
+                COALESCE((SELECT currency FROM order_items WHERE order_id = os.order_id LIMIT 1), 'JPY') AS currency,
+                oc.code AS coupon,
+                pay.amount AS total,
+                pay.tender,
+                pay.tendered,
+                pay.change,
+                pay.paid_at
+            FROM
+                order_status os
+            JOIN
+                payments pay ON os.order_id = pay.order_id
+            LEFT JOIN
+                tickets t ON os.order_id = t.order_id
+            LEFT JOIN
+                order_coupons oc ON os.order_id = oc.order_id
+            WHERE
+                os.order_id = ?
+        "#)
+            .bind(order)
+            .fetch_optional(&mut *con)
+            .await
+            .change_context_lazy(|| FailedQuery)?;
+        
+        let Some(mut receipt) = receipt else {
+            return Ok(None);
+        };
+        
+        // The unit price is recorded as charged by the order, so that the receipt never disagrees with the payment.
+        // language=sqlite
+        let items = sqlx::query_as::<_, ReceiptItem>(r#"
+            SELECT
+                oi.order_id,
+                oi.line,
+                p.name,
+                oi.quantity,
+                oi.unit_price,
+                oi.unit_price * oi.quantity AS amount,
+                oi.tax_rate
+            FROM
+                order_items oi
+            LEFT JOIN
+                products p ON oi.product = p.id
+            WHERE
+                oi.order_id = ?
+            ORDER BY
+                oi.line
+        "#)
+            .bind(order)
+            .fetch_all(&mut *con)
+            .await
+            .change_context_lazy(|| FailedQuery)?;
+        
+        // language=sqlite
+        let options = sqlx::query_as::<_, (i64, String, i64)>(r#"
+            SELECT
+                line,
+                name,
+                price_delta
+            FROM
+                order_item_options
+            WHERE
+                order_id = ?
+            ORDER BY
+                rowid
+        "#)
+            .bind(order)
+            .fetch_all(&mut *con)
+            .await
+            .change_context_lazy(|| FailedQuery)?;
+        
+        let mut options = options.into_iter()
+            .fold(HashMap::<i64, Vec<ReceiptOption>>::new(), |mut acc, (line, name, price_delta)| {
+                acc.entry(line).or_default().push(ReceiptOption { name, price_delta });
+                acc
+            });
+        
+        // language=sqlite
+        let components = sqlx::query_as::<_, (i64, Option<String>)>(r#"
+            SELECT
+                oic.line,
+                p.name
+            FROM
+                order_item_components oic
+            LEFT JOIN
+                products p ON oic.product = p.id
+            WHERE
+                oic.order_id = ?
+            ORDER BY
+                oic.rowid
+        "#)
+            .bind(order)
+            .fetch_all(&mut *con)
+            .await
+            .change_context_lazy(|| FailedQuery)?;
+        
+        let mut components = components.into_iter()
+            .fold(HashMap::<i64, Vec<String>>::new(), |mut acc, (line, name)| {
+                acc.entry(line).or_default().push(name.unwrap_or_default());
+                acc
+            });
+        
+        receipt.items = items.into_iter()
+            .map(|mut item| {
+                item.options = options.remove(&item.line).unwrap_or_default();
+                item.components = components.remove(&item.line).unwrap_or_default();
+                item
+            })
+            .collect();
+        
+        // language=sqlite
+        receipt.taxes = sqlx::query_as::<_, ReceiptTax>(r#"
+            SELECT
+                rate,
+                gross,
+                tax
+            FROM
+                order_taxes
+            WHERE
+                order_id = ?
+            ORDER BY
+                rate
+        "#)
+            .bind(order)
+            .fetch_all(&mut *con)
+            .await
+            .change_context_lazy(|| FailedQuery)?;
+        
+        receipt.subtotal = receipt.items.iter().map(|item| item.amount).sum();
+        receipt.discount = receipt.subtotal - receipt.total;
+        
+        Ok(Some(receipt))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use error_stack::{Report, ResultExt};
+    use kernel::entities::order::{OrderId, OrderStatus, Tender};
+    use kernel::entities::product::ProductId;
+    
+    use super::*;
+    use crate::database;
+    use crate::database::order::test::{accept_order, add_line, change_status, pay_order, place_order};
+    use crate::errors::test::UnrecoverableError;
+    
+    #[tokio::test]
+    async fn test_get_open_orders() -> Result<(), Report<UnrecoverableError>> {
+        let pool = database::init("sqlite:../query.db").await
+            .change_context_lazy(|| UnrecoverableError)?;
+        let mut con = pool.begin().await
+            .change_context_lazy(|| UnrecoverableError)?;
+        
+        let preparing = OrderId::default();
+        place_order(preparing, &mut con).await?;
+        add_line(preparing, 0, ProductId::default(), &mut con).await?;
+        add_line(preparing, 1, ProductId::default(), &mut con).await?;
+        accept_order(preparing, &mut con).await?;
+        change_status(preparing, OrderStatus::Preparing, &mut con).await?;
+        
+        let unconfirmed = OrderId::default();
+        place_order(unconfirmed, &mut con).await?;
+        add_line(unconfirmed, 0, ProductId::default(), &mut con).await?;
+        
+        let open = InternalOrderQueryService::get_open_orders(&mut con).await
+            .change_context_lazy(|| UnrecoverableError)?;
+        
+        let Some(order) = open.preparing.iter().find(|order| &order.id == preparing.as_ref()) else {
+            return Err(Report::new(UnrecoverableError).attach_printable("Preparing order must be listed"));
+        };
+        
+        if order.items.len() != 2 {
+            return Err(Report::new(UnrecoverableError).attach_printable("Order must contain its line items"));
+        }
+        
+        if open.accepted.iter()
+            .chain(open.preparing.iter())
+            .chain(open.ready.iter())
+            .any(|order| &order.id == unconfirmed.as_ref()) 
+        {
+            return Err(Report::new(UnrecoverableError).attach_printable("Unconfirmed order must not be listed"));
+        }
+        
+        con.rollback().await
+            .change_context_lazy(|| UnrecoverableError)?;
+        Ok(())
+    }
+    
+    #[tokio::test]
+    async fn test_get_receipt() -> Result<(), Report<UnrecoverableError>> {
+        let pool = database::init("sqlite:../query.db").await
+            .change_context_lazy(|| UnrecoverableError)?;
+        let mut con = pool.begin().await
+            .change_context_lazy(|| UnrecoverableError)?;
+        
+        let id = OrderId::default();
+        place_order(id, &mut con).await?;
+        add_line(id, 0, ProductId::default(), &mut con).await?;
+        add_line(id, 1, ProductId::default(), &mut con).await?;
+        
+        let unpaid = InternalOrderQueryService::get_receipt(&mut con, id.as_ref()).await
+            .change_context_lazy(|| UnrecoverableError)?;
+        
+        if unpaid.is_some() {
+            return Err(Report::new(UnrecoverableError).attach_printable("Unpaid order must not have a receipt"));
+        }
+        
+        pay_order(id, Tender::Cash, 200, 500, &mut con).await?;
+        
+        let Some(receipt) = InternalOrderQueryService::get_receipt(&mut con, id.as_ref()).await
+            .change_context_lazy(|| UnrecoverableError)? 
+        else {
+            return Err(Report::new(UnrecoverableError).attach_printable("Paid order must have a receipt"));
+        };
+        
+        if receipt.items.len() != 2 || receipt.items.iter().any(|item| item.unit_price != 100) {
+            return Err(Report::new(UnrecoverableError).attach_printable("Receipt must contain the charged line items"));
+        }
+        
+        if receipt.subtotal != 200 || receipt.discount != 0 || receipt.change != 300 || receipt.currency != "JPY" {
+            return Err(Report::new(UnrecoverableError).attach_printable("Receipt totals do not match the payment"));
+        }
+        
+        con.rollback().await
+            .change_context_lazy(|| UnrecoverableError)?;
+        Ok(())
+    }
+    
+    #[tokio::test]
+    async fn test_get_accepted_order() -> Result<(), Report<UnrecoverableError>> {
+        let pool = database::init("sqlite:../query.db").await
+            .change_context_lazy(|| UnrecoverableError)?;
+        let mut con = pool.begin().await
+            .change_context_lazy(|| UnrecoverableError)?;
+        
+        let id = OrderId::default();
+        place_order(id, &mut con).await?;
+        add_line(id, 0, ProductId::default(), &mut con).await?;
+        
+        let placed = InternalOrderQueryService::get_accepted_order(&mut con, id.as_ref()).await
+            .change_context_lazy(|| UnrecoverableError)?;
+        
+        if placed.is_some() {
+            return Err(Report::new(UnrecoverableError).attach_printable("Order must not be listed before it is accepted"));
+        }
+        
+        accept_order(id, &mut con).await?;
+        change_status(id, OrderStatus::Completed, &mut con).await?;
+        
+        let Some(accepted) = InternalOrderQueryService::get_accepted_order(&mut con, id.as_ref()).await
+            .change_context_lazy(|| UnrecoverableError)? 
+        else {
+            return Err(Report::new(UnrecoverableError).attach_printable("Accepted order must be found in any status"));
+        };
+        
+        if accepted.items.len() != 1 || accepted.status != OrderStatus::Completed.as_ref() {
+            return Err(Report::new(UnrecoverableError).attach_printable("Accepted order does not match"));
+        }
+        
+        con.rollback().await
+            .change_context_lazy(|| UnrecoverableError)?;
+        Ok(())
+    }
+}
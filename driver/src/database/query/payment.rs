@@ -0,0 +1,171 @@
+use app_query::errors::QueryError;
+use std::collections::BTreeMap;
+
+use app_query::models::{GetPaymentQueryService, PaymentRecord, PaymentSummary, PaymentTotal, TenderSummary};
+use async_trait::async_trait;
+use error_stack::{Report, ResultExt};
+use kernel::entities::order::{OrderStatus, Tender};
+use kernel::entities::ticket::StoreOffset;
+use sqlx::types::time::Date;
+use sqlx::SqliteConnection;
+
+use crate::errors::FailedQuery;
+
+#[derive(Clone)]
+pub struct PaymentQueryService {
+    pool: sqlx::SqlitePool,
+    offset: StoreOffset,
+}
+
+impl PaymentQueryService {
+    pub fn new(pool: sqlx::SqlitePool, offset: StoreOffset) -> Self {
+        Self { pool, offset }
+    }
+}
+
+#[async_trait]
+impl GetPaymentQueryService for PaymentQueryService {
+    async fn get_payments(&self, day: Option<Date>) -> Result<Vec<PaymentRecord>, Report<QueryError>> {
+        let mut con = self.pool.acquire().await
+            .change_context_lazy(|| QueryError::Driver)?;
+        let payments = InternalPaymentQueryService::get_payments(&mut con, day, &self.offset).await
+            .change_context_lazy(|| QueryError::Driver)?;
+        Ok(payments)
+    }
+    
+    async fn get_payment_summary(&self, day: Option<Date>) -> Result<PaymentSummary, Report<QueryError>> {
+        let mut con = self.pool.acquire().await
+            .change_context_lazy(|| QueryError::Driver)?;
+        let summary = InternalPaymentQueryService::get_payment_summary(&mut con, day, &self.offset).await
+            .change_context_lazy(|| QueryError::Driver)?;
+        Ok(summary)
+    }
+}
+
+pub(crate) struct InternalPaymentQueryService;
+
+impl InternalPaymentQueryService {
+    pub async fn get_payments(con: &mut SqliteConnection, day: Option<Date>, offset: &StoreOffset) -> Result<Vec<PaymentRecord>, Report<FailedQuery>> {
+        // language=sqlite
+        let payments = sqlx::query_as::<_, PaymentRecord>(r#"
+            SELECT
+                p.order_id,
+                p.tender,
+                p.amount,
+                p.tendered,
+                p.change,
+                p.currency,
+                p.paid_at
+            FROM
+                payments p
+            JOIN
+                order_status os ON p.order_id = os.order_id
+            WHERE
+                os.status != ?
+                AND date(p.paid_at, ?) = ?
+            ORDER BY
+                p.paid_at
+        "#)
+            .bind(OrderStatus::Cancelled.as_ref())
+            .bind(offset.sqlite_modifier())
+            .bind(day.unwrap_or_else(|| offset.today().into()))
+            .fetch_all(&mut *con)
+            .await
+            .change_context_lazy(|| FailedQuery)?;
+        
+        Ok(payments)
+    }
+    
+    pub async fn get_payment_summary(con: &mut SqliteConnection, day: Option<Date>, offset: &StoreOffset) -> Result<PaymentSummary, Report<FailedQuery>> {
+        let business_day = day.unwrap_or_else(|| offset.today().into());
+        
+        // language=sqlite
+        let tenders = sqlx::query_as::<_, TenderSummary>(r#"
+            SELECT
+                p.tender,
+                p.currency,
+                COUNT(*) AS count,
+                SUM(p.amount) AS amount,
+                SUM(p.tendered) AS tendered,
+                SUM(p.change) AS change
+            FROM
+                payments p
+            JOIN
+                order_status os ON p.order_id = os.order_id
+            WHERE
+                os.status != ?
+                AND date(p.paid_at, ?) = ?
+            GROUP BY
+                p.tender, p.currency
+            ORDER BY
+                p.currency, p.tender
+        "#)
+            .bind(OrderStatus::Cancelled.as_ref())
+            .bind(offset.sqlite_modifier())
+            .bind(business_day)
+            .fetch_all(&mut *con)
+            .await
+            .change_context_lazy(|| FailedQuery)?;
+        
+        let totals = tenders.iter()
+            .fold(BTreeMap::<&str, PaymentTotal>::new(), |mut acc, summary| {
+                let total = acc.entry(&summary.currency)
+                    .or_insert_with(|| PaymentTotal { currency: summary.currency.clone(), total: 0, cash_in_drawer: 0 });
+                total.total += summary.amount;
+                if summary.tender == Tender::Cash.as_ref() {
+                    total.cash_in_drawer += summary.tendered - summary.change;
+                }
+                acc
+            })
+            .into_values()
+            .collect();
+        
+        Ok(PaymentSummary { business_day, totals, tenders })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use error_stack::{Report, ResultExt};
+    use kernel::entities::money::Currency;
+    use kernel::entities::order::{OrderId, Tender};
+    use kernel::entities::product::ProductId;
+    
+    use super::*;
+    use crate::database;
+    use crate::database::order::test::{accept_order, add_line, pay_order, place_order};
+    use crate::errors::test::UnrecoverableError;
+    
+    #[tokio::test]
+    async fn test_get_payment_summary() -> Result<(), Report<UnrecoverableError>> {
+        let pool = database::init("sqlite:../query.db").await
+            .change_context_lazy(|| UnrecoverableError)?;
+        let mut con = pool.begin().await
+            .change_context_lazy(|| UnrecoverableError)?;
+        
+        let before = InternalPaymentQueryService::get_payment_summary(&mut con, None, &StoreOffset::default()).await
+            .change_context_lazy(|| UnrecoverableError)?;
+        
+        let order_id = OrderId::default();
+        place_order(order_id, &mut con).await?;
+        add_line(order_id, 0, ProductId::default(), &mut con).await?;
+        pay_order(order_id, Tender::Cash, 100, 1000, &mut con).await?;
+        accept_order(order_id, &mut con).await?;
+        
+        let after = InternalPaymentQueryService::get_payment_summary(&mut con, None, &StoreOffset::default()).await
+            .change_context_lazy(|| UnrecoverableError)?;
+        
+        let cash_in_drawer = |summary: &PaymentSummary| summary.totals.iter()
+            .filter(|total| total.currency == Currency::default().as_ref())
+            .map(|total| total.cash_in_drawer)
+            .sum::<i64>();
+        
+        if cash_in_drawer(&after) - cash_in_drawer(&before) != 100 {
+            return Err(Report::new(UnrecoverableError).attach_printable("Change must not be counted as cash in drawer"));
+        }
+        
+        con.rollback().await
+            .change_context_lazy(|| UnrecoverableError)?;
+        Ok(())
+    }
+}
@@ -1,18 +1,23 @@
 use app_query::errors::QueryError;
-use app_query::models::{AllProduct, GetAllProductQueryService, GetProductImageQueryService, GetProductQueryService, OrderedProduct, OrderedProducts, Product, ProductDetails};
+use app_query::models::{AllProduct, GetAllProductQueryService, GetProductImageQueryService, GetProductQueryService, OrderedProduct, OrderedProducts, Product, ProductBundleChoice, ProductBundleSlot, ProductDetails, ProductOption, ProductOptionGroup};
 use async_trait::async_trait;
 use error_stack::{Report, ResultExt};
 use sqlx::types::Uuid;
+use sqlx::types::time::PrimitiveDateTime;
 use std::collections::BTreeSet;
+use kernel::entities::product::{TaxCategory, TaxRates};
+
+use crate::database::schedule;
 
 #[derive(Clone)]
 pub struct ProductQueryService {
     pool: sqlx::SqlitePool,
+    rates: TaxRates,
 }
 
 impl ProductQueryService {
-    pub fn new(pool: sqlx::SqlitePool) -> Self {
-        Self { pool }
+    pub fn new(pool: sqlx::SqlitePool, rates: TaxRates) -> Self {
+        Self { pool, rates }
     }
 }
 
@@ -26,10 +31,10 @@ impl GetAllProductQueryService for ProductQueryService {
         Ok(all)
     }
     
-    async fn get_all_product_by_category(&self, category: &Uuid) -> Result<OrderedProducts, Report<QueryError>> {
+    async fn get_all_product_by_category(&self, category: &Uuid, at: Option<PrimitiveDateTime>) -> Result<OrderedProducts, Report<QueryError>> {
         let mut con = self.pool.acquire().await
             .change_context_lazy(|| QueryError::Driver)?;
-        let all = InternalProductQueryService::get_all_product_by_category(&mut con, category).await
+        let all = InternalProductQueryService::get_all_product_by_category(&mut con, category, at).await
             .change_context_lazy(|| QueryError::Driver)?;
         Ok(all)
     }
@@ -40,7 +45,7 @@ impl GetProductQueryService for ProductQueryService {
     async fn get_product_details(&self, product: &Uuid) -> Result<ProductDetails, Report<QueryError>> {
         let mut con = self.pool.acquire().await
             .change_context_lazy(|| QueryError::Driver)?;
-        let details = InternalProductQueryService::get_product_details(&mut con, product).await
+        let details = InternalProductQueryService::get_product_details(&mut con, product, &self.rates).await
             .change_context_lazy(|| QueryError::Driver)?;
         Ok(details)
     }
@@ -66,7 +71,9 @@ impl InternalProductQueryService {
             SELECT 
                 id, 
                 name, 
-                price
+                price AS amount,
+                currency,
+                available
             FROM
                 products
         "#)
@@ -77,22 +84,45 @@ impl InternalProductQueryService {
         Ok(AllProduct(all.into_iter().collect()))
     }
     
-    pub async fn get_all_product_by_category(con: &mut sqlx::SqliteConnection, category: &Uuid) -> Result<OrderedProducts, Report<QueryError>> {
+    pub async fn get_all_product_by_category(con: &mut sqlx::SqliteConnection, category: &Uuid, at: Option<PrimitiveDateTime>) -> Result<OrderedProducts, Report<QueryError>> {
+        let (weekday, seconds) = schedule::split(at);
+        
         // language=sqlite
         let all = sqlx::query_as::<_, OrderedProduct>(r#"
             SELECT 
                 cpo.ordering,
                 p.id, 
                 p.name, 
-                p.price
+                p.price AS amount,
+                p.currency,
+                p.available,
+                p.stock
             FROM
                 products p
             JOIN
                 category_products_ordering cpo ON p.id = cpo.product
             WHERE
-                cpo.category = ?
+                cpo.category = ?1
+                AND (
+                    ?2 IS NULL
+                    OR NOT EXISTS (SELECT 1 FROM product_schedules ps WHERE ps.product = p.id)
+                    OR EXISTS (
+                        SELECT 1 FROM product_schedules ps 
+                        WHERE ps.product = p.id AND ps.weekday = ?2 AND ps.start <= ?3 AND ?3 < ps.end
+                    )
+                )
+                AND (
+                    ?2 IS NULL
+                    OR NOT EXISTS (SELECT 1 FROM category_schedules cs WHERE cs.category = ?1)
+                    OR EXISTS (
+                        SELECT 1 FROM category_schedules cs 
+                        WHERE cs.category = ?1 AND cs.weekday = ?2 AND cs.start <= ?3 AND ?3 < cs.end
+                    )
+                )
         "#)
             .bind(category)
+            .bind(weekday)
+            .bind(seconds)
             .fetch_all(&mut *con)
             .await
             .change_context_lazy(|| QueryError::Driver)?;
@@ -102,14 +132,18 @@ impl InternalProductQueryService {
         Ok(OrderedProducts(all))
     }
     
-    pub async fn get_product_details(con: &mut sqlx::SqliteConnection, product: &Uuid) -> Result<ProductDetails, Report<QueryError>> {
+    pub async fn get_product_details(con: &mut sqlx::SqliteConnection, product: &Uuid, rates: &TaxRates) -> Result<ProductDetails, Report<QueryError>> {
         // language=sqlite
-        let details = sqlx::query_as::<_, ProductDetails>(r#"
+        let mut details = sqlx::query_as::<_, ProductDetails>(r#"
             SELECT 
                 id, 
                 name, 
                 desc, 
-                price
+                price AS amount,
+                currency,
+                available,
+                tax_category AS tax,
+                tax_inclusive
             FROM
                 products
             WHERE
@@ -120,6 +154,119 @@ impl InternalProductQueryService {
             .await
             .change_context_lazy(|| QueryError::Driver)?;
         
+        let category = match details.tax.as_str() {
+            "reduced" => TaxCategory::Reduced,
+            _ => TaxCategory::Standard,
+        };
+        let rate = rates.rate(&category);
+        details.tax_rate = rate.into();
+        let price = details.price.amount;
+        if details.tax_inclusive {
+            details.price_including_tax = details.price.clone();
+            details.price_excluding_tax = details.price.with_amount(price - rate.tax_within(price));
+        } else {
+            details.price_including_tax = details.price.with_amount(price + rate.tax_on(price));
+            details.price_excluding_tax = details.price.clone();
+        }
+        
+        // language=sqlite
+        let mut groups = sqlx::query_as::<_, ProductOptionGroup>(r#"
+            SELECT 
+                id, 
+                name, 
+                min, 
+                max
+            FROM
+                product_option_groups
+            WHERE
+                product = ?
+            ORDER BY
+                rowid
+        "#)
+            .bind(product)
+            .fetch_all(&mut *con)
+            .await
+            .change_context_lazy(|| QueryError::Driver)?;
+        
+        // language=sqlite
+        let options = sqlx::query_as::<_, ProductOption>(r#"
+            SELECT 
+                po.option_group,
+                po.id, 
+                po.name, 
+                po.price_delta
+            FROM
+                product_options po
+            JOIN
+                product_option_groups pog ON po.option_group = pog.id
+            WHERE
+                pog.product = ?
+            ORDER BY
+                po.rowid
+        "#)
+            .bind(product)
+            .fetch_all(&mut *con)
+            .await
+            .change_context_lazy(|| QueryError::Driver)?;
+        
+        for option in options {
+            if let Some(group) = groups.iter_mut().find(|group| group.id == option.option_group) {
+                group.options.push(option);
+            }
+        }
+        
+        details.options = groups;
+        
+        // language=sqlite
+        let mut slots = sqlx::query_as::<_, ProductBundleSlot>(r#"
+            SELECT 
+                id, 
+                name
+            FROM
+                product_bundle_slots
+            WHERE
+                product = ?
+            ORDER BY
+                rowid
+        "#)
+            .bind(product)
+            .fetch_all(&mut *con)
+            .await
+            .change_context_lazy(|| QueryError::Driver)?;
+        
+        // language=sqlite
+        let choices = sqlx::query_as::<_, ProductBundleChoice>(r#"
+            SELECT 
+                pbc.slot,
+                p.id, 
+                p.name, 
+                p.price AS amount,
+                p.currency,
+                p.available
+            FROM
+                product_bundle_choices pbc
+            JOIN
+                product_bundle_slots pbs ON pbc.slot = pbs.id
+            JOIN
+                products p ON pbc.product = p.id
+            WHERE
+                pbs.product = ?
+            ORDER BY
+                pbc.rowid
+        "#)
+            .bind(product)
+            .fetch_all(&mut *con)
+            .await
+            .change_context_lazy(|| QueryError::Driver)?;
+        
+        for choice in choices {
+            if let Some(slot) = slots.iter_mut().find(|slot| slot.id == choice.slot) {
+                slot.choices.push(choice);
+            }
+        }
+        
+        details.bundle = slots;
+        
         Ok(details)
     }
     
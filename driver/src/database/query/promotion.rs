@@ -0,0 +1,72 @@
+use app_query::errors::QueryError;
+use app_query::models::{GetPromotionQueryService, PromotionRecord};
+use async_trait::async_trait;
+use error_stack::{Report, ResultExt};
+use sqlx::types::Uuid;
+use sqlx::SqliteConnection;
+
+use crate::errors::FailedQuery;
+
+#[derive(Clone)]
+pub struct PromotionQueryService {
+    pool: sqlx::SqlitePool,
+}
+
+impl PromotionQueryService {
+    pub fn new(pool: sqlx::SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl GetPromotionQueryService for PromotionQueryService {
+    async fn get_all_promotions(&self) -> Result<Vec<PromotionRecord>, Report<QueryError>> {
+        let mut con = self.pool.acquire().await
+            .change_context_lazy(|| QueryError::Driver)?;
+        let promotions = InternalPromotionQueryService::get_all_promotions(&mut con).await
+            .change_context_lazy(|| QueryError::Driver)?;
+        Ok(promotions)
+    }
+}
+
+pub(crate) struct InternalPromotionQueryService;
+
+impl InternalPromotionQueryService {
+    pub async fn get_all_promotions(con: &mut SqliteConnection) -> Result<Vec<PromotionRecord>, Report<FailedQuery>> {
+        // language=sqlite
+        let mut promotions = sqlx::query_as::<_, PromotionRecord>(r#"
+            SELECT
+                p.code,
+                p.name,
+                p.discount,
+                p.value,
+                p.currency,
+                p.scope,
+                p.starts_at,
+                p.ends_at,
+                p.cap,
+                (SELECT COUNT(*) FROM promotion_redemptions pr WHERE pr.code = p.code) AS redeemed,
+                p.ended
+            FROM
+                promotions p
+            ORDER BY
+                p.starts_at DESC, p.code
+        "#)
+            .fetch_all(&mut *con)
+            .await
+            .change_context_lazy(|| FailedQuery)?;
+        
+        for promotion in promotions.iter_mut() {
+            // language=sqlite
+            promotion.targets = sqlx::query_scalar::<_, Uuid>(r#"
+                SELECT target FROM promotion_targets WHERE code = ?
+            "#)
+                .bind(&promotion.code)
+                .fetch_all(&mut *con)
+                .await
+                .change_context_lazy(|| FailedQuery)?;
+        }
+        
+        Ok(promotions)
+    }
+}
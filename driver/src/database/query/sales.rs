@@ -0,0 +1,252 @@
+use app_query::errors::QueryError;
+use app_query::models::{CategorySales, HourlySales, ProductSales, SalesReport, SalesReportQueryService, SalesTotal};
+use async_trait::async_trait;
+use error_stack::{Report, ResultExt};
+use kernel::entities::ticket::StoreOffset;
+use sqlx::types::time::Date;
+use sqlx::SqliteConnection;
+
+use crate::errors::FailedQuery;
+
+#[derive(Clone)]
+pub struct SalesQueryService {
+    pool: sqlx::SqlitePool,
+    offset: StoreOffset,
+}
+
+impl SalesQueryService {
+    pub fn new(pool: sqlx::SqlitePool, offset: StoreOffset) -> Self {
+        Self { pool, offset }
+    }
+}
+
+#[async_trait]
+impl SalesReportQueryService for SalesQueryService {
+    async fn get_sales_report(&self, from: Option<Date>, to: Option<Date>) -> Result<SalesReport, Report<QueryError>> {
+        let mut con = self.pool.acquire().await
+            .change_context_lazy(|| QueryError::Driver)?;
+        let report = InternalSalesQueryService::get_sales_report(&mut con, from, to, &self.offset).await
+            .change_context_lazy(|| QueryError::Driver)?;
+        Ok(report)
+    }
+}
+
+pub(crate) struct InternalSalesQueryService;
+
+impl InternalSalesQueryService {
+    pub async fn get_sales_report(con: &mut SqliteConnection, from: Option<Date>, to: Option<Date>, offset: &StoreOffset) -> Result<SalesReport, Report<FailedQuery>> {
+        let today = Date::from(offset.today());
+        let from = from.unwrap_or(today);
+        let to = to.unwrap_or(today);
+        
+        // Sales are recorded in UTC, so they are shifted into the business day of the store.
+        let offset = offset.sqlite_modifier();
+        
+        // language=sqlite
+        let totals = sqlx::query_as::<_, SalesTotal>(r#"
+            SELECT
+                currency,
+                COUNT(DISTINCT order_id) AS orders,
+                SUM(quantity) AS quantity,
+                SUM(amount) AS amount
+            FROM
+                sales
+            WHERE
+                date(sold_at, ?) BETWEEN ? AND ?
+            GROUP BY
+                currency
+            ORDER BY
+                currency
+        "#)
+            .bind(&offset)
+            .bind(from)
+            .bind(to)
+            .fetch_all(&mut *con)
+            .await
+            .change_context_lazy(|| FailedQuery)?;
+        
+        // language=sqlite
+        let products = sqlx::query_as::<_, ProductSales>(r#"
+            SELECT
+                s.product,
+                (
+                    SELECT l.name FROM sales l 
+                    WHERE l.product = s.product AND date(l.sold_at, ?) BETWEEN ? AND ? 
+                    ORDER BY l.sold_at DESC LIMIT 1
+                ) AS name,
+                s.currency,
+                SUM(s.quantity) AS quantity,
+                SUM(s.amount) AS amount
+            FROM
+                sales s
+            WHERE
+                date(s.sold_at, ?) BETWEEN ? AND ?
+            GROUP BY
+                s.product, s.currency
+            ORDER BY
+                amount DESC
+        "#)
+            .bind(&offset)
+            .bind(from)
+            .bind(to)
+            .bind(&offset)
+            .bind(from)
+            .bind(to)
+            .fetch_all(&mut *con)
+            .await
+            .change_context_lazy(|| FailedQuery)?;
+        
+        // language=sqlite
+        let categories = sqlx::query_as::<_, CategorySales>(r#"
+            SELECT
+                s.category,
+                (
+                    SELECT l.category_name FROM sales l 
+                    WHERE l.category = s.category AND date(l.sold_at, ?) BETWEEN ? AND ? 
+                    ORDER BY l.sold_at DESC LIMIT 1
+                ) AS name,
+                s.currency,
+                SUM(s.quantity) AS quantity,
+                SUM(s.amount) AS amount
+            FROM
+                sales s
+            WHERE
+                s.category IS NOT NULL AND date(s.sold_at, ?) BETWEEN ? AND ?
+            GROUP BY
+                s.category, s.currency
+            ORDER BY
+                amount DESC
+        "#)
+            .bind(&offset)
+            .bind(from)
+            .bind(to)
+            .bind(&offset)
+            .bind(from)
+            .bind(to)
+            .fetch_all(&mut *con)
+            .await
+            .change_context_lazy(|| FailedQuery)?;
+        
+        // language=sqlite
+        let hours = sqlx::query_as::<_, HourlySales>(r#"
+            SELECT
+                CAST(strftime('%H', sold_at, ?) AS INTEGER) AS hour,
+                currency,
+                COUNT(DISTINCT order_id) AS orders,
+                SUM(quantity) AS quantity,
+                SUM(amount) AS amount
+            FROM
+                sales
+            WHERE
+                date(sold_at, ?) BETWEEN ? AND ?
+            GROUP BY
+                hour, currency
+            ORDER BY
+                hour, currency
+        "#)
+            .bind(&offset)
+            .bind(&offset)
+            .bind(from)
+            .bind(to)
+            .fetch_all(&mut *con)
+            .await
+            .change_context_lazy(|| FailedQuery)?;
+        
+        Ok(SalesReport {
+            from,
+            to,
+            totals,
+            products,
+            categories,
+            hours,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::BTreeMap;
+    use error_stack::{Report, ResultExt};
+    use kernel::entities::category::{CategoryId, CategoryName};
+    use kernel::entities::order::OrderId;
+    use kernel::entities::product::ProductId;
+    use kernel::io::events::{CategoriesEvent, CategoryEvent};
+    
+    use super::*;
+    use crate::database;
+    use crate::database::InternalCategoryQueryModelService;
+    use crate::database::product::test::register_product;
+    use crate::database::sales::test::record_sale;
+    use crate::errors::test::UnrecoverableError;
+    
+    #[tokio::test]
+    async fn test_get_sales_report() -> Result<(), Report<UnrecoverableError>> {
+        let pool = database::init("sqlite:../query.db").await
+            .change_context_lazy(|| UnrecoverableError)?;
+        let mut con = pool.begin().await
+            .change_context_lazy(|| UnrecoverableError)?;
+        
+        let product = ProductId::default();
+        
+        record_sale(OrderId::default(), product, 2, &mut con).await?;
+        record_sale(OrderId::default(), product, 1, &mut con).await?;
+        
+        let report = InternalSalesQueryService::get_sales_report(&mut con, None, None, &StoreOffset::default()).await
+            .change_context_lazy(|| UnrecoverableError)?;
+        
+        let Some(sales) = report.products.iter().find(|sales| &sales.product == product.as_ref()) else {
+            return Err(Report::new(UnrecoverableError).attach_printable("Sold product must be reported"));
+        };
+        
+        if sales.quantity != 3 || sales.amount != 300 {
+            return Err(Report::new(UnrecoverableError).attach_printable("Sales of a product must be summed up"));
+        }
+        
+        con.rollback().await
+            .change_context_lazy(|| UnrecoverableError)?;
+        Ok(())
+    }
+    
+    #[tokio::test]
+    async fn test_sales_keep_category_of_the_time() -> Result<(), Report<UnrecoverableError>> {
+        let pool = database::init("sqlite:../query.db").await
+            .change_context_lazy(|| UnrecoverableError)?;
+        let mut con = pool.begin().await
+            .change_context_lazy(|| UnrecoverableError)?;
+        
+        let product = ProductId::default();
+        let category = CategoryId::default();
+        
+        register_product(product, &mut con).await?;
+        
+        let name = CategoryName::new("Drinks")
+            .change_context_lazy(|| UnrecoverableError)?;
+        InternalCategoryQueryModelService::create_category(CategoryEvent::Created { id: category, name }, &mut con).await
+            .change_context_lazy(|| UnrecoverableError)?;
+        InternalCategoryQueryModelService::register_category(CategoriesEvent::AddedCategory { id: category, ordering: i64::MAX }, &mut con).await
+            .change_context_lazy(|| UnrecoverableError)?;
+        InternalCategoryQueryModelService::add_product(CategoryEvent::AddedProduct { id: product, category, ordering: 0 }, &mut con).await
+            .change_context_lazy(|| UnrecoverableError)?;
+        
+        record_sale(OrderId::default(), product, 2, &mut con).await?;
+        
+        // Taken off the menu after it was sold.
+        InternalCategoryQueryModelService::invalidate_ordering_product(CategoryEvent::RemovedProduct { category, new: BTreeMap::new() }, &mut con).await
+            .change_context_lazy(|| UnrecoverableError)?;
+        
+        let report = InternalSalesQueryService::get_sales_report(&mut con, None, None, &StoreOffset::default()).await
+            .change_context_lazy(|| UnrecoverableError)?;
+        
+        let Some(sales) = report.categories.iter().find(|sales| &sales.category == category.as_ref()) else {
+            return Err(Report::new(UnrecoverableError).attach_printable("Sales must stay in the category the product was listed in"));
+        };
+        
+        if sales.name != "Drinks" || sales.quantity != 2 {
+            return Err(Report::new(UnrecoverableError).attach_printable("Sales of a category must be summed up"));
+        }
+        
+        con.rollback().await
+            .change_context_lazy(|| UnrecoverableError)?;
+        Ok(())
+    }
+}
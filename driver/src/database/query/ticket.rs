@@ -0,0 +1,87 @@
+use app_query::errors::QueryError;
+use app_query::models::{GetTicketQueryService, IssuedTicket, TicketNumbers};
+use async_trait::async_trait;
+use error_stack::{Report, ResultExt};
+use kernel::entities::ticket::{StoreOffset, TicketCounter};
+use sqlx::types::Uuid;
+use sqlx::SqliteConnection;
+
+#[derive(Clone)]
+pub struct TicketQueryService {
+    pool: sqlx::SqlitePool,
+    offset: StoreOffset,
+}
+
+impl TicketQueryService {
+    pub fn new(pool: sqlx::SqlitePool, offset: StoreOffset) -> Self {
+        Self { pool, offset }
+    }
+}
+
+#[async_trait]
+impl GetTicketQueryService for TicketQueryService {
+    async fn get_ticket_numbers(&self) -> Result<TicketNumbers, Report<QueryError>> {
+        let mut con = self.pool.acquire().await
+            .change_context_lazy(|| QueryError::Driver)?;
+        let numbers = InternalTicketQueryService::get_ticket_numbers(&mut con, &self.offset).await?;
+        Ok(numbers)
+    }
+    
+    async fn get_ticket_by_order(&self, order: &Uuid) -> Result<IssuedTicket, Report<QueryError>> {
+        let mut con = self.pool.acquire().await
+            .change_context_lazy(|| QueryError::Driver)?;
+        let ticket = InternalTicketQueryService::get_ticket_by_order(&mut con, order).await?;
+        Ok(ticket)
+    }
+}
+
+pub(crate) struct InternalTicketQueryService;
+
+impl InternalTicketQueryService {
+    pub async fn get_ticket_numbers(con: &mut SqliteConnection, offset: &StoreOffset) -> Result<TicketNumbers, Report<QueryError>> {
+        let today = offset.today();
+        
+        // language=sqlite
+        let numbers = sqlx::query_as::<_, TicketNumbers>(r#"
+            SELECT
+                ? AS business_day,
+                CASE 
+                    WHEN tc.business_day = ? THEN tc.last_issued 
+                END AS current,
+                tc.last_issued,
+                tc.business_day AS last_issued_day
+            FROM
+                (SELECT 1)
+            LEFT JOIN
+                ticket_counter tc ON tc.id = ?
+        "#)
+            .bind(today.as_ref())
+            .bind(today.as_ref())
+            .bind(TicketCounter::ID)
+            .fetch_one(&mut *con)
+            .await
+            .change_context_lazy(|| QueryError::Driver)?;
+        
+        Ok(numbers)
+    }
+    
+    pub async fn get_ticket_by_order(con: &mut SqliteConnection, order: &Uuid) -> Result<IssuedTicket, Report<QueryError>> {
+        // language=sqlite
+        let ticket = sqlx::query_as::<_, IssuedTicket>(r#"
+            SELECT
+                order_id,
+                number,
+                business_day
+            FROM
+                tickets
+            WHERE
+                order_id = ?
+        "#)
+            .bind(order)
+            .fetch_one(&mut *con)
+            .await
+            .change_context_lazy(|| QueryError::Driver)?;
+        
+        Ok(ticket)
+    }
+}
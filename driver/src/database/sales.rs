@@ -0,0 +1,211 @@
+use async_trait::async_trait;
+use error_stack::{Report, ResultExt};
+use kernel::io::events::OrderEvent;
+use nitinol::eventstream::resolver::{DecodeMapping, SubscriptionMapper};
+use nitinol::eventstream::EventSubscriber;
+use sqlx::{SqliteConnection, SqlitePool};
+
+use crate::errors::FailedBuildReadModel;
+
+/// Records every line of a confirmed order as a sale, 
+/// with the product name and category as they were at the time of the sale.
+/// A product listed in several categories is counted towards the first of them in the menu,
+/// so that the category breakdown adds up to the total.
+/// The recorded price includes the price deltas of the selected options and the tax,
+/// in the minor units of the recorded currency,
+/// while the recorded amount is what was charged for the line after the discount of the coupon.
+#[derive(Clone)]
+pub struct SalesReadModelService {
+    pool: SqlitePool
+}
+
+impl SalesReadModelService {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+impl SubscriptionMapper for SalesReadModelService {
+    fn mapping(mapping: &mut DecodeMapping<Self>) {
+        mapping.register::<OrderEvent>();
+    }
+}
+
+#[async_trait]
+impl EventSubscriber<OrderEvent> for SalesReadModelService {
+    type Error = Report<FailedBuildReadModel>;
+
+    async fn on(&mut self, event: OrderEvent) -> Result<(), Self::Error> {
+        let mut con = self.pool.begin().await
+            .change_context_lazy(|| FailedBuildReadModel)?;
+        match event {
+            OrderEvent::Confirmed { .. } => {
+                InternalSalesReadModelService::record(event, &mut con).await?
+            }
+            OrderEvent::Cancelled { .. } => {
+                InternalSalesReadModelService::revoke(event, &mut con).await?
+            }
+            _ => {}
+        }
+        con.commit().await
+            .change_context_lazy(|| FailedBuildReadModel)?;
+        Ok(())
+    }
+}
+
+
+pub(crate) struct InternalSalesReadModelService;
+
+impl InternalSalesReadModelService {
+    pub async fn record(record: OrderEvent, con: &mut SqliteConnection) -> Result<(), Report<FailedBuildReadModel>> {
+        let OrderEvent::Confirmed { id, lines, coupon } = record else {
+            return Err(Report::new(FailedBuildReadModel).attach_printable("Invalid event type"));
+        };
+        
+        let discounts = coupon.map(|coupon| coupon.allocate(&lines))
+            .unwrap_or_default();
+        
+        for (line, item) in lines {
+            let amount = item.subtotal() - discounts.get(&line).copied().unwrap_or(0);
+            
+            // language=sqlite
+            sqlx::query(r#"
+                INSERT INTO sales(order_id, line, product, name, quantity, price, amount, currency, tax_rate, category, category_name) 
+                SELECT ?1, ?2, ?3, (SELECT name FROM products WHERE id = ?3), ?4, ?5, ?6, ?7, ?8, listed.id, listed.name
+                FROM (SELECT 1) LEFT JOIN (
+                    SELECT c.id, c.name FROM category_products_ordering cpo
+                    JOIN categories c ON c.id = cpo.category
+                    LEFT JOIN categories_ordering co ON co.category = c.id
+                    WHERE cpo.product = ?3
+                    ORDER BY co.ordering IS NULL, co.ordering
+                    LIMIT 1
+                ) listed ON 1
+            "#)
+                .bind(id.as_ref())
+                .bind(line)
+                .bind(item.product().as_ref())
+                .bind(item.quantity().as_ref())
+                .bind(item.unit_price())
+                .bind(amount)
+                .bind(item.currency().as_ref())
+                .bind(item.tax().rate().as_ref())
+                .execute(&mut *con)
+                .await
+                .change_context_lazy(|| FailedBuildReadModel)?;
+        }
+        
+        Ok(())
+    }
+    
+    /// Orders cancelled after confirmation are refunded and no longer count as sales.
+    pub async fn revoke(revoke: OrderEvent, con: &mut SqliteConnection) -> Result<(), Report<FailedBuildReadModel>> {
+        let OrderEvent::Cancelled { id } = revoke else {
+            return Err(Report::new(FailedBuildReadModel).attach_printable("Invalid event type"));
+        };
+        
+        // language=sqlite
+        sqlx::query(r#"
+            DELETE FROM sales WHERE order_id = ?
+        "#)
+            .bind(id.as_ref())
+            .execute(&mut *con)
+            .await
+            .change_context_lazy(|| FailedBuildReadModel)?;
+        
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod test {
+    use std::collections::BTreeMap;
+    use error_stack::{Report, ResultExt};
+    use kernel::entities::order::{AppliedCoupon, LineTax, OrderId, OrderLine, OrderQuantity};
+    use kernel::entities::money::{Currency, Money};
+    use kernel::entities::product::{ProductId, ProductPrice};
+    use kernel::entities::promotion::{CouponCode, Discount};
+    
+    use super::*;
+    use crate::database;
+    use crate::errors::test::UnrecoverableError;
+    
+    fn line(product: ProductId, quantity: i64) -> Result<OrderLine, Report<UnrecoverableError>> {
+        Ok(OrderLine::new(
+            product,
+            OrderQuantity::new(quantity).change_context_lazy(|| UnrecoverableError)?,
+            ProductPrice::new(Money::new(100, Currency::default())).change_context_lazy(|| UnrecoverableError)?,
+            Vec::new(),
+            Vec::new(),
+            LineTax::default(),
+        ))
+    }
+    
+    pub async fn record_sale(id: OrderId, product: ProductId, quantity: i64, con: &mut SqliteConnection) -> Result<(), Report<UnrecoverableError>> {
+        let item = line(product, quantity)?;
+        
+        let record = OrderEvent::Confirmed { id, lines: BTreeMap::from([(0, item)]), coupon: None };
+        
+        InternalSalesReadModelService::record(record, con).await
+            .change_context_lazy(|| UnrecoverableError)?;
+        
+        Ok(())
+    }
+    
+    #[tokio::test]
+    async fn test_record_and_revoke_sale() -> Result<(), Report<UnrecoverableError>> {
+        let pool = database::init("sqlite:../query.db").await
+            .change_context_lazy(|| UnrecoverableError)?;
+        let mut con = pool.begin().await
+            .change_context_lazy(|| UnrecoverableError)?;
+        
+        let order_id = OrderId::default();
+        
+        record_sale(order_id, ProductId::default(), 2, &mut con).await?;
+        
+        InternalSalesReadModelService::revoke(OrderEvent::Cancelled { id: order_id }, &mut con).await
+            .change_context_lazy(|| UnrecoverableError)?;
+        
+        con.rollback().await
+            .change_context_lazy(|| UnrecoverableError)?;
+        Ok(())
+    }
+    
+    #[tokio::test]
+    async fn test_record_discounted_sale() -> Result<(), Report<UnrecoverableError>> {
+        let pool = database::init("sqlite:../query.db").await
+            .change_context_lazy(|| UnrecoverableError)?;
+        let mut con = pool.begin().await
+            .change_context_lazy(|| UnrecoverableError)?;
+        
+        let id = OrderId::default();
+        let coupon = AppliedCoupon::new(
+            CouponCode::new("TEST").change_context_lazy(|| UnrecoverableError)?,
+            Discount::fixed(Money::new(50, Currency::default())).change_context_lazy(|| UnrecoverableError)?,
+            None,
+        );
+        
+        // 50 off the 300 of both lines, allocated as 16 and the remaining 34.
+        let lines = BTreeMap::from([(0, line(ProductId::default(), 1)?), (1, line(ProductId::default(), 2)?)]);
+        let record = OrderEvent::Confirmed { id, lines, coupon: Some(coupon) };
+        
+        InternalSalesReadModelService::record(record, &mut con).await
+            .change_context_lazy(|| UnrecoverableError)?;
+        
+        // language=sqlite
+        let amounts = sqlx::query_scalar::<_, i64>(r#"
+            SELECT amount FROM sales WHERE order_id = ? ORDER BY line
+        "#)
+            .bind(id.as_ref())
+            .fetch_all(&mut *con)
+            .await
+            .change_context_lazy(|| UnrecoverableError)?;
+        
+        if amounts != vec![84, 166] {
+            return Err(Report::new(UnrecoverableError).attach_printable("Discount must be taken off the recorded amounts"));
+        }
+        
+        con.rollback().await
+            .change_context_lazy(|| UnrecoverableError)?;
+        Ok(())
+    }
+}
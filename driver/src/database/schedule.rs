@@ -0,0 +1,25 @@
+use kernel::entities::schedule::Schedule;
+use sqlx::types::time::{PrimitiveDateTime, Time};
+
+/// Rows of `category_schedules` and `product_schedules` as `(weekday, start, end)`.
+pub(crate) fn rows(schedule: &Schedule) -> Vec<(u8, i64, i64)> {
+    schedule.as_ref().iter()
+        .flat_map(|window| {
+            let (start, end) = (seconds(window.start()), seconds(window.end()));
+            window.weekdays().map(move |weekday| (weekday, start, end))
+        })
+        .collect()
+}
+
+/// Splits `at` into the weekday and the seconds since midnight compared with the schedule tables.
+pub(crate) fn split(at: Option<PrimitiveDateTime>) -> (Option<u8>, Option<i64>) {
+    match at {
+        Some(at) => (Some(at.weekday().number_days_from_sunday()), Some(seconds(&at.time()))),
+        None => (None, None),
+    }
+}
+
+fn seconds(time: &Time) -> i64 {
+    let (hour, minute, second) = time.as_hms();
+    hour as i64 * 3600 + minute as i64 * 60 + second as i64
+}
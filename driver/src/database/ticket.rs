@@ -0,0 +1,119 @@
+use async_trait::async_trait;
+use error_stack::{Report, ResultExt};
+use kernel::entities::ticket::TicketCounter;
+use kernel::io::events::TicketCounterEvent;
+use nitinol::eventstream::resolver::{DecodeMapping, SubscriptionMapper};
+use nitinol::eventstream::EventSubscriber;
+use sqlx::{SqliteConnection, SqlitePool};
+
+use crate::errors::FailedBuildReadModel;
+
+#[derive(Clone)]
+pub struct TicketReadModelService {
+    pool: SqlitePool
+}
+
+impl TicketReadModelService {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+impl SubscriptionMapper for TicketReadModelService {
+    fn mapping(mapping: &mut DecodeMapping<Self>) {
+        mapping.register::<TicketCounterEvent>();
+    }
+}
+
+#[async_trait]
+impl EventSubscriber<TicketCounterEvent> for TicketReadModelService {
+    type Error = Report<FailedBuildReadModel>;
+
+    async fn on(&mut self, event: TicketCounterEvent) -> Result<(), Self::Error> {
+        let mut con = self.pool.begin().await
+            .change_context_lazy(|| FailedBuildReadModel)?;
+        match event {
+            TicketCounterEvent::Issued { .. } => {
+                InternalTicketReadModelService::issue(event, &mut con).await?
+            }
+        }
+        con.commit().await
+            .change_context_lazy(|| FailedBuildReadModel)?;
+        Ok(())
+    }
+}
+
+
+pub(crate) struct InternalTicketReadModelService;
+
+impl InternalTicketReadModelService {
+    pub async fn issue(issue: TicketCounterEvent, con: &mut SqliteConnection) -> Result<(), Report<FailedBuildReadModel>> {
+        let TicketCounterEvent::Issued { order, number, day } = issue;
+        
+        // language=sqlite
+        sqlx::query(r#"
+            INSERT INTO tickets(order_id, number, business_day) VALUES (?, ?, ?)
+        "#)
+            .bind(order.as_ref())
+            .bind(number.as_ref())
+            .bind(day.as_ref())
+            .execute(&mut *con)
+            .await
+            .change_context_lazy(|| FailedBuildReadModel)?;
+        
+        // language=sqlite
+        sqlx::query(r#"
+            INSERT INTO ticket_counter(id, business_day, last_issued) VALUES (?, ?, ?)
+            ON CONFLICT (id) DO UPDATE SET 
+                business_day = excluded.business_day, 
+                last_issued = excluded.last_issued
+        "#)
+            .bind(TicketCounter::ID)
+            .bind(day.as_ref())
+            .bind(number.as_ref())
+            .execute(&mut *con)
+            .await
+            .change_context_lazy(|| FailedBuildReadModel)?;
+        
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod test {
+    use error_stack::{Report, ResultExt};
+    use kernel::entities::order::OrderId;
+    use kernel::entities::ticket::{StoreOffset, TicketNumber};
+    
+    use super::*;
+    use crate::database;
+    use crate::errors::test::UnrecoverableError;
+    
+    pub async fn issue_ticket(order: OrderId, number: i64, con: &mut SqliteConnection) -> Result<(), Report<UnrecoverableError>> {
+        let issue = TicketCounterEvent::Issued {
+            order,
+            number: TicketNumber::new(number).change_context_lazy(|| UnrecoverableError)?,
+            day: StoreOffset::default().today(),
+        };
+        
+        InternalTicketReadModelService::issue(issue, con).await
+            .change_context_lazy(|| UnrecoverableError)?;
+        
+        Ok(())
+    }
+    
+    #[tokio::test]
+    async fn test_issue_ticket() -> Result<(), Report<UnrecoverableError>> {
+        let pool = database::init("sqlite:../query.db").await
+            .change_context_lazy(|| UnrecoverableError)?;
+        let mut con = pool.begin().await
+            .change_context_lazy(|| UnrecoverableError)?;
+        
+        issue_ticket(OrderId::default(), 1, &mut con).await?;
+        issue_ticket(OrderId::default(), 2, &mut con).await?;
+        
+        con.rollback().await
+            .change_context_lazy(|| UnrecoverableError)?;
+        Ok(())
+    }
+}
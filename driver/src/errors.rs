@@ -11,6 +11,10 @@ pub struct FailedBuildReadModel;
 #[error("Failed to query the database.")]
 pub struct FailedQuery;
 
+#[derive(Debug, thiserror::Error)]
+#[error("Failed to record the audit log.")]
+pub struct FailedRecordAudit;
+
 #[cfg(test)]
 pub(crate) mod test {
     #[derive(Debug, thiserror::Error)]
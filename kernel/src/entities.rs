@@ -1,4 +1,11 @@
 pub mod categories;
 pub mod category;
+pub mod device;
 pub mod image;
+pub mod money;
+pub mod order;
 pub mod product;
+pub mod promotion;
+pub mod schedule;
+pub mod staff;
+pub mod ticket;
@@ -17,7 +17,8 @@ use nitinol::projection::resolver::{Mapper, ResolveMapping};
 use nitinol::{EntityId, ToEntityId};
 use nitinol::process::eventstream::{WithEventSubscriber, WithStreamPublisher};
 use crate::entities::product::ProductId;
-use crate::errors::{FormationError, ValidationError};
+use crate::entities::schedule::Schedule;
+use crate::errors::{DeletedError, FormationError, ValidationError};
 use crate::io::commands::CategoryCommand;
 use crate::io::events::{CategoryEvent, ProductEvent};
 
@@ -26,6 +27,7 @@ pub struct Category {
     id: CategoryId,
     name: CategoryName,
     products: BTreeMap<i64, ProductId>,
+    schedule: Option<Schedule>,
 }
 
 impl Category {
@@ -34,6 +36,7 @@ impl Category {
             id,
             name,
             products: BTreeMap::new(),
+            schedule: None,
         }
     }
 
@@ -48,6 +51,11 @@ impl Category {
     pub fn products(&self) -> &BTreeMap<i64, ProductId> {
         &self.products
     }
+
+    /// `None` if the category is always available.
+    pub fn schedule(&self) -> Option<&Schedule> {
+        self.schedule.as_ref()
+    }
 }
 
 impl TryFrom<(CategoryId, CategoryCommand)> for Category {
@@ -104,6 +112,15 @@ impl Publisher<CategoryCommand> for Category {
             CategoryCommand::Create { name } => CategoryEvent::Created { id: self.id, name },
             CategoryCommand::Rename { new } => CategoryEvent::Renamed { id: self.id, new },
             CategoryCommand::Delete => CategoryEvent::Deleted { id: self.id },
+            CategoryCommand::ChangeSchedule { new } => CategoryEvent::ChangedSchedule { id: self.id, new },
+            CategoryCommand::ClearSchedule => {
+                if self.schedule.is_none() {
+                    return Err(Report::new(ValidationError)
+                        .attach_printable("Category does not have a schedule"));
+                }
+
+                CategoryEvent::ClearedSchedule { id: self.id }
+            }
             CategoryCommand::AddProduct { id } => {
                 if self.products.iter().any(|(_, p)| p == &id) {
                     return Err(Report::new(ValidationError)
@@ -159,6 +176,12 @@ impl Applicator<CategoryEvent> for Category {
             CategoryEvent::Renamed { new, .. } => {
                 self.name = new;
             }
+            CategoryEvent::ChangedSchedule { new, .. } => {
+                self.schedule = Some(new);
+            }
+            CategoryEvent::ClearedSchedule { .. } => {
+                self.schedule = None;
+            }
             CategoryEvent::Deleted { .. } => {
                 ctx.poison_pill().await;
             }
@@ -182,7 +205,7 @@ impl ResolveMapping for Category {
 
 #[async_trait]
 impl Projection<CategoryEvent> for Category {
-    type Rejection = ValidationError;
+    type Rejection = DeletedError;
 
     async fn first(event: CategoryEvent) -> Result<Self, Self::Rejection> {
         let CategoryEvent::Created { id, name } = event else {
@@ -196,8 +219,14 @@ impl Projection<CategoryEvent> for Category {
             CategoryEvent::Renamed { new, .. } => {
                 self.name = new;
             }
+            CategoryEvent::ChangedSchedule { new, .. } => {
+                self.schedule = Some(new);
+            }
+            CategoryEvent::ClearedSchedule { .. } => {
+                self.schedule = None;
+            }
             CategoryEvent::Deleted { .. } => {
-                panic!("This entity has a delete event issued.");
+                return Err(DeletedError);
             }
             CategoryEvent::AddedProduct { id, ordering, .. } => {
                 self.products.insert(ordering, id);
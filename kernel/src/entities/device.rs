@@ -0,0 +1,181 @@
+mod id;
+mod key;
+mod name;
+mod store;
+
+pub use self::{id::*, key::*, name::*, store::*};
+
+use std::convert::Infallible;
+
+use async_trait::async_trait;
+use destructure::{Destructure, Mutation};
+use error_stack::Report;
+use serde::{Deserialize, Serialize};
+
+use nitinol::process::eventstream::WithStreamPublisher;
+use nitinol::process::persistence::WithPersistence;
+use nitinol::process::{Applicator, Context, Process, Publisher};
+use nitinol::projection::resolver::{Mapper, ResolveMapping};
+use nitinol::projection::Projection;
+use nitinol::{EntityId, ToEntityId};
+use crate::entities::staff::Role;
+use crate::errors::{FormationError, ValidationError};
+use crate::io::commands::DeviceCommand;
+use crate::io::events::DeviceEvent;
+
+/// An unattended kiosk or display, authenticating with an [`ApiKey`] instead of a password.
+///
+/// Each device acts with its own [`Role`] for the store it is bound to, 
+/// and a revoked device is cut off without affecting any other credentials.
+#[derive(Debug, Clone, Deserialize, Serialize, Destructure, Mutation)]
+pub struct Device {
+    id: DeviceId,
+    name: DeviceName,
+    role: Role,
+    store: StoreCode,
+    key: HashedKey,
+    revoked: bool,
+}
+
+impl Device {
+    pub fn new(id: DeviceId, name: DeviceName, role: Role, store: StoreCode, key: HashedKey) -> Device {
+        Device { id, name, role, store, key, revoked: false }
+    }
+
+    pub fn id(&self) -> &DeviceId {
+        &self.id
+    }
+
+    pub fn name(&self) -> &DeviceName {
+        &self.name
+    }
+
+    pub fn role(&self) -> &Role {
+        &self.role
+    }
+
+    pub fn store(&self) -> &StoreCode {
+        &self.store
+    }
+
+    pub fn revoked(&self) -> bool {
+        self.revoked
+    }
+
+    /// `false` for revoked devices and devices bound to another store, whatever the key is.
+    pub fn authenticate(&self, key: &ApiKey, store: &StoreCode) -> bool {
+        !self.revoked && &self.store == store && self.key.verify(key)
+    }
+
+    fn apply(&mut self, event: DeviceEvent) {
+        match event {
+            DeviceEvent::Registered { .. } => {}
+            DeviceEvent::RotatedKey { key, .. } => {
+                self.key = key;
+            }
+            DeviceEvent::Revoked { .. } => {
+                self.revoked = true;
+            }
+        }
+    }
+}
+
+impl TryFrom<(DeviceId, DeviceCommand)> for Device {
+    type Error = Report<FormationError>;
+
+    fn try_from(value: (DeviceId, DeviceCommand)) -> Result<Self, Self::Error> {
+        let DeviceCommand::Register { name, role, store, key } = value.1 else {
+            return Err(Report::new(FormationError)
+                .attach_printable("DeviceCommand::Register is the only command that can be converted to Device"));
+        };
+
+        Ok(Self::new(value.0, name, role, store, key))
+    }
+}
+
+impl Process for Device {}
+
+impl WithPersistence for Device {
+    fn aggregate_id(&self) -> EntityId {
+        self.id.to_entity_id()
+    }
+}
+
+impl WithStreamPublisher for Device {
+    fn aggregate_id(&self) -> EntityId {
+        self.id.to_entity_id()
+    }
+}
+
+#[async_trait]
+impl Publisher<DeviceCommand> for Device {
+    type Event = DeviceEvent;
+    type Rejection = Report<ValidationError>;
+
+    #[tracing::instrument(skip_all, fields(device = %self.id))]
+    async fn publish(
+        &self,
+        command: DeviceCommand,
+        _: &mut Context,
+    ) -> Result<Self::Event, Self::Rejection> {
+        if self.revoked {
+            return Err(Report::new(ValidationError)
+                .attach_printable(format!("Device={} has been revoked", self.id)));
+        }
+
+        match command {
+            DeviceCommand::Register { name, role, store, key } => {
+                // A stolen device must not be able to do more than its own job.
+                if role == Role::Admin {
+                    return Err(Report::new(ValidationError)
+                        .attach_printable("Devices cannot be registered as admin"));
+                }
+
+                Ok(DeviceEvent::Registered { id: self.id, name, role, store, key })
+            }
+            DeviceCommand::RotateKey { key } => {
+                Ok(DeviceEvent::RotatedKey { id: self.id, key })
+            }
+            DeviceCommand::Revoke => {
+                Ok(DeviceEvent::Revoked { id: self.id })
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl Applicator<DeviceEvent> for Device {
+    #[tracing::instrument(skip_all, fields(device = %self.id))]
+    async fn apply(&mut self, event: DeviceEvent, ctx: &mut Context) {
+        self.persist(&event, ctx).await;
+        WithStreamPublisher::publish(self, &event, ctx).await;
+
+        tracing::debug!("Applying event: {:?}", event);
+        Device::apply(self, event);
+        tracing::debug!("State: {:?}", self);
+    }
+}
+
+impl ResolveMapping for Device {
+    fn mapping(mapper: &mut Mapper<Self>) {
+        mapper.register::<DeviceEvent>();
+    }
+}
+
+#[async_trait]
+impl Projection<DeviceEvent> for Device {
+    type Rejection = Infallible;
+
+    async fn first(event: DeviceEvent) -> Result<Self, Self::Rejection> {
+        let DeviceEvent::Registered { id, name, role, store, key } = event else {
+            panic!("Projection must start with `DeviceEvent::Registered` event");
+        };
+
+        Ok(Self::new(id, name, role, store, key))
+    }
+
+    async fn apply(&mut self, event: DeviceEvent) -> Result<(), Self::Rejection> {
+        Device::apply(self, event);
+        Ok(())
+    }
+}
@@ -0,0 +1,36 @@
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+use uuid::Uuid;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Deserialize, Serialize)]
+pub struct DeviceId(Uuid);
+
+impl DeviceId {
+    pub fn new(id: impl Into<Uuid>) -> Self {
+        Self(id.into())
+    }
+}
+
+impl AsRef<Uuid> for DeviceId {
+    fn as_ref(&self) -> &Uuid {
+        &self.0
+    }
+}
+
+impl From<DeviceId> for Uuid {
+    fn from(id: DeviceId) -> Self {
+        id.0
+    }
+}
+
+impl Default for DeviceId {
+    fn default() -> Self {
+        Self::new(Uuid::new_v4())
+    }
+}
+
+impl Display for DeviceId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
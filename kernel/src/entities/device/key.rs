@@ -0,0 +1,78 @@
+use argon2::password_hash::rand_core::{OsRng, RngCore};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fmt::{Debug, Write};
+
+/// Secret a [`Device`](crate::entities::device::Device) authenticates with.
+///
+/// Only the [`HashedKey`] is kept, so the key has to be handed to the device when it is issued.
+#[derive(Clone)]
+pub struct ApiKey(String);
+
+impl ApiKey {
+    /// 256 random bits, hex-encoded.
+    pub fn generate() -> ApiKey {
+        let mut bytes = [0u8; 32];
+        OsRng.fill_bytes(&mut bytes);
+        Self(hex(&bytes))
+    }
+
+    /// A key presented by a device.
+    pub fn new(key: impl Into<String>) -> ApiKey {
+        Self(key.into())
+    }
+
+    pub fn hash(&self) -> HashedKey {
+        HashedKey(hex(&Sha256::digest(self.0.as_bytes())))
+    }
+}
+
+impl AsRef<str> for ApiKey {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Debug for ApiKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("ApiKey(..)")
+    }
+}
+
+/// SHA-256 of an [`ApiKey`].
+///
+/// Unlike passwords, keys are random enough that a fast hash does not make them guessable,
+/// which keeps authenticating every request of a device cheap.
+#[derive(Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct HashedKey(String);
+
+impl HashedKey {
+    pub fn verify(&self, key: &ApiKey) -> bool {
+        let given = key.hash();
+        
+        // Compared in constant time, so that the hash cannot be guessed byte by byte.
+        self.0.len() == given.0.len()
+            && self.0.bytes()
+                .zip(given.0.bytes())
+                .fold(0, |acc, (a, b)| acc | (a ^ b)) == 0
+    }
+}
+
+impl AsRef<str> for HashedKey {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Debug for HashedKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("HashedKey(..)")
+    }
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().fold(String::with_capacity(bytes.len() * 2), |mut acc, byte| {
+        let _ = write!(acc, "{byte:02x}");
+        acc
+    })
+}
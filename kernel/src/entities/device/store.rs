@@ -0,0 +1,46 @@
+use crate::errors::ValidationError;
+use error_stack::Report;
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+/// Store a [`Device`](crate::entities::device::Device) is bound to.
+///
+/// Codes are case-insensitive and stored in lower case.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub struct StoreCode(String);
+
+impl StoreCode {
+    pub fn new(code: impl AsRef<str>) -> Result<StoreCode, Report<ValidationError>> {
+        let code = code.as_ref().trim().to_lowercase();
+
+        if !(1..=32).contains(&code.len()) {
+            return Err(Report::new(ValidationError)
+                .attach_printable("`StoreCode` must be between 1 and 32 characters"));
+        }
+
+        if !code.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+            return Err(Report::new(ValidationError)
+                .attach_printable(format!("`StoreCode` must only contain letters, digits, `-` and `_`, but was `{code}`")));
+        }
+
+        Ok(Self(code))
+    }
+}
+
+impl AsRef<str> for StoreCode {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<StoreCode> for String {
+    fn from(code: StoreCode) -> Self {
+        code.0
+    }
+}
+
+impl Display for StoreCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
@@ -0,0 +1,60 @@
+mod currency;
+
+pub use self::currency::*;
+
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+/// An amount of money in the minor units of its [`Currency`], e.g. cents for `USD` and yen for `JPY`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(from = "MoneyRepr")]
+pub struct Money {
+    amount: i64,
+    currency: Currency,
+}
+
+impl Money {
+    pub fn new(amount: impl Into<i64>, currency: Currency) -> Money {
+        Money { amount: amount.into(), currency }
+    }
+
+    /// Amount in minor units.
+    pub fn amount(&self) -> i64 {
+        self.amount
+    }
+
+    pub fn currency(&self) -> &Currency {
+        &self.currency
+    }
+}
+
+impl Display for Money {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let exponent = self.currency.exponent();
+        if exponent == 0 {
+            return write!(f, "{} {}", self.amount, self.currency);
+        }
+
+        let scale = 10_i64.pow(exponent);
+        let sign = if self.amount < 0 { "-" } else { "" };
+        let amount = self.amount.unsigned_abs();
+        write!(f, "{sign}{}.{:0width$} {}", amount / scale as u64, amount % scale as u64, self.currency, width = exponent as usize)
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum MoneyRepr {
+    Money { amount: i64, currency: Currency },
+    /// Prices were journaled as bare yen amounts before currencies were introduced.
+    Legacy(i64),
+}
+
+impl From<MoneyRepr> for Money {
+    fn from(value: MoneyRepr) -> Self {
+        match value {
+            MoneyRepr::Money { amount, currency } => Money { amount, currency },
+            MoneyRepr::Legacy(amount) => Money { amount, currency: Currency::default() },
+        }
+    }
+}
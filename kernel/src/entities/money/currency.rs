@@ -0,0 +1,66 @@
+use crate::errors::ValidationError;
+use error_stack::Report;
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+/// ISO 4217 currency code such as `JPY` or `USD`.
+///
+/// Deserialized through [`Currency::new`], so that requests and journals cannot bypass the validation.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Currency(String);
+
+impl Currency {
+    pub fn new(code: impl Into<String>) -> Result<Currency, Report<ValidationError>> {
+        let code = code.into().trim().to_ascii_uppercase();
+        if code.len() != 3 || !code.chars().all(|c| c.is_ascii_uppercase()) {
+            return Err(Report::new(ValidationError)
+                .attach_printable(format!("`{code}` is not an ISO 4217 currency code")));
+        }
+
+        Ok(Self(code))
+    }
+
+    /// Number of digits of the minor unit, e.g. 2 for `USD` whose minor unit is the cent.
+    pub fn exponent(&self) -> u32 {
+        match self.0.as_str() {
+            "BIF" | "CLP" | "DJF" | "GNF" | "ISK" | "JPY" | "KMF" | "KRW" | "PYG" 
+            | "RWF" | "UGX" | "UYI" | "VND" | "VUV" | "XAF" | "XOF" | "XPF" => 0,
+            "BHD" | "IQD" | "JOD" | "KWD" | "LYD" | "OMR" | "TND" => 3,
+            _ => 2,
+        }
+    }
+}
+
+impl Default for Currency {
+    /// Everything was priced in yen before currencies were introduced.
+    fn default() -> Self {
+        Self("JPY".to_string())
+    }
+}
+
+impl AsRef<str> for Currency {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl TryFrom<String> for Currency {
+    type Error = Report<ValidationError>;
+
+    fn try_from(code: String) -> Result<Self, Self::Error> {
+        Self::new(code)
+    }
+}
+
+impl From<Currency> for String {
+    fn from(currency: Currency) -> Self {
+        currency.0
+    }
+}
+
+impl Display for Currency {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
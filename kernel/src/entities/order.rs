@@ -0,0 +1,328 @@
+mod coupon;
+mod id;
+mod line;
+mod payment;
+mod quantity;
+mod status;
+mod tax;
+
+pub use self::{coupon::*, id::*, line::*, payment::*, quantity::*, status::*, tax::*};
+
+use std::collections::BTreeMap;
+use std::convert::Infallible;
+
+use async_trait::async_trait;
+use destructure::{Destructure, Mutation};
+use error_stack::Report;
+use serde::{Deserialize, Serialize};
+
+use nitinol::process::eventstream::WithStreamPublisher;
+use nitinol::process::persistence::WithPersistence;
+use nitinol::process::{Applicator, Context, Process, Publisher};
+use nitinol::projection::resolver::{Mapper, ResolveMapping};
+use nitinol::projection::Projection;
+use nitinol::{EntityId, ToEntityId};
+use crate::entities::money::{Currency, Money};
+use crate::errors::{FormationError, ValidationError};
+use crate::io::commands::OrderCommand;
+use crate::io::events::OrderEvent;
+
+#[derive(Debug, Clone, Deserialize, Serialize, Destructure, Mutation)]
+pub struct Order {
+    id: OrderId,
+    status: OrderStatus,
+    lines: BTreeMap<i64, OrderLine>,
+    payment: Option<Payment>,
+    coupon: Option<AppliedCoupon>,
+}
+
+impl Order {
+    pub fn new(id: OrderId) -> Order {
+        Order {
+            id,
+            status: OrderStatus::Placed,
+            lines: BTreeMap::new(),
+            payment: None,
+            coupon: None,
+        }
+    }
+
+    pub fn id(&self) -> &OrderId {
+        &self.id
+    }
+
+    pub fn status(&self) -> &OrderStatus {
+        &self.status
+    }
+
+    pub fn lines(&self) -> &BTreeMap<i64, OrderLine> {
+        &self.lines
+    }
+
+    /// The currency of the lines, which all amounts of the order are in. `None` while the order is empty.
+    pub fn currency(&self) -> Option<&Currency> {
+        self.lines.values().next().map(OrderLine::currency)
+    }
+
+    /// Sum of the lines before any discount.
+    pub fn subtotal(&self) -> i64 {
+        self.lines.values().map(OrderLine::subtotal).sum()
+    }
+
+    /// The amount taken off by the applied coupon, if any.
+    pub fn discount(&self) -> i64 {
+        self.coupon.as_ref()
+            .map(|coupon| coupon.amount(&self.lines))
+            .unwrap_or(0)
+    }
+
+    pub fn total(&self) -> i64 {
+        self.subtotal() - self.discount()
+    }
+
+    pub fn coupon(&self) -> Option<&AppliedCoupon> {
+        self.coupon.as_ref()
+    }
+
+    /// The total broken down by tax rate, in ascending order of the rate.
+    pub fn tax_breakdown(&self) -> Vec<TaxSummary> {
+        tax::breakdown(&self.lines, self.coupon.as_ref())
+    }
+
+    pub fn payment(&self) -> Option<&Payment> {
+        self.payment.as_ref()
+    }
+
+    fn ensure_unpaid(&self) -> Result<(), Report<ValidationError>> {
+        if self.payment.is_some() {
+            return Err(Report::new(ValidationError)
+                .attach_printable(format!("Order={} has already been paid", self.id)));
+        }
+        Ok(())
+    }
+
+    fn apply(&mut self, event: OrderEvent) {
+        match event {
+            OrderEvent::Placed { .. } => {
+                self.status = OrderStatus::Placed;
+            }
+            OrderEvent::AddedLine { line, item, .. } => {
+                self.lines.insert(line, item);
+            }
+            OrderEvent::RemovedLine { line, .. } => {
+                self.lines.remove(&line);
+            }
+            OrderEvent::AppliedCoupon { coupon, .. } => {
+                self.coupon = Some(coupon);
+            }
+            OrderEvent::Paid { payment, .. } => {
+                self.payment = Some(payment);
+            }
+            OrderEvent::Confirmed { .. } => {
+                self.status = OrderStatus::Accepted;
+            }
+            OrderEvent::ChangedStatus { new, .. } => {
+                self.status = new;
+            }
+            OrderEvent::Cancelled { .. } => {
+                self.status = OrderStatus::Cancelled;
+            }
+        }
+    }
+}
+
+impl TryFrom<(OrderId, OrderCommand)> for Order {
+    type Error = Report<FormationError>;
+
+    fn try_from(value: (OrderId, OrderCommand)) -> Result<Self, Self::Error> {
+        let OrderCommand::Place = value.1 else {
+            return Err(Report::new(FormationError)
+                .attach_printable("OrderCommand::Place is the only command that can be converted to Order"));
+        };
+
+        Ok(Self::new(value.0))
+    }
+}
+
+impl Process for Order {}
+
+impl WithPersistence for Order {
+    fn aggregate_id(&self) -> EntityId {
+        self.id.to_entity_id()
+    }
+}
+
+impl WithStreamPublisher for Order {
+    fn aggregate_id(&self) -> EntityId {
+        self.id.to_entity_id()
+    }
+}
+
+#[async_trait]
+impl Publisher<OrderCommand> for Order {
+    type Event = OrderEvent;
+    type Rejection = Report<ValidationError>;
+
+    #[tracing::instrument(skip_all, fields(order = %self.id))]
+    async fn publish(
+        &self,
+        command: OrderCommand,
+        _: &mut Context,
+    ) -> Result<Self::Event, Self::Rejection> {
+        match command {
+            OrderCommand::Place => Ok(OrderEvent::Placed { id: self.id }),
+            OrderCommand::AddLine { product, quantity, price, options, components, tax } => {
+                if self.status != OrderStatus::Placed {
+                    return Err(Report::new(ValidationError)
+                        .attach_printable(format!("Order={} is already {}", self.id, self.status)));
+                }
+
+                self.ensure_unpaid()?;
+
+                if self.currency().is_some_and(|currency| currency != price.currency()) {
+                    return Err(Report::new(ValidationError)
+                        .attach_printable(format!("Product priced in {} cannot be added to order in another currency", price.currency())));
+                }
+
+                if let Some(currency) = self.coupon.as_ref().and_then(|coupon| coupon.discount().currency()) {
+                    if currency != price.currency() {
+                        return Err(Report::new(ValidationError)
+                            .attach_printable(format!("Product priced in {} cannot be added to order with a discount in {currency}", price.currency())));
+                    }
+                }
+
+                let line = self.lines.last_key_value()
+                    .map(|(line, _)| line + 1)
+                    .unwrap_or(0);
+
+                Ok(OrderEvent::AddedLine { id: self.id, line, item: OrderLine::new(product, quantity, price, options, components, tax) })
+            }
+            OrderCommand::RemoveLine { line } => {
+                if self.status != OrderStatus::Placed {
+                    return Err(Report::new(ValidationError)
+                        .attach_printable(format!("Order={} is already {}", self.id, self.status)));
+                }
+
+                self.ensure_unpaid()?;
+
+                if !self.lines.contains_key(&line) {
+                    return Err(Report::new(ValidationError)
+                        .attach_printable(format!("Line={line} does not exist in order")));
+                }
+
+                Ok(OrderEvent::RemovedLine { id: self.id, line })
+            }
+            OrderCommand::ApplyCoupon { coupon } => {
+                if self.status != OrderStatus::Placed {
+                    return Err(Report::new(ValidationError)
+                        .attach_printable(format!("Order={} is already {}", self.id, self.status)));
+                }
+
+                self.ensure_unpaid()?;
+
+                if let Some(applied) = &self.coupon {
+                    return Err(Report::new(ValidationError)
+                        .attach_printable(format!("Order={} already has Coupon={} applied", self.id, applied.code())));
+                }
+
+                if let (Some(currency), Some(discount)) = (self.currency(), coupon.discount().currency()) {
+                    if currency != discount {
+                        return Err(Report::new(ValidationError)
+                            .attach_printable(format!("Discount in {discount} cannot be applied to order in {currency}")));
+                    }
+                }
+
+                Ok(OrderEvent::AppliedCoupon { id: self.id, coupon })
+            }
+            OrderCommand::Pay { tender, tendered } => {
+                if self.status != OrderStatus::Placed {
+                    return Err(Report::new(ValidationError)
+                        .attach_printable(format!("Order={} is already {}", self.id, self.status)));
+                }
+
+                self.ensure_unpaid()?;
+
+                if self.lines.is_empty() {
+                    return Err(Report::new(ValidationError)
+                        .attach_printable("Order without any lines cannot be paid"));
+                }
+
+                let total = Money::new(self.total(), self.currency().cloned().unwrap_or_default());
+                let payment = Payment::new(tender, total, tendered)?;
+
+                Ok(OrderEvent::Paid { id: self.id, payment, taxes: self.tax_breakdown() })
+            }
+            OrderCommand::Confirm => {
+                if self.status != OrderStatus::Placed {
+                    return Err(Report::new(ValidationError)
+                        .attach_printable(format!("Order={} is already {}", self.id, self.status)));
+                }
+
+                if self.lines.is_empty() {
+                    return Err(Report::new(ValidationError)
+                        .attach_printable("Order without any lines cannot be confirmed"));
+                }
+
+                if self.payment.is_none() {
+                    return Err(Report::new(ValidationError)
+                        .attach_printable(format!("Order={} has not been paid yet", self.id)));
+                }
+
+                Ok(OrderEvent::Confirmed { id: self.id, lines: self.lines.clone(), coupon: self.coupon.clone() })
+            }
+            OrderCommand::ChangeStatus { new } => {
+                if !self.status.can_transition_to(&new) {
+                    return Err(Report::new(ValidationError)
+                        .attach_printable(format!("Order={} cannot change status from {} to {new}", self.id, self.status)));
+                }
+
+                Ok(OrderEvent::ChangedStatus { id: self.id, new })
+            }
+            OrderCommand::Cancel => {
+                if !self.status.is_cancellable() {
+                    return Err(Report::new(ValidationError)
+                        .attach_printable(format!("Order={} is already {}", self.id, self.status)));
+                }
+
+                Ok(OrderEvent::Cancelled { id: self.id })
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl Applicator<OrderEvent> for Order {
+    #[tracing::instrument(skip_all, fields(order = %self.id))]
+    async fn apply(&mut self, event: OrderEvent, ctx: &mut Context) {
+        self.persist(&event, ctx).await;
+        WithStreamPublisher::publish(self, &event, ctx).await;
+
+        tracing::debug!("Applying event: {:?}", event);
+        Order::apply(self, event);
+        tracing::debug!("State: {:?}", self);
+    }
+}
+
+impl ResolveMapping for Order {
+    fn mapping(mapper: &mut Mapper<Self>) {
+        mapper.register::<OrderEvent>();
+    }
+}
+
+#[async_trait]
+impl Projection<OrderEvent> for Order {
+    type Rejection = Infallible;
+
+    async fn first(event: OrderEvent) -> Result<Self, Self::Rejection> {
+        let OrderEvent::Placed { id } = event else {
+            panic!("Projection must start with `OrderEvent::Placed` event");
+        };
+
+        Ok(Self::new(id))
+    }
+
+    async fn apply(&mut self, event: OrderEvent) -> Result<(), Self::Rejection> {
+        Order::apply(self, event);
+        Ok(())
+    }
+}
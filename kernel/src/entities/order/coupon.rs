@@ -0,0 +1,86 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::{Deserialize, Serialize};
+
+use crate::entities::order::OrderLine;
+use crate::entities::product::ProductId;
+use crate::entities::promotion::{CouponCode, Discount};
+
+/// A [`Promotion`](crate::entities::promotion::Promotion) redeemed on an [`Order`](crate::entities::order::Order).
+///
+/// The scope of the promotion is resolved into `products` when the coupon is redeemed,
+/// while the discount itself is calculated from the lines whenever the total is computed.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct AppliedCoupon {
+    code: CouponCode,
+    discount: Discount,
+    products: Option<BTreeSet<ProductId>>,
+}
+
+impl AppliedCoupon {
+    /// `products` is `None` when the promotion applies to every line.
+    pub fn new(code: CouponCode, discount: Discount, products: Option<BTreeSet<ProductId>>) -> AppliedCoupon {
+        AppliedCoupon { code, discount, products }
+    }
+
+    pub fn code(&self) -> &CouponCode {
+        &self.code
+    }
+
+    pub fn discount(&self) -> &Discount {
+        &self.discount
+    }
+
+    pub fn products(&self) -> Option<&BTreeSet<ProductId>> {
+        self.products.as_ref()
+    }
+
+    pub fn is_eligible(&self, line: &OrderLine) -> bool {
+        match &self.products {
+            Some(products) => products.contains(line.product()),
+            None => true,
+        }
+    }
+
+    /// The amount taken off the subtotal of the eligible `lines`.
+    pub fn amount(&self, lines: &BTreeMap<i64, OrderLine>) -> i64 {
+        let eligible = lines.values()
+            .filter(|line| self.is_eligible(line))
+            .map(OrderLine::subtotal)
+            .sum();
+
+        self.discount.amount(eligible)
+    }
+
+    /// The amount taken off each of the eligible `lines`, keyed by line number.
+    ///
+    /// The discount is allocated in proportion to the subtotal of each line,
+    /// with the remainder of the rounding going to the last eligible line.
+    pub fn allocate(&self, lines: &BTreeMap<i64, OrderLine>) -> BTreeMap<i64, i64> {
+        let eligible = lines.iter()
+            .filter(|(_, line)| self.is_eligible(line))
+            .map(|(index, line)| (*index, line.subtotal()))
+            .collect::<Vec<_>>();
+
+        let total = eligible.iter().map(|(_, subtotal)| subtotal).sum::<i64>();
+        if total <= 0 {
+            return BTreeMap::new();
+        }
+
+        let discount = self.amount(lines);
+        let mut allocated = 0;
+
+        eligible.iter()
+            .enumerate()
+            .map(|(position, (index, subtotal))| {
+                let share = if position + 1 == eligible.len() {
+                    discount - allocated
+                } else {
+                    discount * subtotal / total
+                };
+                allocated += share;
+                (*index, share)
+            })
+            .collect()
+    }
+}
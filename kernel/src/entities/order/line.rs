@@ -0,0 +1,84 @@
+use serde::{Deserialize, Serialize};
+
+use crate::entities::money::Currency;
+use crate::entities::order::{LineTax, OrderQuantity};
+use crate::entities::product::{BundleChoice, ProductId, ProductPrice, SelectedOption};
+
+/// A single line of an [`Order`](crate::entities::order::Order).
+///
+/// `price` is the unit price of the product at the time it was added to the order,
+/// so later price changes to the [`Product`](crate::entities::product::Product) do not affect it.
+/// The same applies to the price deltas of the selected `options`.
+///
+/// `components` are the products chosen for the slots when the product is a bundle.
+///
+/// Prices are charged tax-inclusive, so the tax is added to the listed price here
+/// if the product was listed without it.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct OrderLine {
+    product: ProductId,
+    quantity: OrderQuantity,
+    price: ProductPrice,
+    #[serde(default)]
+    options: Vec<SelectedOption>,
+    #[serde(default)]
+    components: Vec<BundleChoice>,
+    #[serde(default)]
+    tax: LineTax,
+}
+
+impl OrderLine {
+    pub fn new(
+        product: ProductId,
+        quantity: OrderQuantity,
+        price: ProductPrice,
+        options: Vec<SelectedOption>,
+        components: Vec<BundleChoice>,
+        tax: LineTax,
+    ) -> OrderLine {
+        OrderLine { product, quantity, price, options, components, tax }
+    }
+
+    pub fn product(&self) -> &ProductId {
+        &self.product
+    }
+
+    pub fn quantity(&self) -> &OrderQuantity {
+        &self.quantity
+    }
+
+    pub fn price(&self) -> &ProductPrice {
+        &self.price
+    }
+
+    pub fn options(&self) -> &[SelectedOption] {
+        &self.options
+    }
+
+    pub fn components(&self) -> &[BundleChoice] {
+        &self.components
+    }
+
+    pub fn tax(&self) -> &LineTax {
+        &self.tax
+    }
+
+    /// All amounts of the line are in the minor units of this currency.
+    pub fn currency(&self) -> &Currency {
+        self.price.currency()
+    }
+
+    /// The listed price of the product including the price deltas of the selected options.
+    pub fn listed_price(&self) -> i64 {
+        self.price.amount() + self.options.iter().map(|option| option.price_delta().amount()).sum::<i64>()
+    }
+
+    /// The tax-inclusive price charged for a single unit.
+    pub fn unit_price(&self) -> i64 {
+        self.tax.gross(self.listed_price())
+    }
+
+    pub fn subtotal(&self) -> i64 {
+        self.unit_price() * self.quantity.as_ref()
+    }
+}
@@ -0,0 +1,92 @@
+use crate::entities::money::{Currency, Money};
+use crate::errors::ValidationError;
+use error_stack::Report;
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+/// How the customer paid for an [`Order`](crate::entities::order::Order).
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Tender {
+    Cash,
+    /// QR code payments and other e-money.
+    EMoney,
+    PrepaidCard,
+}
+
+impl Tender {
+    /// Only cash can be tendered in excess of the total and be given change.
+    pub fn gives_change(&self) -> bool {
+        matches!(self, Tender::Cash)
+    }
+}
+
+impl AsRef<str> for Tender {
+    fn as_ref(&self) -> &str {
+        match self {
+            Tender::Cash => "cash",
+            Tender::EMoney => "e_money",
+            Tender::PrepaidCard => "prepaid_card",
+        }
+    }
+}
+
+impl Display for Tender {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_ref())
+    }
+}
+
+/// Amounts are in the currency of the order. 
+/// Payments journaled before currencies were introduced are read as yen.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Payment {
+    tender: Tender,
+    amount: Money,
+    tendered: Money,
+    change: Money,
+}
+
+impl Payment {
+    /// Validates `tendered` against the `amount` due and calculates the change.
+    pub fn new(tender: Tender, amount: Money, tendered: Money) -> Result<Payment, Report<ValidationError>> {
+        if tendered.currency() != amount.currency() {
+            return Err(Report::new(ValidationError)
+                .attach_printable(format!("Tendered {} cannot pay the total in {}", tendered.currency(), amount.currency())));
+        }
+
+        if tendered.amount() < amount.amount() {
+            return Err(Report::new(ValidationError)
+                .attach_printable(format!("Tendered amount {tendered} is less than the total {amount}")));
+        }
+
+        if !tender.gives_change() && tendered != amount {
+            return Err(Report::new(ValidationError)
+                .attach_printable(format!("Tender={tender} must be exactly the total {amount}")));
+        }
+
+        let change = Money::new(tendered.amount() - amount.amount(), amount.currency().clone());
+
+        Ok(Payment { tender, amount, tendered, change })
+    }
+
+    pub fn tender(&self) -> &Tender {
+        &self.tender
+    }
+
+    pub fn amount(&self) -> &Money {
+        &self.amount
+    }
+
+    pub fn tendered(&self) -> &Money {
+        &self.tendered
+    }
+
+    pub fn change(&self) -> &Money {
+        &self.change
+    }
+
+    pub fn currency(&self) -> &Currency {
+        self.amount.currency()
+    }
+}
@@ -0,0 +1,30 @@
+use crate::errors::ValidationError;
+use error_stack::Report;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub struct OrderQuantity(i64);
+
+impl OrderQuantity {
+    pub fn new(quantity: impl Into<i64>) -> Result<OrderQuantity, Report<ValidationError>> {
+        let quantity = quantity.into();
+        if quantity < 1 {
+            return Err(Report::new(ValidationError)
+                .attach_printable("`OrderQuantity` must be greater than zero"));
+        }
+
+        Ok(Self(quantity))
+    }
+}
+
+impl AsRef<i64> for OrderQuantity {
+    fn as_ref(&self) -> &i64 {
+        &self.0
+    }
+}
+
+impl From<OrderQuantity> for i64 {
+    fn from(quantity: OrderQuantity) -> Self {
+        quantity.0
+    }
+}
@@ -0,0 +1,54 @@
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+/// Lifecycle of an [`Order`](crate::entities::order::Order).
+///
+/// ```text
+/// Placed ──(Confirm)──> Accepted ──> Preparing ──> Ready ──> Completed
+///    └───────────────────────┴────────────┴──────────┴──(Cancel)──> Cancelled
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OrderStatus {
+    Placed,
+    Accepted,
+    Preparing,
+    Ready,
+    Completed,
+    Cancelled,
+}
+
+impl OrderStatus {
+    /// Whether the kitchen may move an order from this status to `next`.
+    pub fn can_transition_to(&self, next: &OrderStatus) -> bool {
+        matches!(
+            (self, next),
+            (OrderStatus::Accepted, OrderStatus::Preparing)
+                | (OrderStatus::Preparing, OrderStatus::Ready)
+                | (OrderStatus::Ready, OrderStatus::Completed)
+        )
+    }
+
+    pub fn is_cancellable(&self) -> bool {
+        !matches!(self, OrderStatus::Completed | OrderStatus::Cancelled)
+    }
+}
+
+impl AsRef<str> for OrderStatus {
+    fn as_ref(&self) -> &str {
+        match self {
+            OrderStatus::Placed => "placed",
+            OrderStatus::Accepted => "accepted",
+            OrderStatus::Preparing => "preparing",
+            OrderStatus::Ready => "ready",
+            OrderStatus::Completed => "completed",
+            OrderStatus::Cancelled => "cancelled",
+        }
+    }
+}
+
+impl Display for OrderStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_ref())
+    }
+}
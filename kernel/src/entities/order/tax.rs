@@ -0,0 +1,118 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::entities::order::{AppliedCoupon, OrderLine};
+use crate::entities::product::{ProductTax, TaxRate, TaxRates};
+
+/// How an [`OrderLine`] is taxed, with the rate at the time it was added to the order.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct LineTax {
+    rate: TaxRate,
+    inclusive: bool,
+}
+
+impl LineTax {
+    pub fn new(rate: TaxRate, inclusive: bool) -> LineTax {
+        LineTax { rate, inclusive }
+    }
+
+    /// Resolves the rate of the tax category of a product.
+    pub fn of(tax: &ProductTax, rates: &TaxRates) -> LineTax {
+        LineTax { rate: rates.rate(tax.category()), inclusive: tax.inclusive() }
+    }
+
+    pub fn rate(&self) -> &TaxRate {
+        &self.rate
+    }
+
+    /// `true` if the listed price already includes the tax.
+    pub fn inclusive(&self) -> bool {
+        self.inclusive
+    }
+
+    /// The tax-inclusive amount of a listed price.
+    pub fn gross(&self, listed: i64) -> i64 {
+        if self.inclusive {
+            listed
+        } else {
+            listed + self.rate.tax_on(listed)
+        }
+    }
+}
+
+impl Default for LineTax {
+    /// Lines ordered before taxes were introduced were charged as listed.
+    fn default() -> Self {
+        LineTax { rate: TaxRate::default(), inclusive: true }
+    }
+}
+
+/// Amount charged at a single [`TaxRate`] and the tax contained in it, as printed on receipts.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct TaxSummary {
+    rate: TaxRate,
+    gross: i64,
+    tax: i64,
+}
+
+impl TaxSummary {
+    /// Calculates the tax contained in the tax-inclusive `gross`.
+    pub fn new(rate: TaxRate, gross: i64) -> TaxSummary {
+        TaxSummary { rate, gross, tax: rate.tax_within(gross) }
+    }
+
+    pub fn rate(&self) -> &TaxRate {
+        &self.rate
+    }
+
+    /// Tax-inclusive amount after discounts.
+    pub fn gross(&self) -> i64 {
+        self.gross
+    }
+
+    pub fn tax(&self) -> i64 {
+        self.tax
+    }
+
+    /// Tax-exclusive amount.
+    pub fn net(&self) -> i64 {
+        self.gross - self.tax
+    }
+}
+
+/// Sums the lines by tax rate.
+///
+/// The discount of the coupon is allocated to each rate in proportion to its eligible amount,
+/// with the remainder of the rounding going to the highest rate.
+pub(crate) fn breakdown(lines: &BTreeMap<i64, OrderLine>, coupon: Option<&AppliedCoupon>) -> Vec<TaxSummary> {
+    let mut gross = BTreeMap::<TaxRate, i64>::new();
+    let mut eligible = BTreeMap::<TaxRate, i64>::new();
+
+    for line in lines.values() {
+        *gross.entry(*line.tax().rate()).or_default() += line.subtotal();
+        if coupon.is_some_and(|coupon| coupon.is_eligible(line)) {
+            *eligible.entry(*line.tax().rate()).or_default() += line.subtotal();
+        }
+    }
+
+    if let Some(coupon) = coupon.filter(|_| eligible.values().sum::<i64>() > 0) {
+        let discount = coupon.amount(lines);
+        let total = eligible.values().sum::<i64>();
+        let mut allocated = 0;
+
+        for (index, (rate, amount)) in eligible.iter().enumerate() {
+            let share = if index + 1 == eligible.len() {
+                discount - allocated
+            } else {
+                discount * amount / total
+            };
+            allocated += share;
+            *gross.entry(*rate).or_default() -= share;
+        }
+    }
+
+    gross.into_iter()
+        .map(|(rate, gross)| TaxSummary::new(rate, gross))
+        .collect()
+}
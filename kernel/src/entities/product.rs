@@ -1,14 +1,22 @@
+mod bundle;
 mod desc;
 mod id;
 mod name;
+mod option;
+mod option_id;
+mod option_name;
 mod price;
+mod slot_id;
+mod slot_name;
+mod stock;
+mod tax;
 
-pub use self::{desc::*, id::*, name::*, price::*};
+pub use self::{bundle::*, desc::*, id::*, name::*, option::*, option_id::*, option_name::*, price::*, slot_id::*, slot_name::*, stock::*, tax::*};
 
-use std::convert::Infallible;
+use std::collections::BTreeSet;
 use async_trait::async_trait;
 use destructure::{Destructure, Mutation};
-use error_stack::Report;
+use error_stack::{Report, ResultExt};
 use serde::{Deserialize, Serialize};
 
 use nitinol::process::eventstream::WithStreamPublisher;
@@ -18,7 +26,9 @@ use nitinol::projection::resolver::{Mapper, ResolveMapping};
 use nitinol::projection::Projection;
 use nitinol::{EntityId, ToEntityId};
 use crate::entities::image::{Image, ImageId};
-use crate::errors::{FormationError, ValidationError};
+use crate::entities::money::Money;
+use crate::entities::schedule::Schedule;
+use crate::errors::{DeletedError, FormationError, ValidationError};
 use crate::io::commands::ProductCommand;
 use crate::io::events::ProductEvent;
 
@@ -28,6 +38,13 @@ pub struct Product {
     name: ProductName,
     desc: ProductDesc,
     price: ProductPrice,
+    marked_sold_out: bool,
+    stock: Option<ProductStock>,
+    option_groups: Vec<ProductOptionGroup>,
+    bundle: Vec<BundleSlot>,
+    bundled_in: BTreeSet<ProductId>,
+    schedule: Option<Schedule>,
+    tax: ProductTax,
 }
 
 impl Product {
@@ -42,6 +59,13 @@ impl Product {
             name,
             desc,
             price,
+            marked_sold_out: false,
+            stock: None,
+            option_groups: Vec::new(),
+            bundle: Vec::new(),
+            bundled_in: BTreeSet::new(),
+            schedule: None,
+            tax: ProductTax::default(),
         }
     }
 
@@ -60,6 +84,136 @@ impl Product {
     pub fn price(&self) -> &ProductPrice {
         &self.price
     }
+
+    /// `false` while the product is marked as sold out, or has run out of stock.
+    pub fn available(&self) -> bool {
+        !self.marked_sold_out && !self.stock.is_some_and(|stock| stock.is_empty())
+    }
+
+    /// Whether the product has been marked as sold out by hand, regardless of its stock.
+    pub fn marked_sold_out(&self) -> bool {
+        self.marked_sold_out
+    }
+
+    /// `None` if the stock of this product is not tracked.
+    pub fn stock(&self) -> Option<&ProductStock> {
+        self.stock.as_ref()
+    }
+
+    pub fn option_groups(&self) -> &[ProductOptionGroup] {
+        &self.option_groups
+    }
+
+    pub fn option_group(&self, id: &OptionGroupId) -> Option<&ProductOptionGroup> {
+        self.option_groups.iter().find(|group| group.id().eq(id))
+    }
+
+    /// Validates the selection of options for an order line and snapshots
+    /// their names and price deltas.
+    pub fn select_options(&self, selected: &[OptionId]) -> Result<Vec<SelectedOption>, Report<ValidationError>> {
+        option::select(&self.option_groups, selected)
+    }
+
+    pub fn tax(&self) -> &ProductTax {
+        &self.tax
+    }
+
+    /// `None` if the product is always available.
+    pub fn schedule(&self) -> Option<&Schedule> {
+        self.schedule.as_ref()
+    }
+
+    /// Slots of a set menu. Empty unless this product is a bundle of other products.
+    pub fn bundle(&self) -> &[BundleSlot] {
+        &self.bundle
+    }
+
+    pub fn is_bundle(&self) -> bool {
+        !self.bundle.is_empty()
+    }
+
+    /// Bundles that offer this product as a choice of one of their slots.
+    pub fn bundled_in(&self) -> &BTreeSet<ProductId> {
+        &self.bundled_in
+    }
+
+    /// Validates the products chosen for the slots of this bundle, ordered by slot.
+    pub fn choose_components(&self, chosen: &[BundleChoice]) -> Result<Vec<BundleChoice>, Report<ValidationError>> {
+        bundle::choose(&self.bundle, chosen)
+    }
+
+    fn ensure_option_group(&self, id: &OptionGroupId) -> Result<&ProductOptionGroup, Report<ValidationError>> {
+        self.option_group(id)
+            .ok_or_else(|| Report::new(ValidationError)
+                .attach_printable(format!("OptionGroup={id} does not exist in product")))
+    }
+
+    fn ensure_currency(&self, delta: &Money) -> Result<(), Report<ValidationError>> {
+        if delta.currency() != self.price.currency() {
+            return Err(Report::new(ValidationError)
+                .attach_printable(format!("Price delta in {} does not match the price of Product={} in {}", delta.currency(), self.id, self.price.currency())));
+        }
+        Ok(())
+    }
+
+    fn ensure_bundle_slot(&self, id: &SlotId) -> Result<(), Report<ValidationError>> {
+        if !self.bundle.iter().any(|slot| slot.id().eq(id)) {
+            return Err(Report::new(ValidationError)
+                .attach_printable(format!("Slot={id} does not exist in product")));
+        }
+        Ok(())
+    }
+
+    fn change_bundle(&mut self, event: ProductEvent) {
+        match event {
+            ProductEvent::AddedBundleSlot { slot, .. } => {
+                self.bundle.push(slot);
+            }
+            ProductEvent::ChangedBundleChoices { slot, choices, .. } => {
+                if let Some(slot) = self.bundle.iter_mut().find(|exist| exist.id().eq(&slot)) {
+                    slot.change_choices(choices);
+                }
+            }
+            ProductEvent::RemovedBundleSlot { slot, .. } => {
+                self.bundle.retain(|exist| exist.id().ne(&slot));
+            }
+            ProductEvent::JoinedBundle { bundle, .. } => {
+                self.bundled_in.insert(bundle);
+            }
+            ProductEvent::LeftBundle { bundle, .. } => {
+                self.bundled_in.remove(&bundle);
+            }
+            _ => {}
+        }
+    }
+
+    fn change_options(&mut self, event: ProductEvent) {
+        match event {
+            ProductEvent::AddedOptionGroup { group, .. } => {
+                self.option_groups.push(group);
+            }
+            ProductEvent::EditedOptionGroup { group, name, min, max, .. } => {
+                if let Some(group) = self.option_groups.iter_mut().find(|exist| exist.id().eq(&group)) {
+                    group.edit(name, min, max);
+                }
+            }
+            ProductEvent::RemovedOptionGroup { group, .. } => {
+                self.option_groups.retain(|exist| exist.id().ne(&group));
+            }
+            ProductEvent::AddedOption { group, option, .. }
+            | ProductEvent::EditedOption { group, option, .. } => {
+                if let Some(group) = self.option_groups.iter_mut().find(|exist| exist.id().eq(&group)) {
+                    group.upsert_option(option);
+                }
+            }
+            ProductEvent::RemovedOption { group, option, .. } => {
+                if let Some(group) = self.option_groups.iter_mut().find(|exist| exist.id().eq(&group)) {
+                    group.remove_option(&option);
+                }
+            }
+            _ => {}
+        }
+    }
 }
 
 impl TryFrom<(ProductId, ProductCommand)> for Product {
@@ -71,7 +225,7 @@ impl TryFrom<(ProductId, ProductCommand)> for Product {
                 .attach_printable("ProductCommand::Register is the only command that can be converted to Product", ));
         };
         
-        Ok(Product { id: value.0, name, desc, price })
+        Ok(Product::new(value.0, name, desc, price))
     }
 }
 
@@ -112,13 +266,145 @@ impl Publisher<ProductCommand> for Product {
                 Ok(ProductEvent::EditedProductDesc { id: self.id, new }) 
             }
             ProductCommand::ChangeProductPrice { new } => {
+                let options = self.option_groups.iter().any(|group| !group.options().is_empty());
+                if options && new.currency() != self.price.currency() {
+                    return Err(Report::new(ValidationError)
+                        .attach_printable(format!("Product={} has options priced in {}", self.id, self.price.currency())));
+                }
                 Ok(ProductEvent::ChangedProductPrice { id: self.id, new })
             }
             ProductCommand::ChangeProductImage { image } => {
                 let image = Image::new(ImageId::from(self.id), image);
                 Ok(ProductEvent::ChangedProductImage { id: self.id, image })
             }
+            ProductCommand::MarkSoldOut => {
+                if self.marked_sold_out {
+                    return Err(Report::new(ValidationError)
+                        .attach_printable(format!("Product={} is already marked as sold out", self.id)));
+                }
+                Ok(ProductEvent::MarkedSoldOut { id: self.id })
+            }
+            ProductCommand::MarkAvailable => {
+                if !self.marked_sold_out {
+                    return Err(Report::new(ValidationError)
+                        .attach_printable(format!("Product={} is not marked as sold out", self.id)));
+                }
+                Ok(ProductEvent::MarkedAvailable { id: self.id })
+            }
+            ProductCommand::SetStock { new } => {
+                Ok(ProductEvent::ChangedStock { id: self.id, new })
+            }
+            ProductCommand::Restock { amount } => {
+                if amount.is_empty() {
+                    return Err(Report::new(ValidationError)
+                        .attach_printable("Restock amount must be greater than zero"));
+                }
+                let new = self.stock
+                    .map(|stock| stock.restock(&amount))
+                    .unwrap_or(amount);
+                Ok(ProductEvent::Restocked { id: self.id, amount, new })
+            }
+            ProductCommand::DecrementStock { amount } => {
+                let Some(stock) = self.stock else {
+                    return Err(Report::new(ValidationError)
+                        .attach_printable(format!("Product={} does not track its stock", self.id)));
+                };
+                if amount.is_empty() {
+                    return Err(Report::new(ValidationError)
+                        .attach_printable("Decrement amount must be greater than zero"));
+                }
+                let new = stock.decrement(&amount)
+                    .attach_printable_lazy(|| format!("Product={} does not have enough stock", self.id))?;
+                Ok(ProductEvent::DecrementedStock { id: self.id, amount, new })
+            }
+            ProductCommand::AddOptionGroup { name, min, max } => {
+                let group = ProductOptionGroup::new(OptionGroupId::default(), name, min, max)?;
+                Ok(ProductEvent::AddedOptionGroup { id: self.id, group })
+            }
+            ProductCommand::EditOptionGroup { group, name, min, max } => {
+                self.ensure_option_group(&group)?;
+                ProductOptionGroup::validate_range(min, max)?;
+                Ok(ProductEvent::EditedOptionGroup { id: self.id, group, name, min, max })
+            }
+            ProductCommand::RemoveOptionGroup { group } => {
+                self.ensure_option_group(&group)?;
+                Ok(ProductEvent::RemovedOptionGroup { id: self.id, group })
+            }
+            ProductCommand::AddOption { group, name, price_delta } => {
+                self.ensure_option_group(&group)?;
+                self.ensure_currency(&price_delta)?;
+                let option = ProductOption::new(OptionId::default(), name, price_delta);
+                Ok(ProductEvent::AddedOption { id: self.id, group, option })
+            }
+            ProductCommand::EditOption { group, option, name, price_delta } => {
+                if self.ensure_option_group(&group)?.option(&option).is_none() {
+                    return Err(Report::new(ValidationError)
+                        .attach_printable(format!("Option={option} does not exist in group")));
+                }
+                self.ensure_currency(&price_delta)?;
+                let option = ProductOption::new(option, name, price_delta);
+                Ok(ProductEvent::EditedOption { id: self.id, group, option })
+            }
+            ProductCommand::RemoveOption { group, option } => {
+                if self.ensure_option_group(&group)?.option(&option).is_none() {
+                    return Err(Report::new(ValidationError)
+                        .attach_printable(format!("Option={option} does not exist in group")));
+                }
+                Ok(ProductEvent::RemovedOption { id: self.id, group, option })
+            }
+            ProductCommand::AddBundleSlot { name, choices } => {
+                if let Some(bundle) = self.bundled_in.first() {
+                    return Err(Report::new(ValidationError)
+                        .attach_printable(format!("Product={} is a choice of Bundle={bundle} and cannot be a bundle itself", self.id)));
+                }
+                let choices = bundle::compose(&self.id, &choices)?;
+                Ok(ProductEvent::AddedBundleSlot { id: self.id, slot: BundleSlot::new(SlotId::default(), name, choices) })
+            }
+            ProductCommand::ChangeBundleChoices { slot, choices } => {
+                self.ensure_bundle_slot(&slot)?;
+                let choices = bundle::compose(&self.id, &choices)?;
+                Ok(ProductEvent::ChangedBundleChoices { id: self.id, slot, choices })
+            }
+            ProductCommand::RemoveBundleSlot { slot } => {
+                self.ensure_bundle_slot(&slot)?;
+                Ok(ProductEvent::RemovedBundleSlot { id: self.id, slot })
+            }
+            ProductCommand::JoinBundle { bundle } => {
+                if self.is_bundle() {
+                    return Err(Report::new(ValidationError)
+                        .attach_printable(format!("Product={} is a bundle and cannot be nested", self.id)));
+                }
+                if self.bundled_in.contains(&bundle) {
+                    return Err(Report::new(ValidationError)
+                        .attach_printable(format!("Product={} is already a choice of Bundle={bundle}", self.id)));
+                }
+                Ok(ProductEvent::JoinedBundle { id: self.id, bundle })
+            }
+            ProductCommand::LeaveBundle { bundle } => {
+                if !self.bundled_in.contains(&bundle) {
+                    return Err(Report::new(ValidationError)
+                        .attach_printable(format!("Product={} is not a choice of Bundle={bundle}", self.id)));
+                }
+                Ok(ProductEvent::LeftBundle { id: self.id, bundle })
+            }
+            ProductCommand::ChangeTax { new } => {
+                Ok(ProductEvent::ChangedTax { id: self.id, new })
+            }
+            ProductCommand::ChangeSchedule { new } => {
+                Ok(ProductEvent::ChangedSchedule { id: self.id, new })
+            }
+            ProductCommand::ClearSchedule => {
+                if self.schedule.is_none() {
+                    return Err(Report::new(ValidationError)
+                        .attach_printable(format!("Product={} does not have a schedule", self.id)));
+                }
+                Ok(ProductEvent::ClearedSchedule { id: self.id })
+            }
             ProductCommand::Delete => { 
+                if let Some(bundle) = self.bundled_in.first() {
+                    return Err(Report::new(ValidationError)
+                        .attach_printable(format!("Product={} is still a choice of Bundle={bundle}", self.id)));
+                }
                 Ok(ProductEvent::Deleted { id: self.id }) 
             }
         }
@@ -144,6 +430,41 @@ impl Applicator<ProductEvent> for Product {
             ProductEvent::ChangedProductPrice { new, .. } => {
                 self.price = new;
             }
+            ProductEvent::MarkedSoldOut { .. } => {
+                self.marked_sold_out = true;
+            }
+            ProductEvent::MarkedAvailable { .. } => {
+                self.marked_sold_out = false;
+            }
+            ProductEvent::ChangedStock { new, .. }
+            | ProductEvent::Restocked { new, .. }
+            | ProductEvent::DecrementedStock { new, .. } => {
+                self.stock = Some(new);
+            }
+            ProductEvent::AddedOptionGroup { .. }
+            | ProductEvent::EditedOptionGroup { .. }
+            | ProductEvent::RemovedOptionGroup { .. }
+            | ProductEvent::AddedOption { .. }
+            | ProductEvent::EditedOption { .. }
+            | ProductEvent::RemovedOption { .. } => {
+                self.change_options(event);
+            }
+            ProductEvent::AddedBundleSlot { .. }
+            | ProductEvent::ChangedBundleChoices { .. }
+            | ProductEvent::RemovedBundleSlot { .. }
+            | ProductEvent::JoinedBundle { .. }
+            | ProductEvent::LeftBundle { .. } => {
+                self.change_bundle(event);
+            }
+            ProductEvent::ChangedTax { new, .. } => {
+                self.tax = new;
+            }
+            ProductEvent::ChangedSchedule { new, .. } => {
+                self.schedule = Some(new);
+            }
+            ProductEvent::ClearedSchedule { .. } => {
+                self.schedule = None;
+            }
             ProductEvent::Deleted { .. } => {
                 ctx.poison_pill().await;
             }
@@ -162,7 +483,7 @@ impl ResolveMapping for Product {
 
 #[async_trait]
 impl Projection<ProductEvent> for Product {
-    type Rejection = Infallible;
+    type Rejection = DeletedError;
 
     async fn first(event: ProductEvent) -> Result<Self, Self::Rejection> {
         let ProductEvent::Registered { id, name, desc, price, .. } = event else {
@@ -183,8 +504,43 @@ impl Projection<ProductEvent> for Product {
             ProductEvent::ChangedProductPrice { new, .. } => {
                 self.price = new;
             }
+            ProductEvent::MarkedSoldOut { .. } => {
+                self.marked_sold_out = true;
+            }
+            ProductEvent::MarkedAvailable { .. } => {
+                self.marked_sold_out = false;
+            }
+            ProductEvent::ChangedStock { new, .. }
+            | ProductEvent::Restocked { new, .. }
+            | ProductEvent::DecrementedStock { new, .. } => {
+                self.stock = Some(new);
+            }
+            ProductEvent::AddedOptionGroup { .. }
+            | ProductEvent::EditedOptionGroup { .. }
+            | ProductEvent::RemovedOptionGroup { .. }
+            | ProductEvent::AddedOption { .. }
+            | ProductEvent::EditedOption { .. }
+            | ProductEvent::RemovedOption { .. } => {
+                self.change_options(event);
+            }
+            ProductEvent::AddedBundleSlot { .. }
+            | ProductEvent::ChangedBundleChoices { .. }
+            | ProductEvent::RemovedBundleSlot { .. }
+            | ProductEvent::JoinedBundle { .. }
+            | ProductEvent::LeftBundle { .. } => {
+                self.change_bundle(event);
+            }
+            ProductEvent::ChangedTax { new, .. } => {
+                self.tax = new;
+            }
+            ProductEvent::ChangedSchedule { new, .. } => {
+                self.schedule = Some(new);
+            }
+            ProductEvent::ClearedSchedule { .. } => {
+                self.schedule = None;
+            }
             ProductEvent::Deleted { .. } => {
-                panic!("This entity has a delete event issued.");
+                return Err(DeletedError);
             }
             _ => {}
         }
@@ -0,0 +1,136 @@
+use std::collections::HashSet;
+
+use error_stack::Report;
+use serde::{Deserialize, Serialize};
+
+use crate::entities::product::{Product, ProductId, SlotId, SlotName};
+use crate::errors::ValidationError;
+
+/// A slot of a bundle such as "drink", filled with one of `choices` when ordered.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct BundleSlot {
+    id: SlotId,
+    name: SlotName,
+    choices: Vec<ProductId>,
+}
+
+impl BundleSlot {
+    pub fn new(id: SlotId, name: SlotName, choices: Vec<ProductId>) -> BundleSlot {
+        BundleSlot { id, name, choices }
+    }
+
+    pub fn id(&self) -> &SlotId {
+        &self.id
+    }
+
+    pub fn name(&self) -> &SlotName {
+        &self.name
+    }
+
+    pub fn choices(&self) -> &[ProductId] {
+        &self.choices
+    }
+
+    pub(crate) fn change_choices(&mut self, choices: Vec<ProductId>) {
+        self.choices = choices;
+    }
+}
+
+/// A product that can be put into a [`BundleSlot`].
+///
+/// It can only be created from an existing [`Product`], 
+/// so a bundle never refers to a product that has not been registered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BundleComponent {
+    product: ProductId,
+    bundle: bool,
+}
+
+impl BundleComponent {
+    pub fn product(&self) -> &ProductId {
+        &self.product
+    }
+}
+
+impl From<&Product> for BundleComponent {
+    fn from(product: &Product) -> Self {
+        BundleComponent { product: *product.id(), bundle: product.is_bundle() }
+    }
+}
+
+/// The product chosen for a [`BundleSlot`] of an ordered bundle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub struct BundleChoice {
+    slot: SlotId,
+    product: ProductId,
+}
+
+impl BundleChoice {
+    pub fn new(slot: SlotId, product: ProductId) -> BundleChoice {
+        BundleChoice { slot, product }
+    }
+
+    pub fn slot(&self) -> &SlotId {
+        &self.slot
+    }
+
+    pub fn product(&self) -> &ProductId {
+        &self.product
+    }
+}
+
+/// Checks that `bundle` (the id of the bundle itself) can be composed of `components`.
+pub(crate) fn compose(bundle: &ProductId, components: &[BundleComponent]) -> Result<Vec<ProductId>, Report<ValidationError>> {
+    if components.is_empty() {
+        return Err(Report::new(ValidationError)
+            .attach_printable("Bundle slot must have at least one choice"));
+    }
+
+    let mut choices = Vec::with_capacity(components.len());
+    for component in components {
+        if component.product.eq(bundle) {
+            return Err(Report::new(ValidationError)
+                .attach_printable("Bundle cannot contain itself"));
+        }
+        if component.bundle {
+            return Err(Report::new(ValidationError)
+                .attach_printable(format!("Product={} is a bundle and cannot be nested", component.product)));
+        }
+        if choices.contains(&component.product) {
+            return Err(Report::new(ValidationError)
+                .attach_printable(format!("Product={} is listed twice", component.product)));
+        }
+        choices.push(component.product);
+    }
+
+    Ok(choices)
+}
+
+/// Checks that every slot of `slots` is filled with exactly one of its choices.
+pub(crate) fn choose(slots: &[BundleSlot], chosen: &[BundleChoice]) -> Result<Vec<BundleChoice>, Report<ValidationError>> {
+    let filled = chosen.iter().map(|choice| choice.slot).collect::<HashSet<_>>();
+    if filled.len() != chosen.len() {
+        return Err(Report::new(ValidationError)
+            .attach_printable("The same slot cannot be filled twice"));
+    }
+
+    let mut sorted = Vec::with_capacity(slots.len());
+    for slot in slots {
+        let Some(choice) = chosen.iter().find(|choice| choice.slot.eq(&slot.id)) else {
+            return Err(Report::new(ValidationError)
+                .attach_printable(format!("Slot={} must be filled", slot.id)));
+        };
+        if !slot.choices.contains(&choice.product) {
+            return Err(Report::new(ValidationError)
+                .attach_printable(format!("Product={} is not a choice of Slot={}", choice.product, slot.id)));
+        }
+        sorted.push(*choice);
+    }
+
+    if sorted.len() != chosen.len() {
+        return Err(Report::new(ValidationError)
+            .attach_printable("Chosen components contain an unknown slot"));
+    }
+
+    Ok(sorted)
+}
@@ -0,0 +1,165 @@
+use std::collections::HashSet;
+
+use error_stack::Report;
+use serde::{Deserialize, Serialize};
+
+use crate::entities::money::Money;
+use crate::entities::product::{OptionGroupId, OptionId, OptionName};
+use crate::errors::ValidationError;
+
+/// A choice such as "extra egg", which changes the price of the product by `price_delta`.
+///
+/// The delta is in the currency of the product price.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ProductOption {
+    id: OptionId,
+    name: OptionName,
+    price_delta: Money,
+}
+
+impl ProductOption {
+    pub fn new(id: OptionId, name: OptionName, price_delta: Money) -> ProductOption {
+        ProductOption { id, name, price_delta }
+    }
+
+    pub fn id(&self) -> &OptionId {
+        &self.id
+    }
+
+    pub fn name(&self) -> &OptionName {
+        &self.name
+    }
+
+    pub fn price_delta(&self) -> &Money {
+        &self.price_delta
+    }
+}
+
+/// A set of [`ProductOption`]s of which at least `min` and at most `max` have to be selected.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ProductOptionGroup {
+    id: OptionGroupId,
+    name: OptionName,
+    min: i64,
+    max: i64,
+    options: Vec<ProductOption>,
+}
+
+impl ProductOptionGroup {
+    pub fn new(id: OptionGroupId, name: OptionName, min: i64, max: i64) -> Result<ProductOptionGroup, Report<ValidationError>> {
+        Self::validate_range(min, max)?;
+        Ok(ProductOptionGroup { id, name, min, max, options: Vec::new() })
+    }
+
+    pub fn validate_range(min: i64, max: i64) -> Result<(), Report<ValidationError>> {
+        if min < 0 || max < 1 || min > max {
+            return Err(Report::new(ValidationError)
+                .attach_printable(format!("Selection range {min}..={max} is invalid")));
+        }
+        Ok(())
+    }
+
+    pub fn id(&self) -> &OptionGroupId {
+        &self.id
+    }
+
+    pub fn name(&self) -> &OptionName {
+        &self.name
+    }
+
+    pub fn min(&self) -> i64 {
+        self.min
+    }
+
+    pub fn max(&self) -> i64 {
+        self.max
+    }
+
+    pub fn options(&self) -> &[ProductOption] {
+        &self.options
+    }
+
+    pub fn option(&self, id: &OptionId) -> Option<&ProductOption> {
+        self.options.iter().find(|option| option.id.eq(id))
+    }
+
+    pub(crate) fn edit(&mut self, name: OptionName, min: i64, max: i64) {
+        self.name = name;
+        self.min = min;
+        self.max = max;
+    }
+
+    pub(crate) fn upsert_option(&mut self, option: ProductOption) {
+        match self.options.iter_mut().find(|exist| exist.id.eq(&option.id)) {
+            Some(exist) => *exist = option,
+            None => self.options.push(option),
+        }
+    }
+
+    pub(crate) fn remove_option(&mut self, id: &OptionId) {
+        self.options.retain(|option| option.id.ne(id));
+    }
+}
+
+/// Snapshot of a [`ProductOption`] chosen for an order line.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct SelectedOption {
+    group: OptionGroupId,
+    option: OptionId,
+    name: OptionName,
+    price_delta: Money,
+}
+
+impl SelectedOption {
+    pub fn new(group: OptionGroupId, option: OptionId, name: OptionName, price_delta: Money) -> SelectedOption {
+        SelectedOption { group, option, name, price_delta }
+    }
+
+    pub fn group(&self) -> &OptionGroupId {
+        &self.group
+    }
+
+    pub fn option(&self) -> &OptionId {
+        &self.option
+    }
+
+    pub fn name(&self) -> &OptionName {
+        &self.name
+    }
+
+    pub fn price_delta(&self) -> &Money {
+        &self.price_delta
+    }
+}
+
+/// Checks `selected` against the selection ranges of `groups`.
+pub(crate) fn select(groups: &[ProductOptionGroup], selected: &[OptionId]) -> Result<Vec<SelectedOption>, Report<ValidationError>> {
+    let unique = selected.iter().collect::<HashSet<_>>();
+    if unique.len() != selected.len() {
+        return Err(Report::new(ValidationError)
+            .attach_printable("The same option cannot be selected twice"));
+    }
+
+    let mut snapshot = Vec::with_capacity(selected.len());
+    for group in groups {
+        let chosen = group.options.iter()
+            .filter(|option| unique.contains(&option.id))
+            .map(|option| SelectedOption::new(group.id, option.id, option.name.clone(), option.price_delta.clone()))
+            .collect::<Vec<_>>();
+
+        let count = chosen.len() as i64;
+        if count < group.min || count > group.max {
+            return Err(Report::new(ValidationError)
+                .attach_printable(format!("OptionGroup={} requires {}..={} selections, but got {count}", group.id, group.min, group.max)));
+        }
+
+        snapshot.extend(chosen);
+    }
+
+    if snapshot.len() != selected.len() {
+        return Err(Report::new(ValidationError)
+            .attach_printable("Selected options contain an unknown option"));
+    }
+
+    Ok(snapshot)
+}
@@ -0,0 +1,22 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub struct OptionName(String);
+
+impl OptionName {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self(name.into())
+    }
+}
+
+impl AsRef<str> for OptionName {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<OptionName> for String {
+    fn from(name: OptionName) -> Self {
+        name.0
+    }
+}
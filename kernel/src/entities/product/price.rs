@@ -1,29 +1,39 @@
+use crate::entities::money::{Currency, Money};
 use crate::errors::ValidationError;
 use error_stack::Report;
 use serde::{Deserialize, Serialize};
 
+/// The listed price of a product. Price deltas of its options are in the same currency.
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
-pub struct ProductPrice(i64);
+pub struct ProductPrice(Money);
 
 impl ProductPrice {
-    pub fn new(price: impl Into<i64>) -> Result<ProductPrice, Report<ValidationError>> {
-        let price = price.into();
-        if price < 0 {
+    pub fn new(price: Money) -> Result<ProductPrice, Report<ValidationError>> {
+        if price.amount() < 0 {
             return Err(Report::new(ValidationError)
                 .attach_printable("`ProductPrice` must be greater than or equal to zero"));
         }
 
         Ok(Self(price))
     }
+
+    /// Amount in minor units of the currency.
+    pub fn amount(&self) -> i64 {
+        self.0.amount()
+    }
+
+    pub fn currency(&self) -> &Currency {
+        self.0.currency()
+    }
 }
 
-impl AsRef<i64> for ProductPrice {
-    fn as_ref(&self) -> &i64 {
+impl AsRef<Money> for ProductPrice {
+    fn as_ref(&self) -> &Money {
         &self.0
     }
 }
 
-impl From<ProductPrice> for i64 {
+impl From<ProductPrice> for Money {
     fn from(price: ProductPrice) -> Self {
         price.0
     }
@@ -0,0 +1,22 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub struct SlotName(String);
+
+impl SlotName {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self(name.into())
+    }
+}
+
+impl AsRef<str> for SlotName {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<SlotName> for String {
+    fn from(name: SlotName) -> Self {
+        name.0
+    }
+}
@@ -0,0 +1,49 @@
+use crate::errors::ValidationError;
+use error_stack::Report;
+use serde::{Deserialize, Serialize};
+
+/// Remaining number of a [`Product`](crate::entities::product::Product) that can still be sold.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub struct ProductStock(i64);
+
+impl ProductStock {
+    pub fn new(stock: impl Into<i64>) -> Result<ProductStock, Report<ValidationError>> {
+        let stock = stock.into();
+        if stock < 0 {
+            return Err(Report::new(ValidationError)
+                .attach_printable("`ProductStock` must be greater than or equal to zero"));
+        }
+
+        Ok(Self(stock))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn restock(&self, amount: &ProductStock) -> ProductStock {
+        Self(self.0.saturating_add(amount.0))
+    }
+
+    /// Stock never goes below zero, taking more than is left is rejected.
+    pub fn decrement(&self, amount: &ProductStock) -> Result<ProductStock, Report<ValidationError>> {
+        if self.0 < amount.0 {
+            return Err(Report::new(ValidationError)
+                .attach_printable(format!("Only {} left in stock, {} cannot be taken", self.0, amount.0)));
+        }
+
+        Ok(Self(self.0 - amount.0))
+    }
+}
+
+impl AsRef<i64> for ProductStock {
+    fn as_ref(&self) -> &i64 {
+        &self.0
+    }
+}
+
+impl From<ProductStock> for i64 {
+    fn from(stock: ProductStock) -> Self {
+        stock.0
+    }
+}
@@ -0,0 +1,136 @@
+use crate::errors::ValidationError;
+use error_stack::Report;
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+/// Consumption tax category of a [`Product`](crate::entities::product::Product),
+/// e.g. food taken out is taxed at the reduced rate in Japan.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TaxCategory {
+    #[default]
+    Standard,
+    Reduced,
+}
+
+impl AsRef<str> for TaxCategory {
+    fn as_ref(&self) -> &str {
+        match self {
+            TaxCategory::Standard => "standard",
+            TaxCategory::Reduced => "reduced",
+        }
+    }
+}
+
+impl Display for TaxCategory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_ref())
+    }
+}
+
+/// Tax rate in percent.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Deserialize, Serialize)]
+pub struct TaxRate(i64);
+
+impl TaxRate {
+    pub fn new(rate: impl Into<i64>) -> Result<TaxRate, Report<ValidationError>> {
+        let rate = rate.into();
+        if !(0..=100).contains(&rate) {
+            return Err(Report::new(ValidationError)
+                .attach_printable("`TaxRate` must be between 0 and 100"));
+        }
+
+        Ok(Self(rate))
+    }
+
+    /// Tax added on top of a price that excludes it, rounded down.
+    pub fn tax_on(&self, net: i64) -> i64 {
+        net * self.0 / 100
+    }
+
+    /// Tax contained in a price that includes it, rounded down.
+    pub fn tax_within(&self, gross: i64) -> i64 {
+        gross * self.0 / (100 + self.0)
+    }
+}
+
+impl Default for TaxRate {
+    fn default() -> Self {
+        TaxRates::default().standard
+    }
+}
+
+impl AsRef<i64> for TaxRate {
+    fn as_ref(&self) -> &i64 {
+        &self.0
+    }
+}
+
+impl From<TaxRate> for i64 {
+    fn from(rate: TaxRate) -> Self {
+        rate.0
+    }
+}
+
+impl Display for TaxRate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}%", self.0)
+    }
+}
+
+/// The rate applied to each [`TaxCategory`]. Defaults to the rates in Japan, 10% and 8%.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct TaxRates {
+    standard: TaxRate,
+    reduced: TaxRate,
+}
+
+impl TaxRates {
+    pub fn new(standard: TaxRate, reduced: TaxRate) -> TaxRates {
+        TaxRates { standard, reduced }
+    }
+
+    pub fn rate(&self, category: &TaxCategory) -> TaxRate {
+        match category {
+            TaxCategory::Standard => self.standard,
+            TaxCategory::Reduced => self.reduced,
+        }
+    }
+}
+
+impl Default for TaxRates {
+    fn default() -> Self {
+        TaxRates { standard: TaxRate(10), reduced: TaxRate(8) }
+    }
+}
+
+/// How a [`Product`](crate::entities::product::Product) is taxed.
+///
+/// When `inclusive`, the price of the product (and the price deltas of its options) already include the tax.
+/// Otherwise the tax is added on top of it when ordered.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub struct ProductTax {
+    category: TaxCategory,
+    inclusive: bool,
+}
+
+impl ProductTax {
+    pub fn new(category: TaxCategory, inclusive: bool) -> ProductTax {
+        ProductTax { category, inclusive }
+    }
+
+    pub fn category(&self) -> &TaxCategory {
+        &self.category
+    }
+
+    pub fn inclusive(&self) -> bool {
+        self.inclusive
+    }
+}
+
+impl Default for ProductTax {
+    /// Prices registered before taxes were introduced are what customers paid, i.e. tax-inclusive.
+    fn default() -> Self {
+        ProductTax { category: TaxCategory::Standard, inclusive: true }
+    }
+}
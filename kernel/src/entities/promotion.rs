@@ -0,0 +1,241 @@
+mod cap;
+mod code;
+mod discount;
+mod name;
+mod scope;
+mod validity;
+
+pub use self::{cap::*, code::*, discount::*, name::*, scope::*, validity::*};
+
+use std::collections::BTreeSet;
+use std::convert::Infallible;
+
+use async_trait::async_trait;
+use destructure::{Destructure, Mutation};
+use error_stack::Report;
+use serde::{Deserialize, Serialize};
+
+use nitinol::process::eventstream::WithStreamPublisher;
+use nitinol::process::persistence::WithPersistence;
+use nitinol::process::{Applicator, Context, Process, Publisher};
+use nitinol::projection::resolver::{Mapper, ResolveMapping};
+use nitinol::projection::Projection;
+use nitinol::{EntityId, ToEntityId};
+use crate::entities::order::OrderId;
+use crate::errors::{FormationError, ValidationError};
+use crate::io::commands::PromotionCommand;
+use crate::io::events::PromotionEvent;
+
+/// A discount that customers receive by redeeming its [`CouponCode`] on an order.
+///
+/// Every redemption is recorded, so that a promotion with a [`UsageCap`] 
+/// is rejected once it has been exhausted.
+#[derive(Debug, Clone, Deserialize, Serialize, Destructure, Mutation)]
+pub struct Promotion {
+    code: CouponCode,
+    name: PromotionName,
+    discount: Discount,
+    scope: PromotionScope,
+    validity: Validity,
+    cap: Option<UsageCap>,
+    redemptions: BTreeSet<OrderId>,
+    ended: bool,
+}
+
+impl Promotion {
+    pub fn new(
+        code: CouponCode,
+        name: PromotionName,
+        discount: Discount,
+        scope: PromotionScope,
+        validity: Validity,
+        cap: Option<UsageCap>,
+    ) -> Promotion {
+        Promotion {
+            code,
+            name,
+            discount,
+            scope,
+            validity,
+            cap,
+            redemptions: BTreeSet::new(),
+            ended: false,
+        }
+    }
+
+    pub fn code(&self) -> &CouponCode {
+        &self.code
+    }
+
+    pub fn name(&self) -> &PromotionName {
+        &self.name
+    }
+
+    pub fn discount(&self) -> &Discount {
+        &self.discount
+    }
+
+    pub fn scope(&self) -> &PromotionScope {
+        &self.scope
+    }
+
+    pub fn validity(&self) -> &Validity {
+        &self.validity
+    }
+
+    /// `None` if the promotion can be redeemed any number of times.
+    pub fn cap(&self) -> Option<&UsageCap> {
+        self.cap.as_ref()
+    }
+
+    /// Orders the promotion has been redeemed on.
+    pub fn redemptions(&self) -> &BTreeSet<OrderId> {
+        &self.redemptions
+    }
+
+    pub fn is_exhausted(&self) -> bool {
+        self.cap.is_some_and(|cap| self.redemptions.len() as i64 >= *cap.as_ref())
+    }
+
+    /// `true` once the promotion has been ended before its validity expired.
+    pub fn ended(&self) -> bool {
+        self.ended
+    }
+
+    fn apply(&mut self, event: PromotionEvent) {
+        match event {
+            PromotionEvent::Created { .. } => {}
+            PromotionEvent::Redeemed { order, .. } => {
+                self.redemptions.insert(order);
+            }
+            PromotionEvent::Released { order, .. } => {
+                self.redemptions.remove(&order);
+            }
+            PromotionEvent::Ended { .. } => {
+                self.ended = true;
+            }
+        }
+    }
+}
+
+impl TryFrom<(CouponCode, PromotionCommand)> for Promotion {
+    type Error = Report<FormationError>;
+
+    fn try_from(value: (CouponCode, PromotionCommand)) -> Result<Self, Self::Error> {
+        let PromotionCommand::Create { name, discount, scope, validity, cap } = value.1 else {
+            return Err(Report::new(FormationError)
+                .attach_printable("PromotionCommand::Create is the only command that can be converted to Promotion"));
+        };
+
+        Ok(Self::new(value.0, name, discount, scope, validity, cap))
+    }
+}
+
+impl Process for Promotion {}
+
+impl WithPersistence for Promotion {
+    fn aggregate_id(&self) -> EntityId {
+        self.code.to_entity_id()
+    }
+}
+
+impl WithStreamPublisher for Promotion {
+    fn aggregate_id(&self) -> EntityId {
+        self.code.to_entity_id()
+    }
+}
+
+#[async_trait]
+impl Publisher<PromotionCommand> for Promotion {
+    type Event = PromotionEvent;
+    type Rejection = Report<ValidationError>;
+
+    #[tracing::instrument(skip_all, fields(promotion = %self.code))]
+    async fn publish(
+        &self,
+        command: PromotionCommand,
+        _: &mut Context,
+    ) -> Result<Self::Event, Self::Rejection> {
+        match command {
+            PromotionCommand::Create { name, discount, scope, validity, cap } => {
+                Ok(PromotionEvent::Created { code: self.code.clone(), name, discount, scope, validity, cap })
+            }
+            PromotionCommand::Redeem { order, at } => {
+                if self.ended {
+                    return Err(Report::new(ValidationError)
+                        .attach_printable(format!("Promotion={} has been ended", self.code)));
+                }
+
+                if !self.validity.contains(&at) {
+                    return Err(Report::new(ValidationError)
+                        .attach_printable(format!("Promotion={} is not valid at {at}", self.code)));
+                }
+
+                if self.redemptions.contains(&order) {
+                    return Err(Report::new(ValidationError)
+                        .attach_printable(format!("Promotion={} has already been redeemed on Order={order}", self.code)));
+                }
+
+                if self.is_exhausted() {
+                    return Err(Report::new(ValidationError)
+                        .attach_printable(format!("Promotion={} has been exhausted", self.code)));
+                }
+
+                Ok(PromotionEvent::Redeemed { code: self.code.clone(), order })
+            }
+            PromotionCommand::Release { order } => {
+                if !self.redemptions.contains(&order) {
+                    return Err(Report::new(ValidationError)
+                        .attach_printable(format!("Promotion={} has not been redeemed on Order={order}", self.code)));
+                }
+
+                Ok(PromotionEvent::Released { code: self.code.clone(), order })
+            }
+            PromotionCommand::End => {
+                if self.ended {
+                    return Err(Report::new(ValidationError)
+                        .attach_printable(format!("Promotion={} has already been ended", self.code)));
+                }
+
+                Ok(PromotionEvent::Ended { code: self.code.clone() })
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl Applicator<PromotionEvent> for Promotion {
+    #[tracing::instrument(skip_all, fields(promotion = %self.code))]
+    async fn apply(&mut self, event: PromotionEvent, ctx: &mut Context) {
+        self.persist(&event, ctx).await;
+        WithStreamPublisher::publish(self, &event, ctx).await;
+
+        tracing::debug!("Applying event: {:?}", event);
+        Promotion::apply(self, event);
+        tracing::debug!("State: {:?}", self);
+    }
+}
+
+impl ResolveMapping for Promotion {
+    fn mapping(mapper: &mut Mapper<Self>) {
+        mapper.register::<PromotionEvent>();
+    }
+}
+
+#[async_trait]
+impl Projection<PromotionEvent> for Promotion {
+    type Rejection = Infallible;
+
+    async fn first(event: PromotionEvent) -> Result<Self, Self::Rejection> {
+        let PromotionEvent::Created { code, name, discount, scope, validity, cap } = event else {
+            panic!("Projection must start with `PromotionEvent::Created` event");
+        };
+
+        Ok(Self::new(code, name, discount, scope, validity, cap))
+    }
+
+    async fn apply(&mut self, event: PromotionEvent) -> Result<(), Self::Rejection> {
+        Promotion::apply(self, event);
+        Ok(())
+    }
+}
@@ -0,0 +1,31 @@
+use crate::errors::ValidationError;
+use error_stack::Report;
+use serde::{Deserialize, Serialize};
+
+/// Maximum number of times a [`Promotion`](crate::entities::promotion::Promotion) can be redeemed.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub struct UsageCap(i64);
+
+impl UsageCap {
+    pub fn new(cap: impl Into<i64>) -> Result<UsageCap, Report<ValidationError>> {
+        let cap = cap.into();
+        if cap < 1 {
+            return Err(Report::new(ValidationError)
+                .attach_printable("`UsageCap` must be greater than zero"));
+        }
+
+        Ok(Self(cap))
+    }
+}
+
+impl AsRef<i64> for UsageCap {
+    fn as_ref(&self) -> &i64 {
+        &self.0
+    }
+}
+
+impl From<UsageCap> for i64 {
+    fn from(cap: UsageCap) -> Self {
+        cap.0
+    }
+}
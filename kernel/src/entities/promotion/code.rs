@@ -0,0 +1,47 @@
+use crate::errors::ValidationError;
+use error_stack::Report;
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+/// Code a customer enters to redeem a [`Promotion`](crate::entities::promotion::Promotion).
+///
+/// Codes are case-insensitive and stored in upper case,
+/// and also serve as the identifier of the promotion.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Deserialize, Serialize)]
+pub struct CouponCode(String);
+
+impl CouponCode {
+    pub fn new(code: impl AsRef<str>) -> Result<CouponCode, Report<ValidationError>> {
+        let code = code.as_ref().trim().to_uppercase();
+
+        if !(3..=32).contains(&code.len()) {
+            return Err(Report::new(ValidationError)
+                .attach_printable("`CouponCode` must be between 3 and 32 characters"));
+        }
+
+        if !code.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+            return Err(Report::new(ValidationError)
+                .attach_printable(format!("`CouponCode` must only contain letters, digits, `-` and `_`, but was `{code}`")));
+        }
+
+        Ok(Self(code))
+    }
+}
+
+impl AsRef<str> for CouponCode {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<CouponCode> for String {
+    fn from(code: CouponCode) -> Self {
+        code.0
+    }
+}
+
+impl Display for CouponCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
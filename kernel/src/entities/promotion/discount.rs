@@ -0,0 +1,51 @@
+use crate::entities::money::{Currency, Money};
+use crate::errors::ValidationError;
+use error_stack::Report;
+use serde::{Deserialize, Serialize};
+
+/// How much a [`Promotion`](crate::entities::promotion::Promotion) takes off the eligible subtotal.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Discount {
+    /// `rate` percent of the subtotal, rounded down.
+    Percentage { rate: i64 },
+    /// A fixed amount, never more than the subtotal. Only applies to orders in its currency.
+    Fixed { amount: Money },
+}
+
+impl Discount {
+    pub fn percentage(rate: impl Into<i64>) -> Result<Discount, Report<ValidationError>> {
+        let rate = rate.into();
+        if !(1..=100).contains(&rate) {
+            return Err(Report::new(ValidationError)
+                .attach_printable("Percentage discount must be between 1 and 100"));
+        }
+
+        Ok(Discount::Percentage { rate })
+    }
+
+    pub fn fixed(amount: Money) -> Result<Discount, Report<ValidationError>> {
+        if amount.amount() <= 0 {
+            return Err(Report::new(ValidationError)
+                .attach_printable("Fixed discount must be greater than zero"));
+        }
+
+        Ok(Discount::Fixed { amount })
+    }
+
+    /// The amount taken off `subtotal`.
+    pub fn amount(&self, subtotal: i64) -> i64 {
+        match self {
+            Discount::Percentage { rate } => subtotal * rate / 100,
+            Discount::Fixed { amount } => amount.amount().min(subtotal),
+        }
+    }
+
+    /// `None` if the discount applies to orders in any currency.
+    pub fn currency(&self) -> Option<&Currency> {
+        match self {
+            Discount::Percentage { .. } => None,
+            Discount::Fixed { amount } => Some(amount.currency()),
+        }
+    }
+}
@@ -0,0 +1,22 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub struct PromotionName(String);
+
+impl PromotionName {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self(name.into())
+    }
+}
+
+impl AsRef<str> for PromotionName {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<PromotionName> for String {
+    fn from(name: PromotionName) -> Self {
+        name.0
+    }
+}
@@ -0,0 +1,40 @@
+use std::collections::BTreeSet;
+
+use crate::entities::category::CategoryId;
+use crate::entities::product::ProductId;
+use crate::errors::ValidationError;
+use error_stack::Report;
+use serde::{Deserialize, Serialize};
+
+/// Which lines of an order a [`Promotion`](crate::entities::promotion::Promotion) is applied to.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(tag = "type", content = "ids", rename_all = "snake_case")]
+pub enum PromotionScope {
+    /// Every line of the order.
+    Order,
+    /// Lines of products that belong to any of the categories when the coupon is redeemed.
+    Categories(BTreeSet<CategoryId>),
+    Products(BTreeSet<ProductId>),
+}
+
+impl PromotionScope {
+    pub fn categories(ids: impl IntoIterator<Item = CategoryId>) -> Result<PromotionScope, Report<ValidationError>> {
+        let ids = ids.into_iter().collect::<BTreeSet<_>>();
+        if ids.is_empty() {
+            return Err(Report::new(ValidationError)
+                .attach_printable("Promotion scoped to categories requires at least one category"));
+        }
+
+        Ok(PromotionScope::Categories(ids))
+    }
+
+    pub fn products(ids: impl IntoIterator<Item = ProductId>) -> Result<PromotionScope, Report<ValidationError>> {
+        let ids = ids.into_iter().collect::<BTreeSet<_>>();
+        if ids.is_empty() {
+            return Err(Report::new(ValidationError)
+                .attach_printable("Promotion scoped to products requires at least one product"));
+        }
+
+        Ok(PromotionScope::Products(ids))
+    }
+}
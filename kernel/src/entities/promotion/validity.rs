@@ -0,0 +1,35 @@
+use crate::errors::ValidationError;
+use error_stack::Report;
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+
+/// Period in which a [`Promotion`](crate::entities::promotion::Promotion) can be redeemed,
+/// from `starts_at` (inclusive) until `ends_at` (exclusive).
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Validity {
+    starts_at: OffsetDateTime,
+    ends_at: OffsetDateTime,
+}
+
+impl Validity {
+    pub fn new(starts_at: OffsetDateTime, ends_at: OffsetDateTime) -> Result<Validity, Report<ValidationError>> {
+        if starts_at >= ends_at {
+            return Err(Report::new(ValidationError)
+                .attach_printable(format!("Validity must start before it ends, but was from {starts_at} until {ends_at}")));
+        }
+
+        Ok(Self { starts_at, ends_at })
+    }
+
+    pub fn starts_at(&self) -> &OffsetDateTime {
+        &self.starts_at
+    }
+
+    pub fn ends_at(&self) -> &OffsetDateTime {
+        &self.ends_at
+    }
+
+    pub fn contains(&self, at: &OffsetDateTime) -> bool {
+        &self.starts_at <= at && at < &self.ends_at
+    }
+}
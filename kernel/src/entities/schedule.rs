@@ -0,0 +1,44 @@
+mod window;
+
+pub use self::window::*;
+
+use error_stack::Report;
+use serde::{Deserialize, Serialize};
+use time::PrimitiveDateTime;
+
+use crate::errors::ValidationError;
+
+/// Restricts when a [`Category`](crate::entities::category::Category) or 
+/// [`Product`](crate::entities::product::Product) is offered, e.g. breakfast from 7:00 to 10:30.
+///
+/// Available if any of the windows contains the given time.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Schedule(Vec<TimeWindow>);
+
+impl Schedule {
+    pub fn new(windows: impl Into<Vec<TimeWindow>>) -> Result<Schedule, Report<ValidationError>> {
+        let windows = windows.into();
+        if windows.is_empty() {
+            return Err(Report::new(ValidationError)
+                .attach_printable("`Schedule` must have at least one time window"));
+        }
+
+        Ok(Self(windows))
+    }
+
+    pub fn is_available_at(&self, at: &PrimitiveDateTime) -> bool {
+        self.0.iter().any(|window| window.contains(at))
+    }
+}
+
+impl AsRef<[TimeWindow]> for Schedule {
+    fn as_ref(&self) -> &[TimeWindow] {
+        &self.0
+    }
+}
+
+impl From<Schedule> for Vec<TimeWindow> {
+    fn from(schedule: Schedule) -> Self {
+        schedule.0
+    }
+}
@@ -0,0 +1,54 @@
+use std::collections::BTreeSet;
+
+use error_stack::Report;
+use serde::{Deserialize, Serialize};
+use time::{PrimitiveDateTime, Time, Weekday};
+
+use crate::errors::ValidationError;
+
+/// `start..end` on each of `weekdays`. Windows do not span midnight.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct TimeWindow {
+    weekdays: BTreeSet<u8>,
+    start: Time,
+    end: Time,
+}
+
+impl TimeWindow {
+    pub fn new(weekdays: impl IntoIterator<Item = Weekday>, start: Time, end: Time) -> Result<TimeWindow, Report<ValidationError>> {
+        let weekdays = weekdays.into_iter()
+            .map(|weekday| weekday.number_days_from_sunday())
+            .collect::<BTreeSet<_>>();
+        
+        if weekdays.is_empty() {
+            return Err(Report::new(ValidationError)
+                .attach_printable("`TimeWindow` must apply to at least one weekday"));
+        }
+
+        if start >= end {
+            return Err(Report::new(ValidationError)
+                .attach_printable(format!("`TimeWindow` must start before it ends, but got {start}..{end}")));
+        }
+
+        Ok(Self { weekdays, start, end })
+    }
+
+    /// Days of the week counted from Sunday (0) to Saturday (6), as `strftime('%w')` in SQLite does.
+    pub fn weekdays(&self) -> impl Iterator<Item = u8> + '_ {
+        self.weekdays.iter().copied()
+    }
+
+    pub fn start(&self) -> &Time {
+        &self.start
+    }
+
+    pub fn end(&self) -> &Time {
+        &self.end
+    }
+
+    pub fn contains(&self, at: &PrimitiveDateTime) -> bool {
+        self.weekdays.contains(&at.weekday().number_days_from_sunday())
+            && self.start <= at.time()
+            && at.time() < self.end
+    }
+}
@@ -0,0 +1,178 @@
+mod name;
+mod password;
+mod role;
+
+pub use self::{name::*, password::*, role::*};
+
+use std::convert::Infallible;
+
+use async_trait::async_trait;
+use destructure::{Destructure, Mutation};
+use error_stack::Report;
+use serde::{Deserialize, Serialize};
+
+use nitinol::process::eventstream::WithStreamPublisher;
+use nitinol::process::persistence::WithPersistence;
+use nitinol::process::{Applicator, Context, Process, Publisher};
+use nitinol::projection::resolver::{Mapper, ResolveMapping};
+use nitinol::projection::Projection;
+use nitinol::{EntityId, ToEntityId};
+use crate::errors::{FormationError, ValidationError};
+use crate::io::commands::StaffCommand;
+use crate::io::events::StaffEvent;
+
+/// A member of staff who signs in to manage the store.
+///
+/// Staff are identified by their [`StaffName`], and removed staff can no longer sign in.
+/// What they may do once signed in depends on their [`Role`].
+/// Every password change bumps the credential version, by which earlier sessions are told apart.
+#[derive(Debug, Clone, Deserialize, Serialize, Destructure, Mutation)]
+pub struct Staff {
+    name: StaffName,
+    role: Role,
+    password: HashedPassword,
+    removed: bool,
+    #[serde(default)]
+    credential_version: u32,
+}
+
+impl Staff {
+    pub fn new(name: StaffName, role: Role, password: HashedPassword) -> Staff {
+        Staff { name, role, password, removed: false, credential_version: 0 }
+    }
+
+    pub fn name(&self) -> &StaffName {
+        &self.name
+    }
+
+    pub fn role(&self) -> &Role {
+        &self.role
+    }
+
+    pub fn removed(&self) -> bool {
+        self.removed
+    }
+
+    /// Number of times the password has been changed.
+    pub fn credential_version(&self) -> u32 {
+        self.credential_version
+    }
+
+    /// `false` for removed staff, whatever the password is.
+    pub fn authenticate(&self, password: impl AsRef<str>) -> bool {
+        !self.removed && self.password.verify(password)
+    }
+
+    fn apply(&mut self, event: StaffEvent) {
+        match event {
+            StaffEvent::Registered { .. } => {}
+            StaffEvent::ChangedRole { role, .. } => {
+                self.role = role;
+            }
+            StaffEvent::ChangedPassword { password, .. } => {
+                self.password = password;
+                self.credential_version += 1;
+            }
+            StaffEvent::Removed { .. } => {
+                self.removed = true;
+            }
+        }
+    }
+}
+
+impl TryFrom<(StaffName, StaffCommand)> for Staff {
+    type Error = Report<FormationError>;
+
+    fn try_from(value: (StaffName, StaffCommand)) -> Result<Self, Self::Error> {
+        let StaffCommand::Register { role, password } = value.1 else {
+            return Err(Report::new(FormationError)
+                .attach_printable("StaffCommand::Register is the only command that can be converted to Staff"));
+        };
+
+        Ok(Self::new(value.0, role, password))
+    }
+}
+
+impl Process for Staff {}
+
+impl WithPersistence for Staff {
+    fn aggregate_id(&self) -> EntityId {
+        self.name.to_entity_id()
+    }
+}
+
+impl WithStreamPublisher for Staff {
+    fn aggregate_id(&self) -> EntityId {
+        self.name.to_entity_id()
+    }
+}
+
+#[async_trait]
+impl Publisher<StaffCommand> for Staff {
+    type Event = StaffEvent;
+    type Rejection = Report<ValidationError>;
+
+    #[tracing::instrument(skip_all, fields(staff = %self.name))]
+    async fn publish(
+        &self,
+        command: StaffCommand,
+        _: &mut Context,
+    ) -> Result<Self::Event, Self::Rejection> {
+        if self.removed {
+            return Err(Report::new(ValidationError)
+                .attach_printable(format!("Staff={} has been removed", self.name)));
+        }
+
+        match command {
+            StaffCommand::Register { role, password } => {
+                Ok(StaffEvent::Registered { name: self.name.clone(), role, password })
+            }
+            StaffCommand::ChangeRole { role } => {
+                Ok(StaffEvent::ChangedRole { name: self.name.clone(), role })
+            }
+            StaffCommand::ChangePassword { password } => {
+                Ok(StaffEvent::ChangedPassword { name: self.name.clone(), password })
+            }
+            StaffCommand::Remove => {
+                Ok(StaffEvent::Removed { name: self.name.clone() })
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl Applicator<StaffEvent> for Staff {
+    #[tracing::instrument(skip_all, fields(staff = %self.name))]
+    async fn apply(&mut self, event: StaffEvent, ctx: &mut Context) {
+        self.persist(&event, ctx).await;
+        WithStreamPublisher::publish(self, &event, ctx).await;
+
+        tracing::debug!("Applying event: {:?}", event);
+        Staff::apply(self, event);
+        tracing::debug!("State: {:?}", self);
+    }
+}
+
+impl ResolveMapping for Staff {
+    fn mapping(mapper: &mut Mapper<Self>) {
+        mapper.register::<StaffEvent>();
+    }
+}
+
+#[async_trait]
+impl Projection<StaffEvent> for Staff {
+    type Rejection = Infallible;
+
+    async fn first(event: StaffEvent) -> Result<Self, Self::Rejection> {
+        let StaffEvent::Registered { name, role, password } = event else {
+            panic!("Projection must start with `StaffEvent::Registered` event");
+        };
+
+        Ok(Self::new(name, role, password))
+    }
+
+    async fn apply(&mut self, event: StaffEvent) -> Result<(), Self::Rejection> {
+        Staff::apply(self, event);
+        Ok(())
+    }
+}
@@ -0,0 +1,47 @@
+use crate::errors::ValidationError;
+use error_stack::Report;
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+/// Name a member of staff signs in with.
+///
+/// Names are case-insensitive and stored in lower case,
+/// and also serve as the identifier of the staff.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Deserialize, Serialize)]
+pub struct StaffName(String);
+
+impl StaffName {
+    pub fn new(name: impl AsRef<str>) -> Result<StaffName, Report<ValidationError>> {
+        let name = name.as_ref().trim().to_lowercase();
+
+        if !(3..=32).contains(&name.len()) {
+            return Err(Report::new(ValidationError)
+                .attach_printable("`StaffName` must be between 3 and 32 characters"));
+        }
+
+        if !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.') {
+            return Err(Report::new(ValidationError)
+                .attach_printable(format!("`StaffName` must only contain letters, digits, `-`, `_` and `.`, but was `{name}`")));
+        }
+
+        Ok(Self(name))
+    }
+}
+
+impl AsRef<str> for StaffName {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<StaffName> for String {
+    fn from(name: StaffName) -> Self {
+        name.0
+    }
+}
+
+impl Display for StaffName {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
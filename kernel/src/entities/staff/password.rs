@@ -0,0 +1,57 @@
+use argon2::password_hash::rand_core::OsRng;
+use argon2::password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use argon2::Argon2;
+use error_stack::Report;
+use serde::{Deserialize, Serialize};
+use std::fmt::Debug;
+
+use crate::errors::ValidationError;
+
+/// Argon2 hash of a staff password, in the PHC string format.
+///
+/// The plain password never leaves [`HashedPassword::new`], 
+/// and the hash itself is redacted from `Debug` so that it does not end up in the logs.
+#[derive(Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct HashedPassword(String);
+
+impl HashedPassword {
+    pub fn new(password: impl AsRef<str>) -> Result<HashedPassword, Report<ValidationError>> {
+        let password = password.as_ref();
+
+        if !(8..=128).contains(&password.chars().count()) {
+            return Err(Report::new(ValidationError)
+                .attach_printable("Password must be between 8 and 128 characters"));
+        }
+
+        let salt = SaltString::generate(&mut OsRng);
+        let hash = Argon2::default()
+            .hash_password(password.as_bytes(), &salt)
+            .map_err(|e| Report::new(ValidationError)
+                .attach_printable(format!("Failed to hash password: {e}")))?;
+
+        Ok(Self(hash.to_string()))
+    }
+
+    /// `false` if the password does not match, or the hash cannot be parsed.
+    pub fn verify(&self, password: impl AsRef<str>) -> bool {
+        let Ok(hash) = PasswordHash::new(&self.0) else {
+            return false;
+        };
+
+        Argon2::default()
+            .verify_password(password.as_ref().as_bytes(), &hash)
+            .is_ok()
+    }
+}
+
+impl AsRef<str> for HashedPassword {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Debug for HashedPassword {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("HashedPassword(..)")
+    }
+}
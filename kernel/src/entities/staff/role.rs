@@ -0,0 +1,65 @@
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+/// What a [`Staff`](crate::entities::staff::Staff) is allowed to do, see [`Role::permits`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Role {
+    Admin,
+    Cashier,
+    Kitchen,
+    Kiosk,
+}
+
+/// Operations that are restricted to some [`Role`]s.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Permission {
+    /// Editing categories, products and promotions.
+    EditCatalog,
+    /// Registering and removing staff and devices.
+    ManageStaff,
+    /// Placing orders and changing their lines.
+    PlaceOrder,
+    /// Paying, confirming and cancelling orders.
+    TakePayment,
+    /// Working through the kitchen queue.
+    PrepareOrder,
+    /// Reading payments and sales reports.
+    ViewSales,
+    /// Reading the audit log, and the event history and past menus of the catalog.
+    ViewAudit,
+}
+
+impl Role {
+    /// | Role      | Permissions                    |
+    /// |-----------|--------------------------------|
+    /// | `Admin`   | everything                     |
+    /// | `Cashier` | `PlaceOrder`, `TakePayment`    |
+    /// | `Kitchen` | `PrepareOrder`                 |
+    /// | `Kiosk`   | `PlaceOrder`                   |
+    pub fn permits(&self, permission: Permission) -> bool {
+        match self {
+            Role::Admin => true,
+            Role::Cashier => matches!(permission, Permission::PlaceOrder | Permission::TakePayment),
+            Role::Kitchen => matches!(permission, Permission::PrepareOrder),
+            Role::Kiosk => matches!(permission, Permission::PlaceOrder),
+        }
+    }
+}
+
+impl AsRef<str> for Role {
+    fn as_ref(&self) -> &str {
+        match self {
+            Role::Admin => "admin",
+            Role::Cashier => "cashier",
+            Role::Kitchen => "kitchen",
+            Role::Kiosk => "kiosk",
+        }
+    }
+}
+
+impl Display for Role {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_ref())
+    }
+}
@@ -0,0 +1,123 @@
+mod day;
+mod number;
+mod offset;
+
+pub use self::{day::*, number::*, offset::*};
+
+use std::convert::Infallible;
+
+use async_trait::async_trait;
+use error_stack::Report;
+use serde::{Deserialize, Serialize};
+
+use nitinol::process::eventstream::WithStreamPublisher;
+use nitinol::process::persistence::WithPersistence;
+use nitinol::process::{Applicator, Context, Process, Publisher};
+use nitinol::projection::resolver::{Mapper, ResolveMapping};
+use nitinol::projection::Projection;
+use nitinol::{EntityId, ToEntityId};
+use crate::errors::ValidationError;
+use crate::io::commands::TicketCounterCommand;
+use crate::io::events::TicketCounterEvent;
+
+/// Singleton that hands out [`TicketNumber`]s for confirmed orders.
+///
+/// Since every command goes through a single process, numbers are never issued twice
+/// within the same [`BusinessDay`] (until they wrap around).
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct TicketCounter {
+    day: Option<BusinessDay>,
+    last: Option<TicketNumber>,
+}
+
+impl TicketCounter {
+    pub const ID: &'static str = "ticket-counter";
+
+    pub fn day(&self) -> Option<&BusinessDay> {
+        self.day.as_ref()
+    }
+
+    pub fn last(&self) -> Option<&TicketNumber> {
+        self.last.as_ref()
+    }
+
+    fn apply(&mut self, event: TicketCounterEvent) {
+        match event {
+            TicketCounterEvent::Issued { number, day, .. } => {
+                self.day = Some(day);
+                self.last = Some(number);
+            }
+        }
+    }
+}
+
+impl Process for TicketCounter {}
+
+impl WithPersistence for TicketCounter {
+    fn aggregate_id(&self) -> EntityId {
+        TicketCounter::ID.to_entity_id()
+    }
+}
+
+impl WithStreamPublisher for TicketCounter {
+    fn aggregate_id(&self) -> EntityId {
+        TicketCounter::ID.to_entity_id()
+    }
+}
+
+#[async_trait]
+impl Publisher<TicketCounterCommand> for TicketCounter {
+    type Event = TicketCounterEvent;
+    type Rejection = Report<ValidationError>;
+
+    #[tracing::instrument(skip_all, name = "ticket-counter")]
+    async fn publish(
+        &self,
+        command: TicketCounterCommand,
+        _: &mut Context,
+    ) -> Result<Self::Event, Self::Rejection> {
+        match command {
+            TicketCounterCommand::Issue { order, day } => {
+                let number = match (self.day, self.last) {
+                    (Some(current), _) if day < current => {
+                        return Err(Report::new(ValidationError)
+                            .attach_printable(format!("BusinessDay={day} is before the current business day={current}")));
+                    }
+                    (Some(current), Some(last)) if day == current => last.next(),
+                    _ => TicketNumber::first(),
+                };
+
+                Ok(TicketCounterEvent::Issued { order, number, day })
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl Applicator<TicketCounterEvent> for TicketCounter {
+    #[tracing::instrument(skip_all, name = "ticket-counter")]
+    async fn apply(&mut self, event: TicketCounterEvent, ctx: &mut Context) {
+        self.persist(&event, ctx).await;
+        WithStreamPublisher::publish(self, &event, ctx).await;
+
+        tracing::debug!("Applying event: {:?}", event);
+        TicketCounter::apply(self, event);
+        tracing::debug!("State: {:?}", self);
+    }
+}
+
+impl ResolveMapping for TicketCounter {
+    fn mapping(mapper: &mut Mapper<Self>) {
+        mapper.register::<TicketCounterEvent>();
+    }
+}
+
+#[async_trait]
+impl Projection<TicketCounterEvent> for TicketCounter {
+    type Rejection = Infallible;
+
+    async fn apply(&mut self, event: TicketCounterEvent) -> Result<(), Self::Rejection> {
+        TicketCounter::apply(self, event);
+        Ok(())
+    }
+}
@@ -0,0 +1,38 @@
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+use time::{Date, OffsetDateTime};
+
+use crate::entities::ticket::StoreOffset;
+
+/// The day on which ticket numbers are issued. Numbers start over every business day.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Deserialize, Serialize)]
+pub struct BusinessDay(Date);
+
+impl BusinessDay {
+    pub fn new(day: impl Into<Date>) -> Self {
+        Self(day.into())
+    }
+
+    /// The day on which `at` falls in the store.
+    pub fn of(at: OffsetDateTime, offset: StoreOffset) -> Self {
+        Self(at.to_offset(offset.into()).date())
+    }
+}
+
+impl AsRef<Date> for BusinessDay {
+    fn as_ref(&self) -> &Date {
+        &self.0
+    }
+}
+
+impl From<BusinessDay> for Date {
+    fn from(day: BusinessDay) -> Self {
+        day.0
+    }
+}
+
+impl Display for BusinessDay {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
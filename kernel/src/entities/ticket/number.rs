@@ -0,0 +1,55 @@
+use crate::errors::ValidationError;
+use error_stack::Report;
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+/// Short human-readable call number printed on a ticket.
+///
+/// Numbers run from [`TicketNumber::MIN`] to [`TicketNumber::MAX`] and wrap around.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Deserialize, Serialize)]
+pub struct TicketNumber(i64);
+
+impl TicketNumber {
+    pub const MIN: i64 = 1;
+    pub const MAX: i64 = 999;
+
+    pub fn new(number: impl Into<i64>) -> Result<TicketNumber, Report<ValidationError>> {
+        let number = number.into();
+        if !(Self::MIN..=Self::MAX).contains(&number) {
+            return Err(Report::new(ValidationError)
+                .attach_printable(format!("`TicketNumber` must be between {} and {}", Self::MIN, Self::MAX)));
+        }
+
+        Ok(Self(number))
+    }
+
+    pub fn first() -> TicketNumber {
+        Self(Self::MIN)
+    }
+
+    pub fn next(&self) -> TicketNumber {
+        if self.0 >= Self::MAX {
+            return Self::first();
+        }
+
+        Self(self.0 + 1)
+    }
+}
+
+impl AsRef<i64> for TicketNumber {
+    fn as_ref(&self) -> &i64 {
+        &self.0
+    }
+}
+
+impl From<TicketNumber> for i64 {
+    fn from(number: TicketNumber) -> Self {
+        number.0
+    }
+}
+
+impl Display for TicketNumber {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
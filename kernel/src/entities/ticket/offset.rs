@@ -0,0 +1,77 @@
+use crate::entities::ticket::BusinessDay;
+use crate::errors::ValidationError;
+use error_stack::Report;
+use std::fmt::Display;
+use time::{OffsetDateTime, UtcOffset};
+
+/// UTC offset of the store, which decides the [`BusinessDay`]
+/// as well as the local time of schedules, receipts and reports.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct StoreOffset(UtcOffset);
+
+impl StoreOffset {
+    pub fn new(offset: UtcOffset) -> Self {
+        Self(offset)
+    }
+
+    /// Parses `±HH:MM` or `±HH`.
+    pub fn parse(offset: impl AsRef<str>) -> Result<StoreOffset, Report<ValidationError>> {
+        let offset = offset.as_ref().trim();
+        let invalid = || Report::new(ValidationError)
+            .attach_printable(format!("`{offset}` is not a UTC offset such as `+09:00`"));
+
+        let (sign, rest) = match offset.split_at_checked(1) {
+            Some(("+", rest)) => (1, rest),
+            Some(("-", rest)) => (-1, rest),
+            _ => return Err(invalid()),
+        };
+
+        let (hours, minutes) = rest.split_once(':').unwrap_or((rest, "0"));
+        let hours = hours.parse::<i8>().map_err(|_| invalid())?;
+        let minutes = minutes.parse::<i8>().map_err(|_| invalid())?;
+
+        let offset = UtcOffset::from_hms(sign * hours, sign * minutes, 0)
+            .map_err(|_| invalid())?;
+
+        Ok(Self(offset))
+    }
+
+    /// Current time in the store.
+    pub fn now(&self) -> OffsetDateTime {
+        OffsetDateTime::now_utc().to_offset(self.0)
+    }
+
+    /// Current business day of the store.
+    pub fn today(&self) -> BusinessDay {
+        BusinessDay::of(OffsetDateTime::now_utc(), *self)
+    }
+
+    /// Modifier that shifts a UTC time by this offset in SQLite date functions, such as `+540 minutes`.
+    pub fn sqlite_modifier(&self) -> String {
+        format!("{:+} minutes", self.0.whole_minutes())
+    }
+}
+
+impl Default for StoreOffset {
+    fn default() -> Self {
+        Self(UtcOffset::UTC)
+    }
+}
+
+impl AsRef<UtcOffset> for StoreOffset {
+    fn as_ref(&self) -> &UtcOffset {
+        &self.0
+    }
+}
+
+impl From<StoreOffset> for UtcOffset {
+    fn from(offset: StoreOffset) -> Self {
+        offset.0
+    }
+}
+
+impl Display for StoreOffset {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
@@ -6,6 +6,11 @@ pub struct FormationError;
 #[error("violation of validation rules")]
 pub struct ValidationError;
 
+/// Rejects replaying an entity past its delete event.
+#[derive(Debug, thiserror::Error)]
+#[error("entity has been deleted")]
+pub struct DeletedError;
+
 #[derive(Debug, thiserror::Error)]
 #[error("driver error")]
 pub struct DriverError;
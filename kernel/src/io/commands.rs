@@ -1,5 +1,10 @@
 mod categories;
 mod category;
+mod device;
+mod order;
 mod product;
+mod promotion;
+mod staff;
+mod ticket;
 
-pub use self::{categories::*, category::*, product::*};
+pub use self::{categories::*, category::*, device::*, order::*, product::*, promotion::*, staff::*, ticket::*};
@@ -1,5 +1,6 @@
 use crate::entities::category::CategoryName;
 use crate::entities::product::ProductId;
+use crate::entities::schedule::Schedule;
 use nitinol::macros::Command;
 use std::collections::BTreeMap;
 use error_stack::Report;
@@ -12,6 +13,8 @@ use crate::io::events::ProductEvent;
 /// - `Create`: Creates a new category.
 /// - `Rename`: Renames the category.
 /// - `Delete`: Deletes the category.
+/// - `ChangeSchedule`: Limits the category to the given time windows.
+/// - `ClearSchedule`: Makes the category available at any time.
 /// - `AddProduct`: Adds a product to the category.
 /// - `RemoveProduct`: Removes a product from the category.
 /// - `ChangeProductOrdering`: Changes the ordering of the products.
@@ -21,6 +24,8 @@ pub enum CategoryCommand {
     Create { name: CategoryName },
     Rename { new: CategoryName },
     Delete,
+    ChangeSchedule { new: Schedule },
+    ClearSchedule,
 
     AddProduct { id: ProductId },
     RemoveProduct { id: ProductId },
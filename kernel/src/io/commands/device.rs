@@ -0,0 +1,25 @@
+use crate::entities::device::{DeviceName, HashedKey, StoreCode};
+use crate::entities::staff::Role;
+use nitinol::macros::Command;
+
+/// This command is used to interact with a [`Device`](crate::entities::device::Device) entity.
+///
+/// # Commands
+/// | Command     | Description                                                         |
+/// |-------------|---------------------------------------------------------------------|
+/// | `Register`  | Registers a device with the hash of its key. **Not as `Admin`**.    |
+/// | `RotateKey` | Replaces the key of the device, invalidating the previous one.      |
+/// | `Revoke`    | Revokes the device. **Revoked devices can no longer authenticate**. |
+#[derive(Debug, Clone, Command)]
+pub enum DeviceCommand {
+    Register {
+        name: DeviceName,
+        role: Role,
+        store: StoreCode,
+        key: HashedKey,
+    },
+    RotateKey {
+        key: HashedKey,
+    },
+    Revoke,
+}
@@ -0,0 +1,47 @@
+use crate::entities::money::Money;
+use crate::entities::order::{AppliedCoupon, LineTax, OrderQuantity, OrderStatus, Tender};
+use crate::entities::product::{BundleChoice, ProductId, ProductPrice, SelectedOption};
+use nitinol::macros::Command;
+
+/// This command is used to interact with an [`Order`](crate::entities::order::Order) entity.
+///
+/// # Commands
+/// | Command        | Description                                                          |
+/// |----------------|----------------------------------------------------------------------|
+/// | `Place`        | Places a new empty order.                                            |
+/// | `AddLine`      | Adds a product and options with the prices at the time of purchase.  |
+/// | `RemoveLine`   | Removes a line from the order.                                       |
+/// | `ApplyCoupon`  | Applies a redeemed coupon. Only one coupon can be applied per order. |
+/// | `Pay`          | Pays the order total. **Lines can no longer be changed**.            |
+/// | `Confirm`      | Confirms a paid order as accepted.                                   |
+/// | `ChangeStatus` | Moves an accepted order through the kitchen.                         |
+/// | `Cancel`       | Cancels the order unless it has already been completed.              |
+///
+/// See [`OrderStatus`] for the allowed transitions.
+#[derive(Debug, Clone, Command)]
+pub enum OrderCommand {
+    Place,
+    AddLine {
+        product: ProductId,
+        quantity: OrderQuantity,
+        price: ProductPrice,
+        options: Vec<SelectedOption>,
+        components: Vec<BundleChoice>,
+        tax: LineTax,
+    },
+    RemoveLine {
+        line: i64,
+    },
+    ApplyCoupon {
+        coupon: AppliedCoupon,
+    },
+    Pay {
+        tender: Tender,
+        tendered: Money,
+    },
+    Confirm,
+    ChangeStatus {
+        new: OrderStatus,
+    },
+    Cancel,
+}
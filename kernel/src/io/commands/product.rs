@@ -1,4 +1,6 @@
-use crate::entities::product::{ProductDesc, ProductName, ProductPrice};
+use crate::entities::money::Money;
+use crate::entities::product::{BundleComponent, OptionGroupId, OptionId, OptionName, ProductDesc, ProductId, ProductName, ProductPrice, ProductStock, ProductTax, SlotId, SlotName};
+use crate::entities::schedule::Schedule;
 use nitinol::macros::Command;
 
 /// This command is used to interact with a [`Product`](crate::entities::product::Product) entity.
@@ -10,6 +12,25 @@ use nitinol::macros::Command;
 /// | `RenameProductName` | Renames the product.             |
 /// | `EditProductDesc`   | Edits the product description.   |
 /// | `ChangeProductPrice`| Changes the product price.       |
+/// | `MarkSoldOut`       | Marks the product as sold out.   |
+/// | `MarkAvailable`     | Lifts the mark of `MarkSoldOut`. |
+/// | `SetStock`          | Sets the remaining stock.        |
+/// | `Restock`           | Adds to the remaining stock.     |
+/// | `DecrementStock`    | Subtracts from the stock.        |
+/// | `AddOptionGroup`    | Adds a group of options.         |
+/// | `EditOptionGroup`   | Edits a group of options.        |
+/// | `RemoveOptionGroup` | Removes a group of options.      |
+/// | `AddOption`         | Adds an option to a group.       |
+/// | `EditOption`        | Edits an option in a group.      |
+/// | `RemoveOption`      | Removes an option from a group.  |
+/// | `AddBundleSlot`     | Adds a slot to a set menu.       |
+/// | `ChangeBundleChoices`| Replaces the choices of a slot. |
+/// | `RemoveBundleSlot`  | Removes a slot from a set menu.  |
+/// | `JoinBundle`        | Becomes a choice of a set menu.  |
+/// | `LeaveBundle`       | Is no longer a choice of it.     |
+/// | `ChangeTax`         | Changes how the price is taxed.  |
+/// | `ChangeSchedule`    | Limits when it is offered.       |
+/// | `ClearSchedule`     | Offers it at any time.           |
 /// | `Delete`            | Deletes the product.             |
 #[derive(Debug, Clone, Command)]
 pub enum ProductCommand {
@@ -31,5 +52,69 @@ pub enum ProductCommand {
     ChangeProductImage {
         image: Vec<u8>,
     },
+    MarkSoldOut,
+    MarkAvailable,
+    SetStock {
+        new: ProductStock,
+    },
+    Restock {
+        amount: ProductStock,
+    },
+    DecrementStock {
+        amount: ProductStock,
+    },
+    AddOptionGroup {
+        name: OptionName,
+        min: i64,
+        max: i64,
+    },
+    EditOptionGroup {
+        group: OptionGroupId,
+        name: OptionName,
+        min: i64,
+        max: i64,
+    },
+    RemoveOptionGroup {
+        group: OptionGroupId,
+    },
+    AddOption {
+        group: OptionGroupId,
+        name: OptionName,
+        price_delta: Money,
+    },
+    EditOption {
+        group: OptionGroupId,
+        option: OptionId,
+        name: OptionName,
+        price_delta: Money,
+    },
+    RemoveOption {
+        group: OptionGroupId,
+        option: OptionId,
+    },
+    AddBundleSlot {
+        name: SlotName,
+        choices: Vec<BundleComponent>,
+    },
+    ChangeBundleChoices {
+        slot: SlotId,
+        choices: Vec<BundleComponent>,
+    },
+    RemoveBundleSlot {
+        slot: SlotId,
+    },
+    JoinBundle {
+        bundle: ProductId,
+    },
+    LeaveBundle {
+        bundle: ProductId,
+    },
+    ChangeTax {
+        new: ProductTax,
+    },
+    ChangeSchedule {
+        new: Schedule,
+    },
+    ClearSchedule,
     Delete,
 }
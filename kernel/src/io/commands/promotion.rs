@@ -0,0 +1,32 @@
+use crate::entities::order::OrderId;
+use crate::entities::promotion::{Discount, PromotionName, PromotionScope, UsageCap, Validity};
+use nitinol::macros::Command;
+use time::OffsetDateTime;
+
+/// This command is used to interact with a [`Promotion`](crate::entities::promotion::Promotion) entity.
+///
+/// # Commands
+/// | Command  | Description                                                                 |
+/// |----------|-----------------------------------------------------------------------------|
+/// | `Create` | Creates a promotion redeemed with the coupon code it is identified by.      |
+/// | `Redeem` | Records a redemption on an order. **Rejected once the cap is exhausted**.   |
+/// | `Release`| Gives back the redemption of an order that has been cancelled.              |
+/// | `End`    | Ends the promotion before its validity expires.                             |
+#[derive(Debug, Clone, Command)]
+pub enum PromotionCommand {
+    Create {
+        name: PromotionName,
+        discount: Discount,
+        scope: PromotionScope,
+        validity: Validity,
+        cap: Option<UsageCap>,
+    },
+    Redeem {
+        order: OrderId,
+        at: OffsetDateTime,
+    },
+    Release {
+        order: OrderId,
+    },
+    End,
+}
@@ -0,0 +1,26 @@
+use crate::entities::staff::{HashedPassword, Role};
+use nitinol::macros::Command;
+
+/// This command is used to interact with a [`Staff`](crate::entities::staff::Staff) entity.
+///
+/// # Commands
+/// | Command          | Description                                                     |
+/// |------------------|-----------------------------------------------------------------|
+/// | `Register`       | Registers a member of staff under the name it is identified by. |
+/// | `ChangeRole`     | Changes what the staff is allowed to do.                        |
+/// | `ChangePassword` | Replaces the password of the staff.                             |
+/// | `Remove`         | Removes the staff. **Removed staff can no longer sign in**.     |
+#[derive(Debug, Clone, Command)]
+pub enum StaffCommand {
+    Register {
+        role: Role,
+        password: HashedPassword,
+    },
+    ChangeRole {
+        role: Role,
+    },
+    ChangePassword {
+        password: HashedPassword,
+    },
+    Remove,
+}
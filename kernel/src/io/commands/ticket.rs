@@ -0,0 +1,15 @@
+use nitinol::macros::Command;
+
+use crate::entities::order::OrderId;
+use crate::entities::ticket::BusinessDay;
+
+/// TicketCounterCommand is a command that can be applied to a [`TicketCounter`](crate::entities::ticket::TicketCounter) entity.
+///
+/// # Commands
+/// - `Issue`: Issues the next ticket number of the business day for the order.
+///   - **Numbers start over when the business day changes**.
+///   - `day` is the business day of the store at the time the order was confirmed.
+#[derive(Debug, Clone, Command)]
+pub enum TicketCounterCommand {
+    Issue { order: OrderId, day: BusinessDay },
+}
@@ -1,5 +1,6 @@
 use crate::entities::category::{CategoryId, CategoryName};
 use crate::entities::product::ProductId;
+use crate::entities::schedule::Schedule;
 use nitinol::macros::Event;
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
@@ -10,6 +11,8 @@ pub enum CategoryEvent {
     Created { id: CategoryId, name: CategoryName },
     Renamed { id: CategoryId, new: CategoryName },
     Deleted { id: CategoryId },
+    ChangedSchedule { id: CategoryId, new: Schedule },
+    ClearedSchedule { id: CategoryId },
 
     AddedProduct { id: ProductId, category: CategoryId, ordering: i64 },
     RemovedProduct { category: CategoryId, new: BTreeMap<i64, ProductId> },
@@ -0,0 +1,23 @@
+use crate::entities::device::{DeviceId, DeviceName, HashedKey, StoreCode};
+use crate::entities::staff::Role;
+use nitinol::macros::Event;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Event, Deserialize, Serialize)]
+#[persist(enc = "serde_json::to_vec", dec = "serde_json::from_slice")]
+pub enum DeviceEvent {
+    Registered {
+        id: DeviceId,
+        name: DeviceName,
+        role: Role,
+        store: StoreCode,
+        key: HashedKey,
+    },
+    RotatedKey {
+        id: DeviceId,
+        key: HashedKey,
+    },
+    Revoked {
+        id: DeviceId,
+    },
+}
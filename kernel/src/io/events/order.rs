@@ -0,0 +1,45 @@
+use crate::entities::order::{AppliedCoupon, OrderId, OrderLine, OrderStatus, Payment, TaxSummary};
+use nitinol::macros::Event;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone, Event, Deserialize, Serialize)]
+#[persist(enc = "serde_json::to_vec", dec = "serde_json::from_slice")]
+pub enum OrderEvent {
+    Placed {
+        id: OrderId,
+    },
+    AddedLine {
+        id: OrderId,
+        line: i64,
+        item: OrderLine,
+    },
+    RemovedLine {
+        id: OrderId,
+        line: i64,
+    },
+    AppliedCoupon {
+        id: OrderId,
+        coupon: AppliedCoupon,
+    },
+    Paid {
+        id: OrderId,
+        payment: Payment,
+        /// The total broken down by tax rate as printed on the receipt.
+        #[serde(default)]
+        taxes: Vec<TaxSummary>,
+    },
+    Confirmed {
+        id: OrderId,
+        lines: BTreeMap<i64, OrderLine>,
+        #[serde(default)]
+        coupon: Option<AppliedCoupon>,
+    },
+    ChangedStatus {
+        id: OrderId,
+        new: OrderStatus,
+    },
+    Cancelled {
+        id: OrderId,
+    },
+}
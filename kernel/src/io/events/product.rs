@@ -1,5 +1,6 @@
 use crate::entities::image::Image;
-use crate::entities::product::{ProductDesc, ProductId, ProductName, ProductPrice};
+use crate::entities::product::{BundleSlot, OptionGroupId, OptionId, OptionName, ProductDesc, ProductId, ProductName, ProductOption, ProductOptionGroup, ProductPrice, ProductStock, ProductTax, SlotId};
+use crate::entities::schedule::Schedule;
 use nitinol::macros::Event;
 use serde::{Deserialize, Serialize};
 
@@ -29,6 +30,92 @@ pub enum ProductEvent {
         id: ProductId,
         image: Image,
     },
+    MarkedSoldOut {
+        id: ProductId,
+    },
+    MarkedAvailable {
+        id: ProductId,
+    },
+    /// Reaching zero stock also marks the product as sold out, and vice versa.
+    ChangedStock {
+        id: ProductId,
+        new: ProductStock,
+    },
+    Restocked {
+        id: ProductId,
+        amount: ProductStock,
+        new: ProductStock,
+    },
+    DecrementedStock {
+        id: ProductId,
+        amount: ProductStock,
+        new: ProductStock,
+    },
+    AddedOptionGroup {
+        id: ProductId,
+        group: ProductOptionGroup,
+    },
+    EditedOptionGroup {
+        id: ProductId,
+        group: OptionGroupId,
+        name: OptionName,
+        min: i64,
+        max: i64,
+    },
+    RemovedOptionGroup {
+        id: ProductId,
+        group: OptionGroupId,
+    },
+    AddedOption {
+        id: ProductId,
+        group: OptionGroupId,
+        option: ProductOption,
+    },
+    /// Replaces the name and price delta of an existing option.
+    EditedOption {
+        id: ProductId,
+        group: OptionGroupId,
+        option: ProductOption,
+    },
+    RemovedOption {
+        id: ProductId,
+        group: OptionGroupId,
+        option: OptionId,
+    },
+    AddedBundleSlot {
+        id: ProductId,
+        slot: BundleSlot,
+    },
+    ChangedBundleChoices {
+        id: ProductId,
+        slot: SlotId,
+        choices: Vec<ProductId>,
+    },
+    RemovedBundleSlot {
+        id: ProductId,
+        slot: SlotId,
+    },
+    /// Recorded on a product that has become a choice of `bundle`, 
+    /// so that it is neither deleted nor turned into a bundle while it is offered there.
+    JoinedBundle {
+        id: ProductId,
+        bundle: ProductId,
+    },
+    LeftBundle {
+        id: ProductId,
+        bundle: ProductId,
+    },
+    ChangedTax {
+        id: ProductId,
+        new: ProductTax,
+    },
+    ChangedSchedule {
+        id: ProductId,
+        new: Schedule,
+    },
+    ClearedSchedule {
+        id: ProductId,
+    },
     Deleted {
         id: ProductId,
     },
@@ -0,0 +1,28 @@
+use crate::entities::order::OrderId;
+use crate::entities::promotion::{CouponCode, Discount, PromotionName, PromotionScope, UsageCap, Validity};
+use nitinol::macros::Event;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Event, Deserialize, Serialize)]
+#[persist(enc = "serde_json::to_vec", dec = "serde_json::from_slice")]
+pub enum PromotionEvent {
+    Created {
+        code: CouponCode,
+        name: PromotionName,
+        discount: Discount,
+        scope: PromotionScope,
+        validity: Validity,
+        cap: Option<UsageCap>,
+    },
+    Redeemed {
+        code: CouponCode,
+        order: OrderId,
+    },
+    Released {
+        code: CouponCode,
+        order: OrderId,
+    },
+    Ended {
+        code: CouponCode,
+    },
+}
@@ -0,0 +1,30 @@
+use crate::entities::staff::{HashedPassword, Role, StaffName};
+use nitinol::macros::Event;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Event, Deserialize, Serialize)]
+#[persist(enc = "serde_json::to_vec", dec = "serde_json::from_slice")]
+pub enum StaffEvent {
+    Registered {
+        name: StaffName,
+        /// Staff registered before roles were introduced could do everything.
+        #[serde(default = "admin")]
+        role: Role,
+        password: HashedPassword,
+    },
+    ChangedRole {
+        name: StaffName,
+        role: Role,
+    },
+    ChangedPassword {
+        name: StaffName,
+        password: HashedPassword,
+    },
+    Removed {
+        name: StaffName,
+    },
+}
+
+fn admin() -> Role {
+    Role::Admin
+}
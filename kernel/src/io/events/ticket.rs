@@ -0,0 +1,12 @@
+use crate::entities::order::OrderId;
+use crate::entities::ticket::{BusinessDay, TicketNumber};
+use nitinol::macros::Event;
+use serde::{Deserialize, Serialize};
+
+/// This event that is produced when a [`TicketCounterCommand`](crate::io::commands::TicketCounterCommand)
+/// is applied to a [`TicketCounter`](crate::entities::ticket::TicketCounter) entity.
+#[derive(Debug, Clone, Event, Deserialize, Serialize)]
+#[persist(enc = "serde_json::to_vec", dec = "serde_json::from_slice")]
+pub enum TicketCounterEvent {
+    Issued { order: OrderId, number: TicketNumber, day: BusinessDay },
+}
@@ -0,0 +1,12 @@
+CREATE TABLE ticket_counter(
+    id           TEXT    NOT NULL PRIMARY KEY,
+    business_day TEXT    NOT NULL,
+    last_issued  INTEGER NOT NULL
+);
+
+CREATE TABLE tickets(
+    order_id     TEXT    NOT NULL PRIMARY KEY,
+    number       INTEGER NOT NULL,
+    business_day TEXT    NOT NULL,
+    issued_at    TEXT    NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
@@ -0,0 +1,9 @@
+CREATE TABLE order_status(
+    order_id    TEXT NOT NULL PRIMARY KEY,
+    status      TEXT NOT NULL,
+    placed_at   TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    accepted_at TEXT,
+    updated_at  TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX order_status_status ON order_status (status);
@@ -0,0 +1,11 @@
+CREATE TABLE order_items(
+    order_id TEXT    NOT NULL,
+    line     INTEGER NOT NULL,
+    product  TEXT    NOT NULL,
+    quantity INTEGER NOT NULL,
+    price    INTEGER NOT NULL,
+
+    PRIMARY KEY (order_id, line),
+
+    FOREIGN KEY (order_id) REFERENCES order_status (order_id) ON DELETE CASCADE
+);
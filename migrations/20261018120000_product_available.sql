@@ -0,0 +1 @@
+ALTER TABLE products ADD COLUMN available INTEGER NOT NULL DEFAULT 1;
@@ -0,0 +1,10 @@
+CREATE TABLE payments(
+    order_id TEXT    NOT NULL PRIMARY KEY,
+    tender   TEXT    NOT NULL,
+    amount   INTEGER NOT NULL,
+    tendered INTEGER NOT NULL,
+    change   INTEGER NOT NULL,
+    paid_at  TEXT    NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    FOREIGN KEY (order_id) REFERENCES order_status (order_id) ON DELETE CASCADE
+);
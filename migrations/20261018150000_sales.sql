@@ -0,0 +1,13 @@
+CREATE TABLE sales(
+    order_id TEXT    NOT NULL,
+    line     INTEGER NOT NULL,
+    product  TEXT    NOT NULL,
+    name     TEXT,
+    quantity INTEGER NOT NULL,
+    price    INTEGER NOT NULL,
+    sold_at  TEXT    NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    PRIMARY KEY (order_id, line)
+);
+
+CREATE INDEX sales_sold_at ON sales (sold_at);
@@ -0,0 +1,30 @@
+CREATE TABLE product_option_groups(
+    id      TEXT    NOT NULL PRIMARY KEY,
+    product TEXT    NOT NULL,
+    name    TEXT    NOT NULL,
+    min     INTEGER NOT NULL,
+    max     INTEGER NOT NULL,
+
+    FOREIGN KEY (product) REFERENCES products (id) ON DELETE CASCADE
+);
+
+CREATE TABLE product_options(
+    id           TEXT    NOT NULL PRIMARY KEY,
+    option_group TEXT    NOT NULL,
+    name         TEXT    NOT NULL,
+    price_delta  INTEGER NOT NULL,
+
+    FOREIGN KEY (option_group) REFERENCES product_option_groups (id) ON DELETE CASCADE
+);
+
+CREATE TABLE order_item_options(
+    order_id    TEXT    NOT NULL,
+    line        INTEGER NOT NULL,
+    option      TEXT    NOT NULL,
+    name        TEXT    NOT NULL,
+    price_delta INTEGER NOT NULL,
+
+    PRIMARY KEY (order_id, line, option),
+
+    FOREIGN KEY (order_id, line) REFERENCES order_items (order_id, line) ON DELETE CASCADE
+);
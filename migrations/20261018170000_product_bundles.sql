@@ -0,0 +1,28 @@
+CREATE TABLE product_bundle_slots(
+    id      TEXT NOT NULL PRIMARY KEY,
+    product TEXT NOT NULL,
+    name    TEXT NOT NULL,
+
+    FOREIGN KEY (product) REFERENCES products (id) ON DELETE CASCADE
+);
+
+CREATE TABLE product_bundle_choices(
+    slot    TEXT NOT NULL,
+    product TEXT NOT NULL,
+
+    PRIMARY KEY (slot, product),
+
+    FOREIGN KEY (slot) REFERENCES product_bundle_slots (id) ON DELETE CASCADE,
+    FOREIGN KEY (product) REFERENCES products (id) ON DELETE CASCADE
+);
+
+CREATE TABLE order_item_components(
+    order_id TEXT NOT NULL,
+    line     INTEGER NOT NULL,
+    slot     TEXT NOT NULL,
+    product  TEXT NOT NULL,
+
+    PRIMARY KEY (order_id, line, slot),
+
+    FOREIGN KEY (order_id, line) REFERENCES order_items (order_id, line) ON DELETE CASCADE
+);
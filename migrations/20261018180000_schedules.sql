@@ -0,0 +1,21 @@
+-- `weekday` counts from Sunday (0) as `strftime('%w')` does, `start` and `end` are seconds since midnight.
+CREATE TABLE category_schedules(
+    category TEXT    NOT NULL,
+    weekday  INTEGER NOT NULL,
+    start    INTEGER NOT NULL,
+    end      INTEGER NOT NULL,
+
+    FOREIGN KEY (category) REFERENCES categories (id) ON DELETE CASCADE
+);
+
+CREATE TABLE product_schedules(
+    product TEXT    NOT NULL,
+    weekday INTEGER NOT NULL,
+    start   INTEGER NOT NULL,
+    end     INTEGER NOT NULL,
+
+    FOREIGN KEY (product) REFERENCES products (id) ON DELETE CASCADE
+);
+
+CREATE INDEX category_schedules_category ON category_schedules (category);
+CREATE INDEX product_schedules_product ON product_schedules (product);
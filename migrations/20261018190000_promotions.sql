@@ -0,0 +1,39 @@
+-- `discount` is either `percentage` or `fixed`, `value` is the rate or the amount respectively.
+-- `scope` is one of `order`, `categories` or `products`, listing the ids in `promotion_targets`.
+CREATE TABLE promotions(
+    code      TEXT    NOT NULL PRIMARY KEY,
+    name      TEXT    NOT NULL,
+    discount  TEXT    NOT NULL,
+    value     INTEGER NOT NULL,
+    scope     TEXT    NOT NULL,
+    starts_at TEXT    NOT NULL,
+    ends_at   TEXT    NOT NULL,
+    cap       INTEGER,
+    ended     INTEGER NOT NULL DEFAULT 0
+);
+
+CREATE TABLE promotion_targets(
+    code   TEXT NOT NULL,
+    target TEXT NOT NULL,
+
+    PRIMARY KEY (code, target),
+
+    FOREIGN KEY (code) REFERENCES promotions (code) ON DELETE CASCADE
+);
+
+CREATE TABLE promotion_redemptions(
+    code        TEXT NOT NULL,
+    order_id    TEXT NOT NULL,
+    redeemed_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    PRIMARY KEY (code, order_id),
+
+    FOREIGN KEY (code) REFERENCES promotions (code) ON DELETE CASCADE
+);
+
+CREATE TABLE order_coupons(
+    order_id TEXT NOT NULL PRIMARY KEY,
+    code     TEXT NOT NULL,
+
+    FOREIGN KEY (order_id) REFERENCES order_status (order_id) ON DELETE CASCADE
+);
@@ -0,0 +1,7 @@
+-- Prices registered before taxes were introduced are treated as tax-inclusive at the standard rate.
+ALTER TABLE products ADD COLUMN tax_category TEXT NOT NULL DEFAULT 'standard';
+ALTER TABLE products ADD COLUMN tax_inclusive INTEGER NOT NULL DEFAULT 1;
+
+-- Rates in percent at the time of the order.
+ALTER TABLE order_items ADD COLUMN tax_rate INTEGER NOT NULL DEFAULT 10;
+ALTER TABLE sales ADD COLUMN tax_rate INTEGER NOT NULL DEFAULT 10;
@@ -0,0 +1,4 @@
+-- Prices are in the minor units of the currency. Everything was priced in yen before currencies were introduced.
+ALTER TABLE products ADD COLUMN currency TEXT NOT NULL DEFAULT 'JPY';
+ALTER TABLE order_items ADD COLUMN currency TEXT NOT NULL DEFAULT 'JPY';
+ALTER TABLE sales ADD COLUMN currency TEXT NOT NULL DEFAULT 'JPY';
@@ -0,0 +1,14 @@
+-- Whether the price of the line was listed including the tax, needed to print the charged unit price.
+ALTER TABLE order_items ADD COLUMN tax_inclusive INTEGER NOT NULL DEFAULT 1;
+
+-- The total of a paid order broken down by tax rate.
+CREATE TABLE order_taxes(
+    order_id TEXT    NOT NULL,
+    rate     INTEGER NOT NULL,
+    gross    INTEGER NOT NULL,
+    tax      INTEGER NOT NULL,
+
+    PRIMARY KEY (order_id, rate),
+
+    FOREIGN KEY (order_id) REFERENCES order_status (order_id) ON DELETE CASCADE
+);
@@ -0,0 +1,11 @@
+-- Only the devices themselves are listed, their keys are kept in the journal as hashes.
+-- `role` is one of `cashier`, `kitchen` or `kiosk`.
+CREATE TABLE devices(
+    id            TEXT    NOT NULL PRIMARY KEY,
+    name          TEXT    NOT NULL,
+    role          TEXT    NOT NULL,
+    store         TEXT    NOT NULL,
+    registered_at TEXT    NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    rotated_at    TEXT,
+    revoked       INTEGER NOT NULL DEFAULT 0
+);
@@ -0,0 +1,17 @@
+-- Who dispatched each catalog command, written next to the event it produced.
+-- `aggregate` is one of `product`, `category` or `categories`; `event` is the name of the event variant.
+-- `staff` and `device` are both NULL for commands the application dispatched by itself.
+CREATE TABLE audit_log(
+    id           INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+    aggregate    TEXT    NOT NULL,
+    aggregate_id TEXT    NOT NULL,
+    event        TEXT    NOT NULL,
+    staff        TEXT,
+    device       TEXT,
+    request_id   TEXT,
+    client_ip    TEXT,
+    recorded_at  TEXT    NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX audit_log_aggregate ON audit_log(aggregate, aggregate_id);
+CREATE INDEX audit_log_recorded_at ON audit_log(recorded_at);
@@ -0,0 +1,4 @@
+-- Amount charged for the line after the discount of the coupon, in the minor units of the currency.
+-- Coupons were not taken into account before this column was introduced.
+ALTER TABLE sales ADD COLUMN amount INTEGER NOT NULL DEFAULT 0;
+UPDATE sales SET amount = quantity * price;
@@ -0,0 +1,5 @@
+-- Payments and fixed discounts carry their currency. Everything was paid in yen before currencies were introduced.
+ALTER TABLE payments ADD COLUMN currency TEXT NOT NULL DEFAULT 'JPY';
+-- Only set for `fixed` discounts, percentages apply to orders in any currency.
+ALTER TABLE promotions ADD COLUMN currency TEXT;
+UPDATE promotions SET currency = 'JPY' WHERE discount = 'fixed';
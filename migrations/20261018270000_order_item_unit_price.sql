@@ -0,0 +1,15 @@
+-- Tax-inclusive price charged for a single unit, as calculated by the order when the line was added.
+ALTER TABLE order_items ADD COLUMN unit_price INTEGER NOT NULL DEFAULT 0;
+
+-- Lines added before this column was introduced are filled in the way the order calculates them,
+-- adding the tax to the listed price of each unit rounded down unless it was included.
+UPDATE order_items
+SET unit_price = (
+    SELECT CASE WHEN tax_inclusive THEN listed ELSE listed + listed * tax_rate / 100 END
+    FROM (
+        SELECT order_items.price + COALESCE(
+            (SELECT SUM(oio.price_delta) FROM order_item_options oio WHERE oio.order_id = order_items.order_id AND oio.line = order_items.line),
+            0
+        ) AS listed
+    )
+);
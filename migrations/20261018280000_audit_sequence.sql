@@ -0,0 +1,5 @@
+-- Sequence of the event in the journal of its aggregate, so that each entry can be matched with the event it was recorded for.
+-- Entries written before this column was introduced are left NULL.
+ALTER TABLE audit_log ADD COLUMN sequence INTEGER;
+
+CREATE UNIQUE INDEX audit_log_sequence ON audit_log(aggregate, aggregate_id, sequence);
@@ -0,0 +1,4 @@
+-- Sold out by hand, as opposed to running out of stock. `available` is only set again once neither applies.
+ALTER TABLE products ADD COLUMN marked_sold_out INTEGER NOT NULL DEFAULT 0;
+
+UPDATE products SET marked_sold_out = 1 WHERE available = 0 AND (stock IS NULL OR stock > 0);
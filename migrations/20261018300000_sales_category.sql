@@ -0,0 +1,13 @@
+-- Category a sale is counted towards, as the product was listed when it was sold.
+-- Sales recorded before this column was introduced are attributed by the current listing.
+ALTER TABLE sales ADD COLUMN category TEXT;
+ALTER TABLE sales ADD COLUMN category_name TEXT;
+
+UPDATE sales SET category = (
+    SELECT cpo.category FROM category_products_ordering cpo
+    LEFT JOIN categories_ordering co ON co.category = cpo.category
+    WHERE cpo.product = sales.product
+    ORDER BY co.ordering IS NULL, co.ordering
+    LIMIT 1
+);
+UPDATE sales SET category_name = (SELECT name FROM categories WHERE id = sales.category);
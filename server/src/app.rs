@@ -8,20 +8,48 @@ use nitinol::process::manager::ProcessManager;
 use nitinol::process::persistence::PersistenceExtension;
 use nitinol::projection::EventProjector;
 use nitinol::protocol::adapter::sqlite::SqliteEventStore;
-use app_cmd::adapter::{DependOnEventProjector, DependOnProcessManager};
+use nitinol::protocol::io::ReadProtocol;
+use app_cmd::adapter::{DependOnEventProjector, DependOnProcessManager, DependOnStoreOffset, DependOnTaxRates};
+use app_cmd::audit::DependOnAuditLog;
 use app_cmd::services::categories::DependOnCategoriesCommandService;
 use app_cmd::services::category::DependOnCategoryCommandService;
+use app_cmd::services::device::DependOnDeviceCommandService;
+use app_cmd::services::order::DependOnOrderCommandService;
 use app_cmd::services::product::DependOnProductCommandService;
-use app_cmd::workflow::product::DependOnRegisterProductWithCategoryWorkflow;
+use app_cmd::services::promotion::DependOnPromotionCommandService;
+use app_cmd::services::staff::{DependOnStaffCommandService, StaffCommandService};
+use app_cmd::services::ticket::DependOnTicketCounterCommandService;
+use app_cmd::workflow::order::{DependOnAddProductToOrderWorkflow, DependOnRedeemCouponWorkflow};
+use app_cmd::workflow::product::{DependOnComposeBundleWorkflow, DependOnDeleteProductWorkflow, DependOnRegisterProductWithCategoryWorkflow};
+use app_cmd::workflow::device::DependOnAuthenticateDeviceWorkflow;
+use app_cmd::workflow::staff::{DependOnAuthenticateStaffWorkflow, DependOnVerifyStaffSessionWorkflow};
+use app_cmd::errors::ApplicationError;
 use app_query::models::{
     DependOnGetAllCategoriesQueryService, 
+    DependOnGetAuditLogQueryService,
     DependOnGetAllProductQueryService, 
+    DependOnGetDeviceQueryService,
+    DependOnGetHistoryQueryService,
+    DependOnGetMenuQueryService,
+    DependOnGetOpenOrdersQueryService,
+    DependOnGetPaymentQueryService,
     DependOnGetProductImageQueryService, 
-    DependOnGetProductQueryService
+    DependOnGetProductQueryService,
+    DependOnGetPromotionQueryService,
+    DependOnGetReceiptQueryService,
+    DependOnGetTicketQueryService,
+    DependOnSalesReportQueryService,
 };
-use driver::database::{CategoryQueryModelService, ProductReadModelService};
-use driver::database::query::{CategoryQueryService, ProductQueryService};
+use driver::database::{AuditLogService, CategoryQueryModelService, DeviceReadModelService, OrderReadModelService, ProductReadModelService, PromotionReadModelService, SalesReadModelService, TicketReadModelService};
+use kernel::entities::product::{TaxRate, TaxRates};
+use kernel::entities::staff::{HashedPassword, Role, StaffName};
+use kernel::entities::ticket::StoreOffset;
+use kernel::io::commands::StaffCommand;
+use driver::database::query::{AuditQueryService, CategoryQueryService, DeviceQueryService, HistoryQueryService, OrderQueryService, PaymentQueryService, ProductQueryService, PromotionQueryService, SalesQueryService, TicketQueryService};
+use crate::auth::{Authenticator, DependOnAuthenticator};
 use crate::errors::UnrecoverableError;
+use crate::events::{DependOnEventBroadcaster, EventBroadcaster};
+use crate::receipt::{DependOnReceiptSettings, ReceiptSettings, StoreHeader};
 
 pub struct AppModule {
     inner: Arc<Handler>
@@ -44,44 +72,164 @@ impl Deref for AppModule {
 pub struct Handler {
     manager: ProcessManager,
     projector: EventProjector,
+    broadcaster: EventBroadcaster,
+    authenticator: Authenticator,
+    tax_rates: TaxRates,
+    store_offset: StoreOffset,
+    receipt: ReceiptSettings,
+    audit: AuditLogService,
+    query_audit: AuditQueryService,
     query_category: CategoryQueryService,
+    query_device: DeviceQueryService,
+    query_history: HistoryQueryService,
     query_product: ProductQueryService,
+    query_order: OrderQueryService,
+    query_payment: PaymentQueryService,
+    query_promotion: PromotionQueryService,
+    query_sales: SalesQueryService,
+    query_ticket: TicketQueryService,
 }
 
 impl AppModule {
     pub async fn setup() -> Result<AppModule, Report<UnrecoverableError>> {
-        let query = driver::database::init("sqlite:./.database/query.db").await
+        Self::connect("sqlite:./.database/query.db", "sqlite:./.database/journal.db").await
+    }
+    
+    /// Sets up the application on the given read model and journal databases.
+    pub async fn connect(query: &str, journal: &str) -> Result<AppModule, Report<UnrecoverableError>> {
+        let query = driver::database::init(query).await
             .change_context_lazy(|| UnrecoverableError)?;
         
-        let eventstore = SqliteEventStore::setup("sqlite:./.database/journal.db").await
+        let eventstore = SqliteEventStore::setup(journal).await
             .change_context_lazy(|| UnrecoverableError)?;
         
         let eventstream = EventStream::default();
         
         eventstream.subscribe(CategoryQueryModelService::new(query.clone())).await;
         eventstream.subscribe(ProductReadModelService::new(query.clone())).await;
+        eventstream.subscribe(OrderReadModelService::new(query.clone())).await;
+        eventstream.subscribe(SalesReadModelService::new(query.clone())).await;
+        eventstream.subscribe(TicketReadModelService::new(query.clone())).await;
+        eventstream.subscribe(PromotionReadModelService::new(query.clone())).await;
+        eventstream.subscribe(DeviceReadModelService::new(query.clone())).await;
+        
+        let broadcaster = EventBroadcaster::default();
+        eventstream.subscribe(broadcaster.clone()).await;
         
         let manager = ProcessManager::with_extension(|ext| {
             ext.install(PersistenceExtension::new(eventstore.clone()))?
                 .install(EventStreamExtension::new(eventstream))
         }).change_context_lazy(|| UnrecoverableError)?;
         
+        let journal = ReadProtocol::new(eventstore.clone());
         let projector = EventProjector::new(eventstore);
         
+        let tax_rates = tax_rates()?;
+        let store_offset = store_offset()?;
+        let receipt = receipt_settings(store_offset)?;
+        let authenticator = Authenticator::from_env()?;
+        
+        let audit = AuditLogService::new(query.clone());
+        
+        let query_audit = AuditQueryService::new(query.clone());
         let query_category = CategoryQueryService::new(query.clone());
-        let query_product = ProductQueryService::new(query);
+        let query_device = DeviceQueryService::new(query.clone());
+        let query_history = HistoryQueryService::new(journal, store_offset);
+        let query_product = ProductQueryService::new(query.clone(), tax_rates);
+        let query_order = OrderQueryService::new(query.clone());
+        let query_payment = PaymentQueryService::new(query.clone(), store_offset);
+        let query_promotion = PromotionQueryService::new(query.clone());
+        let query_sales = SalesQueryService::new(query.clone(), store_offset);
+        let query_ticket = TicketQueryService::new(query, store_offset);
+
+        let handler = Handler {
+            manager,
+            projector,
+            broadcaster,
+            authenticator,
+            tax_rates,
+            store_offset,
+            receipt,
+            audit,
+            query_audit,
+            query_category,
+            query_device,
+            query_history,
+            query_product,
+            query_order,
+            query_payment,
+            query_promotion,
+            query_sales,
+            query_ticket,
+        };
+        
+        bootstrap_staff(&handler).await?;
+
+        Ok(AppModule { inner: Arc::new(handler) })
+    }
+}
 
-        Ok(AppModule {
-            inner: Arc::new(Handler {
-                manager,
-                projector,
-                query_category,
-                query_product,
-            })
-        })
+/// Registers an admin named by `STAFF_ADMIN_NAME` with `STAFF_ADMIN_PASSWORD`, 
+/// so that someone can sign in to a fresh installation. Does nothing once the name is taken.
+async fn bootstrap_staff(handler: &Handler) -> Result<(), Report<UnrecoverableError>> {
+    let (Ok(name), Ok(password)) = (std::env::var("STAFF_ADMIN_NAME"), std::env::var("STAFF_ADMIN_PASSWORD")) else {
+        return Ok(());
+    };
+    
+    let name = StaffName::new(name)
+        .change_context_lazy(|| UnrecoverableError)?;
+    let password = HashedPassword::new(password)
+        .change_context_lazy(|| UnrecoverableError)?;
+    
+    match handler.staff_command_service().execute(name.clone(), StaffCommand::Register { role: Role::Admin, password }).await {
+        Ok(()) => {
+            tracing::info!("registered staff `{name}`.");
+            Ok(())
+        }
+        Err(e) if matches!(e.current_context(), ApplicationError::InvalidCommand) => Ok(()),
+        Err(e) => Err(e.change_context(UnrecoverableError)),
     }
 }
 
+/// Reads the tax rates from `TAX_RATE_STANDARD` and `TAX_RATE_REDUCED`, falling back to 10% and 8%.
+fn tax_rates() -> Result<TaxRates, Report<UnrecoverableError>> {
+    fn read(key: &str, default: i64) -> Result<TaxRate, Report<UnrecoverableError>> {
+        let rate = match std::env::var(key) {
+            Ok(rate) => rate.parse::<i64>()
+                .change_context_lazy(|| UnrecoverableError)
+                .attach_printable_lazy(|| format!("`{key}` must be an integer"))?,
+            Err(_) => default,
+        };
+        TaxRate::new(rate)
+            .change_context_lazy(|| UnrecoverableError)
+    }
+    
+    Ok(TaxRates::new(read("TAX_RATE_STANDARD", 10)?, read("TAX_RATE_REDUCED", 8)?))
+}
+
+/// Reads the UTC offset of the store from `STORE_UTC_OFFSET` (such as `+09:00`), falling back to UTC.
+/// 
+/// The local offset of the host cannot be read once the runtime has started other threads, 
+/// so it has to be configured instead.
+fn store_offset() -> Result<StoreOffset, Report<UnrecoverableError>> {
+    match std::env::var("STORE_UTC_OFFSET") {
+        Ok(offset) => StoreOffset::parse(offset)
+            .change_context_lazy(|| UnrecoverableError),
+        Err(_) => Ok(StoreOffset::default()),
+    }
+}
+
+fn receipt_settings(offset: StoreOffset) -> Result<ReceiptSettings, Report<UnrecoverableError>> {
+    let font = match std::env::var("RECEIPT_FONT") {
+        Ok(path) => Some(std::fs::read(&path)
+            .change_context_lazy(|| UnrecoverableError)
+            .attach_printable_lazy(|| format!("Failed to read the receipt font at `{path}`"))?),
+        Err(_) => None,
+    };
+    
+    Ok(ReceiptSettings { store: StoreHeader::from_env(offset), font })
+}
+
 impl DependOnProcessManager for Handler {
     fn process_manager(&self) -> &ProcessManager {
         &self.manager
@@ -94,6 +242,44 @@ impl DependOnEventProjector for Handler {
     }
 }
 
+impl DependOnTaxRates for Handler {
+    fn tax_rates(&self) -> &TaxRates {
+        &self.tax_rates
+    }
+}
+
+impl DependOnStoreOffset for Handler {
+    fn store_offset(&self) -> &StoreOffset {
+        &self.store_offset
+    }
+}
+
+impl DependOnReceiptSettings for Handler {
+    fn receipt_settings(&self) -> &ReceiptSettings {
+        &self.receipt
+    }
+}
+
+impl DependOnAuthenticator for Handler {
+    fn authenticator(&self) -> &Authenticator {
+        &self.authenticator
+    }
+}
+
+impl DependOnEventBroadcaster for Handler {
+    fn event_broadcaster(&self) -> &EventBroadcaster {
+        &self.broadcaster
+    }
+}
+
+impl DependOnAuditLog for Handler {
+    type AuditLog = AuditLogService;
+
+    fn audit_log(&self) -> &Self::AuditLog {
+        &self.audit
+    }
+}
+
 impl DependOnCategoryCommandService for Handler {
     type CategoryCommandService = Self;
 
@@ -118,6 +304,54 @@ impl DependOnProductCommandService for Handler {
     }
 }
 
+impl DependOnOrderCommandService for Handler {
+    type OrderCommandService = Self;
+
+    fn order_command_service(&self) -> &Self::OrderCommandService {
+        self
+    }
+}
+
+impl DependOnPromotionCommandService for Handler {
+    type PromotionCommandService = Self;
+
+    fn promotion_command_service(&self) -> &Self::PromotionCommandService {
+        self
+    }
+}
+
+impl DependOnStaffCommandService for Handler {
+    type StaffCommandService = Self;
+
+    fn staff_command_service(&self) -> &Self::StaffCommandService {
+        self
+    }
+}
+
+impl DependOnDeviceCommandService for Handler {
+    type DeviceCommandService = Self;
+
+    fn device_command_service(&self) -> &Self::DeviceCommandService {
+        self
+    }
+}
+
+impl DependOnTicketCounterCommandService for Handler {
+    type TicketCounterCommandService = Self;
+
+    fn ticket_counter_command_service(&self) -> &Self::TicketCounterCommandService {
+        self
+    }
+}
+
+impl DependOnGetAuditLogQueryService for Handler {
+    type GetAuditLogQueryService = AuditQueryService;
+
+    fn get_audit_log_query_service(&self) -> &Self::GetAuditLogQueryService {
+        &self.query_audit
+    }
+}
+
 impl DependOnGetAllCategoriesQueryService for Handler {
     type GetAllCategoriesQueryService = CategoryQueryService;
 
@@ -150,6 +384,78 @@ impl DependOnGetProductImageQueryService for Handler {
     }
 }
 
+impl DependOnGetDeviceQueryService for Handler {
+    type GetDeviceQueryService = DeviceQueryService;
+
+    fn get_device_query_service(&self) -> &Self::GetDeviceQueryService {
+        &self.query_device
+    }
+}
+
+impl DependOnGetHistoryQueryService for Handler {
+    type GetHistoryQueryService = HistoryQueryService;
+
+    fn get_history_query_service(&self) -> &Self::GetHistoryQueryService {
+        &self.query_history
+    }
+}
+
+impl DependOnGetMenuQueryService for Handler {
+    type GetMenuQueryService = HistoryQueryService;
+
+    fn get_menu_query_service(&self) -> &Self::GetMenuQueryService {
+        &self.query_history
+    }
+}
+
+impl DependOnGetOpenOrdersQueryService for Handler {
+    type GetOpenOrdersQueryService = OrderQueryService;
+
+    fn get_open_orders_query_service(&self) -> &Self::GetOpenOrdersQueryService {
+        &self.query_order
+    }
+}
+
+impl DependOnGetReceiptQueryService for Handler {
+    type GetReceiptQueryService = OrderQueryService;
+
+    fn get_receipt_query_service(&self) -> &Self::GetReceiptQueryService {
+        &self.query_order
+    }
+}
+
+impl DependOnGetPaymentQueryService for Handler {
+    type GetPaymentQueryService = PaymentQueryService;
+
+    fn get_payment_query_service(&self) -> &Self::GetPaymentQueryService {
+        &self.query_payment
+    }
+}
+
+impl DependOnGetPromotionQueryService for Handler {
+    type GetPromotionQueryService = PromotionQueryService;
+
+    fn get_promotion_query_service(&self) -> &Self::GetPromotionQueryService {
+        &self.query_promotion
+    }
+}
+
+impl DependOnGetTicketQueryService for Handler {
+    type GetTicketQueryService = TicketQueryService;
+
+    fn get_ticket_query_service(&self) -> &Self::GetTicketQueryService {
+        &self.query_ticket
+    }
+}
+
+impl DependOnSalesReportQueryService for Handler {
+    type SalesReportQueryService = SalesQueryService;
+
+    fn sales_report_query_service(&self) -> &Self::SalesReportQueryService {
+        &self.query_sales
+    }
+}
+
 // -- Workflows
 
 impl DependOnRegisterProductWithCategoryWorkflow for Handler {
@@ -158,4 +464,60 @@ impl DependOnRegisterProductWithCategoryWorkflow for Handler {
     fn register_product_with_category_workflow(&self) -> &Self::RegisterProductWithCategoryWorkflow {
         self
     }
-}
\ No newline at end of file
+}
+
+impl DependOnComposeBundleWorkflow for Handler {
+    type ComposeBundleWorkflow = Self;
+
+    fn compose_bundle_workflow(&self) -> &Self::ComposeBundleWorkflow {
+        self
+    }
+}
+
+impl DependOnDeleteProductWorkflow for Handler {
+    type DeleteProductWorkflow = Self;
+
+    fn delete_product_workflow(&self) -> &Self::DeleteProductWorkflow {
+        self
+    }
+}
+
+impl DependOnAddProductToOrderWorkflow for Handler {
+    type AddProductToOrderWorkflow = Self;
+
+    fn add_product_to_order_workflow(&self) -> &Self::AddProductToOrderWorkflow {
+        self
+    }
+}
+
+impl DependOnRedeemCouponWorkflow for Handler {
+    type RedeemCouponWorkflow = Self;
+
+    fn redeem_coupon_workflow(&self) -> &Self::RedeemCouponWorkflow {
+        self
+    }
+}
+
+impl DependOnAuthenticateStaffWorkflow for Handler {
+    type AuthenticateStaffWorkflow = Self;
+
+    fn authenticate_staff_workflow(&self) -> &Self::AuthenticateStaffWorkflow {
+        self
+    }
+}
+
+impl DependOnVerifyStaffSessionWorkflow for Handler {
+    type VerifyStaffSessionWorkflow = Self;
+
+    fn verify_staff_session_workflow(&self) -> &Self::VerifyStaffSessionWorkflow {
+        self
+    }
+}
+
+impl DependOnAuthenticateDeviceWorkflow for Handler {
+    type AuthenticateDeviceWorkflow = Self;
+
+    fn authenticate_device_workflow(&self) -> &Self::AuthenticateDeviceWorkflow {
+        self
+    }
+}
@@ -0,0 +1,42 @@
+use std::net::SocketAddr;
+
+use axum::extract::{ConnectInfo, FromRequestParts};
+use axum::http::request::Parts;
+use axum::http::StatusCode;
+use uuid::Uuid;
+
+use app_cmd::audit::Actor;
+use kernel::entities::device::DeviceId;
+use kernel::entities::staff::StaffName;
+
+use crate::auth::Principal;
+use crate::AppModule;
+
+/// Header a request id is taken from, generated when missing.
+pub const REQUEST_ID: &str = "x-request-id";
+
+/// The [`Actor`] of an authenticated request, which catalog commands are recorded in the audit log as.
+/// 
+/// The client IP is the peer address of the connection, so it is the proxy's if there is one in front.
+pub struct Dispatcher(pub Actor);
+
+impl FromRequestParts<AppModule> for Dispatcher {
+    type Rejection = StatusCode;
+
+    async fn from_request_parts(parts: &mut Parts, app: &AppModule) -> Result<Self, Self::Rejection> {
+        let (staff, device) = match Principal::from_request_parts(parts, app).await? {
+            Principal::Staff { name, .. } => (StaffName::new(name).ok(), None),
+            Principal::Device { id, .. } => (None, Some(DeviceId::new(id))),
+        };
+        
+        let request_id = parts.headers.get(REQUEST_ID)
+            .and_then(|id| id.to_str().ok())
+            .map(ToString::to_string)
+            .unwrap_or_else(|| Uuid::new_v4().to_string());
+        
+        let client_ip = parts.extensions.get::<ConnectInfo<SocketAddr>>()
+            .map(|ConnectInfo(addr)| addr.ip());
+        
+        Ok(Self(Actor { staff, device, request_id: Some(request_id), client_ip }))
+    }
+}
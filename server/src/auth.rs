@@ -0,0 +1,262 @@
+use axum::extract::{FromRequestParts, Request, State};
+use axum::http::header::AUTHORIZATION;
+use axum::http::request::Parts;
+use axum::http::{HeaderMap, Method, StatusCode};
+use axum::middleware::Next;
+use axum::response::Response;
+use error_stack::{Report, ResultExt};
+use jsonwebtoken::{DecodingKey, EncodingKey, Header, Validation};
+use serde::{Deserialize, Serialize};
+use time::{Duration, OffsetDateTime};
+
+use uuid::Uuid;
+
+use app_cmd::workflow::device::{AuthenticateDeviceWorkflow, DependOnAuthenticateDeviceWorkflow};
+use app_cmd::workflow::staff::{DependOnVerifyStaffSessionWorkflow, VerifyStaffSessionWorkflow};
+use kernel::entities::device::{ApiKey, DeviceId, StoreCode};
+use kernel::entities::staff::{Permission, Role, Staff, StaffName};
+
+use crate::AppModule;
+use crate::errors::{ServerError, UnrecoverableError};
+
+/// Lifetime of a session, unless `JWT_TTL_SECS` says otherwise.
+const DEFAULT_TTL: Duration = Duration::hours(12);
+
+/// Header devices authenticate with, carrying `<device id>.<api key>`.
+pub const DEVICE_KEY: &str = "x-device-key";
+
+/// Whoever a request has been authenticated as.
+#[derive(Debug, Clone)]
+pub enum Principal {
+    Staff { name: String, role: Role },
+    Device { id: Uuid, role: Role },
+}
+
+impl Principal {
+    pub fn role(&self) -> Role {
+        match self {
+            Principal::Staff { role, .. } | Principal::Device { role, .. } => *role,
+        }
+    }
+}
+
+/// Payload of the JWTs issued on sign-in.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Claims {
+    /// Name of the signed-in staff.
+    pub sub: String,
+    /// Role at the time of sign-in, for clients only.
+    /// Requests are authorized with the role the staff has now.
+    pub role: Role,
+    /// Credential version of the staff at the time of sign-in.
+    /// Sessions issued before the password was changed are rejected.
+    #[serde(default)]
+    pub ver: u32,
+    pub iat: i64,
+    pub exp: i64,
+}
+
+/// Issues and verifies the HS256-signed JWTs that staff sessions are carried in,
+/// and knows the store devices must be bound to.
+pub struct Authenticator {
+    encoding: EncodingKey,
+    decoding: DecodingKey,
+    ttl: Duration,
+    store: StoreCode,
+}
+
+pub trait DependOnAuthenticator: 'static + Sync + Send {
+    fn authenticator(&self) -> &Authenticator;
+}
+
+impl Authenticator {
+    pub fn new(secret: &[u8], ttl: Duration, store: StoreCode) -> Self {
+        Self {
+            encoding: EncodingKey::from_secret(secret),
+            decoding: DecodingKey::from_secret(secret),
+            ttl,
+            store,
+        }
+    }
+    
+    /// Reads the signing secret from `JWT_SECRET` and the session lifetime from `JWT_TTL_SECS`,
+    /// along with the code of this store from `STORE_CODE`, which defaults to `main`.
+    ///
+    /// Without a secret, a random one is generated, so sessions do not survive a restart.
+    pub fn from_env() -> Result<Self, Report<UnrecoverableError>> {
+        let secret = match std::env::var("JWT_SECRET") {
+            Ok(secret) if !secret.is_empty() => secret,
+            _ => {
+                tracing::warn!("`JWT_SECRET` is not set, sessions will be invalidated on restart.");
+                format!("{}{}", uuid::Uuid::new_v4().simple(), uuid::Uuid::new_v4().simple())
+            }
+        };
+        
+        let ttl = match std::env::var("JWT_TTL_SECS") {
+            Ok(ttl) => ttl.parse::<i64>()
+                .ok()
+                .filter(|ttl| *ttl > 0)
+                .map(Duration::seconds)
+                .ok_or_else(|| Report::new(UnrecoverableError)
+                    .attach_printable("`JWT_TTL_SECS` must be a positive integer"))?,
+            Err(_) => DEFAULT_TTL,
+        };
+        
+        let store = StoreCode::new(std::env::var("STORE_CODE").unwrap_or_else(|_| "main".to_string()))
+            .change_context_lazy(|| UnrecoverableError)?;
+        
+        Ok(Self::new(secret.as_bytes(), ttl, store))
+    }
+    
+    /// Lifetime of the issued tokens.
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+    
+    pub fn store(&self) -> &StoreCode {
+        &self.store
+    }
+    
+    pub fn issue(&self, staff: &Staff) -> Result<String, Report<ServerError>> {
+        let now = OffsetDateTime::now_utc();
+        
+        let claims = Claims {
+            sub: staff.name().to_string(),
+            role: *staff.role(),
+            ver: staff.credential_version(),
+            iat: now.unix_timestamp(),
+            exp: (now + self.ttl).unix_timestamp(),
+        };
+        
+        jsonwebtoken::encode(&Header::default(), &claims, &self.encoding)
+            .change_context_lazy(|| ServerError::Unauthorized)
+    }
+    
+    pub fn verify(&self, token: &str) -> Result<Claims, Report<ServerError>> {
+        jsonwebtoken::decode::<Claims>(token, &self.decoding, &Validation::default())
+            .map(|data| data.claims)
+            .change_context_lazy(|| ServerError::Unauthorized)
+    }
+}
+
+/// Extracts the token from an `Authorization: Bearer <token>` header.
+pub fn bearer(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get(AUTHORIZATION)?
+        .to_str()
+        .ok()?
+        .strip_prefix("Bearer ")
+        .map(str::trim)
+}
+
+/// Authenticates a device by its [`DEVICE_KEY`] header, or else staff by their session,
+/// rejecting the request with `401` if neither is valid.
+///
+/// A session is only as good as the staff behind it, so removed staff are rejected 
+/// even though their token has not expired yet, as are sessions issued before a password change, 
+/// and a role change applies right away.
+pub async fn authenticate(app: &AppModule, headers: &HeaderMap) -> Result<Principal, StatusCode> {
+    if let Some(value) = headers.get(DEVICE_KEY) {
+        let Some((id, key)) = value.to_str().ok().and_then(|value| value.trim().split_once('.')) else {
+            return Err(StatusCode::UNAUTHORIZED);
+        };
+        
+        let id = Uuid::parse_str(id)
+            .map(DeviceId::new)
+            .map_err(|_| StatusCode::UNAUTHORIZED)?;
+        
+        let store = app.authenticator().store();
+        
+        return match app.authenticate_device_workflow().execute(id, ApiKey::new(key), store).await {
+            Ok(device) => Ok(Principal::Device { id: (*device.id()).into(), role: *device.role() }),
+            Err(e) => {
+                tracing::warn!("rejected device: {:?}", e);
+                Err(StatusCode::UNAUTHORIZED)
+            }
+        };
+    }
+    
+    let Some(token) = bearer(headers) else {
+        return Err(StatusCode::UNAUTHORIZED);
+    };
+    
+    let claims = match app.authenticator().verify(token) {
+        Ok(claims) => claims,
+        Err(e) => {
+            tracing::warn!("rejected session: {:?}", e);
+            return Err(StatusCode::UNAUTHORIZED);
+        }
+    };
+    
+    let name = StaffName::new(&claims.sub)
+        .map_err(|_| StatusCode::UNAUTHORIZED)?;
+    
+    match app.verify_staff_session_workflow().execute(name).await {
+        Ok(staff) if staff.credential_version() != claims.ver => {
+            tracing::warn!("rejected session of {} issued before a password change", claims.sub);
+            Err(StatusCode::UNAUTHORIZED)
+        }
+        Ok(staff) => Ok(Principal::Staff { name: claims.sub, role: *staff.role() }),
+        Err(e) => {
+            tracing::warn!("rejected session: {:?}", e);
+            Err(StatusCode::UNAUTHORIZED)
+        }
+    }
+}
+
+/// Reuses the [`Principal`] a [`Guard`] has authenticated, or authenticates the request itself.
+impl FromRequestParts<AppModule> for Principal {
+    type Rejection = StatusCode;
+
+    async fn from_request_parts(parts: &mut Parts, app: &AppModule) -> Result<Self, Self::Rejection> {
+        if let Some(principal) = parts.extensions.get::<Principal>() {
+            return Ok(principal.clone());
+        }
+        
+        authenticate(app, &parts.headers).await
+    }
+}
+
+/// State of [`authorize`], restricting the routes it is layered on to a [`Permission`].
+#[derive(Clone)]
+pub struct Guard {
+    app: AppModule,
+    permission: Permission,
+    public_reads: bool,
+}
+
+impl Guard {
+    pub fn new(app: &AppModule, permission: Permission) -> Self {
+        Self { app: app.clone(), permission, public_reads: false }
+    }
+    
+    /// Lets reads through without a session, guarding every other method.
+    pub fn writes(app: &AppModule, permission: Permission) -> Self {
+        Self { public_reads: true, ..Self::new(app, permission) }
+    }
+}
+
+/// Rejects requests that cannot be [`authenticate`]d with `401`, 
+/// and those whose role lacks the permission of the [`Guard`] with `403`.
+/// 
+/// The [`Principal`] is inserted into the request extensions for the handlers.
+pub async fn authorize(
+    State(guard): State<Guard>,
+    mut req: Request,
+    next: Next,
+) -> Result<Response, StatusCode> {
+    if guard.public_reads && matches!(*req.method(), Method::GET | Method::HEAD | Method::OPTIONS) {
+        return Ok(next.run(req).await);
+    }
+    
+    let principal = authenticate(&guard.app, req.headers()).await?;
+    
+    if !principal.role().permits(guard.permission) {
+        tracing::warn!("{:?} is not permitted to {:?}", principal, guard.permission);
+        return Err(StatusCode::FORBIDDEN);
+    }
+    
+    req.extensions_mut().insert(principal);
+    
+    Ok(next.run(req).await)
+}
@@ -6,6 +6,10 @@ pub enum ServerError {
     InvalidFormat,
     #[error("Invalid request format")]
     UnknownFormat,
+    #[error("failed to export a file")]
+    Export,
+    #[error("session is missing or invalid")]
+    Unauthorized,
 }
 
 #[derive(Debug, thiserror::Error)]
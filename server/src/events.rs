@@ -0,0 +1,245 @@
+use std::convert::Infallible;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use async_trait::async_trait;
+use error_stack::Report;
+use serde::Serialize;
+use tokio::sync::broadcast;
+
+use kernel::entities::image::Image;
+use kernel::entities::order::{OrderId, OrderStatus};
+use kernel::io::events::{CategoriesEvent, CategoryEvent, OrderEvent, ProductEvent, TicketCounterEvent};
+use nitinol::eventstream::resolver::{DecodeMapping, SubscriptionMapper};
+use nitinol::eventstream::EventSubscriber;
+
+use crate::errors::ServerError;
+
+/// Number of events kept for slow receivers before they start lagging behind.
+const CAPACITY: usize = 256;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Aggregate {
+    Product,
+    Category,
+    Categories,
+    Order,
+    Ticket,
+}
+
+impl Aggregate {
+    /// Aggregates streamed on the public `GET /events`.
+    /// Orders and tickets are only followed by the pickup board.
+    pub const CATALOG: [Aggregate; 3] = [Aggregate::Product, Aggregate::Category, Aggregate::Categories];
+    
+    pub fn is_catalog(&self) -> bool {
+        Self::CATALOG.contains(self)
+    }
+}
+
+impl AsRef<str> for Aggregate {
+    fn as_ref(&self) -> &str {
+        match self {
+            Aggregate::Product => "product",
+            Aggregate::Category => "category",
+            Aggregate::Categories => "categories",
+            Aggregate::Order => "order",
+            Aggregate::Ticket => "ticket",
+        }
+    }
+}
+
+impl Display for Aggregate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_ref())
+    }
+}
+
+impl FromStr for Aggregate {
+    type Err = Report<ServerError>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "product" => Ok(Aggregate::Product),
+            "category" => Ok(Aggregate::Category),
+            "categories" => Ok(Aggregate::Categories),
+            "order" => Ok(Aggregate::Order),
+            "ticket" => Ok(Aggregate::Ticket),
+            _ => Err(Report::new(ServerError::InvalidFormat)
+                .attach_printable(format!("Unknown aggregate `{s}`"))),
+        }
+    }
+}
+
+/// Domain events as they are pushed to live displays.
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+pub enum DomainEvent {
+    Product(ProductEvent),
+    Category(CategoryEvent),
+    Categories(CategoriesEvent),
+    Order(OrderProgress),
+    Ticket(TicketCounterEvent),
+}
+
+/// Status changes of an order, which is all that live displays follow of it.
+/// 
+/// Lines, coupon codes and payments never leave the server this way.
+#[derive(Debug, Clone, Serialize)]
+pub enum OrderProgress {
+    ChangedStatus {
+        id: OrderId,
+        new: OrderStatus,
+    },
+    Cancelled {
+        id: OrderId,
+    },
+}
+
+impl DomainEvent {
+    pub fn aggregate(&self) -> Aggregate {
+        match self {
+            DomainEvent::Product(_) => Aggregate::Product,
+            DomainEvent::Category(_) => Aggregate::Category,
+            DomainEvent::Categories(_) => Aggregate::Categories,
+            DomainEvent::Order(_) => Aggregate::Order,
+            DomainEvent::Ticket(_) => Aggregate::Ticket,
+        }
+    }
+}
+
+impl From<ProductEvent> for DomainEvent {
+    fn from(event: ProductEvent) -> Self {
+        // Image bytes are served by `GET /images/{image_id}`, so only the id is rebroadcast.
+        let strip = |image: Image| Image::new(*image.id(), Vec::new());
+        let event = match event {
+            ProductEvent::Registered { id, name, desc, price, image } => {
+                ProductEvent::Registered { id, name, desc, price, image: strip(image) }
+            }
+            ProductEvent::ChangedProductImage { id, image } => {
+                ProductEvent::ChangedProductImage { id, image: strip(image) }
+            }
+            other => other,
+        };
+        DomainEvent::Product(event)
+    }
+}
+
+impl From<CategoryEvent> for DomainEvent {
+    fn from(event: CategoryEvent) -> Self {
+        DomainEvent::Category(event)
+    }
+}
+
+impl From<CategoriesEvent> for DomainEvent {
+    fn from(event: CategoriesEvent) -> Self {
+        DomainEvent::Categories(event)
+    }
+}
+
+impl From<OrderProgress> for DomainEvent {
+    fn from(event: OrderProgress) -> Self {
+        DomainEvent::Order(event)
+    }
+}
+
+impl From<TicketCounterEvent> for DomainEvent {
+    fn from(event: TicketCounterEvent) -> Self {
+        DomainEvent::Ticket(event)
+    }
+}
+
+/// Rebroadcasts events from the [`EventStream`](nitinol::eventstream::EventStream)
+/// to every connected client.
+#[derive(Clone)]
+pub struct EventBroadcaster {
+    tx: broadcast::Sender<DomainEvent>
+}
+
+impl Default for EventBroadcaster {
+    fn default() -> Self {
+        let (tx, _) = broadcast::channel(CAPACITY);
+        Self { tx }
+    }
+}
+
+impl EventBroadcaster {
+    pub fn subscribe(&self) -> broadcast::Receiver<DomainEvent> {
+        self.tx.subscribe()
+    }
+
+    fn send(&self, event: impl Into<DomainEvent>) {
+        // An error only means that no client is currently connected.
+        let _ = self.tx.send(event.into());
+    }
+}
+
+impl SubscriptionMapper for EventBroadcaster {
+    fn mapping(mapping: &mut DecodeMapping<Self>) {
+        mapping
+            .register::<ProductEvent>()
+            .register::<CategoryEvent>()
+            .register::<CategoriesEvent>()
+            .register::<OrderEvent>()
+            .register::<TicketCounterEvent>();
+    }
+}
+
+#[async_trait]
+impl EventSubscriber<ProductEvent> for EventBroadcaster {
+    type Error = Infallible;
+
+    async fn on(&mut self, event: ProductEvent) -> Result<(), Self::Error> {
+        self.send(event);
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl EventSubscriber<CategoryEvent> for EventBroadcaster {
+    type Error = Infallible;
+
+    async fn on(&mut self, event: CategoryEvent) -> Result<(), Self::Error> {
+        self.send(event);
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl EventSubscriber<CategoriesEvent> for EventBroadcaster {
+    type Error = Infallible;
+
+    async fn on(&mut self, event: CategoriesEvent) -> Result<(), Self::Error> {
+        self.send(event);
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl EventSubscriber<OrderEvent> for EventBroadcaster {
+    type Error = Infallible;
+
+    async fn on(&mut self, event: OrderEvent) -> Result<(), Self::Error> {
+        let progress = match event {
+            OrderEvent::ChangedStatus { id, new } => OrderProgress::ChangedStatus { id, new },
+            OrderEvent::Cancelled { id } => OrderProgress::Cancelled { id },
+            _ => return Ok(()),
+        };
+        self.send(progress);
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl EventSubscriber<TicketCounterEvent> for EventBroadcaster {
+    type Error = Infallible;
+
+    async fn on(&mut self, event: TicketCounterEvent) -> Result<(), Self::Error> {
+        self.send(event);
+        Ok(())
+    }
+}
+
+pub trait DependOnEventBroadcaster: 'static + Sync + Send {
+    fn event_broadcaster(&self) -> &EventBroadcaster;
+}
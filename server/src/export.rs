@@ -0,0 +1,101 @@
+//! Spreadsheet renderings of reports for accounting.
+
+use error_stack::{Report, ResultExt};
+use rust_xlsxwriter::{Format, Workbook, Worksheet};
+
+use app_query::models::SalesReport;
+
+use crate::errors::ServerError;
+
+/// Lets Excel detect UTF-8, otherwise Japanese product names are garbled.
+const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+
+const PRODUCT_HEADER: [&str; 5] = ["product_id", "name", "currency", "quantity", "amount"];
+const HOURLY_HEADER: [&str; 5] = ["hour", "currency", "orders", "quantity", "amount"];
+
+/// Renders the per-product and per-hour breakdowns as two tables separated by an empty row.
+pub fn sales_csv(report: &SalesReport, bom: bool) -> Result<Vec<u8>, Report<ServerError>> {
+    let mut buf = Vec::new();
+    if bom {
+        buf.extend_from_slice(UTF8_BOM);
+    }
+    
+    let mut writer = csv::WriterBuilder::new()
+        .flexible(true)
+        .from_writer(buf);
+    
+    writer.write_record(PRODUCT_HEADER)
+        .change_context_lazy(|| ServerError::Export)?;
+    for sales in &report.products {
+        writer.write_record([
+            sales.product.to_string(),
+            sales.name.clone().unwrap_or_default(),
+            sales.currency.clone(),
+            sales.quantity.to_string(),
+            sales.amount.to_string(),
+        ]).change_context_lazy(|| ServerError::Export)?;
+    }
+    
+    writer.write_record([""])
+        .change_context_lazy(|| ServerError::Export)?;
+    
+    writer.write_record(HOURLY_HEADER)
+        .change_context_lazy(|| ServerError::Export)?;
+    for sales in &report.hours {
+        writer.write_record([
+            sales.hour.to_string(),
+            sales.currency.clone(),
+            sales.orders.to_string(),
+            sales.quantity.to_string(),
+            sales.amount.to_string(),
+        ]).change_context_lazy(|| ServerError::Export)?;
+    }
+    
+    writer.into_inner()
+        .change_context_lazy(|| ServerError::Export)
+}
+
+/// Renders the per-product and per-hour breakdowns into separate worksheets.
+pub fn sales_xlsx(report: &SalesReport) -> Result<Vec<u8>, Report<ServerError>> {
+    let mut workbook = Workbook::new();
+    let bold = Format::new().set_bold();
+    
+    let products = workbook.add_worksheet()
+        .set_name("Products")
+        .change_context_lazy(|| ServerError::Export)?;
+    write_header(products, &PRODUCT_HEADER, &bold)?;
+    for (row, sales) in (1..).zip(&report.products) {
+        products.write_string(row, 0, sales.product.to_string())
+            .and_then(|sheet| sheet.write_string(row, 1, sales.name.as_deref().unwrap_or_default()))
+            .and_then(|sheet| sheet.write_string(row, 2, &sales.currency))
+            .and_then(|sheet| sheet.write_number(row, 3, sales.quantity as f64))
+            .and_then(|sheet| sheet.write_number(row, 4, sales.amount as f64))
+            .change_context_lazy(|| ServerError::Export)?;
+    }
+    products.autofit();
+    
+    let hours = workbook.add_worksheet()
+        .set_name("Hours")
+        .change_context_lazy(|| ServerError::Export)?;
+    write_header(hours, &HOURLY_HEADER, &bold)?;
+    for (row, sales) in (1..).zip(&report.hours) {
+        hours.write_number(row, 0, sales.hour as f64)
+            .and_then(|sheet| sheet.write_string(row, 1, &sales.currency))
+            .and_then(|sheet| sheet.write_number(row, 2, sales.orders as f64))
+            .and_then(|sheet| sheet.write_number(row, 3, sales.quantity as f64))
+            .and_then(|sheet| sheet.write_number(row, 4, sales.amount as f64))
+            .change_context_lazy(|| ServerError::Export)?;
+    }
+    hours.autofit();
+    
+    workbook.save_to_buffer()
+        .change_context_lazy(|| ServerError::Export)
+}
+
+fn write_header(sheet: &mut Worksheet, header: &[&str], format: &Format) -> Result<(), Report<ServerError>> {
+    for (col, title) in (0..).zip(header) {
+        sheet.write_string_with_format(0, col, *title, format)
+            .change_context_lazy(|| ServerError::Export)?;
+    }
+    Ok(())
+}
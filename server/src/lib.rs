@@ -1,6 +1,12 @@
+pub mod audit;
+pub mod auth;
 pub mod errors;
+pub mod events;
+pub mod export;
 pub mod logging;
+pub mod receipt;
 pub mod routing;
+pub mod slip;
 mod app;
 
 
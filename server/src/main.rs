@@ -1,12 +1,13 @@
+use std::net::SocketAddr;
+
 use axum::extract::DefaultBodyLimit;
-use axum::routing::{delete, get};
+use axum::routing::get;
 use axum::Router;
 use error_stack::{Report, ResultExt};
 use tokio::net::TcpListener;
 use tower_http::cors::CorsLayer;
 use tower_http::trace::TraceLayer;
 use server::AppModule;
-use server::routing::*;
 use server::errors::UnrecoverableError;
 
 #[tokio::main]
@@ -17,33 +18,9 @@ async fn main() -> Result<(), Report<UnrecoverableError>> {
 
     tracing::info!("starting ez-ticket-api.");
 
-    let categories = Router::new()
-        .route("/", get(categories::categories)
-            .post(categories::create)
-            .put(categories::change_ordering))
-        .route("/{category_id}", get(categories::get_products_in_category)
-            .post(categories::add_product)
-            .put(categories::change_product_ordering)
-            .patch(categories::update_name)
-            .delete(categories::delete))
-        .route("/{category_id}/{product_id}", delete(categories::remove_product));
-    
-    let products = Router::new()
-        .route("/", get(products::get_all_products)
-            .post(products::register))
-        .route("/{product_id}", get(products::product_details)
-            .patch(products::patch)
-            .delete(products::delete));
-    
-    let images = Router::new()
-        .route("/{image_id}", get(images::get));
-
     let cors = CorsLayer::permissive();
 
-    let router = Router::new()
-        .nest("/categories", categories)
-        .nest("/products", products)
-        .nest("/images", images)
+    let router = server::routing::routes(&app)
         .merge(apidoc())
         .layer(DefaultBodyLimit::disable())
         .layer(TraceLayer::new_for_http())
@@ -54,7 +31,8 @@ async fn main() -> Result<(), Report<UnrecoverableError>> {
         .await
         .change_context_lazy(|| UnrecoverableError)?;
 
-    axum::serve(listener, router.into_make_service())
+    // The peer address is recorded as the client IP in the audit log.
+    axum::serve(listener, router.into_make_service_with_connect_info::<SocketAddr>())
         .with_graceful_shutdown(shutdown_signal())
         .await
         .change_context_lazy(|| UnrecoverableError)?;
@@ -69,8 +47,21 @@ fn apidoc() -> Router<AppModule> {
     #[derive(OpenApi)]
     #[openapi(
         paths(
+            server::routing::auth::login,
+        
+            server::routing::staff::register,
+            server::routing::staff::change_role,
+            server::routing::staff::change_password,
+            server::routing::staff::remove,
+        
+            server::routing::devices::devices,
+            server::routing::devices::register,
+            server::routing::devices::rotate_key,
+            server::routing::devices::revoke,
+        
             server::routing::categories::categories,
             server::routing::categories::get_products_in_category,
+            server::routing::categories::history,
             server::routing::categories::create,
             server::routing::categories::delete,
             server::routing::categories::update_name,
@@ -78,14 +69,66 @@ fn apidoc() -> Router<AppModule> {
             server::routing::categories::add_product,
             server::routing::categories::remove_product,
             server::routing::categories::change_product_ordering,
+            server::routing::categories::change_schedule,
+            server::routing::categories::clear_schedule,
+        
+            server::routing::events::subscribe,
         
             server::routing::images::get,
         
+            server::routing::orders::place,
+            server::routing::orders::add_line,
+            server::routing::orders::remove_line,
+            server::routing::orders::redeem_coupon,
+            server::routing::orders::pay,
+            server::routing::orders::confirm,
+            server::routing::orders::change_status,
+            server::routing::orders::cancel,
+            server::routing::orders::receipt,
+        
+            server::routing::pickup::board,
+        
+            server::routing::promotions::promotions,
+            server::routing::promotions::create,
+            server::routing::promotions::end,
+        
+            server::routing::payments::payments,
+            server::routing::payments::summary,
+        
+            server::routing::reports::sales,
+            server::routing::reports::sales_csv,
+            server::routing::reports::sales_xlsx,
+        
+            server::routing::audit::audit_log,
+            server::routing::menu::as_of,
+        
+            server::routing::kitchen::orders,
+            server::routing::kitchen::slip,
+        
+            server::routing::tickets::numbers,
+            server::routing::tickets::get_by_order,
+        
             server::routing::products::get_all_products,
             server::routing::products::product_details,
+            server::routing::products::history,
             server::routing::products::register,
             server::routing::products::patch,
-            server::routing::products::delete
+            server::routing::products::delete,
+            server::routing::products::mark_sold_out,
+            server::routing::products::mark_available,
+            server::routing::products::patch_stock,
+            server::routing::products::add_option_group,
+            server::routing::products::edit_option_group,
+            server::routing::products::remove_option_group,
+            server::routing::products::add_option,
+            server::routing::products::edit_option,
+            server::routing::products::remove_option,
+            server::routing::products::add_bundle_slot,
+            server::routing::products::change_bundle_choices,
+            server::routing::products::remove_bundle_slot,
+            server::routing::products::change_schedule,
+            server::routing::products::clear_schedule,
+            server::routing::products::change_tax
         )
     )]
     struct ApiDocs;
@@ -0,0 +1,168 @@
+//! Printable renderings of the receipt of a paid order.
+//! 
+//! Both renderings share the same layout in fixed-width columns, 
+//! where full-width characters such as kanji take up two columns.
+
+mod escpos;
+mod pdf;
+
+pub use self::escpos::escpos;
+pub use self::pdf::pdf;
+
+use app_query::models::Receipt;
+use kernel::entities::money::{Currency, Money};
+use kernel::entities::ticket::StoreOffset;
+
+/// Columns of font A on 80mm paper.
+const COLUMNS: usize = 48;
+
+/// Printed at the top of every receipt. Read from `STORE_NAME`, `STORE_ADDRESS`, `STORE_PHONE` 
+/// and `STORE_REGISTRATION`, the latter being the registration number of a qualified invoice issuer.
+#[derive(Debug, Clone)]
+pub struct StoreHeader {
+    pub name: String,
+    pub lines: Vec<String>,
+    /// Offset in which the time of payment is printed.
+    pub offset: StoreOffset,
+}
+
+impl StoreHeader {
+    pub fn from_env(offset: StoreOffset) -> StoreHeader {
+        let name = std::env::var("STORE_NAME")
+            .unwrap_or_else(|_| "ez-ticket".to_string());
+        let lines = ["STORE_ADDRESS", "STORE_PHONE", "STORE_REGISTRATION"]
+            .into_iter()
+            .filter_map(|key| std::env::var(key).ok())
+            .filter(|line| !line.trim().is_empty())
+            .collect();
+        
+        StoreHeader { name, lines, offset }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ReceiptSettings {
+    pub store: StoreHeader,
+    /// TrueType font used for PDF receipts and PNG ticket slips, read from the path in `RECEIPT_FONT`.
+    /// A monospaced font covering Japanese, such as BIZ UDGothic, is expected.
+    /// Receipts fall back to Courier, which can only print ASCII.
+    pub font: Option<Vec<u8>>,
+}
+
+pub trait DependOnReceiptSettings: 'static + Sync + Send {
+    fn receipt_settings(&self) -> &ReceiptSettings;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Row {
+    /// Printed in double width and height.
+    Title(String),
+    Centered(String),
+    Text(String),
+    /// `left` and `right` aligned to both edges, `right` wrapping to the next line if they do not fit.
+    Pair { left: String, right: String, bold: bool },
+    Rule,
+    Blank,
+}
+
+impl Row {
+    fn pair(left: impl Into<String>, right: impl Into<String>) -> Row {
+        Row::Pair { left: left.into(), right: right.into(), bold: false }
+    }
+}
+
+fn layout(receipt: &Receipt, store: &StoreHeader) -> Vec<Row> {
+    let currency = Currency::new(receipt.currency.as_str()).unwrap_or_default();
+    let money = |amount: i64| Money::new(amount, currency.clone()).to_string();
+    
+    let mut rows = vec![Row::Title(store.name.clone())];
+    rows.extend(store.lines.iter().cloned().map(Row::Centered));
+    rows.push(Row::Blank);
+    rows.push(Row::Centered("RECEIPT".to_string()));
+    rows.push(Row::Text(paid_at(receipt, store)));
+    rows.push(Row::pair("Ticket No.", receipt.number.map(|number| number.to_string()).unwrap_or_else(|| "-".to_string())));
+    rows.push(Row::pair("Order", receipt.order_id.simple().to_string()[..8].to_string()));
+    rows.push(Row::Rule);
+    
+    for item in &receipt.items {
+        rows.push(Row::Text(item.name.clone().unwrap_or_else(|| "(deleted)".to_string())));
+        for option in &item.options {
+            rows.push(Row::Text(format!("  + {}", option.name)));
+        }
+        for component in &item.components {
+            rows.push(Row::Text(format!("  - {component}")));
+        }
+        rows.push(Row::pair(
+            format!("  {} x {} ({}%)", money(item.unit_price), item.quantity, item.tax_rate),
+            money(item.amount)
+        ));
+    }
+    
+    rows.push(Row::Rule);
+    rows.push(Row::pair("Subtotal", money(receipt.subtotal)));
+    if receipt.discount != 0 {
+        let label = match &receipt.coupon {
+            Some(code) => format!("Discount ({code})"),
+            None => "Discount".to_string(),
+        };
+        rows.push(Row::pair(label, money(-receipt.discount)));
+    }
+    rows.push(Row::Pair { left: "Total".to_string(), right: money(receipt.total), bold: true });
+    for tax in &receipt.taxes {
+        rows.push(Row::pair(format!("  {}% taxable", tax.rate), money(tax.gross)));
+        rows.push(Row::pair(format!("  {}% tax", tax.rate), money(tax.tax)));
+    }
+    
+    rows.push(Row::Rule);
+    rows.push(Row::pair(tender(&receipt.tender), money(receipt.tendered)));
+    rows.push(Row::pair("Change", money(receipt.change)));
+    rows.push(Row::Blank);
+    rows.push(Row::Centered("Thank you!".to_string()));
+    
+    rows
+}
+
+/// Payments are recorded in UTC, but printed in the local time of the store.
+fn paid_at(receipt: &Receipt, store: &StoreHeader) -> String {
+    let at = receipt.paid_at.assume_utc().to_offset(store.offset.into());
+    
+    format!("{} {:02}:{:02}", at.date(), at.hour(), at.minute())
+}
+
+fn tender(tender: &str) -> String {
+    match tender {
+        "cash" => "Cash".to_string(),
+        "e_money" => "E-money".to_string(),
+        "prepaid_card" => "Prepaid card".to_string(),
+        other => other.to_string(),
+    }
+}
+
+/// Columns taken up by the text, counting characters outside ASCII as full-width.
+fn width(text: &str) -> usize {
+    text.chars().map(|c| if c.is_ascii() { 1 } else { 2 }).sum()
+}
+
+/// Lays out a row into lines of at most `columns`, paired with whether they are bold.
+fn lines(row: &Row, columns: usize) -> Vec<(String, bool)> {
+    match row {
+        Row::Title(text) | Row::Centered(text) => vec![(center(text, columns), false)],
+        Row::Text(text) => vec![(text.clone(), false)],
+        Row::Pair { left, right, bold } => {
+            let used = width(left) + width(right);
+            if used < columns {
+                vec![(format!("{left}{}{right}", " ".repeat(columns - used)), *bold)]
+            } else {
+                let pad = columns.saturating_sub(width(right));
+                vec![(left.clone(), *bold), (format!("{}{right}", " ".repeat(pad)), *bold)]
+            }
+        }
+        Row::Rule => vec![("-".repeat(columns), false)],
+        Row::Blank => vec![(String::new(), false)],
+    }
+}
+
+fn center(text: &str, columns: usize) -> String {
+    let pad = columns.saturating_sub(width(text)) / 2;
+    format!("{}{text}", " ".repeat(pad))
+}
@@ -0,0 +1,48 @@
+use app_query::models::Receipt;
+
+use super::{layout, lines, Row, StoreHeader, COLUMNS};
+
+const ESC: u8 = 0x1B;
+const FS: u8 = 0x1C;
+const GS: u8 = 0x1D;
+const LF: u8 = 0x0A;
+
+/// Renders the receipt as ESC/POS commands for thermal printers.
+/// 
+/// Text is encoded in Shift_JIS with kanji mode enabled, as expected by printers sold in Japan.
+pub fn escpos(receipt: &Receipt, store: &StoreHeader) -> Vec<u8> {
+    let mut buf = Vec::new();
+    
+    // Initialize, then select Shift_JIS kanji mode.
+    buf.extend_from_slice(&[ESC, b'@', FS, b'C', 1, FS, b'&']);
+    
+    for row in layout(receipt, store) {
+        let title = matches!(row, Row::Title(_));
+        let columns = if title { COLUMNS / 2 } else { COLUMNS };
+        
+        if title {
+            buf.extend_from_slice(&[GS, b'!', 0x11]);
+        }
+        
+        for (line, bold) in lines(&row, columns) {
+            if bold {
+                buf.extend_from_slice(&[ESC, b'E', 1]);
+            }
+            let (encoded, _, _) = encoding_rs::SHIFT_JIS.encode(&line);
+            buf.extend_from_slice(&encoded);
+            buf.push(LF);
+            if bold {
+                buf.extend_from_slice(&[ESC, b'E', 0]);
+            }
+        }
+        
+        if title {
+            buf.extend_from_slice(&[GS, b'!', 0x00]);
+        }
+    }
+    
+    // Feed past the cutter, then cut partially.
+    buf.extend_from_slice(&[ESC, b'd', 4, GS, b'V', 66, 0]);
+    
+    buf
+}
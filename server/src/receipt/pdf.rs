@@ -0,0 +1,64 @@
+use std::io::Cursor;
+
+use error_stack::{Report, ResultExt};
+use printpdf::{BuiltinFont, IndirectFontRef, Mm, PdfDocument, PdfDocumentReference};
+
+use app_query::models::Receipt;
+
+use crate::errors::ServerError;
+use super::{layout, lines, ReceiptSettings, Row, COLUMNS};
+
+/// Width of the paper, printable area being 72mm.
+const PAGE_WIDTH: f32 = 80.0;
+const MARGIN: f32 = 4.0;
+const LINE_HEIGHT: f32 = 4.0;
+/// Points per millimeter.
+const PT_PER_MM: f32 = 72.0 / 25.4;
+
+/// Renders the receipt as a single PDF page of 80mm width, as long as the receipt needs.
+pub fn pdf(receipt: &Receipt, settings: &ReceiptSettings) -> Result<Vec<u8>, Report<ServerError>> {
+    let rows = layout(receipt, &settings.store)
+        .into_iter()
+        .map(|row| {
+            let title = matches!(row, Row::Title(_));
+            let columns = if title { COLUMNS / 2 } else { COLUMNS };
+            (title, lines(&row, columns))
+        })
+        .collect::<Vec<_>>();
+    
+    let count = rows.iter()
+        .map(|(title, lines)| lines.len() * if *title { 2 } else { 1 })
+        .sum::<usize>();
+    let height = MARGIN * 2.0 + LINE_HEIGHT * count as f32;
+    
+    let (doc, page, layer) = PdfDocument::new("Receipt", Mm(PAGE_WIDTH), Mm(height), "Receipt");
+    let layer = doc.get_page(page).get_layer(layer);
+    
+    let (font, advance) = font(&doc, settings)?;
+    // Size at which a half-width character takes up a single column.
+    let size = (PAGE_WIDTH - MARGIN * 2.0) / COLUMNS as f32 * PT_PER_MM / advance;
+    
+    let mut y = height - MARGIN;
+    for (title, lines) in rows {
+        let scale = if title { 2.0 } else { 1.0 };
+        for (line, _) in lines {
+            y -= LINE_HEIGHT * scale;
+            layer.use_text(line, size * scale, Mm(MARGIN), Mm(y), &font);
+        }
+    }
+    
+    doc.save_to_bytes()
+        .change_context_lazy(|| ServerError::Export)
+}
+
+/// The font and the advance of a half-width character relative to its size.
+fn font(doc: &PdfDocumentReference, settings: &ReceiptSettings) -> Result<(IndirectFontRef, f32), Report<ServerError>> {
+    match &settings.font {
+        Some(font) => doc.add_external_font(Cursor::new(font))
+            .map(|font| (font, 0.5))
+            .change_context_lazy(|| ServerError::Export),
+        None => doc.add_builtin_font(BuiltinFont::Courier)
+            .map(|font| (font, 0.6))
+            .change_context_lazy(|| ServerError::Export),
+    }
+}
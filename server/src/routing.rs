@@ -1,5 +1,177 @@
+pub mod audit;
+pub mod auth;
 pub mod categories;
-
+pub mod devices;
+pub mod events;
+pub mod kitchen;
+pub mod menu;
+pub mod orders;
+pub mod payments;
+pub mod pickup;
 pub mod products;
+pub mod promotions;
+pub mod reports;
+pub mod staff;
 pub mod images;
+pub mod tickets;
 mod request;
+mod response;
+
+use axum::middleware;
+use axum::routing::{delete, get, patch, post, put};
+use axum::Router;
+
+use kernel::entities::staff::Permission;
+
+use crate::AppModule;
+use crate::auth::{authorize, Guard};
+
+/// Every route of the API, guarded by the [`Permission`] it requires.
+///
+/// The catalog can be read without a session, as can the ticket numbers, 
+/// pickup board, images and event stream that customer-facing displays rely on.
+pub fn routes(app: &AppModule) -> Router<AppModule> {
+    let guard = |permission| middleware::from_fn_with_state(Guard::new(app, permission), authorize);
+    let guard_writes = |permission| middleware::from_fn_with_state(Guard::writes(app, permission), authorize);
+    
+    let auth = Router::new()
+        .route("/login", post(auth::login));
+    
+    let staff = Router::new()
+        .route("/", post(staff::register))
+        .route("/{name}", delete(staff::remove))
+        .route("/{name}/role", put(staff::change_role))
+        .route("/{name}/password", put(staff::change_password))
+        .route_layer(guard(Permission::ManageStaff));
+    
+    let devices = Router::new()
+        .route("/", get(devices::devices)
+            .post(devices::register))
+        .route("/{device_id}", delete(devices::revoke))
+        .route("/{device_id}/key", post(devices::rotate_key))
+        .route_layer(guard(Permission::ManageStaff));
+    
+    let categories = Router::new()
+        .route("/", get(categories::categories)
+            .post(categories::create)
+            .put(categories::change_ordering))
+        .route("/{category_id}", get(categories::get_products_in_category)
+            .post(categories::add_product)
+            .put(categories::change_product_ordering)
+            .patch(categories::update_name)
+            .delete(categories::delete))
+        .route("/{category_id}/schedule", put(categories::change_schedule)
+            .delete(categories::clear_schedule))
+        .route("/{category_id}/history", get(categories::history)
+            .route_layer(guard(Permission::ViewAudit)))
+        .route("/{category_id}/{product_id}", delete(categories::remove_product))
+        .route_layer(guard_writes(Permission::EditCatalog));
+    
+    let products = Router::new()
+        .route("/", get(products::get_all_products)
+            .post(products::register))
+        .route("/{product_id}", get(products::product_details)
+            .patch(products::patch)
+            .delete(products::delete))
+        .route("/{product_id}/sold-out", post(products::mark_sold_out)
+            .delete(products::mark_available))
+        .route("/{product_id}/stock", patch(products::patch_stock))
+        .route("/{product_id}/options", post(products::add_option_group))
+        .route("/{product_id}/options/{group_id}", post(products::add_option)
+            .put(products::edit_option_group)
+            .delete(products::remove_option_group))
+        .route("/{product_id}/options/{group_id}/{option_id}", put(products::edit_option)
+            .delete(products::remove_option))
+        .route("/{product_id}/bundle", post(products::add_bundle_slot))
+        .route("/{product_id}/bundle/{slot_id}", put(products::change_bundle_choices)
+            .delete(products::remove_bundle_slot))
+        .route("/{product_id}/schedule", put(products::change_schedule)
+            .delete(products::clear_schedule))
+        .route("/{product_id}/tax", put(products::change_tax))
+        .route("/{product_id}/history", get(products::history)
+            .route_layer(guard(Permission::ViewAudit)))
+        .route_layer(guard_writes(Permission::EditCatalog));
+    
+    let orders = Router::new()
+        .route("/", post(orders::place)
+            .route_layer(guard(Permission::PlaceOrder)))
+        .route("/{order_id}", post(orders::add_line)
+            .route_layer(guard(Permission::PlaceOrder)))
+        .route("/{order_id}/coupon", post(orders::redeem_coupon)
+            .route_layer(guard(Permission::PlaceOrder)))
+        .route("/{order_id}/pay", post(orders::pay)
+            .route_layer(guard(Permission::TakePayment)))
+        .route("/{order_id}/confirm", post(orders::confirm)
+            .route_layer(guard(Permission::TakePayment)))
+        .route("/{order_id}/status", patch(orders::change_status)
+            .route_layer(guard(Permission::PrepareOrder)))
+        .route("/{order_id}/cancel", post(orders::cancel)
+            .route_layer(guard(Permission::TakePayment)))
+        .route("/{order_id}/receipt", get(orders::receipt)
+            .route_layer(guard(Permission::TakePayment)))
+        .route("/{order_id}/{line}", delete(orders::remove_line)
+            .route_layer(guard(Permission::PlaceOrder)));
+    
+    // Coupon codes are not meant to be listed to anyone but the admins.
+    let promotions = Router::new()
+        .route("/", get(promotions::promotions)
+            .post(promotions::create))
+        .route("/{code}", delete(promotions::end))
+        .route_layer(guard(Permission::EditCatalog));
+    
+    let payments = Router::new()
+        .route("/", get(payments::payments))
+        .route("/summary", get(payments::summary))
+        .route_layer(guard(Permission::ViewSales));
+    
+    let reports = Router::new()
+        .route("/sales", get(reports::sales))
+        .route("/sales.csv", get(reports::sales_csv))
+        .route("/sales.xlsx", get(reports::sales_xlsx))
+        .route_layer(guard(Permission::ViewSales));
+    
+    let audit = Router::new()
+        .route("/", get(audit::audit_log))
+        .route_layer(guard(Permission::ViewAudit));
+    
+    // The menu as it was at a past moment, to settle disputes over the displayed prices.
+    let menu = Router::new()
+        .route("/", get(menu::as_of))
+        .route_layer(guard(Permission::ViewAudit));
+    
+    let kitchen = Router::new()
+        .route("/orders", get(kitchen::orders))
+        .route("/orders/{order_id}/slip", get(kitchen::slip))
+        .route_layer(guard(Permission::PrepareOrder));
+    
+    let tickets = Router::new()
+        .route("/", get(tickets::numbers))
+        .route("/{order_id}", get(tickets::get_by_order));
+    
+    let events = Router::new()
+        .route("/", get(events::subscribe));
+    
+    let pickup = Router::new()
+        .route("/", get(pickup::board));
+    
+    let images = Router::new()
+        .route("/{image_id}", get(images::get));
+    
+    Router::new()
+        .nest("/auth", auth)
+        .nest("/staff", staff)
+        .nest("/devices", devices)
+        .nest("/categories", categories)
+        .nest("/products", products)
+        .nest("/orders", orders)
+        .nest("/promotions", promotions)
+        .nest("/payments", payments)
+        .nest("/reports", reports)
+        .nest("/audit", audit)
+        .nest("/menu", menu)
+        .nest("/kitchen", kitchen)
+        .nest("/tickets", tickets)
+        .nest("/images", images)
+        .nest("/events", events)
+        .nest("/pickup", pickup)
+}
@@ -0,0 +1,42 @@
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use axum::Json;
+
+use app_query::models::{AuditEntry, DependOnGetAuditLogQueryService, GetAuditLogQueryService};
+
+use crate::AppModule;
+use crate::routing::request::audit::{utc, AuditRange};
+
+
+#[cfg_attr(
+    feature = "apidoc",
+    utoipa::path(
+        get,
+        path = "/audit",
+        params(AuditRange),
+        responses(
+            (status = OK, body = Vec<AuditEntry>),
+            (status = BAD_REQUEST),
+            (status = UNAUTHORIZED),
+            (status = FORBIDDEN),
+            (status = INTERNAL_SERVER_ERROR)
+        )
+    )
+)]
+pub async fn audit_log(
+    State(app): State<AppModule>,
+    Query(range): Query<AuditRange>,
+) -> Result<Json<Vec<AuditEntry>>, StatusCode> {
+    let entries = match app.get_audit_log_query_service()
+        .get_audit_log(range.aggregate, range.from.map(utc), range.to.map(utc))
+        .await
+    {
+        Ok(entries) => entries,
+        Err(e) => {
+            tracing::error!("failed to get the audit log: {:?}", e);
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+    };
+    
+    Ok(Json(entries))
+}
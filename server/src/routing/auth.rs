@@ -0,0 +1,52 @@
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::Json;
+
+use app_cmd::workflow::staff::{AuthenticateStaffWorkflow, DependOnAuthenticateStaffWorkflow};
+
+use crate::AppModule;
+use crate::auth::DependOnAuthenticator;
+use crate::routing::request::staff::Login;
+use crate::routing::response::staff::Session;
+
+
+#[cfg_attr(
+    feature = "apidoc",
+    utoipa::path(
+        post,
+        path = "/auth/login",
+        request_body = Login,
+        responses(
+            (status = OK, body = Session),
+            (status = UNAUTHORIZED, description = "The name or password is wrong"),
+            (status = INTERNAL_SERVER_ERROR)
+        )
+    )
+)]
+pub async fn login(
+    State(app): State<AppModule>,
+    Json(req): Json<Login>,
+) -> Result<Json<Session>, StatusCode> {
+    let name = req.name()
+        .map_err(|_| StatusCode::UNAUTHORIZED)?;
+    
+    let staff = match app.authenticate_staff_workflow().execute(name, req.password).await {
+        Ok(staff) => staff,
+        Err(e) => {
+            tracing::warn!("failed to authenticate staff: {:?}", e);
+            return Err(StatusCode::UNAUTHORIZED);
+        }
+    };
+    
+    let authenticator = app.authenticator();
+    
+    let token = match authenticator.issue(&staff) {
+        Ok(token) => token,
+        Err(e) => {
+            tracing::error!("failed to issue token: {:?}", e);
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+    };
+    
+    Ok(Json(Session { token, role: *staff.role(), expires_in: authenticator.ttl().whole_seconds() }))
+}
@@ -1,16 +1,19 @@
-use axum::extract::{Path, State};
+use axum::extract::{Path, Query, State};
 use axum::http::StatusCode;
 use axum::Json;
 
+use app_cmd::adapter::DependOnStoreOffset;
 use app_cmd::services::category::{CategoryCommandService, DependOnCategoryCommandService};
 use app_cmd::services::categories::{CategoriesCommandService, DependOnCategoriesCommandService};
-use app_query::models::{AllCategories, DependOnGetAllCategoriesQueryService, DependOnGetAllProductQueryService, GetAllCategoriesQueryService, GetAllProductQueryService, OrderedProducts};
+use app_query::models::{AllCategories, CategoryHistory, DependOnGetAllCategoriesQueryService, DependOnGetAllProductQueryService, DependOnGetHistoryQueryService, GetAllCategoriesQueryService, GetAllProductQueryService, GetHistoryQueryService, OrderedProducts};
 
 use kernel::entities::category::CategoryId;
+use kernel::entities::schedule::Schedule;
 use kernel::entities::product::ProductId;
 use kernel::io::commands::CategoryCommand;
 
 use crate::AppModule;
+use crate::audit::Dispatcher;
 use crate::routing::request::categories::{
     AddProduct, 
     ChangeCategoryOrdering, 
@@ -18,6 +21,7 @@ use crate::routing::request::categories::{
     CreateCategory, 
     RenameCategory
 };
+use crate::routing::request::schedules::{AvailableAt, PutSchedule};
 
 
 #[cfg_attr(
@@ -25,13 +29,18 @@ use crate::routing::request::categories::{
     utoipa::path(
         get,
         path = "/categories",
+        params(AvailableAt)
     )
 )]
 pub async fn categories(
-    State(app): State<AppModule>
+    State(app): State<AppModule>,
+    Query(query): Query<AvailableAt>,
 ) -> Result<Json<AllCategories>, StatusCode> {
+    let at = query.at(app.store_offset())
+        .map_err(|_| StatusCode::BAD_REQUEST)?;
+    
     let categories = match app.get_all_categories_query_service()
-        .get_all_categories()
+        .get_all_categories(at)
         .await 
     {
         Ok(categories) => categories,
@@ -51,16 +60,21 @@ pub async fn categories(
         get,
         path = "/categories/{category_id}",
         params(
-            ("category_id" = Uuid, Path)
+            ("category_id" = Uuid, Path),
+            AvailableAt
         ),
     )
 )]
 pub async fn get_products_in_category(
     State(app): State<AppModule>,
-    Path(category_id): Path<CategoryId>
+    Path(category_id): Path<CategoryId>,
+    Query(query): Query<AvailableAt>,
 ) -> Result<Json<OrderedProducts>, StatusCode> {
+    let at = query.at(app.store_offset())
+        .map_err(|_| StatusCode::BAD_REQUEST)?;
+    
     let res = match app.get_all_product_query_service()
-        .get_all_product_by_category(category_id.as_ref())
+        .get_all_product_by_category(category_id.as_ref(), at)
         .await
     {
         Ok(filtered) => filtered,
@@ -74,6 +88,46 @@ pub async fn get_products_in_category(
 }
 
 
+#[cfg_attr(
+    feature = "apidoc",
+    utoipa::path(
+        get,
+        path = "/categories/{category_id}/history",
+        params(
+            ("category_id" = Uuid, Path)
+        ),
+        responses(
+            (status = OK, body = Vec<CategoryHistory>),
+            (status = NOT_FOUND),
+            (status = UNAUTHORIZED),
+            (status = FORBIDDEN),
+            (status = INTERNAL_SERVER_ERROR)
+        )
+    )
+)]
+pub async fn history(
+    State(app): State<AppModule>,
+    Path(category_id): Path<CategoryId>,
+) -> Result<Json<Vec<CategoryHistory>>, StatusCode> {
+    let history = match app.get_history_query_service()
+        .get_category_history(category_id.as_ref())
+        .await
+    {
+        Ok(history) => history,
+        Err(e) => {
+            tracing::error!("failed to get category history: {:?}", e);
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+    };
+    
+    if history.is_empty() {
+        return Err(StatusCode::NOT_FOUND);
+    }
+    
+    Ok(Json(history))
+}
+
+
 #[cfg_attr(
     feature = "apidoc",
     utoipa::path(
@@ -88,12 +142,13 @@ pub async fn get_products_in_category(
 )]
 pub async fn create(
     State(app): State<AppModule>,
+    Dispatcher(actor): Dispatcher,
     Json(req): Json<CreateCategory>
 ) -> Result<StatusCode, StatusCode> {
     let cmd = req.try_into()
         .map_err(|_| StatusCode::BAD_REQUEST)?;
     
-    match CategoryCommandService::execute(app.category_command_service(), None, cmd).await { 
+    match CategoryCommandService::execute(app.category_command_service(), &actor, None, cmd).await { 
         Ok(_) => {}
         Err(e) => {
             tracing::error!("failed to register category: {:?}", e);
@@ -122,13 +177,14 @@ pub async fn create(
 )]
 pub async fn update_name(
     State(app): State<AppModule>,
+    Dispatcher(actor): Dispatcher,
     Path(category_id): Path<CategoryId>,
     Json(req): Json<RenameCategory>
 ) -> Result<StatusCode, StatusCode> {
     let cmd = req.try_into()
         .map_err(|_| StatusCode::BAD_REQUEST)?;
     
-    match CategoryCommandService::execute(app.category_command_service(), category_id, cmd).await {
+    match CategoryCommandService::execute(app.category_command_service(), &actor, category_id, cmd).await {
         Ok(_) => {}
         Err(e) => {
             tracing::error!("failed to update category name: {:?}", e);
@@ -156,9 +212,10 @@ pub async fn update_name(
 )]
 pub async fn delete(
     State(app): State<AppModule>,
+    Dispatcher(actor): Dispatcher,
     Path(dest): Path<CategoryId>
 ) -> Result<StatusCode, StatusCode> {
-    match CategoryCommandService::execute(app.category_command_service(), dest, CategoryCommand::Delete).await {
+    match CategoryCommandService::execute(app.category_command_service(), &actor, dest, CategoryCommand::Delete).await {
         Ok(_) => {}
         Err(e) => {
             tracing::error!("failed to delete category: {:?}", e);
@@ -184,12 +241,13 @@ pub async fn delete(
 )]
 pub async fn change_ordering(
     State(app): State<AppModule>,
+    Dispatcher(actor): Dispatcher,
     Json(req): Json<ChangeCategoryOrdering>
 ) -> Result<StatusCode, StatusCode> {
     let cmd = req.try_into()
         .map_err(|_| StatusCode::BAD_REQUEST)?;
     
-    if let Err(e) = CategoriesCommandService::execute(app.categories_command_service(), cmd).await {
+    if let Err(e) = CategoriesCommandService::execute(app.categories_command_service(), &actor, cmd).await {
         tracing::error!("failed to change category ordering: {:?}", e);
         return Err(StatusCode::INTERNAL_SERVER_ERROR);
     }
@@ -216,13 +274,14 @@ pub async fn change_ordering(
 )]
 pub async fn add_product(
     State(app): State<AppModule>,
+    Dispatcher(actor): Dispatcher,
     Path(category_id): Path<CategoryId>,
     Json(req): Json<AddProduct>
 ) -> Result<StatusCode, StatusCode> {
     let cmd = req.try_into()
         .map_err(|_| StatusCode::BAD_REQUEST)?;
     
-    if let Err(e) = CategoryCommandService::execute(app.category_command_service(), category_id, cmd).await {
+    if let Err(e) = CategoryCommandService::execute(app.category_command_service(), &actor, category_id, cmd).await {
         tracing::error!("failed to add product to category: {:?}", e);
         return Err(StatusCode::INTERNAL_SERVER_ERROR);
     }
@@ -249,10 +308,11 @@ pub async fn add_product(
 )]
 pub async fn remove_product(
     State(app): State<AppModule>,
+    Dispatcher(actor): Dispatcher,
     Path((category_id, product_id)): Path<(CategoryId, ProductId)>,
 ) -> Result<StatusCode, StatusCode> {
     let cmd = CategoryCommand::RemoveProduct { id: product_id };
-    if let Err(e) = CategoryCommandService::execute(app.category_command_service(), category_id, cmd).await {
+    if let Err(e) = CategoryCommandService::execute(app.category_command_service(), &actor, category_id, cmd).await {
         tracing::error!("failed to remove product from category: {:?}", e);
         return Err(StatusCode::INTERNAL_SERVER_ERROR);
     }
@@ -279,16 +339,79 @@ pub async fn remove_product(
 )]
 pub async fn change_product_ordering(
     State(app): State<AppModule>,
+    Dispatcher(actor): Dispatcher,
     Path(category_id): Path<CategoryId>,
     Json(req): Json<ChangeProductOrdering>
 ) -> Result<StatusCode, StatusCode> {
     let cmd = req.try_into()
         .map_err(|_| StatusCode::BAD_REQUEST)?;
     
-    if let Err(e) = CategoryCommandService::execute(app.category_command_service(), category_id, cmd).await {
+    if let Err(e) = CategoryCommandService::execute(app.category_command_service(), &actor, category_id, cmd).await {
         tracing::error!("failed to change product ordering: {:?}", e);
         return Err(StatusCode::INTERNAL_SERVER_ERROR);
     }
     
     Ok(StatusCode::NO_CONTENT)
 }
+
+
+#[cfg_attr(
+    feature = "apidoc",
+    utoipa::path(
+        put,
+        path = "/categories/{category_id}/schedule",
+        params(
+            ("category_id" = Uuid, Path)
+        ),
+        request_body = PutSchedule,
+        responses(
+            (status = OK),
+            (status = BAD_REQUEST),
+            (status = INTERNAL_SERVER_ERROR)
+        )
+    )
+)]
+pub async fn change_schedule(
+    State(app): State<AppModule>,
+    Dispatcher(actor): Dispatcher,
+    Path(category_id): Path<CategoryId>,
+    Json(req): Json<PutSchedule>
+) -> Result<StatusCode, StatusCode> {
+    let new = Schedule::try_from(req)
+        .map_err(|_| StatusCode::BAD_REQUEST)?;
+    
+    if let Err(e) = CategoryCommandService::execute(app.category_command_service(), &actor, category_id, CategoryCommand::ChangeSchedule { new }).await {
+        tracing::error!("failed to change category schedule: {:?}", e);
+        return Err(StatusCode::INTERNAL_SERVER_ERROR);
+    }
+    
+    Ok(StatusCode::OK)
+}
+
+
+#[cfg_attr(
+    feature = "apidoc",
+    utoipa::path(
+        delete,
+        path = "/categories/{category_id}/schedule",
+        params(
+            ("category_id" = Uuid, Path)
+        ),
+        responses(
+            (status = OK),
+            (status = INTERNAL_SERVER_ERROR)
+        )
+    )
+)]
+pub async fn clear_schedule(
+    State(app): State<AppModule>,
+    Dispatcher(actor): Dispatcher,
+    Path(category_id): Path<CategoryId>,
+) -> Result<StatusCode, StatusCode> {
+    if let Err(e) = CategoryCommandService::execute(app.category_command_service(), &actor, category_id, CategoryCommand::ClearSchedule).await {
+        tracing::error!("failed to clear category schedule: {:?}", e);
+        return Err(StatusCode::INTERNAL_SERVER_ERROR);
+    }
+    
+    Ok(StatusCode::OK)
+}
@@ -0,0 +1,165 @@
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::Json;
+use uuid::Uuid;
+
+use app_cmd::errors::ApplicationError;
+use app_cmd::services::device::{DependOnDeviceCommandService, DeviceCommandService};
+use app_query::models::{DependOnGetDeviceQueryService, DeviceRecord, GetDeviceQueryService};
+use kernel::entities::device::{ApiKey, DeviceId};
+use kernel::io::commands::DeviceCommand;
+
+use crate::AppModule;
+use crate::auth::DependOnAuthenticator;
+use crate::routing::request::devices::RegisterDevice;
+use crate::routing::response::devices::IssuedKey;
+
+
+#[cfg_attr(
+    feature = "apidoc",
+    utoipa::path(
+        get,
+        path = "/devices",
+        responses(
+            (status = OK, body = Vec<DeviceRecord>),
+            (status = UNAUTHORIZED),
+            (status = FORBIDDEN),
+            (status = INTERNAL_SERVER_ERROR)
+        )
+    )
+)]
+pub async fn devices(
+    State(app): State<AppModule>,
+) -> Result<Json<Vec<DeviceRecord>>, StatusCode> {
+    let devices = match app.get_device_query_service()
+        .get_all_devices()
+        .await
+    {
+        Ok(devices) => devices,
+        Err(e) => {
+            tracing::error!("failed to get devices: {:?}", e);
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+    };
+    
+    Ok(Json(devices))
+}
+
+
+#[cfg_attr(
+    feature = "apidoc",
+    utoipa::path(
+        post,
+        path = "/devices",
+        request_body = RegisterDevice,
+        responses(
+            (status = CREATED, body = IssuedKey),
+            (status = BAD_REQUEST, description = "The store is invalid, or the role is `admin`"),
+            (status = UNAUTHORIZED),
+            (status = FORBIDDEN),
+            (status = INTERNAL_SERVER_ERROR)
+        )
+    )
+)]
+pub async fn register(
+    State(app): State<AppModule>,
+    Json(req): Json<RegisterDevice>,
+) -> Result<(StatusCode, Json<IssuedKey>), StatusCode> {
+    let key = ApiKey::generate();
+    
+    let cmd = match req.into_command(app.authenticator().store(), key.hash()) {
+        Ok(cmd) => cmd,
+        Err(e) => {
+            tracing::error!("failed to validate device: {:?}", e);
+            return Err(StatusCode::BAD_REQUEST);
+        }
+    };
+    
+    let id = match DeviceCommandService::execute(app.device_command_service(), None, cmd).await {
+        Ok(id) => id,
+        Err(e) => {
+            tracing::error!("failed to register device: {:?}", e);
+            return match e.current_context() {
+                ApplicationError::Kernel => Err(StatusCode::BAD_REQUEST),
+                _ => Err(StatusCode::INTERNAL_SERVER_ERROR),
+            };
+        }
+    };
+    
+    Ok((StatusCode::CREATED, Json(IssuedKey::new(id, &key))))
+}
+
+
+#[cfg_attr(
+    feature = "apidoc",
+    utoipa::path(
+        post,
+        path = "/devices/{device_id}/key",
+        params(
+            ("device_id" = Uuid, Path)
+        ),
+        responses(
+            (status = OK, body = IssuedKey),
+            (status = UNAUTHORIZED),
+            (status = FORBIDDEN),
+            (status = CONFLICT, description = "The device has been revoked"),
+            (status = INTERNAL_SERVER_ERROR)
+        )
+    )
+)]
+pub async fn rotate_key(
+    State(app): State<AppModule>,
+    Path(device_id): Path<Uuid>,
+) -> Result<Json<IssuedKey>, StatusCode> {
+    let key = ApiKey::generate();
+    
+    let cmd = DeviceCommand::RotateKey { key: key.hash() };
+    
+    let id = match DeviceCommandService::execute(app.device_command_service(), DeviceId::new(device_id), cmd).await {
+        Ok(id) => id,
+        Err(e) => {
+            tracing::error!("failed to rotate key: {:?}", e);
+            return match e.current_context() {
+                ApplicationError::Kernel => Err(StatusCode::CONFLICT),
+                _ => Err(StatusCode::INTERNAL_SERVER_ERROR),
+            };
+        }
+    };
+    
+    Ok(Json(IssuedKey::new(id, &key)))
+}
+
+
+#[cfg_attr(
+    feature = "apidoc",
+    utoipa::path(
+        delete,
+        path = "/devices/{device_id}",
+        params(
+            ("device_id" = Uuid, Path)
+        ),
+        responses(
+            (status = OK),
+            (status = UNAUTHORIZED),
+            (status = FORBIDDEN),
+            (status = CONFLICT, description = "The device has already been revoked"),
+            (status = INTERNAL_SERVER_ERROR)
+        )
+    )
+)]
+pub async fn revoke(
+    State(app): State<AppModule>,
+    Path(device_id): Path<Uuid>,
+) -> Result<StatusCode, StatusCode> {
+    let cmd = DeviceCommand::Revoke;
+    
+    if let Err(e) = DeviceCommandService::execute(app.device_command_service(), DeviceId::new(device_id), cmd).await {
+        tracing::error!("failed to revoke device: {:?}", e);
+        return match e.current_context() {
+            ApplicationError::Kernel => Err(StatusCode::CONFLICT),
+            _ => Err(StatusCode::INTERNAL_SERVER_ERROR),
+        };
+    }
+    
+    Ok(StatusCode::OK)
+}
@@ -0,0 +1,60 @@
+use std::convert::Infallible;
+
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use axum::response::sse::{Event, KeepAlive, Sse};
+use tokio_stream::wrappers::BroadcastStream;
+use tokio_stream::{Stream, StreamExt};
+
+use crate::AppModule;
+use crate::events::DependOnEventBroadcaster;
+use crate::routing::request::events::EventFilter;
+
+
+#[cfg_attr(
+    feature = "apidoc",
+    utoipa::path(
+        get,
+        path = "/events",
+        params(EventFilter),
+        responses(
+            (status = OK, content_type = "text/event-stream"),
+            (status = BAD_REQUEST)
+        )
+    )
+)]
+pub async fn subscribe(
+    State(app): State<AppModule>,
+    Query(filter): Query<EventFilter>,
+) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, StatusCode> {
+    let aggregates = filter.aggregates()
+        .map_err(|e| {
+            tracing::error!("invalid event filter: {:?}", e);
+            StatusCode::BAD_REQUEST
+        })?;
+    
+    let stream = BroadcastStream::new(app.event_broadcaster().subscribe())
+        .filter_map(move |received| {
+            let event = match received {
+                Ok(event) => event,
+                Err(e) => {
+                    tracing::warn!("event stream client lagged behind: {:?}", e);
+                    return None;
+                }
+            };
+            
+            if !aggregates.contains(&event.aggregate()) {
+                return None;
+            }
+            
+            match Event::default().event(event.aggregate().as_ref()).json_data(&event) {
+                Ok(sse) => Some(Ok(sse)),
+                Err(e) => {
+                    tracing::error!("failed to serialize event: {:?}", e);
+                    None
+                }
+            }
+        });
+    
+    Ok(Sse::new(stream).keep_alive(KeepAlive::default()))
+}
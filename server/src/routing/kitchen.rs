@@ -0,0 +1,97 @@
+use axum::body::Body;
+use axum::extract::{Path, Query, State};
+use axum::http::header::{CONTENT_DISPOSITION, CONTENT_TYPE};
+use axum::http::StatusCode;
+use axum::response::Response;
+use axum::Json;
+
+use app_query::models::{DependOnGetOpenOrdersQueryService, GetOpenOrdersQueryService, OpenOrders};
+use kernel::entities::order::OrderId;
+
+use crate::AppModule;
+use crate::receipt::DependOnReceiptSettings;
+use crate::routing::request::orders::{SlipFormat, SlipQuery};
+use crate::slip;
+
+
+#[cfg_attr(
+    feature = "apidoc",
+    utoipa::path(
+        get,
+        path = "/kitchen/orders",
+        responses(
+            (status = OK, body = OpenOrders),
+            (status = INTERNAL_SERVER_ERROR)
+        )
+    )
+)]
+pub async fn orders(
+    State(app): State<AppModule>
+) -> Result<Json<OpenOrders>, StatusCode> {
+    let open = match app.get_open_orders_query_service()
+        .get_open_orders()
+        .await
+    {
+        Ok(open) => open,
+        Err(e) => {
+            tracing::error!("failed to get open orders: {:?}", e);
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+    };
+    
+    Ok(Json(open))
+}
+
+
+#[cfg_attr(
+    feature = "apidoc",
+    utoipa::path(
+        get,
+        path = "/kitchen/orders/{order_id}/slip",
+        params(
+            ("order_id" = Uuid, Path),
+            SlipQuery
+        ),
+        responses(
+            (status = OK, content_type = "image/png"),
+            (status = OK, content_type = "application/octet-stream"),
+            (status = NOT_FOUND, description = "Order does not exist or has not been accepted"),
+            (status = INTERNAL_SERVER_ERROR)
+        )
+    )
+)]
+pub async fn slip(
+    State(app): State<AppModule>,
+    Path(order_id): Path<OrderId>,
+    Query(query): Query<SlipQuery>,
+) -> Result<Response, StatusCode> {
+    let order = match app.get_open_orders_query_service()
+        .get_accepted_order(order_id.as_ref())
+        .await
+    {
+        Ok(Some(order)) => order,
+        Ok(None) => return Err(StatusCode::NOT_FOUND),
+        Err(e) => {
+            tracing::error!("failed to get accepted order: {:?}", e);
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+    };
+    
+    let (body, mime, ext) = match query.format {
+        SlipFormat::Png => {
+            let png = slip::png(&order, app.receipt_settings())
+                .map_err(|e| {
+                    tracing::error!("failed to render ticket slip as png: {:?}", e);
+                    StatusCode::INTERNAL_SERVER_ERROR
+                })?;
+            (png, mime::IMAGE_PNG.as_ref(), "png")
+        }
+        SlipFormat::Escpos => (slip::escpos(&order), "application/octet-stream", "bin"),
+    };
+    
+    Response::builder()
+        .header(CONTENT_TYPE, mime)
+        .header(CONTENT_DISPOSITION, format!("inline; filename=\"slip_{}.{ext}\"", order_id.as_ref()))
+        .body(Body::from(body))
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)
+}
@@ -0,0 +1,52 @@
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use axum::Json;
+
+use app_query::models::{DependOnGetMenuQueryService, GetMenuQueryService, Menu};
+
+use crate::AppModule;
+use crate::routing::request::menu::AsOf;
+
+
+#[cfg_attr(
+    feature = "apidoc",
+    utoipa::path(
+        get,
+        path = "/menu",
+        params(AsOf),
+        responses(
+            (status = OK, body = Menu),
+            (status = BAD_REQUEST),
+            (status = NOT_FOUND),
+            (status = UNAUTHORIZED),
+            (status = FORBIDDEN),
+            (status = INTERNAL_SERVER_ERROR)
+        )
+    )
+)]
+pub async fn as_of(
+    State(app): State<AppModule>,
+    Query(query): Query<AsOf>,
+) -> Result<Json<Menu>, StatusCode> {
+    let cutoff = match query.cutoff() {
+        Ok(cutoff) => cutoff,
+        Err(e) => {
+            tracing::error!("invalid cutoff: {:?}", e);
+            return Err(StatusCode::BAD_REQUEST);
+        }
+    };
+    
+    let menu = match app.get_menu_query_service()
+        .get_menu_as_of(cutoff)
+        .await
+    {
+        Ok(Some(menu)) => menu,
+        Ok(None) => return Err(StatusCode::NOT_FOUND),
+        Err(e) => {
+            tracing::error!("failed to reconstruct the menu: {:?}", e);
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+    };
+    
+    Ok(Json(menu))
+}
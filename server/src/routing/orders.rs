@@ -0,0 +1,348 @@
+use axum::body::Body;
+use axum::extract::{Path, Query, State};
+use axum::http::header::{CONTENT_DISPOSITION, CONTENT_TYPE};
+use axum::http::StatusCode;
+use axum::response::Response;
+use axum::Json;
+
+use app_cmd::errors::ApplicationError;
+use app_cmd::services::order::{DependOnOrderCommandService, OrderCommandService};
+use app_cmd::workflow::order::{AddProductToOrderWorkflow, DependOnAddProductToOrderWorkflow, DependOnRedeemCouponWorkflow, RedeemCouponWorkflow};
+use app_query::models::{DependOnGetReceiptQueryService, GetReceiptQueryService};
+use kernel::entities::order::OrderId;
+use kernel::io::commands::OrderCommand;
+
+use crate::AppModule;
+use crate::receipt::{self, DependOnReceiptSettings};
+use crate::routing::request::orders::{AddOrderLine, ChangeOrderStatus, PayOrder, ReceiptFormat, ReceiptQuery};
+use crate::routing::request::promotions::RedeemCoupon;
+use crate::routing::response::orders::PlacedOrder;
+
+
+#[cfg_attr(
+    feature = "apidoc",
+    utoipa::path(
+        post,
+        path = "/orders",
+        responses(
+            (status = CREATED, body = PlacedOrder),
+            (status = INTERNAL_SERVER_ERROR)
+        )
+    )
+)]
+pub async fn place(
+    State(app): State<AppModule>,
+) -> Result<(StatusCode, Json<PlacedOrder>), StatusCode> {
+    let id = match OrderCommandService::execute(app.order_command_service(), None, OrderCommand::Place).await {
+        Ok(id) => id,
+        Err(e) => {
+            tracing::error!("Failed to place order: {:?}", e);
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+    };
+
+    Ok((StatusCode::CREATED, Json(PlacedOrder::from(id))))
+}
+
+
+#[cfg_attr(
+    feature = "apidoc",
+    utoipa::path(
+        post,
+        path = "/orders/{order_id}",
+        params(
+            ("order_id" = Uuid, Path)
+        ),
+        responses(
+            (status = OK),
+            (status = BAD_REQUEST),
+            (status = CONFLICT, description = "The product is sold out or the selected options are invalid"),
+            (status = INTERNAL_SERVER_ERROR)
+        )
+    )
+)]
+pub async fn add_line(
+    State(app): State<AppModule>,
+    Path(order_id): Path<OrderId>,
+    Json(req): Json<AddOrderLine>
+) -> Result<StatusCode, StatusCode> {
+    let quantity = req.quantity()
+        .map_err(|_| StatusCode::BAD_REQUEST)?;
+
+    let workflow = app.add_product_to_order_workflow();
+    if let Err(e) = AddProductToOrderWorkflow::execute(workflow, order_id, req.product, quantity, req.options, req.components).await {
+        tracing::error!("Failed to add product to order: {:?}", e);
+        return match e.current_context() {
+            ApplicationError::InvalidCommand => Err(StatusCode::CONFLICT),
+            _ => Err(StatusCode::INTERNAL_SERVER_ERROR),
+        };
+    }
+
+    Ok(StatusCode::OK)
+}
+
+
+#[cfg_attr(
+    feature = "apidoc",
+    utoipa::path(
+        delete,
+        path = "/orders/{order_id}/{line}",
+        params(
+            ("order_id" = Uuid, Path),
+            ("line" = i64, Path)
+        ),
+        responses(
+            (status = OK),
+            (status = CONFLICT, description = "The order is no longer open or has no such line"),
+            (status = INTERNAL_SERVER_ERROR)
+        )
+    )
+)]
+pub async fn remove_line(
+    State(app): State<AppModule>,
+    Path((order_id, line)): Path<(OrderId, i64)>,
+) -> Result<StatusCode, StatusCode> {
+    let cmd = OrderCommand::RemoveLine { line };
+    if let Err(e) = OrderCommandService::execute(app.order_command_service(), order_id, cmd).await {
+        tracing::error!("Failed to remove line from order: {:?}", e);
+        return match e.current_context() {
+            ApplicationError::Kernel | ApplicationError::InvalidCommand => Err(StatusCode::CONFLICT),
+            _ => Err(StatusCode::INTERNAL_SERVER_ERROR),
+        };
+    }
+
+    Ok(StatusCode::OK)
+}
+
+
+#[cfg_attr(
+    feature = "apidoc",
+    utoipa::path(
+        post,
+        path = "/orders/{order_id}/coupon",
+        params(
+            ("order_id" = Uuid, Path)
+        ),
+        request_body = RedeemCoupon,
+        responses(
+            (status = OK),
+            (status = BAD_REQUEST),
+            (status = NOT_FOUND, description = "The coupon code does not exist"),
+            (status = CONFLICT, description = "The coupon is exhausted or not valid, or the order already has a coupon"),
+            (status = INTERNAL_SERVER_ERROR)
+        )
+    )
+)]
+pub async fn redeem_coupon(
+    State(app): State<AppModule>,
+    Path(order_id): Path<OrderId>,
+    Json(req): Json<RedeemCoupon>
+) -> Result<StatusCode, StatusCode> {
+    let code = req.code()
+        .map_err(|_| StatusCode::BAD_REQUEST)?;
+
+    let workflow = app.redeem_coupon_workflow();
+    if let Err(e) = RedeemCouponWorkflow::execute(workflow, order_id, code).await {
+        tracing::error!("Failed to redeem coupon: {:?}", e);
+        return match e.current_context() {
+            ApplicationError::NotFound => Err(StatusCode::NOT_FOUND),
+            ApplicationError::InvalidCommand => Err(StatusCode::CONFLICT),
+            _ => Err(StatusCode::INTERNAL_SERVER_ERROR),
+        };
+    }
+
+    Ok(StatusCode::OK)
+}
+
+
+#[cfg_attr(
+    feature = "apidoc",
+    utoipa::path(
+        post,
+        path = "/orders/{order_id}/pay",
+        params(
+            ("order_id" = Uuid, Path)
+        ),
+        request_body = PayOrder,
+        responses(
+            (status = OK),
+            (status = BAD_REQUEST),
+            (status = CONFLICT, description = "The order is already paid, or the tendered amount does not cover the total"),
+            (status = INTERNAL_SERVER_ERROR)
+        )
+    )
+)]
+pub async fn pay(
+    State(app): State<AppModule>,
+    Path(order_id): Path<OrderId>,
+    Json(req): Json<PayOrder>
+) -> Result<StatusCode, StatusCode> {
+    let cmd = match OrderCommand::try_from(req) {
+        Ok(cmd) => cmd,
+        Err(e) => {
+            tracing::error!("Failed to validate payment: {:?}", e);
+            return Err(StatusCode::BAD_REQUEST);
+        }
+    };
+    
+    if let Err(e) = OrderCommandService::execute(app.order_command_service(), order_id, cmd).await {
+        tracing::error!("Failed to pay order: {:?}", e);
+        return match e.current_context() {
+            ApplicationError::Kernel | ApplicationError::InvalidCommand => Err(StatusCode::CONFLICT),
+            _ => Err(StatusCode::INTERNAL_SERVER_ERROR),
+        };
+    }
+
+    Ok(StatusCode::OK)
+}
+
+
+#[cfg_attr(
+    feature = "apidoc",
+    utoipa::path(
+        post,
+        path = "/orders/{order_id}/confirm",
+        params(
+            ("order_id" = Uuid, Path)
+        ),
+        responses(
+            (status = OK),
+            (status = CONFLICT, description = "The order is not paid, is empty or already confirmed, or a product is sold out"),
+            (status = INTERNAL_SERVER_ERROR)
+        )
+    )
+)]
+pub async fn confirm(
+    State(app): State<AppModule>,
+    Path(order_id): Path<OrderId>,
+) -> Result<StatusCode, StatusCode> {
+    if let Err(e) = OrderCommandService::execute(app.order_command_service(), order_id, OrderCommand::Confirm).await {
+        tracing::error!("Failed to confirm order: {:?}", e);
+        return match e.current_context() {
+            ApplicationError::Kernel | ApplicationError::InvalidCommand => Err(StatusCode::CONFLICT),
+            _ => Err(StatusCode::INTERNAL_SERVER_ERROR),
+        };
+    }
+
+    Ok(StatusCode::OK)
+}
+
+
+#[cfg_attr(
+    feature = "apidoc",
+    utoipa::path(
+        patch,
+        path = "/orders/{order_id}/status",
+        params(
+            ("order_id" = Uuid, Path)
+        ),
+        request_body = ChangeOrderStatus,
+        responses(
+            (status = OK),
+            (status = CONFLICT, description = "The order cannot move to the requested status"),
+            (status = INTERNAL_SERVER_ERROR)
+        )
+    )
+)]
+pub async fn change_status(
+    State(app): State<AppModule>,
+    Path(order_id): Path<OrderId>,
+    Json(req): Json<ChangeOrderStatus>
+) -> Result<StatusCode, StatusCode> {
+    let cmd = OrderCommand::from(req);
+    if let Err(e) = OrderCommandService::execute(app.order_command_service(), order_id, cmd).await {
+        tracing::error!("Failed to change order status: {:?}", e);
+        return match e.current_context() {
+            ApplicationError::Kernel | ApplicationError::InvalidCommand => Err(StatusCode::CONFLICT),
+            _ => Err(StatusCode::INTERNAL_SERVER_ERROR),
+        };
+    }
+
+    Ok(StatusCode::OK)
+}
+
+
+#[cfg_attr(
+    feature = "apidoc",
+    utoipa::path(
+        post,
+        path = "/orders/{order_id}/cancel",
+        params(
+            ("order_id" = Uuid, Path)
+        ),
+        responses(
+            (status = OK),
+            (status = CONFLICT, description = "The order has already been completed or cancelled"),
+            (status = INTERNAL_SERVER_ERROR)
+        )
+    )
+)]
+pub async fn cancel(
+    State(app): State<AppModule>,
+    Path(order_id): Path<OrderId>,
+) -> Result<StatusCode, StatusCode> {
+    if let Err(e) = OrderCommandService::execute(app.order_command_service(), order_id, OrderCommand::Cancel).await {
+        tracing::error!("Failed to cancel order: {:?}", e);
+        return match e.current_context() {
+            ApplicationError::Kernel | ApplicationError::InvalidCommand => Err(StatusCode::CONFLICT),
+            _ => Err(StatusCode::INTERNAL_SERVER_ERROR),
+        };
+    }
+
+    Ok(StatusCode::OK)
+}
+
+
+#[cfg_attr(
+    feature = "apidoc",
+    utoipa::path(
+        get,
+        path = "/orders/{order_id}/receipt",
+        params(
+            ("order_id" = Uuid, Path),
+            ReceiptQuery
+        ),
+        responses(
+            (status = OK, content_type = "application/pdf"),
+            (status = OK, content_type = "application/octet-stream"),
+            (status = NOT_FOUND, description = "Order does not exist or has not been paid"),
+            (status = INTERNAL_SERVER_ERROR)
+        )
+    )
+)]
+pub async fn receipt(
+    State(app): State<AppModule>,
+    Path(order_id): Path<OrderId>,
+    Query(query): Query<ReceiptQuery>,
+) -> Result<Response, StatusCode> {
+    let receipt = match app.get_receipt_query_service()
+        .get_receipt(order_id.as_ref())
+        .await
+    {
+        Ok(Some(receipt)) => receipt,
+        Ok(None) => return Err(StatusCode::NOT_FOUND),
+        Err(e) => {
+            tracing::error!("Failed to get receipt: {:?}", e);
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+    };
+    
+    let settings = app.receipt_settings();
+    let (body, mime, ext) = match query.format {
+        ReceiptFormat::Pdf => {
+            let pdf = receipt::pdf(&receipt, settings)
+                .map_err(|e| {
+                    tracing::error!("Failed to render receipt as pdf: {:?}", e);
+                    StatusCode::INTERNAL_SERVER_ERROR
+                })?;
+            (pdf, "application/pdf", "pdf")
+        }
+        ReceiptFormat::Escpos => (receipt::escpos(&receipt, &settings.store), "application/octet-stream", "bin"),
+    };
+    
+    Response::builder()
+        .header(CONTENT_TYPE, mime)
+        .header(CONTENT_DISPOSITION, format!("inline; filename=\"receipt_{}.{ext}\"", order_id.as_ref()))
+        .body(Body::from(body))
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)
+}
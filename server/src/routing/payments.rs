@@ -0,0 +1,70 @@
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use axum::Json;
+
+use app_query::models::{DependOnGetPaymentQueryService, GetPaymentQueryService, PaymentRecord, PaymentSummary};
+
+use crate::AppModule;
+use crate::routing::request::payments::BusinessDay;
+
+
+#[cfg_attr(
+    feature = "apidoc",
+    utoipa::path(
+        get,
+        path = "/payments",
+        params(BusinessDay),
+        responses(
+            (status = OK, body = Vec<PaymentRecord>),
+            (status = INTERNAL_SERVER_ERROR)
+        )
+    )
+)]
+pub async fn payments(
+    State(app): State<AppModule>,
+    Query(query): Query<BusinessDay>,
+) -> Result<Json<Vec<PaymentRecord>>, StatusCode> {
+    let payments = match app.get_payment_query_service()
+        .get_payments(query.day)
+        .await
+    {
+        Ok(payments) => payments,
+        Err(e) => {
+            tracing::error!("failed to get payments: {:?}", e);
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+    };
+    
+    Ok(Json(payments))
+}
+
+
+#[cfg_attr(
+    feature = "apidoc",
+    utoipa::path(
+        get,
+        path = "/payments/summary",
+        params(BusinessDay),
+        responses(
+            (status = OK, body = PaymentSummary),
+            (status = INTERNAL_SERVER_ERROR)
+        )
+    )
+)]
+pub async fn summary(
+    State(app): State<AppModule>,
+    Query(query): Query<BusinessDay>,
+) -> Result<Json<PaymentSummary>, StatusCode> {
+    let summary = match app.get_payment_query_service()
+        .get_payment_summary(query.day)
+        .await
+    {
+        Ok(summary) => summary,
+        Err(e) => {
+            tracing::error!("failed to get payment summary: {:?}", e);
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+    };
+    
+    Ok(Json(summary))
+}
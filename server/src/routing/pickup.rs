@@ -0,0 +1,122 @@
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::extract::State;
+use axum::response::Response;
+use tokio::sync::broadcast::error::RecvError;
+
+use app_query::models::{DependOnGetOpenOrdersQueryService, DependOnGetTicketQueryService, GetOpenOrdersQueryService, GetTicketQueryService};
+use kernel::entities::order::{OrderId, OrderStatus};
+use kernel::io::events::TicketCounterEvent;
+
+use crate::AppModule;
+use crate::events::{DependOnEventBroadcaster, DomainEvent, OrderProgress};
+use crate::routing::response::pickup::{PickupMessage, PickupNumber};
+
+
+#[cfg_attr(
+    feature = "apidoc",
+    utoipa::path(
+        get,
+        path = "/pickup",
+        responses(
+            (status = SWITCHING_PROTOCOLS, body = PickupMessage)
+        )
+    )
+)]
+pub async fn board(
+    State(app): State<AppModule>,
+    ws: WebSocketUpgrade,
+) -> Response {
+    ws.on_upgrade(move |socket| serve(socket, app))
+}
+
+async fn serve(mut socket: WebSocket, app: AppModule) {
+    // Subscribe before taking the snapshot so no update falls in between.
+    let mut rx = app.event_broadcaster().subscribe();
+    
+    if !send_snapshot(&mut socket, &app).await {
+        return;
+    }
+    
+    loop {
+        tokio::select! {
+            received = rx.recv() => {
+                let sent = match received {
+                    Ok(event) => match update(event, &app).await {
+                        Some(update) => send(&mut socket, &PickupMessage::Update(update)).await,
+                        None => true,
+                    },
+                    Err(RecvError::Lagged(skipped)) => {
+                        tracing::warn!("pickup board lagged behind by {skipped} events, resyncing.");
+                        send_snapshot(&mut socket, &app).await
+                    }
+                    Err(RecvError::Closed) => break,
+                };
+                
+                if !sent {
+                    break;
+                }
+            }
+            incoming = socket.recv() => {
+                match incoming {
+                    Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                    Some(Ok(_)) => {}
+                }
+            }
+        }
+    }
+}
+
+async fn update(event: DomainEvent, app: &AppModule) -> Option<PickupNumber> {
+    let (order, status) = match event {
+        DomainEvent::Ticket(TicketCounterEvent::Issued { number, .. }) => {
+            return Some(PickupNumber::from((number, OrderStatus::Accepted)));
+        }
+        DomainEvent::Order(OrderProgress::ChangedStatus { id, new }) => (id, new),
+        DomainEvent::Order(OrderProgress::Cancelled { id }) => (id, OrderStatus::Cancelled),
+        _ => return None,
+    };
+    
+    number_of(&order, app).await
+        .map(|number| PickupNumber::new(number, status))
+}
+
+async fn number_of(order: &OrderId, app: &AppModule) -> Option<i64> {
+    match app.get_ticket_query_service()
+        .get_ticket_by_order(order.as_ref())
+        .await
+    {
+        Ok(ticket) => Some(ticket.number),
+        Err(e) => {
+            // Orders cancelled before confirmation never got a number.
+            tracing::debug!("no ticket number for order={order}: {:?}", e);
+            None
+        }
+    }
+}
+
+async fn send_snapshot(socket: &mut WebSocket, app: &AppModule) -> bool {
+    let open = match app.get_open_orders_query_service()
+        .get_open_orders()
+        .await
+    {
+        Ok(open) => open,
+        Err(e) => {
+            tracing::error!("failed to get ready orders: {:?}", e);
+            return false;
+        }
+    };
+    
+    send(socket, &PickupMessage::snapshot(open.ready)).await
+}
+
+async fn send(socket: &mut WebSocket, message: &PickupMessage) -> bool {
+    let text = match serde_json::to_string(message) {
+        Ok(text) => text,
+        Err(e) => {
+            tracing::error!("failed to serialize pickup message: {:?}", e);
+            return false;
+        }
+    };
+    
+    socket.send(Message::Text(text.into())).await.is_ok()
+}
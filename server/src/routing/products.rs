@@ -2,20 +2,29 @@ use axum::extract::{Multipart, Path, Query, State};
 use axum::http::StatusCode;
 use axum::Json;
 
+use app_cmd::errors::ApplicationError;
 use app_cmd::services::product::{DependOnProductCommandService, ProductCommandService};
+use app_cmd::workflow::product::{ComposeBundleWorkflow, DeleteProductWorkflow, DependOnComposeBundleWorkflow, DependOnDeleteProductWorkflow};
 use app_query::models::{
     AllProduct,
     ProductDetails,
     DependOnGetAllProductQueryService,
     DependOnGetProductQueryService,
+    DependOnGetHistoryQueryService,
     GetAllProductQueryService,
     GetProductQueryService, 
+    GetHistoryQueryService,
+    ProductHistory,
 };
-use kernel::entities::product::ProductId;
+use kernel::entities::money::{Currency, Money};
+use kernel::entities::product::{OptionGroupId, OptionId, ProductId, ProductPrice, SlotId};
+use kernel::entities::schedule::Schedule;
 use kernel::io::commands::ProductCommand;
 
 use crate::AppModule;
-use crate::routing::request::products::{PatchProduct, RegisterProduct, RegisterProductWithCategory};
+use crate::audit::Dispatcher;
+use crate::routing::request::schedules::PutSchedule;
+use crate::routing::request::products::{AddBundleSlot, PatchProduct, PatchProductStock, PutBundleChoices, PutOption, PutOptionGroup, PutProductTax, RegisterProduct, RegisterProductWithCategory};
 
 
 #[cfg_attr(
@@ -72,6 +81,46 @@ pub async fn product_details(
 }
 
 
+#[cfg_attr(
+    feature = "apidoc",
+    utoipa::path(
+        get,
+        path = "/products/{product_id}/history",
+        params(
+            ("product_id" = Uuid, Path)
+        ),
+        responses(
+            (status = OK, body = Vec<ProductHistory>),
+            (status = NOT_FOUND),
+            (status = UNAUTHORIZED),
+            (status = FORBIDDEN),
+            (status = INTERNAL_SERVER_ERROR)
+        )
+    )
+)]
+pub async fn history(
+    State(app): State<AppModule>,
+    Path(product_id): Path<ProductId>,
+) -> Result<Json<Vec<ProductHistory>>, StatusCode> {
+    let history = match app.get_history_query_service()
+        .get_product_history(product_id.as_ref())
+        .await
+    {
+        Ok(history) => history,
+        Err(e) => {
+            tracing::error!("Failed to get product history: {:?}", e);
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+    };
+    
+    if history.is_empty() {
+        return Err(StatusCode::NOT_FOUND);
+    }
+    
+    Ok(Json(history))
+}
+
+
 #[cfg_attr(
     feature = "apidoc",
     utoipa::path(
@@ -85,6 +134,7 @@ pub async fn product_details(
 )]
 pub async fn register(
     State(app): State<AppModule>,
+    Dispatcher(actor): Dispatcher,
     Query(query): Query<RegisterProductWithCategory>,
     multipart: Multipart,
 ) -> Result<StatusCode, StatusCode> {
@@ -105,7 +155,7 @@ pub async fn register(
     match query.category {
         None => {
             if let Err(e) = app.product_command_service()
-                .execute(None, cmd)
+                .execute(&actor, None, cmd)
                 .await
             {
                 tracing::error!("Failed to register product: {:?}", e);
@@ -115,9 +165,12 @@ pub async fn register(
         Some(dest) => {
             use app_cmd::workflow::product::{DependOnRegisterProductWithCategoryWorkflow, RegisterProductWithCategoryWorkflow};
             let app = app.register_product_with_category_workflow();
-            if let Err(e) = RegisterProductWithCategoryWorkflow::execute(app, dest, cmd).await {
+            if let Err(e) = RegisterProductWithCategoryWorkflow::execute(app, &actor, dest, cmd).await {
                 tracing::error!("Failed to register product with category: {:?}", e);
-                return Err(StatusCode::INTERNAL_SERVER_ERROR);
+                return match e.current_context() {
+                    ApplicationError::NotFound => Err(StatusCode::NOT_FOUND),
+                    _ => Err(StatusCode::INTERNAL_SERVER_ERROR),
+                };
             }
         }
     }
@@ -141,6 +194,7 @@ pub async fn register(
 )]
 pub async fn patch(
     State(app): State<AppModule>,
+    Dispatcher(actor): Dispatcher,
     Path(product_id): Path<ProductId>,
     multipart: Multipart,
 ) -> Result<StatusCode, StatusCode> {
@@ -148,6 +202,7 @@ pub async fn patch(
         name, 
         desc, 
         price, 
+        currency, 
         image 
     } = match PatchProduct::from_multipart(multipart).await { 
         Ok(req) => req,
@@ -157,9 +212,28 @@ pub async fn patch(
         }
     };
     
+    // Validated up front so that nothing is changed if the price is invalid.
+    let price = match price {
+        Some(price) => {
+            let currency = match currency {
+                Some(currency) => currency,
+                None => current_currency(&app, &product_id).await?,
+            };
+            
+            match ProductPrice::new(Money::new(price, currency)) {
+                Ok(price) => Some(price),
+                Err(e) => {
+                    tracing::error!("Failed to validate product price: {:?}", e);
+                    return Err(StatusCode::BAD_REQUEST);
+                }
+            }
+        }
+        None => None,
+    };
+    
     if let Some(name) = name {
         if let Err(e) = app.product_command_service()
-            .execute(product_id, ProductCommand::RenameProductName { new: name })
+            .execute(&actor, product_id, ProductCommand::RenameProductName { new: name })
             .await
         {
             tracing::error!("Failed to rename product name: {:?}", e);
@@ -169,7 +243,7 @@ pub async fn patch(
     
     if let Some(desc) = desc {
         if let Err(e) = app.product_command_service()
-            .execute(product_id, ProductCommand::EditProductDesc { new: desc })
+            .execute(&actor, product_id, ProductCommand::EditProductDesc { new: desc })
             .await
         {
             tracing::error!("Failed to change product desc: {:?}", e);
@@ -179,7 +253,7 @@ pub async fn patch(
     
     if let Some(price) = price {
         if let Err(e) = app.product_command_service()
-            .execute(product_id, ProductCommand::ChangeProductPrice { new: price })
+            .execute(&actor, product_id, ProductCommand::ChangeProductPrice { new: price })
             .await
         {
             tracing::error!("Failed to change product price: {:?}", e);
@@ -189,7 +263,7 @@ pub async fn patch(
     
     if let Some(image) = image {
         if let Err(e) = app.product_command_service()
-            .execute(product_id, ProductCommand::ChangeProductImage { image })
+            .execute(&actor, product_id, ProductCommand::ChangeProductImage { image })
             .await
         {
             tracing::error!("Failed to change product image: {:?}", e);
@@ -200,6 +274,24 @@ pub async fn patch(
     Ok(StatusCode::OK)
 }
 
+async fn current_currency(app: &AppModule, product_id: &ProductId) -> Result<Currency, StatusCode> {
+    let details = match app.get_product_query_service()
+        .get_product_details(product_id.as_ref())
+        .await
+    {
+        Ok(details) => details,
+        Err(e) => {
+            tracing::error!("Failed to get product details: {:?}", e);
+            return Err(StatusCode::NOT_FOUND);
+        }
+    };
+    
+    Currency::new(details.price.currency).map_err(|e| {
+        tracing::error!("Invalid currency in read model: {:?}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })
+}
+
 
 
 #[cfg_attr(
@@ -209,18 +301,483 @@ pub async fn patch(
         path = "/products/{product_id}",
         params(
             ("product_id" = Uuid, Path)
+        ),
+        responses(
+            (status = OK),
+            (status = CONFLICT, description = "The product is still a choice of a bundle"),
+            (status = INTERNAL_SERVER_ERROR)
         )
     )
 )]
 pub async fn delete(
     State(app): State<AppModule>,
+    Dispatcher(actor): Dispatcher,
+    Path(product_id): Path<ProductId>,
+) -> Result<StatusCode, StatusCode> {
+    let workflow = app.delete_product_workflow();
+    if let Err(e) = DeleteProductWorkflow::execute(workflow, &actor, product_id).await {
+        tracing::error!("Failed to delete product: {:?}", e);
+        return match e.current_context() {
+            ApplicationError::Kernel => Err(StatusCode::CONFLICT),
+            _ => Err(StatusCode::INTERNAL_SERVER_ERROR),
+        };
+    };
+    
+    Ok(StatusCode::OK)
+}
+
+
+#[cfg_attr(
+    feature = "apidoc",
+    utoipa::path(
+        post,
+        path = "/products/{product_id}/sold-out",
+        params(
+            ("product_id" = Uuid, Path)
+        )
+    )
+)]
+pub async fn mark_sold_out(
+    State(app): State<AppModule>,
+    Dispatcher(actor): Dispatcher,
     Path(product_id): Path<ProductId>,
 ) -> Result<StatusCode, StatusCode> {
     if let Err(e) = app.product_command_service()
-        .execute(product_id, ProductCommand::Delete)
+        .execute(&actor, product_id, ProductCommand::MarkSoldOut)
         .await
     {
-        tracing::error!("Failed to delete product: {:?}", e);
+        tracing::error!("Failed to mark product as sold out: {:?}", e);
+        return Err(StatusCode::INTERNAL_SERVER_ERROR);
+    };
+    
+    Ok(StatusCode::OK)
+}
+
+
+#[cfg_attr(
+    feature = "apidoc",
+    utoipa::path(
+        delete,
+        path = "/products/{product_id}/sold-out",
+        params(
+            ("product_id" = Uuid, Path)
+        )
+    )
+)]
+pub async fn mark_available(
+    State(app): State<AppModule>,
+    Dispatcher(actor): Dispatcher,
+    Path(product_id): Path<ProductId>,
+) -> Result<StatusCode, StatusCode> {
+    if let Err(e) = app.product_command_service()
+        .execute(&actor, product_id, ProductCommand::MarkAvailable)
+        .await
+    {
+        tracing::error!("Failed to mark product as available: {:?}", e);
+        return Err(StatusCode::INTERNAL_SERVER_ERROR);
+    };
+    
+    Ok(StatusCode::OK)
+}
+
+
+#[cfg_attr(
+    feature = "apidoc",
+    utoipa::path(
+        patch,
+        path = "/products/{product_id}/stock",
+        params(
+            ("product_id" = Uuid, Path)
+        ),
+        request_body = PatchProductStock
+    )
+)]
+pub async fn patch_stock(
+    State(app): State<AppModule>,
+    Dispatcher(actor): Dispatcher,
+    Path(product_id): Path<ProductId>,
+    Json(req): Json<PatchProductStock>,
+) -> Result<StatusCode, StatusCode> {
+    let cmd = match ProductCommand::try_from(req) {
+        Ok(cmd) => cmd,
+        Err(e) => {
+            tracing::error!("Failed to validate stock: {:?}", e);
+            return Err(StatusCode::BAD_REQUEST);
+        }
+    };
+    
+    if let Err(e) = app.product_command_service()
+        .execute(&actor, product_id, cmd)
+        .await
+    {
+        tracing::error!("Failed to change product stock: {:?}", e);
+        return Err(StatusCode::INTERNAL_SERVER_ERROR);
+    };
+    
+    Ok(StatusCode::OK)
+}
+
+
+#[cfg_attr(
+    feature = "apidoc",
+    utoipa::path(
+        post,
+        path = "/products/{product_id}/options",
+        params(
+            ("product_id" = Uuid, Path)
+        ),
+        request_body = PutOptionGroup
+    )
+)]
+pub async fn add_option_group(
+    State(app): State<AppModule>,
+    Dispatcher(actor): Dispatcher,
+    Path(product_id): Path<ProductId>,
+    Json(req): Json<PutOptionGroup>,
+) -> Result<StatusCode, StatusCode> {
+    if let Err(e) = app.product_command_service()
+        .execute(&actor, product_id, req.add())
+        .await
+    {
+        tracing::error!("Failed to add option group: {:?}", e);
+        return Err(StatusCode::INTERNAL_SERVER_ERROR);
+    };
+    
+    Ok(StatusCode::CREATED)
+}
+
+
+#[cfg_attr(
+    feature = "apidoc",
+    utoipa::path(
+        put,
+        path = "/products/{product_id}/options/{group_id}",
+        params(
+            ("product_id" = Uuid, Path),
+            ("group_id" = Uuid, Path)
+        ),
+        request_body = PutOptionGroup
+    )
+)]
+pub async fn edit_option_group(
+    State(app): State<AppModule>,
+    Dispatcher(actor): Dispatcher,
+    Path((product_id, group_id)): Path<(ProductId, OptionGroupId)>,
+    Json(req): Json<PutOptionGroup>,
+) -> Result<StatusCode, StatusCode> {
+    if let Err(e) = app.product_command_service()
+        .execute(&actor, product_id, req.edit(group_id))
+        .await
+    {
+        tracing::error!("Failed to edit option group: {:?}", e);
+        return Err(StatusCode::INTERNAL_SERVER_ERROR);
+    };
+    
+    Ok(StatusCode::OK)
+}
+
+
+#[cfg_attr(
+    feature = "apidoc",
+    utoipa::path(
+        delete,
+        path = "/products/{product_id}/options/{group_id}",
+        params(
+            ("product_id" = Uuid, Path),
+            ("group_id" = Uuid, Path)
+        )
+    )
+)]
+pub async fn remove_option_group(
+    State(app): State<AppModule>,
+    Dispatcher(actor): Dispatcher,
+    Path((product_id, group_id)): Path<(ProductId, OptionGroupId)>,
+) -> Result<StatusCode, StatusCode> {
+    if let Err(e) = app.product_command_service()
+        .execute(&actor, product_id, ProductCommand::RemoveOptionGroup { group: group_id })
+        .await
+    {
+        tracing::error!("Failed to remove option group: {:?}", e);
+        return Err(StatusCode::INTERNAL_SERVER_ERROR);
+    };
+    
+    Ok(StatusCode::OK)
+}
+
+
+#[cfg_attr(
+    feature = "apidoc",
+    utoipa::path(
+        post,
+        path = "/products/{product_id}/options/{group_id}",
+        params(
+            ("product_id" = Uuid, Path),
+            ("group_id" = Uuid, Path)
+        ),
+        request_body = PutOption
+    )
+)]
+pub async fn add_option(
+    State(app): State<AppModule>,
+    Dispatcher(actor): Dispatcher,
+    Path((product_id, group_id)): Path<(ProductId, OptionGroupId)>,
+    Json(req): Json<PutOption>,
+) -> Result<StatusCode, StatusCode> {
+    if let Err(e) = app.product_command_service()
+        .execute(&actor, product_id, req.add(group_id))
+        .await
+    {
+        tracing::error!("Failed to add option: {:?}", e);
+        return Err(StatusCode::INTERNAL_SERVER_ERROR);
+    };
+    
+    Ok(StatusCode::CREATED)
+}
+
+
+#[cfg_attr(
+    feature = "apidoc",
+    utoipa::path(
+        put,
+        path = "/products/{product_id}/options/{group_id}/{option_id}",
+        params(
+            ("product_id" = Uuid, Path),
+            ("group_id" = Uuid, Path),
+            ("option_id" = Uuid, Path)
+        ),
+        request_body = PutOption
+    )
+)]
+pub async fn edit_option(
+    State(app): State<AppModule>,
+    Dispatcher(actor): Dispatcher,
+    Path((product_id, group_id, option_id)): Path<(ProductId, OptionGroupId, OptionId)>,
+    Json(req): Json<PutOption>,
+) -> Result<StatusCode, StatusCode> {
+    if let Err(e) = app.product_command_service()
+        .execute(&actor, product_id, req.edit(group_id, option_id))
+        .await
+    {
+        tracing::error!("Failed to edit option: {:?}", e);
+        return Err(StatusCode::INTERNAL_SERVER_ERROR);
+    };
+    
+    Ok(StatusCode::OK)
+}
+
+
+#[cfg_attr(
+    feature = "apidoc",
+    utoipa::path(
+        delete,
+        path = "/products/{product_id}/options/{group_id}/{option_id}",
+        params(
+            ("product_id" = Uuid, Path),
+            ("group_id" = Uuid, Path),
+            ("option_id" = Uuid, Path)
+        )
+    )
+)]
+pub async fn remove_option(
+    State(app): State<AppModule>,
+    Dispatcher(actor): Dispatcher,
+    Path((product_id, group_id, option_id)): Path<(ProductId, OptionGroupId, OptionId)>,
+) -> Result<StatusCode, StatusCode> {
+    if let Err(e) = app.product_command_service()
+        .execute(&actor, product_id, ProductCommand::RemoveOption { group: group_id, option: option_id })
+        .await
+    {
+        tracing::error!("Failed to remove option: {:?}", e);
+        return Err(StatusCode::INTERNAL_SERVER_ERROR);
+    };
+    
+    Ok(StatusCode::OK)
+}
+
+
+#[cfg_attr(
+    feature = "apidoc",
+    utoipa::path(
+        post,
+        path = "/products/{product_id}/bundle",
+        params(
+            ("product_id" = Uuid, Path)
+        ),
+        request_body = AddBundleSlot,
+        responses(
+            (status = CREATED),
+            (status = NOT_FOUND, description = "A choice is not a registered product"),
+            (status = INTERNAL_SERVER_ERROR)
+        )
+    )
+)]
+pub async fn add_bundle_slot(
+    State(app): State<AppModule>,
+    Dispatcher(actor): Dispatcher,
+    Path(product_id): Path<ProductId>,
+    Json(req): Json<AddBundleSlot>,
+) -> Result<StatusCode, StatusCode> {
+    let workflow = app.compose_bundle_workflow();
+    if let Err(e) = ComposeBundleWorkflow::add_slot(workflow, &actor, product_id, req.name(), req.choices).await {
+        tracing::error!("Failed to add bundle slot: {:?}", e);
+        return match e.current_context() {
+            ApplicationError::NotFound => Err(StatusCode::NOT_FOUND),
+            _ => Err(StatusCode::INTERNAL_SERVER_ERROR),
+        };
+    };
+    
+    Ok(StatusCode::CREATED)
+}
+
+
+#[cfg_attr(
+    feature = "apidoc",
+    utoipa::path(
+        put,
+        path = "/products/{product_id}/bundle/{slot_id}",
+        params(
+            ("product_id" = Uuid, Path),
+            ("slot_id" = Uuid, Path)
+        ),
+        request_body = PutBundleChoices,
+        responses(
+            (status = OK),
+            (status = NOT_FOUND, description = "A choice is not a registered product"),
+            (status = INTERNAL_SERVER_ERROR)
+        )
+    )
+)]
+pub async fn change_bundle_choices(
+    State(app): State<AppModule>,
+    Dispatcher(actor): Dispatcher,
+    Path((product_id, slot_id)): Path<(ProductId, SlotId)>,
+    Json(req): Json<PutBundleChoices>,
+) -> Result<StatusCode, StatusCode> {
+    let workflow = app.compose_bundle_workflow();
+    if let Err(e) = ComposeBundleWorkflow::change_choices(workflow, &actor, product_id, slot_id, req.choices).await {
+        tracing::error!("Failed to change bundle choices: {:?}", e);
+        return match e.current_context() {
+            ApplicationError::NotFound => Err(StatusCode::NOT_FOUND),
+            _ => Err(StatusCode::INTERNAL_SERVER_ERROR),
+        };
+    };
+    
+    Ok(StatusCode::OK)
+}
+
+
+#[cfg_attr(
+    feature = "apidoc",
+    utoipa::path(
+        delete,
+        path = "/products/{product_id}/bundle/{slot_id}",
+        params(
+            ("product_id" = Uuid, Path),
+            ("slot_id" = Uuid, Path)
+        )
+    )
+)]
+pub async fn remove_bundle_slot(
+    State(app): State<AppModule>,
+    Dispatcher(actor): Dispatcher,
+    Path((product_id, slot_id)): Path<(ProductId, SlotId)>,
+) -> Result<StatusCode, StatusCode> {
+    let workflow = app.compose_bundle_workflow();
+    if let Err(e) = ComposeBundleWorkflow::remove_slot(workflow, &actor, product_id, slot_id).await {
+        tracing::error!("Failed to remove bundle slot: {:?}", e);
+        return Err(StatusCode::INTERNAL_SERVER_ERROR);
+    };
+    
+    Ok(StatusCode::OK)
+}
+
+
+#[cfg_attr(
+    feature = "apidoc",
+    utoipa::path(
+        put,
+        path = "/products/{product_id}/schedule",
+        params(
+            ("product_id" = Uuid, Path)
+        ),
+        request_body = PutSchedule
+    )
+)]
+pub async fn change_schedule(
+    State(app): State<AppModule>,
+    Dispatcher(actor): Dispatcher,
+    Path(product_id): Path<ProductId>,
+    Json(req): Json<PutSchedule>,
+) -> Result<StatusCode, StatusCode> {
+    let new = match Schedule::try_from(req) {
+        Ok(new) => new,
+        Err(e) => {
+            tracing::error!("Failed to validate schedule: {:?}", e);
+            return Err(StatusCode::BAD_REQUEST);
+        }
+    };
+    
+    if let Err(e) = app.product_command_service()
+        .execute(&actor, product_id, ProductCommand::ChangeSchedule { new })
+        .await
+    {
+        tracing::error!("Failed to change product schedule: {:?}", e);
+        return Err(StatusCode::INTERNAL_SERVER_ERROR);
+    };
+    
+    Ok(StatusCode::OK)
+}
+
+
+#[cfg_attr(
+    feature = "apidoc",
+    utoipa::path(
+        delete,
+        path = "/products/{product_id}/schedule",
+        params(
+            ("product_id" = Uuid, Path)
+        )
+    )
+)]
+pub async fn clear_schedule(
+    State(app): State<AppModule>,
+    Dispatcher(actor): Dispatcher,
+    Path(product_id): Path<ProductId>,
+) -> Result<StatusCode, StatusCode> {
+    if let Err(e) = app.product_command_service()
+        .execute(&actor, product_id, ProductCommand::ClearSchedule)
+        .await
+    {
+        tracing::error!("Failed to clear product schedule: {:?}", e);
+        return Err(StatusCode::INTERNAL_SERVER_ERROR);
+    };
+    
+    Ok(StatusCode::OK)
+}
+
+
+#[cfg_attr(
+    feature = "apidoc",
+    utoipa::path(
+        put,
+        path = "/products/{product_id}/tax",
+        params(
+            ("product_id" = Uuid, Path)
+        ),
+        request_body = PutProductTax
+    )
+)]
+pub async fn change_tax(
+    State(app): State<AppModule>,
+    Dispatcher(actor): Dispatcher,
+    Path(product_id): Path<ProductId>,
+    Json(req): Json<PutProductTax>,
+) -> Result<StatusCode, StatusCode> {
+    if let Err(e) = app.product_command_service()
+        .execute(&actor, product_id, req.into())
+        .await
+    {
+        tracing::error!("Failed to change product tax: {:?}", e);
         return Err(StatusCode::INTERNAL_SERVER_ERROR);
     };
     
@@ -0,0 +1,113 @@
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::Json;
+
+use app_cmd::errors::ApplicationError;
+use app_cmd::services::promotion::{DependOnPromotionCommandService, PromotionCommandService};
+use app_query::models::{DependOnGetPromotionQueryService, GetPromotionQueryService, PromotionRecord};
+use kernel::entities::promotion::CouponCode;
+use kernel::io::commands::PromotionCommand;
+
+use crate::AppModule;
+use crate::routing::request::promotions::CreatePromotion;
+
+
+#[cfg_attr(
+    feature = "apidoc",
+    utoipa::path(
+        get,
+        path = "/promotions",
+        responses(
+            (status = OK, body = Vec<PromotionRecord>),
+            (status = INTERNAL_SERVER_ERROR)
+        )
+    )
+)]
+pub async fn promotions(
+    State(app): State<AppModule>,
+) -> Result<Json<Vec<PromotionRecord>>, StatusCode> {
+    let promotions = match app.get_promotion_query_service()
+        .get_all_promotions()
+        .await
+    {
+        Ok(promotions) => promotions,
+        Err(e) => {
+            tracing::error!("failed to get promotions: {:?}", e);
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+    };
+    
+    Ok(Json(promotions))
+}
+
+
+#[cfg_attr(
+    feature = "apidoc",
+    utoipa::path(
+        post,
+        path = "/promotions",
+        request_body = CreatePromotion,
+        responses(
+            (status = CREATED),
+            (status = BAD_REQUEST),
+            (status = CONFLICT, description = "The coupon code is already taken"),
+            (status = INTERNAL_SERVER_ERROR)
+        )
+    )
+)]
+pub async fn create(
+    State(app): State<AppModule>,
+    Json(req): Json<CreatePromotion>
+) -> Result<StatusCode, StatusCode> {
+    let code = req.code()
+        .map_err(|_| StatusCode::BAD_REQUEST)?;
+    
+    let cmd = match PromotionCommand::try_from(req) {
+        Ok(cmd) => cmd,
+        Err(e) => {
+            tracing::error!("failed to validate promotion: {:?}", e);
+            return Err(StatusCode::BAD_REQUEST);
+        }
+    };
+    
+    if let Err(e) = app.promotion_command_service().execute(code, cmd).await {
+        tracing::error!("failed to create promotion: {:?}", e);
+        return match e.current_context() {
+            ApplicationError::InvalidCommand => Err(StatusCode::CONFLICT),
+            _ => Err(StatusCode::INTERNAL_SERVER_ERROR),
+        };
+    }
+    
+    Ok(StatusCode::CREATED)
+}
+
+
+#[cfg_attr(
+    feature = "apidoc",
+    utoipa::path(
+        delete,
+        path = "/promotions/{code}",
+        params(
+            ("code" = String, Path)
+        ),
+        responses(
+            (status = OK),
+            (status = BAD_REQUEST),
+            (status = INTERNAL_SERVER_ERROR)
+        )
+    )
+)]
+pub async fn end(
+    State(app): State<AppModule>,
+    Path(code): Path<String>,
+) -> Result<StatusCode, StatusCode> {
+    let code = CouponCode::new(code)
+        .map_err(|_| StatusCode::BAD_REQUEST)?;
+    
+    if let Err(e) = app.promotion_command_service().execute(code, PromotionCommand::End).await {
+        tracing::error!("failed to end promotion: {:?}", e);
+        return Err(StatusCode::INTERNAL_SERVER_ERROR);
+    }
+    
+    Ok(StatusCode::OK)
+}
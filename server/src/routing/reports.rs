@@ -0,0 +1,126 @@
+use axum::body::Body;
+use axum::extract::{Query, State};
+use axum::http::header::{CONTENT_DISPOSITION, CONTENT_TYPE};
+use axum::http::StatusCode;
+use axum::response::Response;
+use axum::Json;
+use time::Date;
+
+use app_query::models::{DependOnSalesReportQueryService, SalesReport, SalesReportQueryService};
+
+use crate::AppModule;
+use crate::export;
+use crate::routing::request::reports::{SalesExport, SalesRange};
+
+const XLSX: &str = "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet";
+
+
+#[cfg_attr(
+    feature = "apidoc",
+    utoipa::path(
+        get,
+        path = "/reports/sales",
+        params(SalesRange),
+        responses(
+            (status = OK, body = SalesReport),
+            (status = BAD_REQUEST),
+            (status = INTERNAL_SERVER_ERROR)
+        )
+    )
+)]
+pub async fn sales(
+    State(app): State<AppModule>,
+    Query(range): Query<SalesRange>,
+) -> Result<Json<SalesReport>, StatusCode> {
+    let report = sales_report(&app, range.from, range.to).await?;
+    Ok(Json(report))
+}
+
+
+#[cfg_attr(
+    feature = "apidoc",
+    utoipa::path(
+        get,
+        path = "/reports/sales.csv",
+        params(SalesExport),
+        responses(
+            (status = OK, content_type = "text/csv"),
+            (status = BAD_REQUEST),
+            (status = INTERNAL_SERVER_ERROR)
+        )
+    )
+)]
+pub async fn sales_csv(
+    State(app): State<AppModule>,
+    Query(query): Query<SalesExport>,
+) -> Result<Response, StatusCode> {
+    let report = sales_report(&app, query.from, query.to).await?;
+    
+    let csv = export::sales_csv(&report, query.bom)
+        .map_err(|e| {
+            tracing::error!("failed to export sales report as csv: {:?}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+    
+    attachment(csv, "text/csv; charset=utf-8", filename(&report, "csv"))
+}
+
+
+#[cfg_attr(
+    feature = "apidoc",
+    utoipa::path(
+        get,
+        path = "/reports/sales.xlsx",
+        params(SalesExport),
+        responses(
+            (status = OK, content_type = "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet"),
+            (status = BAD_REQUEST),
+            (status = INTERNAL_SERVER_ERROR)
+        )
+    )
+)]
+pub async fn sales_xlsx(
+    State(app): State<AppModule>,
+    Query(query): Query<SalesExport>,
+) -> Result<Response, StatusCode> {
+    let report = sales_report(&app, query.from, query.to).await?;
+    
+    let xlsx = export::sales_xlsx(&report)
+        .map_err(|e| {
+            tracing::error!("failed to export sales report as xlsx: {:?}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+    
+    attachment(xlsx, XLSX, filename(&report, "xlsx"))
+}
+
+async fn sales_report(app: &AppModule, from: Option<Date>, to: Option<Date>) -> Result<SalesReport, StatusCode> {
+    if let (Some(from), Some(to)) = (from, to) {
+        if from > to {
+            return Err(StatusCode::BAD_REQUEST);
+        }
+    }
+    
+    match app.sales_report_query_service()
+        .get_sales_report(from, to)
+        .await
+    {
+        Ok(report) => Ok(report),
+        Err(e) => {
+            tracing::error!("failed to get sales report: {:?}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+fn filename(report: &SalesReport, ext: &str) -> String {
+    format!("sales_{}_{}.{ext}", report.from, report.to)
+}
+
+fn attachment(body: Vec<u8>, mime: &str, filename: String) -> Result<Response, StatusCode> {
+    Response::builder()
+        .header(CONTENT_TYPE, mime)
+        .header(CONTENT_DISPOSITION, format!("attachment; filename=\"{filename}\""))
+        .body(Body::from(body))
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)
+}
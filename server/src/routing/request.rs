@@ -1,2 +1,12 @@
+pub mod audit;
 pub mod categories;
-pub mod products;
\ No newline at end of file
+pub mod devices;
+pub mod events;
+pub mod menu;
+pub mod orders;
+pub mod payments;
+pub mod products;
+pub mod promotions;
+pub mod reports;
+pub mod schedules;
+pub mod staff;
\ No newline at end of file
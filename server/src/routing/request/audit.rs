@@ -0,0 +1,22 @@
+use serde::Deserialize;
+use time::{OffsetDateTime, PrimitiveDateTime, UtcOffset};
+
+/// `from` and `to` are inclusive RFC 3339 times, and `aggregate` is either 
+/// `product`, `category` or `categories`, or the id of one.
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "apidoc", derive(utoipa::IntoParams))]
+pub struct AuditRange {
+    pub aggregate: Option<String>,
+    #[serde(default, with = "time::serde::rfc3339::option")]
+    #[cfg_attr(feature = "apidoc", param(value_type = Option<String>))]
+    pub from: Option<OffsetDateTime>,
+    #[serde(default, with = "time::serde::rfc3339::option")]
+    #[cfg_attr(feature = "apidoc", param(value_type = Option<String>))]
+    pub to: Option<OffsetDateTime>,
+}
+
+/// The audit log is recorded in UTC.
+pub fn utc(at: OffsetDateTime) -> PrimitiveDateTime {
+    let at = at.to_offset(UtcOffset::UTC);
+    PrimitiveDateTime::new(at.date(), at.time())
+}
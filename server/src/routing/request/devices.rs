@@ -0,0 +1,35 @@
+use error_stack::{Report, ResultExt};
+use serde::Deserialize;
+use kernel::entities::device::{DeviceName, HashedKey, StoreCode};
+use kernel::entities::staff::Role;
+use kernel::io::commands::DeviceCommand;
+
+use crate::errors::ServerError;
+
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "apidoc", derive(utoipa::ToSchema))]
+pub struct RegisterDevice {
+    pub name: String,
+    /// `cashier`, `kitchen` or `kiosk`.
+    #[cfg_attr(feature = "apidoc", schema(value_type = String, example = "kiosk"))]
+    pub role: Role,
+    /// Store the device is bound to, this store when omitted.
+    pub store: Option<String>,
+}
+
+impl RegisterDevice {
+    pub fn into_command(self, store: &StoreCode, key: HashedKey) -> Result<DeviceCommand, Report<ServerError>> {
+        let store = match self.store {
+            Some(store) => StoreCode::new(store)
+                .change_context_lazy(|| ServerError::Validation)?,
+            None => store.clone(),
+        };
+        
+        Ok(DeviceCommand::Register {
+            name: DeviceName::new(self.name),
+            role: self.role,
+            store,
+            key,
+        })
+    }
+}
@@ -0,0 +1,37 @@
+use std::collections::HashSet;
+
+use error_stack::Report;
+use serde::Deserialize;
+
+use crate::errors::ServerError;
+use crate::events::Aggregate;
+
+/// Comma separated list of catalog aggregates to receive, e.g. `?aggregate=product,category`.
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "apidoc", derive(utoipa::IntoParams))]
+pub struct EventFilter {
+    pub aggregate: Option<String>,
+}
+
+impl EventFilter {
+    /// Every catalog aggregate unless narrowed down; 
+    /// asking for anything outside the catalog is rejected.
+    pub fn aggregates(&self) -> Result<HashSet<Aggregate>, Report<ServerError>> {
+        let Some(aggregate) = self.aggregate.as_deref() else {
+            return Ok(HashSet::from(Aggregate::CATALOG));
+        };
+        
+        aggregate.split(',')
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(|s| {
+                let aggregate = s.parse::<Aggregate>()?;
+                if !aggregate.is_catalog() {
+                    return Err(Report::new(ServerError::Validation)
+                        .attach_printable(format!("`{aggregate}` is not streamed on /events")));
+                }
+                Ok(aggregate)
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,32 @@
+use error_stack::Report;
+use serde::Deserialize;
+use time::OffsetDateTime;
+use uuid::Uuid;
+use app_query::models::MenuCutoff;
+
+use crate::errors::ServerError;
+
+/// Either `at`, or `id` along with `sequence`.
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "apidoc", derive(utoipa::IntoParams))]
+pub struct AsOf {
+    /// RFC 3339 time, such as when the disputed ticket was bought.
+    #[serde(default, with = "time::serde::rfc3339::option")]
+    #[cfg_attr(feature = "apidoc", param(value_type = Option<String>))]
+    pub at: Option<OffsetDateTime>,
+    /// Product or category whose history `sequence` is taken from.
+    pub id: Option<Uuid>,
+    /// Sequence of an event in the history of `id`, after which the menu is shown.
+    pub sequence: Option<i64>,
+}
+
+impl AsOf {
+    pub fn cutoff(&self) -> Result<MenuCutoff, Report<ServerError>> {
+        match (self.at, self.id, self.sequence) {
+            (Some(at), None, None) => Ok(MenuCutoff::At(at)),
+            (None, Some(id), Some(sequence)) => Ok(MenuCutoff::Sequence { id, sequence }),
+            _ => Err(Report::new(ServerError::InvalidFormat)
+                .attach_printable("Either `at`, or both `id` and `sequence` must be given")),
+        }
+    }
+}
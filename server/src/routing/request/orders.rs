@@ -0,0 +1,108 @@
+use error_stack::{Report, ResultExt};
+use serde::Deserialize;
+use kernel::entities::money::{Currency, Money};
+use kernel::entities::order::{OrderQuantity, OrderStatus, Tender};
+use kernel::entities::product::{BundleChoice, OptionId, ProductId};
+use kernel::io::commands::OrderCommand;
+
+use crate::errors::ServerError;
+
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "apidoc", derive(utoipa::ToSchema))]
+pub struct AddOrderLine {
+    #[cfg_attr(feature = "apidoc", schema(value_type = Uuid))]
+    pub product: ProductId,
+    pub quantity: i64,
+    #[serde(default)]
+    #[cfg_attr(feature = "apidoc", schema(value_type = Vec<Uuid>))]
+    pub options: Vec<OptionId>,
+    /// `[{"slot": .., "product": ..}]`, one for every slot when the product is a bundle.
+    #[serde(default)]
+    #[cfg_attr(feature = "apidoc", schema(value_type = Vec<Object>))]
+    pub components: Vec<BundleChoice>,
+}
+
+impl AddOrderLine {
+    pub fn quantity(&self) -> Result<OrderQuantity, Report<ServerError>> {
+        OrderQuantity::new(self.quantity)
+            .change_context_lazy(|| ServerError::Validation)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "apidoc", derive(utoipa::ToSchema))]
+pub struct ChangeOrderStatus {
+    #[cfg_attr(feature = "apidoc", schema(value_type = String, example = "preparing"))]
+    pub status: OrderStatus,
+}
+
+impl From<ChangeOrderStatus> for OrderCommand {
+    fn from(value: ChangeOrderStatus) -> Self {
+        OrderCommand::ChangeStatus { new: value.status }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "apidoc", derive(utoipa::ToSchema))]
+pub struct PayOrder {
+    #[cfg_attr(feature = "apidoc", schema(value_type = String, example = "cash"))]
+    pub tender: Tender,
+    /// In minor units of the currency.
+    pub tendered: i64,
+    /// ISO 4217 currency code of `tendered`, which must be that of the order. Defaults to `JPY`.
+    #[cfg_attr(feature = "apidoc", schema(value_type = Option<String>))]
+    pub currency: Option<Currency>,
+}
+
+impl TryFrom<PayOrder> for OrderCommand {
+    type Error = Report<ServerError>;
+
+    fn try_from(value: PayOrder) -> Result<Self, Self::Error> {
+        if value.tendered < 0 {
+            return Err(Report::new(ServerError::Validation)
+                .attach_printable("Tendered amount must not be negative"));
+        }
+        
+        Ok(OrderCommand::Pay { tender: value.tender, tendered: Money::new(value.tendered, value.currency.unwrap_or_default()) })
+    }
+}
+
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+#[cfg_attr(feature = "apidoc", derive(utoipa::ToSchema))]
+pub enum ReceiptFormat {
+    #[default]
+    Pdf,
+    /// Command stream for thermal printers.
+    Escpos,
+}
+
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "apidoc", derive(utoipa::IntoParams))]
+pub struct ReceiptQuery {
+    /// `pdf` or `escpos`. Defaults to `pdf`.
+    #[serde(default)]
+    #[cfg_attr(feature = "apidoc", param(value_type = Option<String>))]
+    pub format: ReceiptFormat,
+}
+
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+#[cfg_attr(feature = "apidoc", derive(utoipa::ToSchema))]
+pub enum SlipFormat {
+    #[default]
+    Png,
+    /// Command stream for thermal printers.
+    Escpos,
+}
+
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "apidoc", derive(utoipa::IntoParams))]
+pub struct SlipQuery {
+    /// `png` or `escpos`. Defaults to `png`.
+    #[serde(default)]
+    #[cfg_attr(feature = "apidoc", param(value_type = Option<String>))]
+    pub format: SlipFormat,
+}
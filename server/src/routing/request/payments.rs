@@ -0,0 +1,10 @@
+use serde::Deserialize;
+use time::Date;
+
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "apidoc", derive(utoipa::IntoParams))]
+pub struct BusinessDay {
+    /// Defaults to the current business day, e.g. `2026-10-18`.
+    #[cfg_attr(feature = "apidoc", param(value_type = Option<String>))]
+    pub day: Option<Date>,
+}
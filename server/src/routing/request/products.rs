@@ -2,7 +2,8 @@ use axum::extract::Multipart;
 use error_stack::{Report, ResultExt};
 use serde::Deserialize;
 use kernel::entities::category::CategoryId;
-use kernel::entities::product::{ProductDesc, ProductName, ProductPrice};
+use kernel::entities::money::{Currency, Money};
+use kernel::entities::product::{OptionGroupId, OptionId, OptionName, ProductDesc, ProductId, ProductName, ProductPrice, ProductStock, ProductTax, SlotName, TaxCategory};
 use kernel::io::commands::ProductCommand;
 
 use crate::errors::ServerError;
@@ -20,7 +21,10 @@ pub struct RegisterProductWithCategory {
 pub struct RegisterProduct {
     name: String,
     desc: String,
+    /// In minor units of the currency, e.g. cents for `USD`.
     price: i64,
+    /// ISO 4217 currency code. Defaults to `JPY`.
+    currency: Option<String>,
     #[cfg_attr(feature = "apidoc", schema(value_type = String, format = Binary, content_media_type = "application/octet-stream"))]
     image: Vec<u8>
 }
@@ -31,6 +35,7 @@ impl RegisterProduct {
         let mut name: Option<String> = None;
         let mut desc: Option<String> = None;
         let mut price: Option<i64> = None;
+        let mut currency: Option<String> = None;
         let mut image: Option<Vec<u8>> = None;
 
         while let Some(field) = multipart.next_field().await
@@ -48,6 +53,8 @@ impl RegisterProduct {
                     .change_context_lazy(|| ServerError::InvalidFormat)?
                     .parse::<i64>()
                     .change_context_lazy(|| ServerError::InvalidFormat)?),
+                "currency" => currency = Some(field.text().await
+                    .change_context_lazy(|| ServerError::InvalidFormat)?),
                 "image" => image = Some(field.bytes().await
                     .change_context_lazy(|| ServerError::InvalidFormat)?.to_vec()),
                 _ => {
@@ -61,6 +68,7 @@ impl RegisterProduct {
             name: name.ok_or(ServerError::InvalidFormat)?,
             desc: desc.ok_or(ServerError::InvalidFormat)?,
             price: price.ok_or(ServerError::InvalidFormat)?,
+            currency,
             image: image.ok_or(ServerError::InvalidFormat)?,
         })
     }
@@ -73,7 +81,8 @@ impl TryFrom<RegisterProduct> for ProductCommand {
         Ok(ProductCommand::Register {
             name: ProductName::new(value.name),
             desc: ProductDesc::new(value.desc),
-            price: ProductPrice::new(value.price).change_context_lazy(|| ServerError::Validation)?,
+            price: ProductPrice::new(Money::new(value.price, currency(value.currency)?.unwrap_or_default()))
+                .change_context_lazy(|| ServerError::Validation)?,
             image: value.image,
         })
     }
@@ -87,8 +96,11 @@ pub struct PatchProduct {
     pub name: Option<ProductName>,
     #[cfg_attr(feature = "apidoc", schema(value_type = String))]
     pub desc: Option<ProductDesc>,
-    #[cfg_attr(feature = "apidoc", schema(value_type = i64))]
-    pub price: Option<ProductPrice>,
+    /// In minor units of the currency.
+    pub price: Option<i64>,
+    /// ISO 4217 currency code of `price`. Defaults to the current currency of the product.
+    #[cfg_attr(feature = "apidoc", schema(value_type = String))]
+    pub currency: Option<Currency>,
     #[cfg_attr(feature = "apidoc", schema(value_type = String, format = Binary, content_media_type = "application/octet-stream"))]
     pub image: Option<Vec<u8>>
 }
@@ -99,6 +111,7 @@ impl PatchProduct {
         let mut name: Option<String> = None;
         let mut desc: Option<String> = None;
         let mut price: Option<i64> = None;
+        let mut currency: Option<String> = None;
         let mut image: Option<Vec<u8>> = None;
 
         while let Some(field) = multipart.next_field().await
@@ -116,6 +129,8 @@ impl PatchProduct {
                     .change_context_lazy(|| ServerError::InvalidFormat)?
                     .parse::<i64>()
                     .change_context_lazy(|| ServerError::InvalidFormat)?),
+                "currency" => currency = Some(field.text().await
+                    .change_context_lazy(|| ServerError::InvalidFormat)?),
                 "image" => image = Some(field.bytes().await
                     .change_context_lazy(|| ServerError::InvalidFormat)?.to_vec()),
                 _ => {
@@ -128,10 +143,122 @@ impl PatchProduct {
         Ok(Self {
             name: name.map(ProductName::new),
             desc: desc.map(ProductDesc::new),
-            price: price.map(ProductPrice::new)
-                .transpose()
-                .change_context_lazy(|| ServerError::Validation)?,
+            price,
+            currency: currency(currency)?,
             image,
         })
     }
-}
\ No newline at end of file
+}
+
+
+fn currency(code: Option<String>) -> Result<Option<Currency>, Report<ServerError>> {
+    code.map(Currency::new)
+        .transpose()
+        .change_context_lazy(|| ServerError::Validation)
+}
+
+
+/// `{"op": "set", "amount": 10}` replaces the stock, while `restock` and `decrement` adjust it.
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "apidoc", derive(utoipa::ToSchema))]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum PatchProductStock {
+    Set { amount: i64 },
+    Restock { amount: i64 },
+    Decrement { amount: i64 },
+}
+
+impl TryFrom<PatchProductStock> for ProductCommand {
+    type Error = Report<ServerError>;
+
+    fn try_from(value: PatchProductStock) -> Result<Self, Self::Error> {
+        let stock = |amount: i64| ProductStock::new(amount)
+            .change_context_lazy(|| ServerError::Validation);
+        
+        Ok(match value {
+            PatchProductStock::Set { amount } => ProductCommand::SetStock { new: stock(amount)? },
+            PatchProductStock::Restock { amount } => ProductCommand::Restock { amount: stock(amount)? },
+            PatchProductStock::Decrement { amount } => ProductCommand::DecrementStock { amount: stock(amount)? },
+        })
+    }
+}
+
+
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "apidoc", derive(utoipa::ToSchema))]
+pub struct PutOptionGroup {
+    pub name: String,
+    pub min: i64,
+    pub max: i64,
+}
+
+impl PutOptionGroup {
+    pub fn add(self) -> ProductCommand {
+        ProductCommand::AddOptionGroup { name: OptionName::new(self.name), min: self.min, max: self.max }
+    }
+    
+    pub fn edit(self, group: OptionGroupId) -> ProductCommand {
+        ProductCommand::EditOptionGroup { group, name: OptionName::new(self.name), min: self.min, max: self.max }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "apidoc", derive(utoipa::ToSchema))]
+pub struct PutOption {
+    pub name: String,
+    /// Added to the product price when selected. May be negative.
+    pub price_delta: i64,
+    /// ISO 4217 currency code of `price_delta`, which must be that of the product price. Defaults to `JPY`.
+    #[cfg_attr(feature = "apidoc", schema(value_type = Option<String>))]
+    pub currency: Option<Currency>,
+}
+
+impl PutOption {
+    pub fn add(self, group: OptionGroupId) -> ProductCommand {
+        ProductCommand::AddOption { group, name: OptionName::new(self.name), price_delta: Money::new(self.price_delta, self.currency.unwrap_or_default()) }
+    }
+    
+    pub fn edit(self, group: OptionGroupId, option: OptionId) -> ProductCommand {
+        ProductCommand::EditOption { group, option, name: OptionName::new(self.name), price_delta: Money::new(self.price_delta, self.currency.unwrap_or_default()) }
+    }
+}
+
+
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "apidoc", derive(utoipa::ToSchema))]
+pub struct AddBundleSlot {
+    pub name: String,
+    #[cfg_attr(feature = "apidoc", schema(value_type = Vec<Uuid>))]
+    pub choices: Vec<ProductId>,
+}
+
+impl AddBundleSlot {
+    pub fn name(&self) -> SlotName {
+        SlotName::new(&self.name)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "apidoc", derive(utoipa::ToSchema))]
+pub struct PutBundleChoices {
+    #[cfg_attr(feature = "apidoc", schema(value_type = Vec<Uuid>))]
+    pub choices: Vec<ProductId>,
+}
+
+
+/// `{"category": "reduced", "inclusive": false}`
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "apidoc", derive(utoipa::ToSchema))]
+pub struct PutProductTax {
+    /// `standard` or `reduced`.
+    #[cfg_attr(feature = "apidoc", schema(value_type = String, example = "reduced"))]
+    pub category: TaxCategory,
+    /// Whether the price of the product already includes the tax.
+    pub inclusive: bool,
+}
+
+impl From<PutProductTax> for ProductCommand {
+    fn from(value: PutProductTax) -> Self {
+        ProductCommand::ChangeTax { new: ProductTax::new(value.category, value.inclusive) }
+    }
+}
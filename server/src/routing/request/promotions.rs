@@ -0,0 +1,115 @@
+use error_stack::{Report, ResultExt};
+use serde::Deserialize;
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
+use kernel::entities::category::CategoryId;
+use kernel::entities::money::{Currency, Money};
+use kernel::entities::product::ProductId;
+use kernel::entities::promotion::{CouponCode, Discount, PromotionName, PromotionScope, UsageCap, Validity};
+use kernel::io::commands::PromotionCommand;
+
+use crate::errors::ServerError;
+
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "apidoc", derive(utoipa::ToSchema))]
+pub struct CreatePromotion {
+    pub code: String,
+    pub name: String,
+    pub discount: PutDiscount,
+    pub scope: PutPromotionScope,
+    /// RFC 3339, e.g. `2026-10-18T09:00:00+09:00`.
+    pub starts_at: String,
+    /// RFC 3339, exclusive.
+    pub ends_at: String,
+    /// Number of times the coupon can be redeemed, unlimited when omitted.
+    pub cap: Option<i64>,
+}
+
+/// `{"type": "percentage", "rate": 10}` or `{"type": "fixed", "amount": 100, "currency": "JPY"}`
+/// 
+/// The currency of a fixed discount defaults to `JPY`.
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "apidoc", derive(utoipa::ToSchema))]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum PutDiscount {
+    Percentage { rate: i64 },
+    Fixed { 
+        amount: i64,
+        #[cfg_attr(feature = "apidoc", schema(value_type = Option<String>))]
+        currency: Option<Currency>,
+    },
+}
+
+/// `{"type": "order"}`, or `{"type": "categories", "ids": [..]}` and `{"type": "products", "ids": [..]}`
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "apidoc", derive(utoipa::ToSchema))]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum PutPromotionScope {
+    Order,
+    Categories { 
+        #[cfg_attr(feature = "apidoc", schema(value_type = Vec<Uuid>))]
+        ids: Vec<CategoryId> 
+    },
+    Products { 
+        #[cfg_attr(feature = "apidoc", schema(value_type = Vec<Uuid>))]
+        ids: Vec<ProductId> 
+    },
+}
+
+impl CreatePromotion {
+    pub fn code(&self) -> Result<CouponCode, Report<ServerError>> {
+        CouponCode::new(&self.code)
+            .change_context_lazy(|| ServerError::Validation)
+    }
+}
+
+impl TryFrom<CreatePromotion> for PromotionCommand {
+    type Error = Report<ServerError>;
+
+    fn try_from(value: CreatePromotion) -> Result<Self, Self::Error> {
+        let discount = match value.discount {
+            PutDiscount::Percentage { rate } => Discount::percentage(rate),
+            PutDiscount::Fixed { amount, currency } => Discount::fixed(Money::new(amount, currency.unwrap_or_default())),
+        }.change_context_lazy(|| ServerError::Validation)?;
+        
+        let scope = match value.scope {
+            PutPromotionScope::Order => Ok(PromotionScope::Order),
+            PutPromotionScope::Categories { ids } => PromotionScope::categories(ids),
+            PutPromotionScope::Products { ids } => PromotionScope::products(ids),
+        }.change_context_lazy(|| ServerError::Validation)?;
+        
+        let validity = Validity::new(timestamp(&value.starts_at)?, timestamp(&value.ends_at)?)
+            .change_context_lazy(|| ServerError::Validation)?;
+        
+        let cap = value.cap.map(UsageCap::new)
+            .transpose()
+            .change_context_lazy(|| ServerError::Validation)?;
+        
+        Ok(PromotionCommand::Create { 
+            name: PromotionName::new(value.name), 
+            discount, 
+            scope, 
+            validity, 
+            cap 
+        })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "apidoc", derive(utoipa::ToSchema))]
+pub struct RedeemCoupon {
+    pub code: String,
+}
+
+impl RedeemCoupon {
+    pub fn code(&self) -> Result<CouponCode, Report<ServerError>> {
+        CouponCode::new(&self.code)
+            .change_context_lazy(|| ServerError::Validation)
+    }
+}
+
+fn timestamp(value: &str) -> Result<OffsetDateTime, Report<ServerError>> {
+    OffsetDateTime::parse(value, &Rfc3339)
+        .change_context_lazy(|| ServerError::InvalidFormat)
+        .attach_printable_lazy(|| format!("`{value}` is not formatted as RFC 3339"))
+}
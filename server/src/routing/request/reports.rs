@@ -0,0 +1,24 @@
+use serde::Deserialize;
+use time::Date;
+
+/// Both ends are inclusive and default to the current business day.
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "apidoc", derive(utoipa::IntoParams))]
+pub struct SalesRange {
+    #[cfg_attr(feature = "apidoc", param(value_type = Option<String>))]
+    pub from: Option<Date>,
+    #[cfg_attr(feature = "apidoc", param(value_type = Option<String>))]
+    pub to: Option<Date>,
+}
+
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "apidoc", derive(utoipa::IntoParams))]
+pub struct SalesExport {
+    #[cfg_attr(feature = "apidoc", param(value_type = Option<String>))]
+    pub from: Option<Date>,
+    #[cfg_attr(feature = "apidoc", param(value_type = Option<String>))]
+    pub to: Option<Date>,
+    /// Prepends a UTF-8 BOM to CSV files so that Excel reads them correctly.
+    #[serde(default)]
+    pub bom: bool,
+}
@@ -0,0 +1,85 @@
+use error_stack::{Report, ResultExt};
+use serde::Deserialize;
+use time::format_description::well_known::Iso8601;
+use time::{PrimitiveDateTime, Time, Weekday};
+use kernel::entities::schedule::{Schedule, TimeWindow};
+use kernel::entities::ticket::StoreOffset;
+
+use crate::errors::ServerError;
+
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "apidoc", derive(utoipa::IntoParams))]
+pub struct AvailableAt {
+    /// Local time of the store such as `2026-10-18T07:30`, or `now`. 
+    /// Items that are not available at that time are left out. Without it, everything is listed.
+    pub at: Option<String>,
+}
+
+impl AvailableAt {
+    /// `now` is resolved in the `offset` of the store.
+    pub fn at(&self, offset: &StoreOffset) -> Result<Option<PrimitiveDateTime>, Report<ServerError>> {
+        let Some(at) = self.at.as_deref() else {
+            return Ok(None);
+        };
+
+        if at == "now" {
+            let now = offset.now();
+            return Ok(Some(PrimitiveDateTime::new(now.date(), now.time())));
+        }
+
+        PrimitiveDateTime::parse(at, &Iso8601::DEFAULT)
+            .map(Some)
+            .change_context_lazy(|| ServerError::InvalidFormat)
+            .attach_printable_lazy(|| format!("`{at}` is not a local date and time"))
+    }
+}
+
+/// `{"windows": [{"weekdays": ["Monday", "Tuesday"], "start": "07:00", "end": "10:30"}]}`
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "apidoc", derive(utoipa::ToSchema))]
+pub struct PutSchedule {
+    pub windows: Vec<PutTimeWindow>,
+}
+
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "apidoc", derive(utoipa::ToSchema))]
+pub struct PutTimeWindow {
+    #[cfg_attr(feature = "apidoc", schema(value_type = Vec<String>))]
+    pub weekdays: Vec<Weekday>,
+    #[cfg_attr(feature = "apidoc", schema(example = "07:00"))]
+    pub start: String,
+    #[cfg_attr(feature = "apidoc", schema(example = "10:30"))]
+    pub end: String,
+}
+
+impl TryFrom<PutSchedule> for Schedule {
+    type Error = Report<ServerError>;
+
+    fn try_from(value: PutSchedule) -> Result<Self, Self::Error> {
+        let windows = value.windows.into_iter()
+            .map(|window| {
+                let start = clock(&window.start)?;
+                let end = clock(&window.end)?;
+                TimeWindow::new(window.weekdays, start, end)
+                    .change_context_lazy(|| ServerError::Validation)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Schedule::new(windows)
+            .change_context_lazy(|| ServerError::Validation)
+    }
+}
+
+/// Parses `HH:MM`.
+fn clock(time: &str) -> Result<Time, Report<ServerError>> {
+    let invalid = || Report::new(ServerError::InvalidFormat)
+        .attach_printable(format!("`{time}` is not formatted as HH:MM"));
+
+    let (hour, minute) = time.split_once(':')
+        .ok_or_else(invalid)?;
+    let hour = hour.parse::<u8>().map_err(|_| invalid())?;
+    let minute = minute.parse::<u8>().map_err(|_| invalid())?;
+
+    Time::from_hms(hour, minute, 0)
+        .change_context_lazy(|| ServerError::Validation)
+}
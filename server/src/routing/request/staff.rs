@@ -0,0 +1,80 @@
+use error_stack::{Report, ResultExt};
+use serde::Deserialize;
+use kernel::entities::staff::{HashedPassword, Role, StaffName};
+use kernel::io::commands::StaffCommand;
+
+use crate::errors::ServerError;
+
+#[derive(Deserialize)]
+#[cfg_attr(feature = "apidoc", derive(utoipa::ToSchema))]
+pub struct Login {
+    pub name: String,
+    pub password: String,
+}
+
+impl Login {
+    pub fn name(&self) -> Result<StaffName, Report<ServerError>> {
+        StaffName::new(&self.name)
+            .change_context_lazy(|| ServerError::Validation)
+    }
+}
+
+#[derive(Deserialize)]
+#[cfg_attr(feature = "apidoc", derive(utoipa::ToSchema))]
+pub struct RegisterStaff {
+    pub name: String,
+    /// `admin`, `cashier`, `kitchen` or `kiosk`.
+    #[cfg_attr(feature = "apidoc", schema(value_type = String, example = "cashier"))]
+    pub role: Role,
+    /// Between 8 and 128 characters.
+    pub password: String,
+}
+
+impl RegisterStaff {
+    pub fn name(&self) -> Result<StaffName, Report<ServerError>> {
+        StaffName::new(&self.name)
+            .change_context_lazy(|| ServerError::Validation)
+    }
+}
+
+impl TryFrom<RegisterStaff> for StaffCommand {
+    type Error = Report<ServerError>;
+
+    fn try_from(value: RegisterStaff) -> Result<Self, Self::Error> {
+        let password = HashedPassword::new(value.password)
+            .change_context_lazy(|| ServerError::Validation)?;
+        
+        Ok(StaffCommand::Register { role: value.role, password })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "apidoc", derive(utoipa::ToSchema))]
+pub struct ChangeRole {
+    #[cfg_attr(feature = "apidoc", schema(value_type = String, example = "kitchen"))]
+    pub role: Role,
+}
+
+impl From<ChangeRole> for StaffCommand {
+    fn from(value: ChangeRole) -> Self {
+        StaffCommand::ChangeRole { role: value.role }
+    }
+}
+
+#[derive(Deserialize)]
+#[cfg_attr(feature = "apidoc", derive(utoipa::ToSchema))]
+pub struct ChangePassword {
+    /// Between 8 and 128 characters.
+    pub password: String,
+}
+
+impl TryFrom<ChangePassword> for StaffCommand {
+    type Error = Report<ServerError>;
+
+    fn try_from(value: ChangePassword) -> Result<Self, Self::Error> {
+        let password = HashedPassword::new(value.password)
+            .change_context_lazy(|| ServerError::Validation)?;
+        
+        Ok(StaffCommand::ChangePassword { password })
+    }
+}
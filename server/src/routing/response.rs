@@ -0,0 +1,4 @@
+pub mod devices;
+pub mod orders;
+pub mod pickup;
+pub mod staff;
\ No newline at end of file
@@ -0,0 +1,18 @@
+use serde::Serialize;
+use uuid::Uuid;
+use kernel::entities::device::{ApiKey, DeviceId};
+
+/// Only ever shown once, as only its hash is kept.
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "apidoc", derive(utoipa::ToSchema))]
+pub struct IssuedKey {
+    pub id: Uuid,
+    /// Sent as the `X-Device-Key` header by the device.
+    pub key: String,
+}
+
+impl IssuedKey {
+    pub fn new(id: DeviceId, key: &ApiKey) -> Self {
+        Self { id: id.into(), key: format!("{id}.{}", key.as_ref()) }
+    }
+}
@@ -0,0 +1,15 @@
+use serde::Serialize;
+use uuid::Uuid;
+use kernel::entities::order::OrderId;
+
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "apidoc", derive(utoipa::ToSchema))]
+pub struct PlacedOrder {
+    pub id: Uuid,
+}
+
+impl From<OrderId> for PlacedOrder {
+    fn from(id: OrderId) -> Self {
+        Self { id: id.into() }
+    }
+}
@@ -0,0 +1,46 @@
+use serde::Serialize;
+use app_query::models::OpenOrder;
+use kernel::entities::order::OrderStatus;
+use kernel::entities::ticket::TicketNumber;
+
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "apidoc", derive(utoipa::ToSchema))]
+pub struct PickupNumber {
+    pub number: i64,
+    #[cfg_attr(feature = "apidoc", schema(value_type = String))]
+    pub status: OrderStatus,
+}
+
+impl PickupNumber {
+    pub fn new(number: impl Into<i64>, status: OrderStatus) -> Self {
+        Self { number: number.into(), status }
+    }
+}
+
+impl From<(TicketNumber, OrderStatus)> for PickupNumber {
+    fn from((number, status): (TicketNumber, OrderStatus)) -> Self {
+        Self::new(number, status)
+    }
+}
+
+/// Messages pushed to the pickup-number board.
+///
+/// A `snapshot` is sent on every (re)connect and whenever the client fell behind,
+/// so the board can simply replace its list instead of replaying missed updates.
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "apidoc", derive(utoipa::ToSchema))]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum PickupMessage {
+    Snapshot { ready: Vec<PickupNumber> },
+    Update(PickupNumber),
+}
+
+impl PickupMessage {
+    pub fn snapshot(ready: Vec<OpenOrder>) -> Self {
+        let ready = ready.into_iter()
+            .filter_map(|order| order.number)
+            .map(|number| PickupNumber::new(number, OrderStatus::Ready))
+            .collect();
+        PickupMessage::Snapshot { ready }
+    }
+}
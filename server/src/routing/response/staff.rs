@@ -0,0 +1,13 @@
+use serde::Serialize;
+use kernel::entities::staff::Role;
+
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "apidoc", derive(utoipa::ToSchema))]
+pub struct Session {
+    /// Sent back as `Authorization: Bearer <token>` on write requests.
+    pub token: String,
+    #[cfg_attr(feature = "apidoc", schema(value_type = String, example = "cashier"))]
+    pub role: Role,
+    /// Seconds until the token expires.
+    pub expires_in: i64,
+}
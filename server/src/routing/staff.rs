@@ -0,0 +1,165 @@
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::Json;
+
+use app_cmd::errors::ApplicationError;
+use app_cmd::services::staff::{DependOnStaffCommandService, StaffCommandService};
+use kernel::entities::staff::StaffName;
+use kernel::io::commands::StaffCommand;
+
+use crate::AppModule;
+use crate::routing::request::staff::{ChangePassword, ChangeRole, RegisterStaff};
+
+
+#[cfg_attr(
+    feature = "apidoc",
+    utoipa::path(
+        post,
+        path = "/staff",
+        request_body = RegisterStaff,
+        responses(
+            (status = CREATED),
+            (status = BAD_REQUEST),
+            (status = UNAUTHORIZED),
+            (status = FORBIDDEN),
+            (status = CONFLICT, description = "The name is already taken"),
+            (status = INTERNAL_SERVER_ERROR)
+        )
+    )
+)]
+pub async fn register(
+    State(app): State<AppModule>,
+    Json(req): Json<RegisterStaff>,
+) -> Result<StatusCode, StatusCode> {
+    let name = req.name()
+        .map_err(|_| StatusCode::BAD_REQUEST)?;
+    
+    let cmd = match StaffCommand::try_from(req) {
+        Ok(cmd) => cmd,
+        Err(e) => {
+            tracing::error!("failed to validate staff: {:?}", e);
+            return Err(StatusCode::BAD_REQUEST);
+        }
+    };
+    
+    if let Err(e) = app.staff_command_service().execute(name, cmd).await {
+        tracing::error!("failed to register staff: {:?}", e);
+        return match e.current_context() {
+            ApplicationError::InvalidCommand => Err(StatusCode::CONFLICT),
+            _ => Err(StatusCode::INTERNAL_SERVER_ERROR),
+        };
+    }
+    
+    Ok(StatusCode::CREATED)
+}
+
+
+#[cfg_attr(
+    feature = "apidoc",
+    utoipa::path(
+        put,
+        path = "/staff/{name}/role",
+        params(
+            ("name" = String, Path)
+        ),
+        request_body = ChangeRole,
+        responses(
+            (status = OK),
+            (status = BAD_REQUEST),
+            (status = UNAUTHORIZED),
+            (status = FORBIDDEN),
+            (status = INTERNAL_SERVER_ERROR)
+        )
+    )
+)]
+pub async fn change_role(
+    State(app): State<AppModule>,
+    Path(name): Path<String>,
+    Json(req): Json<ChangeRole>,
+) -> Result<StatusCode, StatusCode> {
+    let name = StaffName::new(name)
+        .map_err(|_| StatusCode::BAD_REQUEST)?;
+    
+    if let Err(e) = app.staff_command_service().execute(name, StaffCommand::from(req)).await {
+        tracing::error!("failed to change role: {:?}", e);
+        return Err(StatusCode::INTERNAL_SERVER_ERROR);
+    }
+    
+    Ok(StatusCode::OK)
+}
+
+
+#[cfg_attr(
+    feature = "apidoc",
+    utoipa::path(
+        put,
+        path = "/staff/{name}/password",
+        params(
+            ("name" = String, Path)
+        ),
+        request_body = ChangePassword,
+        responses(
+            (status = OK),
+            (status = BAD_REQUEST),
+            (status = UNAUTHORIZED),
+            (status = FORBIDDEN),
+            (status = INTERNAL_SERVER_ERROR)
+        )
+    )
+)]
+pub async fn change_password(
+    State(app): State<AppModule>,
+    Path(name): Path<String>,
+    Json(req): Json<ChangePassword>,
+) -> Result<StatusCode, StatusCode> {
+    let name = StaffName::new(name)
+        .map_err(|_| StatusCode::BAD_REQUEST)?;
+    
+    let cmd = match StaffCommand::try_from(req) {
+        Ok(cmd) => cmd,
+        Err(e) => {
+            tracing::error!("failed to validate password: {:?}", e);
+            return Err(StatusCode::BAD_REQUEST);
+        }
+    };
+    
+    if let Err(e) = app.staff_command_service().execute(name, cmd).await {
+        tracing::error!("failed to change password: {:?}", e);
+        return Err(StatusCode::INTERNAL_SERVER_ERROR);
+    }
+    
+    Ok(StatusCode::OK)
+}
+
+
+#[cfg_attr(
+    feature = "apidoc",
+    utoipa::path(
+        delete,
+        path = "/staff/{name}",
+        params(
+            ("name" = String, Path)
+        ),
+        responses(
+            (status = OK),
+            (status = BAD_REQUEST),
+            (status = UNAUTHORIZED),
+            (status = FORBIDDEN),
+            (status = INTERNAL_SERVER_ERROR)
+        )
+    )
+)]
+pub async fn remove(
+    State(app): State<AppModule>,
+    Path(name): Path<String>,
+) -> Result<StatusCode, StatusCode> {
+    let name = StaffName::new(name)
+        .map_err(|_| StatusCode::BAD_REQUEST)?;
+    
+    if let Err(e) = app.staff_command_service().execute(name, StaffCommand::Remove).await {
+        tracing::error!("failed to remove staff: {:?}", e);
+        return Err(StatusCode::INTERNAL_SERVER_ERROR);
+    }
+    
+    Ok(StatusCode::OK)
+}
@@ -0,0 +1,62 @@
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::Json;
+
+use app_query::models::{DependOnGetTicketQueryService, GetTicketQueryService, IssuedTicket, TicketNumbers};
+use kernel::entities::order::OrderId;
+
+use crate::AppModule;
+
+
+#[cfg_attr(
+    feature = "apidoc",
+    utoipa::path(
+        get,
+        path = "/tickets",
+    )
+)]
+pub async fn numbers(
+    State(app): State<AppModule>
+) -> Result<Json<TicketNumbers>, StatusCode> {
+    let numbers = match app.get_ticket_query_service()
+        .get_ticket_numbers()
+        .await
+    {
+        Ok(numbers) => numbers,
+        Err(e) => {
+            tracing::error!("failed to get ticket numbers: {:?}", e);
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+    };
+    
+    Ok(Json(numbers))
+}
+
+
+#[cfg_attr(
+    feature = "apidoc",
+    utoipa::path(
+        get,
+        path = "/tickets/{order_id}",
+        params(
+            ("order_id" = Uuid, Path)
+        ),
+    )
+)]
+pub async fn get_by_order(
+    State(app): State<AppModule>,
+    Path(order_id): Path<OrderId>
+) -> Result<Json<IssuedTicket>, StatusCode> {
+    let ticket = match app.get_ticket_query_service()
+        .get_ticket_by_order(order_id.as_ref())
+        .await
+    {
+        Ok(ticket) => ticket,
+        Err(e) => {
+            tracing::error!("failed to get ticket of order: {:?}", e);
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+    };
+    
+    Ok(Json(ticket))
+}
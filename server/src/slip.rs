@@ -0,0 +1,168 @@
+//! Ticket slips handed to the kitchen along with the order, showing the call number large
+//! and a QR code of the order id that staff scan to mark the order as handed over.
+
+use std::io::Cursor;
+
+use ab_glyph::{FontRef, PxScale};
+use error_stack::{Report, ResultExt};
+use image::{DynamicImage, GrayImage, ImageFormat, Luma};
+use imageproc::drawing::{draw_filled_rect_mut, draw_text_mut, text_size};
+use imageproc::rect::Rect;
+use qrcode::{Color, EcLevel, QrCode};
+
+use app_query::models::OpenOrder;
+
+use crate::errors::ServerError;
+use crate::receipt::ReceiptSettings;
+
+const ESC: u8 = 0x1B;
+const FS: u8 = 0x1C;
+const GS: u8 = 0x1D;
+const LF: u8 = 0x0A;
+
+/// Dots of the printable area of 80mm paper at 203dpi.
+const WIDTH: u32 = 576;
+const MARGIN: u32 = 16;
+/// Size of a module of the QR code in pixels, and of the quiet zone around it in modules.
+const MODULE: u32 = 6;
+const QUIET_ZONE: u32 = 4;
+
+const BLACK: Luma<u8> = Luma([0]);
+const WHITE: Luma<u8> = Luma([255]);
+
+fn number(order: &OpenOrder) -> String {
+    order.number.map(|number| number.to_string()).unwrap_or_else(|| "-".to_string())
+}
+
+fn items(order: &OpenOrder) -> Vec<(String, Vec<String>)> {
+    order.items.iter()
+        .map(|item| {
+            let name = format!("{} x{}", item.name.as_deref().unwrap_or("(deleted)"), item.quantity);
+            let details = item.options.iter().map(|option| format!("+ {option}"))
+                .chain(item.components.iter().map(|component| format!("- {component}")))
+                .collect();
+            (name, details)
+        })
+        .collect()
+}
+
+fn qrcode(order: &OpenOrder) -> Result<QrCode, Report<ServerError>> {
+    QrCode::with_error_correction_level(order.id.to_string(), EcLevel::M)
+        .change_context_lazy(|| ServerError::Export)
+}
+
+/// Renders the slip as a grayscale PNG the width of 80mm paper.
+/// 
+/// Requires the font of [`ReceiptSettings`], as the `image` crate cannot draw text by itself.
+pub fn png(order: &OpenOrder, settings: &ReceiptSettings) -> Result<Vec<u8>, Report<ServerError>> {
+    let Some(font) = settings.font.as_deref() else {
+        return Err(Report::new(ServerError::Export)
+            .attach_printable("`RECEIPT_FONT` must be set to render ticket slips as PNG"));
+    };
+    let font = FontRef::try_from_slice(font)
+        .change_context_lazy(|| ServerError::Export)?;
+    
+    let number_scale = PxScale::from(160.0);
+    let item_scale = PxScale::from(36.0);
+    let detail_scale = PxScale::from(28.0);
+    let line = |scale: PxScale| scale.y as u32 + 8;
+    
+    let code = qrcode(order)?;
+    let modules = code.width() as u32;
+    let qr_size = (modules + QUIET_ZONE * 2) * MODULE;
+    
+    let items = items(order);
+    let height = MARGIN * 2
+        + line(number_scale)
+        + items.iter()
+            .map(|(_, details)| line(item_scale) + line(detail_scale) * details.len() as u32)
+            .sum::<u32>()
+        + 16
+        + qr_size;
+    
+    let mut canvas = GrayImage::from_pixel(WIDTH, height, WHITE);
+    let mut y = MARGIN as i32;
+    
+    let number = number(order);
+    let (width, _) = text_size(number_scale, &font, &number);
+    draw_text_mut(&mut canvas, BLACK, (WIDTH.saturating_sub(width) / 2) as i32, y, number_scale, &font, &number);
+    y += line(number_scale) as i32;
+    
+    for (name, details) in &items {
+        draw_text_mut(&mut canvas, BLACK, MARGIN as i32, y, item_scale, &font, name);
+        y += line(item_scale) as i32;
+        for detail in details {
+            draw_text_mut(&mut canvas, BLACK, (MARGIN * 3) as i32, y, detail_scale, &font, detail);
+            y += line(detail_scale) as i32;
+        }
+    }
+    
+    draw_filled_rect_mut(&mut canvas, Rect::at(MARGIN as i32, y + 6).of_size(WIDTH - MARGIN * 2, 2), BLACK);
+    y += 16;
+    
+    let left = (WIDTH.saturating_sub(qr_size) / 2) as i32;
+    let top = y;
+    for (index, color) in code.to_colors().into_iter().enumerate() {
+        if color != Color::Dark {
+            continue;
+        }
+        let x = (index as u32 % modules + QUIET_ZONE) * MODULE;
+        let y = (index as u32 / modules + QUIET_ZONE) * MODULE;
+        draw_filled_rect_mut(&mut canvas, Rect::at(left + x as i32, top + y as i32).of_size(MODULE, MODULE), BLACK);
+    }
+    
+    let mut buf = Vec::new();
+    DynamicImage::ImageLuma8(canvas)
+        .write_to(&mut Cursor::new(&mut buf), ImageFormat::Png)
+        .change_context_lazy(|| ServerError::Export)?;
+    
+    Ok(buf)
+}
+
+/// Renders the slip as ESC/POS commands, printing the QR code with the printer's own command
+/// so that it stays sharp at any resolution.
+pub fn escpos(order: &OpenOrder) -> Vec<u8> {
+    let encode = |text: &str| encoding_rs::SHIFT_JIS.encode(text).0.into_owned();
+    let mut buf = Vec::new();
+    
+    // Initialize, then select Shift_JIS kanji mode.
+    buf.extend_from_slice(&[ESC, b'@', FS, b'C', 1, FS, b'&']);
+    
+    // Centered call number at 8 times the width and height.
+    buf.extend_from_slice(&[ESC, b'a', 1, GS, b'!', 0x77]);
+    buf.extend(encode(&number(order)));
+    buf.extend_from_slice(&[LF, GS, b'!', 0x00, ESC, b'a', 0]);
+    buf.extend(encode(&"-".repeat(48)));
+    buf.push(LF);
+    
+    for (name, details) in items(order) {
+        // Bold at double height.
+        buf.extend_from_slice(&[ESC, b'E', 1, GS, b'!', 0x01]);
+        buf.extend(encode(&name));
+        buf.extend_from_slice(&[LF, GS, b'!', 0x00, ESC, b'E', 0]);
+        for detail in details {
+            buf.extend(encode(&format!("    {detail}")));
+            buf.push(LF);
+        }
+    }
+    
+    buf.extend(encode(&"-".repeat(48)));
+    buf.push(LF);
+    
+    let data = order.id.to_string().into_bytes();
+    let len = data.len() + 3;
+    buf.extend_from_slice(&[ESC, b'a', 1]);
+    // Model 2, module size, error correction level M, then store and print the data.
+    buf.extend_from_slice(&[GS, b'(', b'k', 4, 0, 49, 65, 50, 0]);
+    buf.extend_from_slice(&[GS, b'(', b'k', 3, 0, 49, 67, MODULE as u8]);
+    buf.extend_from_slice(&[GS, b'(', b'k', 3, 0, 49, 69, 49]);
+    buf.extend_from_slice(&[GS, b'(', b'k', (len % 256) as u8, (len / 256) as u8, 49, 80, 48]);
+    buf.extend(data);
+    buf.extend_from_slice(&[GS, b'(', b'k', 3, 0, 49, 81, 48]);
+    buf.extend_from_slice(&[LF, ESC, b'a', 0]);
+    
+    // Feed past the cutter, then cut partially.
+    buf.extend_from_slice(&[ESC, b'd', 4, GS, b'V', 66, 0]);
+    
+    buf
+}
@@ -0,0 +1,363 @@
+use axum::body::Body;
+use axum::http::header::AUTHORIZATION;
+use axum::http::{Method, Request, StatusCode};
+use axum::Router;
+use error_stack::{Report, ResultExt};
+use tower::ServiceExt;
+use uuid::Uuid;
+use app_cmd::services::device::{DependOnDeviceCommandService, DeviceCommandService};
+use app_cmd::services::staff::{DependOnStaffCommandService, StaffCommandService};
+use app_cmd::workflow::staff::{AuthenticateStaffWorkflow, DependOnAuthenticateStaffWorkflow};
+use kernel::entities::device::{ApiKey, DeviceId, DeviceName};
+use kernel::entities::staff::{HashedPassword, Role, Staff, StaffName};
+use kernel::io::commands::{DeviceCommand, StaffCommand};
+use server::AppModule;
+use server::auth::{DependOnAuthenticator, DEVICE_KEY};
+use server::errors::UnrecoverableError;
+
+const ROLES: [Role; 4] = [Role::Admin, Role::Cashier, Role::Kitchen, Role::Kiosk];
+
+const ADMIN: &[Role] = &[Role::Admin];
+const ORDERING: &[Role] = &[Role::Admin, Role::Cashier, Role::Kiosk];
+const CASHIER: &[Role] = &[Role::Admin, Role::Cashier];
+const KITCHEN: &[Role] = &[Role::Admin, Role::Kitchen];
+
+const ID: &str = "00000000-0000-0000-0000-000000000000";
+
+/// Every guarded route along with the roles that are allowed through.
+fn guarded() -> Vec<(Method, String, &'static [Role])> {
+    vec![
+        (Method::POST, "/staff".into(), ADMIN),
+        (Method::DELETE, "/staff/someone".into(), ADMIN),
+        (Method::PUT, "/staff/someone/role".into(), ADMIN),
+        (Method::PUT, "/staff/someone/password".into(), ADMIN),
+        
+        (Method::GET, "/devices".into(), ADMIN),
+        (Method::POST, "/devices".into(), ADMIN),
+        (Method::DELETE, format!("/devices/{ID}"), ADMIN),
+        (Method::POST, format!("/devices/{ID}/key"), ADMIN),
+        
+        (Method::POST, "/categories".into(), ADMIN),
+        (Method::PUT, "/categories".into(), ADMIN),
+        (Method::POST, format!("/categories/{ID}"), ADMIN),
+        (Method::PUT, format!("/categories/{ID}"), ADMIN),
+        (Method::PATCH, format!("/categories/{ID}"), ADMIN),
+        (Method::DELETE, format!("/categories/{ID}"), ADMIN),
+        (Method::PUT, format!("/categories/{ID}/schedule"), ADMIN),
+        (Method::DELETE, format!("/categories/{ID}/schedule"), ADMIN),
+        (Method::DELETE, format!("/categories/{ID}/{ID}"), ADMIN),
+        (Method::GET, format!("/categories/{ID}/history"), ADMIN),
+        
+        (Method::POST, "/products".into(), ADMIN),
+        (Method::PATCH, format!("/products/{ID}"), ADMIN),
+        (Method::DELETE, format!("/products/{ID}"), ADMIN),
+        (Method::POST, format!("/products/{ID}/sold-out"), ADMIN),
+        (Method::DELETE, format!("/products/{ID}/sold-out"), ADMIN),
+        (Method::PATCH, format!("/products/{ID}/stock"), ADMIN),
+        (Method::POST, format!("/products/{ID}/options"), ADMIN),
+        (Method::POST, format!("/products/{ID}/options/{ID}"), ADMIN),
+        (Method::PUT, format!("/products/{ID}/options/{ID}"), ADMIN),
+        (Method::DELETE, format!("/products/{ID}/options/{ID}"), ADMIN),
+        (Method::PUT, format!("/products/{ID}/options/{ID}/{ID}"), ADMIN),
+        (Method::DELETE, format!("/products/{ID}/options/{ID}/{ID}"), ADMIN),
+        (Method::POST, format!("/products/{ID}/bundle"), ADMIN),
+        (Method::PUT, format!("/products/{ID}/bundle/{ID}"), ADMIN),
+        (Method::DELETE, format!("/products/{ID}/bundle/{ID}"), ADMIN),
+        (Method::PUT, format!("/products/{ID}/schedule"), ADMIN),
+        (Method::DELETE, format!("/products/{ID}/schedule"), ADMIN),
+        (Method::PUT, format!("/products/{ID}/tax"), ADMIN),
+        (Method::GET, format!("/products/{ID}/history"), ADMIN),
+        
+        (Method::POST, "/orders".into(), ORDERING),
+        (Method::POST, format!("/orders/{ID}"), ORDERING),
+        (Method::POST, format!("/orders/{ID}/coupon"), ORDERING),
+        (Method::DELETE, format!("/orders/{ID}/1"), ORDERING),
+        (Method::POST, format!("/orders/{ID}/pay"), CASHIER),
+        (Method::POST, format!("/orders/{ID}/confirm"), CASHIER),
+        (Method::POST, format!("/orders/{ID}/cancel"), CASHIER),
+        (Method::GET, format!("/orders/{ID}/receipt"), CASHIER),
+        (Method::PATCH, format!("/orders/{ID}/status"), KITCHEN),
+        
+        (Method::GET, "/promotions".into(), ADMIN),
+        (Method::POST, "/promotions".into(), ADMIN),
+        (Method::DELETE, "/promotions/WELCOME".into(), ADMIN),
+        
+        (Method::GET, "/payments".into(), ADMIN),
+        (Method::GET, "/payments/summary".into(), ADMIN),
+        
+        (Method::GET, "/reports/sales".into(), ADMIN),
+        (Method::GET, "/reports/sales.csv".into(), ADMIN),
+        (Method::GET, "/reports/sales.xlsx".into(), ADMIN),
+        
+        (Method::GET, "/audit".into(), ADMIN),
+        (Method::GET, "/menu?at=2026-10-18T12:00:00Z".into(), ADMIN),
+        
+        (Method::GET, "/kitchen/orders".into(), KITCHEN),
+        (Method::GET, format!("/kitchen/orders/{ID}/slip"), KITCHEN),
+    ]
+}
+
+async fn setup() -> Result<(AppModule, Router), Report<UnrecoverableError>> {
+    let dir = std::env::temp_dir().join(format!("ez-ticket-api-{}", Uuid::new_v4()));
+    std::fs::create_dir_all(&dir)
+        .change_context_lazy(|| UnrecoverableError)?;
+    
+    let app = AppModule::connect(
+        &format!("sqlite:{}", dir.join("query.db").display()),
+        &format!("sqlite:{}", dir.join("journal.db").display()),
+    ).await?;
+    
+    let router = server::routing::routes(&app).with_state(app.clone());
+    
+    Ok((app, router))
+}
+
+/// Registers staff with the given role, returning the session they would get on sign-in.
+async fn token(app: &AppModule, role: Role) -> Result<String, Report<UnrecoverableError>> {
+    let name = StaffName::new(format!("test-{role}"))
+        .change_context_lazy(|| UnrecoverableError)?;
+    let password = HashedPassword::new("password")
+        .change_context_lazy(|| UnrecoverableError)?;
+    
+    let cmd = StaffCommand::Register { role, password: password.clone() };
+    StaffCommandService::execute(app.staff_command_service(), name.clone(), cmd).await
+        .change_context_lazy(|| UnrecoverableError)?;
+    
+    app.authenticator().issue(&Staff::new(name, role, password))
+        .change_context_lazy(|| UnrecoverableError)
+}
+
+/// Registers a device with the given role, returning its id and the value of its key header.
+async fn device(app: &AppModule, role: Role) -> Result<(DeviceId, String), Report<UnrecoverableError>> {
+    let key = ApiKey::generate();
+    let cmd = DeviceCommand::Register {
+        name: DeviceName::new("test"),
+        role,
+        store: app.authenticator().store().clone(),
+        key: key.hash(),
+    };
+    
+    let id = DeviceCommandService::execute(app.device_command_service(), None, cmd).await
+        .change_context_lazy(|| UnrecoverableError)?;
+    
+    Ok((id, format!("{id}.{}", key.as_ref())))
+}
+
+async fn send(router: &Router, method: Method, uri: &str, token: Option<&str>) -> Result<StatusCode, Report<UnrecoverableError>> {
+    let mut req = Request::builder().method(method).uri(uri);
+    if let Some(token) = token {
+        req = req.header(AUTHORIZATION, format!("Bearer {token}"));
+    }
+    
+    request(router, req).await
+}
+
+async fn send_as_device(router: &Router, method: Method, uri: &str, key: &str) -> Result<StatusCode, Report<UnrecoverableError>> {
+    request(router, Request::builder().method(method).uri(uri).header(DEVICE_KEY, key)).await
+}
+
+async fn request(router: &Router, req: axum::http::request::Builder) -> Result<StatusCode, Report<UnrecoverableError>> {
+    let req = req.body(Body::empty())
+        .change_context_lazy(|| UnrecoverableError)?;
+    
+    let res = router.clone().oneshot(req).await
+        .change_context_lazy(|| UnrecoverableError)?;
+    
+    Ok(res.status())
+}
+
+#[tokio::test]
+async fn test_reject_unauthorized_roles() -> Result<(), Report<UnrecoverableError>> {
+    let (app, router) = setup().await?;
+    
+    for role in ROLES {
+        let token = token(&app, role).await?;
+        
+        for (method, uri, allowed) in guarded() {
+            let status = send(&router, method.clone(), &uri, Some(&token)).await?;
+            
+            if allowed.contains(&role) {
+                if matches!(status, StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN) {
+                    return Err(Report::new(UnrecoverableError)
+                        .attach_printable(format!("{role} must be allowed to {method} {uri}, but was {status}")));
+                }
+            } else if status != StatusCode::FORBIDDEN {
+                return Err(Report::new(UnrecoverableError)
+                    .attach_printable(format!("{role} must be forbidden to {method} {uri}, but was {status}")));
+            }
+        }
+    }
+    
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_reject_missing_session() -> Result<(), Report<UnrecoverableError>> {
+    let (_, router) = setup().await?;
+    
+    for (method, uri, _) in guarded() {
+        for token in [None, Some("invalid")] {
+            let status = send(&router, method.clone(), &uri, token).await?;
+            
+            if status != StatusCode::UNAUTHORIZED {
+                return Err(Report::new(UnrecoverableError)
+                    .attach_printable(format!("{method} {uri} must require a session, but was {status}")));
+            }
+        }
+    }
+    
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_public_routes() -> Result<(), Report<UnrecoverableError>> {
+    let (_, router) = setup().await?;
+    
+    let public = [
+        "/categories".to_string(),
+        "/products".to_string(),
+        format!("/products/{ID}"),
+        "/tickets".to_string(),
+        "/pickup".to_string(),
+    ];
+    
+    for uri in public {
+        let status = send(&router, Method::GET, &uri, None).await?;
+        
+        if matches!(status, StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN) {
+            return Err(Report::new(UnrecoverableError)
+                .attach_printable(format!("GET {uri} must be public, but was {status}")));
+        }
+    }
+    
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_authenticate_device() -> Result<(), Report<UnrecoverableError>> {
+    let (app, router) = setup().await?;
+    
+    let (id, key) = device(&app, Role::Kiosk).await?;
+    
+    let status = send_as_device(&router, Method::POST, "/orders", &key).await?;
+    if !status.is_success() {
+        return Err(Report::new(UnrecoverableError)
+            .attach_printable(format!("Kiosk must be able to place an order, but was {status}")));
+    }
+    
+    let status = send_as_device(&router, Method::POST, &format!("/orders/{ID}/pay"), &key).await?;
+    if status != StatusCode::FORBIDDEN {
+        return Err(Report::new(UnrecoverableError)
+            .attach_printable(format!("Kiosk must be forbidden to take payments, but was {status}")));
+    }
+    
+    let forged = format!("{id}.{}", ApiKey::generate().as_ref());
+    let status = send_as_device(&router, Method::POST, "/orders", &forged).await?;
+    if status != StatusCode::UNAUTHORIZED {
+        return Err(Report::new(UnrecoverableError)
+            .attach_printable(format!("Wrong key must be rejected, but was {status}")));
+    }
+    
+    DeviceCommandService::execute(app.device_command_service(), id, DeviceCommand::Revoke).await
+        .change_context_lazy(|| UnrecoverableError)?;
+    
+    let status = send_as_device(&router, Method::POST, "/orders", &key).await?;
+    if status != StatusCode::UNAUTHORIZED {
+        return Err(Report::new(UnrecoverableError)
+            .attach_printable(format!("Revoked device must be rejected, but was {status}")));
+    }
+    
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_reject_removed_staff() -> Result<(), Report<UnrecoverableError>> {
+    let (app, router) = setup().await?;
+    
+    let token = token(&app, Role::Admin).await?;
+    
+    let status = send(&router, Method::GET, "/devices", Some(&token)).await?;
+    if !status.is_success() {
+        return Err(Report::new(UnrecoverableError)
+            .attach_printable(format!("Admin must be able to list devices, but was {status}")));
+    }
+    
+    let name = StaffName::new(format!("test-{}", Role::Admin))
+        .change_context_lazy(|| UnrecoverableError)?;
+    StaffCommandService::execute(app.staff_command_service(), name, StaffCommand::Remove).await
+        .change_context_lazy(|| UnrecoverableError)?;
+    
+    let status = send(&router, Method::GET, "/devices", Some(&token)).await?;
+    if status != StatusCode::UNAUTHORIZED {
+        return Err(Report::new(UnrecoverableError)
+            .attach_printable(format!("Session of removed staff must be rejected, but was {status}")));
+    }
+    
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_reject_demoted_staff() -> Result<(), Report<UnrecoverableError>> {
+    let (app, router) = setup().await?;
+    
+    let token = token(&app, Role::Admin).await?;
+    
+    let status = send(&router, Method::GET, "/devices", Some(&token)).await?;
+    if !status.is_success() {
+        return Err(Report::new(UnrecoverableError)
+            .attach_printable(format!("Admin must be able to list devices, but was {status}")));
+    }
+    
+    let name = StaffName::new(format!("test-{}", Role::Admin))
+        .change_context_lazy(|| UnrecoverableError)?;
+    let cmd = StaffCommand::ChangeRole { role: Role::Cashier };
+    StaffCommandService::execute(app.staff_command_service(), name, cmd).await
+        .change_context_lazy(|| UnrecoverableError)?;
+    
+    let status = send(&router, Method::GET, "/devices", Some(&token)).await?;
+    if status != StatusCode::FORBIDDEN {
+        return Err(Report::new(UnrecoverableError)
+            .attach_printable(format!("Demoted staff must be forbidden to list devices, but was {status}")));
+    }
+    
+    let status = send(&router, Method::POST, &format!("/orders/{ID}/pay"), Some(&token)).await?;
+    if matches!(status, StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN) {
+        return Err(Report::new(UnrecoverableError)
+            .attach_printable(format!("Demoted staff must keep the permissions of their new role, but was {status}")));
+    }
+    
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_reject_session_before_password_change() -> Result<(), Report<UnrecoverableError>> {
+    let (app, router) = setup().await?;
+    
+    let token = token(&app, Role::Admin).await?;
+    
+    let name = StaffName::new(format!("test-{}", Role::Admin))
+        .change_context_lazy(|| UnrecoverableError)?;
+    let password = HashedPassword::new("changed")
+        .change_context_lazy(|| UnrecoverableError)?;
+    StaffCommandService::execute(app.staff_command_service(), name.clone(), StaffCommand::ChangePassword { password }).await
+        .change_context_lazy(|| UnrecoverableError)?;
+    
+    let status = send(&router, Method::GET, "/devices", Some(&token)).await?;
+    if status != StatusCode::UNAUTHORIZED {
+        return Err(Report::new(UnrecoverableError)
+            .attach_printable(format!("Session issued before the password change must be rejected, but was {status}")));
+    }
+    
+    let staff = app.authenticate_staff_workflow().execute(name, "changed".to_string()).await
+        .change_context_lazy(|| UnrecoverableError)?;
+    let token = app.authenticator().issue(&staff)
+        .change_context_lazy(|| UnrecoverableError)?;
+    
+    let status = send(&router, Method::GET, "/devices", Some(&token)).await?;
+    if !status.is_success() {
+        return Err(Report::new(UnrecoverableError)
+            .attach_printable(format!("Session issued after the password change must be accepted, but was {status}")));
+    }
+    
+    Ok(())
+}
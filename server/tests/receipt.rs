@@ -0,0 +1,141 @@
+use error_stack::{Report, ResultExt};
+use time::{Date, Month, PrimitiveDateTime, Time};
+use uuid::Uuid;
+use app_query::models::{Receipt, ReceiptItem, ReceiptOption, ReceiptTax};
+use kernel::entities::ticket::StoreOffset;
+use server::errors::UnrecoverableError;
+use server::receipt::{escpos, pdf, ReceiptSettings, StoreHeader};
+
+const ESC: u8 = 0x1B;
+const FS: u8 = 0x1C;
+const GS: u8 = 0x1D;
+
+fn store() -> StoreHeader {
+    StoreHeader { name: "ez-ticket".to_string(), lines: vec!["T1234567890123".to_string()], offset: StoreOffset::default() }
+}
+
+/// A cash payment of 1000 for a total of 864, with a kanji product name and a discount.
+fn receipt() -> Result<Receipt, Report<UnrecoverableError>> {
+    let order_id = Uuid::new_v4();
+    let date = Date::from_calendar_date(2026, Month::October, 18)
+        .change_context_lazy(|| UnrecoverableError)?;
+    let time = Time::from_hms(3, 4, 0)
+        .change_context_lazy(|| UnrecoverableError)?;
+
+    Ok(Receipt {
+        order_id,
+        number: Some(42),
+        currency: "JPY".to_string(),
+        coupon: Some("WELCOME".to_string()),
+        subtotal: 960,
+        discount: 96,
+        total: 864,
+        tender: "cash".to_string(),
+        tendered: 1000,
+        change: 136,
+        paid_at: PrimitiveDateTime::new(date, time),
+        items: vec![ReceiptItem {
+            order_id,
+            line: 0,
+            name: Some("醤油ラーメン".to_string()),
+            quantity: 2,
+            unit_price: 480,
+            amount: 960,
+            tax_rate: 8,
+            options: vec![ReceiptOption { name: "extra egg".to_string(), price_delta: 80 }],
+            components: Vec::new(),
+        }],
+        taxes: vec![ReceiptTax { rate: 8, gross: 864, tax: 64 }],
+    })
+}
+
+fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+    haystack.windows(needle.len()).any(|window| window == needle)
+}
+
+#[test]
+fn test_escpos_receipt() -> Result<(), Report<UnrecoverableError>> {
+    let buf = escpos(&receipt()?, &store());
+
+    if !buf.starts_with(&[ESC, b'@', FS, b'C', 1, FS, b'&']) {
+        return Err(Report::new(UnrecoverableError).attach_printable("Printer must be initialized in Shift_JIS kanji mode"));
+    }
+
+    if !buf.ends_with(&[ESC, b'd', 4, GS, b'V', 66, 0]) {
+        return Err(Report::new(UnrecoverableError).attach_printable("Paper must be fed and cut at the end"));
+    }
+
+    let (name, _, _) = encoding_rs::SHIFT_JIS.encode("醤油ラーメン");
+    if !contains(&buf, &name) {
+        return Err(Report::new(UnrecoverableError).attach_printable("Product name must be encoded in Shift_JIS"));
+    }
+
+    let total = format!("Total{}864 JPY", " ".repeat(48 - "Total".len() - "864 JPY".len()));
+    let bold = [&[ESC, b'E', 1][..], total.as_bytes(), &[0x0A, ESC, b'E', 0]].concat();
+    if !contains(&buf, &bold) {
+        return Err(Report::new(UnrecoverableError).attach_printable("Total must be printed in bold across the full width"));
+    }
+
+    for text in ["2026-10-18 03:04", "Discount (WELCOME)", "-96 JPY", "8% tax", "Change", "136 JPY"] {
+        if !contains(&buf, text.as_bytes()) {
+            return Err(Report::new(UnrecoverableError).attach_printable(format!("Receipt must contain `{text}`")));
+        }
+    }
+
+    let offset = StoreOffset::parse("+09:00")
+        .change_context_lazy(|| UnrecoverableError)?;
+    let buf = escpos(&receipt()?, &StoreHeader { offset, ..store() });
+
+    if !contains(&buf, b"2026-10-18 12:04") {
+        return Err(Report::new(UnrecoverableError).attach_printable("Time of payment must be printed in the offset of the store"));
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_pdf_receipt() -> Result<(), Report<UnrecoverableError>> {
+    let settings = ReceiptSettings { store: store(), font: None };
+
+    let buf = pdf(&receipt()?, &settings)
+        .change_context_lazy(|| UnrecoverableError)?;
+
+    if !buf.starts_with(b"%PDF-") {
+        return Err(Report::new(UnrecoverableError).attach_printable("Receipt must be rendered as a PDF"));
+    }
+
+    let longer = Receipt {
+        items: (0..20).map(|line| ReceiptItem {
+            order_id: Uuid::nil(),
+            line,
+            name: Some(format!("item {line}")),
+            quantity: 1,
+            unit_price: 100,
+            amount: 100,
+            tax_rate: 10,
+            options: Vec::new(),
+            components: Vec::new(),
+        }).collect(),
+        ..receipt()?
+    };
+
+    let longer = pdf(&longer, &settings)
+        .change_context_lazy(|| UnrecoverableError)?;
+
+    if page_height(&longer) <= page_height(&buf) {
+        return Err(Report::new(UnrecoverableError).attach_printable("Page must grow with the number of lines"));
+    }
+
+    Ok(())
+}
+
+/// Height of the media box of the only page, in points.
+fn page_height(pdf: &[u8]) -> f32 {
+    let text = String::from_utf8_lossy(pdf);
+    text.split("/MediaBox")
+        .nth(1)
+        .and_then(|rest| rest.split(']').next())
+        .and_then(|rect| rect.trim_start_matches([' ', '[']).split_whitespace().nth(3))
+        .and_then(|height| height.parse().ok())
+        .unwrap_or_default()
+}